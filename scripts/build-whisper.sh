@@ -1,15 +1,27 @@
 #!/usr/bin/env bash
 # build-whisper.sh — Compile whisper.cpp avec CoreML + Metal (Task 3)
+#                    ou backend CPU + OpenBLAS sur Linux
 #
 # Usage:
 #   ./scripts/build-whisper.sh
+#   WHISPER_BLAS=0 ./scripts/build-whisper.sh   # Linux : ignorer OpenBLAS
+#   WHISPER_X86_LEVEL=v3 ./scripts/build-whisper.sh  # Linux x86_64 : AVX2/FMA
 #
-# Prérequis:
+# WHISPER_X86_LEVEL fixe le jeu d'instructions du binaire, indépendamment de
+# la machine de build :
+#   v2 (défaut) — x86-64-v2 : SSE4.2, tourne sur tout CPU x86_64 depuis ~2009
+#   v3          — x86-64-v3 : AVX2, FMA, F16C ; SIGILL sur un CPU sans AVX2
+#
+# Prérequis macOS:
 #   - macOS Apple Silicon (M1+)
 #   - Xcode complet (pas seulement Command Line Tools) pour CoreML framework
 #   - cmake >= 3.21
 #   - python3 + coremltools (pour générer les modèles CoreML)
 #
+# Prérequis Linux (x86_64 / aarch64):
+#   - cmake >= 3.21, g++
+#   - libopenblas-dev (optionnel — accélère l'encodeur sur CPU)
+#
 # Output:
 #   vendor/whisper.cpp/build/src/libwhisper.a
 #   vendor/whisper.cpp/ggml-metal.metal (copié dans resources/ via build.rs)
@@ -21,15 +33,65 @@ PROJECT_ROOT="$(dirname "$SCRIPT_DIR")"
 VENDOR_DIR="$PROJECT_ROOT/vendor"
 WHISPER_DIR="$VENDOR_DIR/whisper.cpp"
 BUILD_DIR="$WHISPER_DIR/build"
-NCPU=$(sysctl -n hw.ncpu 2>/dev/null || echo 4)
+NCPU=$(sysctl -n hw.ncpu 2>/dev/null || nproc 2>/dev/null || echo 4)
+OS="$(uname -s)"
+ARCH="$(uname -m)"
 
-echo "=== Build whisper.cpp avec CoreML + Metal ==="
+if [ "$OS" = "Linux" ]; then
+    echo "=== Build whisper.cpp CPU ($ARCH) ==="
+else
+    echo "=== Build whisper.cpp avec CoreML + Metal ==="
+fi
 echo "Project root: $PROJECT_ROOT"
 echo "Whisper dir:  $WHISPER_DIR"
 echo "CPUs:         $NCPU"
 
-# Vérifier Xcode (nécessaire pour CoreML)
-if ! xcode-select -p &>/dev/null || [ "$(xcode-select -p)" = "/Library/Developer/CommandLineTools" ]; then
+if [ "$OS" = "Linux" ]; then
+    COREML_FLAG="-DWHISPER_COREML=OFF"
+
+    # Pas de GGML_NATIVE : le binaire doit tourner sur d'autres machines que
+    # celle du build. Le niveau x86 vient donc de WHISPER_X86_LEVEL et jamais
+    # du CPU de la machine de build ; chaque option ggml est fixée explicitement
+    # car ggml active AVX/AVX2 par défaut quand GGML_NATIVE=OFF.
+    PLATFORM_FLAGS=(-DGGML_NATIVE=OFF -DGGML_OPENMP=OFF)
+    case "$ARCH" in
+        x86_64)
+            case "${WHISPER_X86_LEVEL:-v2}" in
+                v2)
+                    echo "Niveau x86-64-v2 (SSE4.2)"
+                    PLATFORM_FLAGS+=(-DGGML_SSE42=ON -DGGML_AVX=OFF -DGGML_AVX2=OFF
+                        -DGGML_BMI2=OFF -DGGML_FMA=OFF -DGGML_F16C=OFF -DGGML_AVX512=OFF)
+                    ;;
+                v3)
+                    echo "Niveau x86-64-v3 (AVX2, FMA, F16C) — ne tourne pas sans AVX2"
+                    PLATFORM_FLAGS+=(-DGGML_SSE42=ON -DGGML_AVX=ON -DGGML_AVX2=ON
+                        -DGGML_BMI2=ON -DGGML_FMA=ON -DGGML_F16C=ON -DGGML_AVX512=OFF)
+                    ;;
+                *)
+                    echo "ERREUR: WHISPER_X86_LEVEL doit valoir v2 ou v3 (reçu : $WHISPER_X86_LEVEL)"
+                    exit 1
+                    ;;
+            esac
+            ;;
+        aarch64)
+            echo "NEON (toujours présent sur aarch64)"
+            ;;
+        *)
+            echo "ERREUR: architecture Linux non supportée: $ARCH"
+            exit 1
+            ;;
+    esac
+
+    if [ "${WHISPER_BLAS:-1}" != "0" ] && \
+        { pkg-config --exists openblas 2>/dev/null || ldconfig -p 2>/dev/null | grep -q libopenblas; }; then
+        echo "OpenBLAS détecté"
+        PLATFORM_FLAGS+=(-DGGML_BLAS=ON -DGGML_BLAS_VENDOR=OpenBLAS)
+    else
+        echo "OpenBLAS absent — backend CPU seul"
+        PLATFORM_FLAGS+=(-DGGML_BLAS=OFF)
+    fi
+# macOS : vérifier Xcode (nécessaire pour CoreML)
+elif ! xcode-select -p &>/dev/null || [ "$(xcode-select -p)" = "/Library/Developer/CommandLineTools" ]; then
     echo ""
     echo "AVERTISSEMENT: Xcode complet non détecté."
     echo "CoreML support nécessite Xcode.app (pas seulement Command Line Tools)."
@@ -43,6 +105,10 @@ else
     echo "Xcode détecté: $(xcode-select -p)"
 fi
 
+if [ "$OS" != "Linux" ]; then
+    PLATFORM_FLAGS=(-DWHISPER_METAL=ON -DCMAKE_OSX_ARCHITECTURES=arm64)
+fi
+
 # Cloner whisper.cpp si absent
 if [ ! -d "$WHISPER_DIR" ]; then
     echo ""
@@ -61,12 +127,11 @@ echo "Configuration CMake..."
 cmake -B "$BUILD_DIR" \
     -S "$WHISPER_DIR" \
     $COREML_FLAG \
-    -DWHISPER_METAL=ON \
+    "${PLATFORM_FLAGS[@]}" \
     -DBUILD_SHARED_LIBS=OFF \
     -DWHISPER_BUILD_TESTS=OFF \
     -DWHISPER_BUILD_EXAMPLES=OFF \
-    -DCMAKE_BUILD_TYPE=Release \
-    -DCMAKE_OSX_ARCHITECTURES=arm64
+    -DCMAKE_BUILD_TYPE=Release
 
 # Compiler
 echo ""
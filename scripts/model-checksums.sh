@@ -0,0 +1,92 @@
+#!/usr/bin/env bash
+# model-checksums.sh — Renseigne le SHA-256 des modèles du catalogue intégré
+#                      (src-tauri/resources/model_catalog.json)
+#
+# Usage:
+#   ./scripts/model-checksums.sh                    # télécharge chaque modèle
+#   MODELS_DIR=~/models ./scripts/model-checksums.sh  # hache des copies locales
+#   ./scripts/model-checksums.sh --check            # échoue s'il manque un sha256
+#
+# Chaque modèle est haché depuis MODELS_DIR/<filename> s'il y existe, sinon
+# téléchargé depuis son `url` dans un dossier temporaire. Un sha256 déjà
+# présent qui ne correspond plus au fichier fait échouer le script : la
+# source a changé et le catalogue doit être revu à la main.
+#
+# Prérequis : curl, python3, sha256sum (ou shasum sur macOS)
+
+set -euo pipefail
+
+SCRIPT_DIR="$(cd "$(dirname "${BASH_SOURCE[0]}")" && pwd)"
+CATALOG="$SCRIPT_DIR/../src-tauri/resources/model_catalog.json"
+MODELS_DIR="${MODELS_DIR:-}"
+
+if [[ "${1:-}" == "--check" ]]; then
+    python3 - "$CATALOG" <<'EOF'
+import json, sys
+models = json.load(open(sys.argv[1]))["models"]
+missing = [m["id"] for m in models if not m.get("sha256")]
+if missing:
+    sys.exit("Models without sha256: " + ", ".join(missing))
+EOF
+    echo "All built-in models have a sha256"
+    exit 0
+fi
+
+if command -v sha256sum >/dev/null; then
+    hash_file() { sha256sum "$1" | cut -d' ' -f1; }
+else
+    hash_file() { shasum -a 256 "$1" | cut -d' ' -f1; }
+fi
+
+TMP_DIR="$(mktemp -d)"
+trap 'rm -rf "$TMP_DIR"' EXIT
+
+python3 - "$CATALOG" <<'EOF' > "$TMP_DIR/models.tsv"
+import json, sys
+for m in json.load(open(sys.argv[1]))["models"]:
+    print("\t".join([m["id"], m["filename"], m.get("url", ""), m.get("sha256") or ""]))
+EOF
+
+: > "$TMP_DIR/digests.tsv"
+while IFS=$'\t' read -r id filename url expected; do
+    if [[ -n "$MODELS_DIR" && -f "$MODELS_DIR/$filename" ]]; then
+        file="$MODELS_DIR/$filename"
+    else
+        echo "==> Téléchargement de $id ($url)"
+        file="$TMP_DIR/$filename"
+        curl -fL --retry 3 -o "$file" "$url"
+    fi
+    digest="$(hash_file "$file")"
+    [[ "$file" == "$TMP_DIR/"* ]] && rm -f "$file"
+
+    if [[ -n "$expected" && "$expected" != "$digest" ]]; then
+        echo "ERREUR: $id : sha256 $digest, le catalogue attend $expected" >&2
+        exit 1
+    fi
+    echo "    $id  $digest"
+    printf '%s\t%s\n' "$id" "$digest" >> "$TMP_DIR/digests.tsv"
+done < "$TMP_DIR/models.tsv"
+
+# Réécrit le catalogue en gardant l'ordre des clés, sha256 après size_mb
+python3 - "$CATALOG" "$TMP_DIR/digests.tsv" <<'EOF'
+import json, sys
+catalog_path, digests_path = sys.argv[1:]
+digests = dict(line.rstrip("\n").split("\t") for line in open(digests_path))
+catalog = json.load(open(catalog_path))
+models = []
+for model in catalog["models"]:
+    entry = {}
+    for key, value in model.items():
+        if key == "sha256":
+            continue
+        entry[key] = value
+        if key == "size_mb":
+            entry["sha256"] = digests[model["id"]]
+    models.append(entry)
+catalog["models"] = models
+with open(catalog_path, "w") as f:
+    json.dump(catalog, f, indent=2, ensure_ascii=False)
+    f.write("\n")
+EOF
+
+echo "Catalogue mis à jour : $CATALOG"
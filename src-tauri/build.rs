@@ -2,11 +2,19 @@ fn main() {
     // Déclarer whisper_native comme cfg connu (évite les warnings unexpected_cfgs)
     println!("cargo:rustc-check-cfg=cfg(whisper_native)");
     println!("cargo:rustc-check-cfg=cfg(whisper_coreml)");
+    println!("cargo:rustc-check-cfg=cfg(whisper_blas)");
 
     // Lier whisper.cpp si compilé (Task 3 — scripts/build-whisper.sh)
     #[cfg(all(target_os = "macos", target_arch = "aarch64"))]
     link_whisper_cpp();
 
+    // Linux : CPU (AVX2 / NEON) + OpenBLAS si détecté par le script
+    #[cfg(all(
+        target_os = "linux",
+        any(target_arch = "x86_64", target_arch = "aarch64")
+    ))]
+    link_whisper_cpp_linux();
+
     // Compiler et lier le plugin Swift AccessibilityPaste (macOS uniquement)
     #[cfg(target_os = "macos")]
     compile_swift_plugins();
@@ -212,6 +220,100 @@ fn link_whisper_cpp() {
     }
 }
 
+/// Lier whisper.cpp sur Linux (x86_64 / aarch64), backend CPU + OpenBLAS optionnel
+///
+/// Prérequis : scripts/build-whisper.sh (détecte AVX2 et OpenBLAS, écrit le CMakeCache).
+/// Les options réellement compilées sont relues depuis CMakeCache.txt pour lier
+/// les bonnes dépendances système (openblas, gomp).
+#[cfg(all(
+    target_os = "linux",
+    any(target_arch = "x86_64", target_arch = "aarch64")
+))]
+fn link_whisper_cpp_linux() {
+    use std::io::Write;
+    use std::path::Path;
+    use std::process::{Command, Stdio};
+
+    let manifest_dir = std::env::var("CARGO_MANIFEST_DIR").unwrap();
+    let whisper_dir = Path::new(&manifest_dir)
+        .parent()
+        .unwrap()
+        .join("vendor/whisper.cpp");
+    let build_dir = whisper_dir.join("build");
+
+    println!("cargo:rerun-if-changed=../vendor/whisper.cpp/build/src/libwhisper.a");
+    println!("cargo:rerun-if-changed=../vendor/whisper.cpp/build/CMakeCache.txt");
+
+    let whisper_lib = build_dir.join("src/libwhisper.a");
+    let ggml_libs = [
+        build_dir.join("ggml/src/libggml.a"),
+        build_dir.join("ggml/src/libggml-base.a"),
+        build_dir.join("ggml/src/libggml-cpu.a"),
+    ];
+
+    if !whisper_lib.exists() || ggml_libs.iter().any(|l| !l.exists()) {
+        println!(
+            "cargo:warning=whisper.cpp non compilé — mode stub actif. \
+             Exécuter scripts/build-whisper.sh pour activer le backend natif."
+        );
+        return;
+    }
+
+    let cmake_cache = std::fs::read_to_string(build_dir.join("CMakeCache.txt")).unwrap_or_default();
+    let cmake_option = |name: &str| {
+        cmake_cache.lines().any(|line| {
+            line.split_once('=').is_some_and(|(key, value)| {
+                key.split(':').next() == Some(name) && value.eq_ignore_ascii_case("ON")
+            })
+        })
+    };
+    let blas_lib = build_dir.join("ggml/src/ggml-blas/libggml-blas.a");
+    let blas = cmake_option("GGML_BLAS") && blas_lib.exists();
+
+    cc::Build::new()
+        .file("src/whisper_wrapper.c")
+        .include(whisper_dir.join("include"))
+        .include(whisper_dir.join("ggml/include"))
+        .compile("whisper_wrapper");
+
+    // Même fusion que sur macOS (conflit de nom avec la libwhisper.a de whisper-rs-sys),
+    // via un script MRI pour `ar` faute de libtool
+    let out_dir = std::env::var("OUT_DIR").unwrap();
+    let merged = format!("{out_dir}/libwhisper_full.a");
+    let mut mri = format!("create {merged}\naddlib {}\n", whisper_lib.display());
+    for lib in ggml_libs.iter().chain(blas.then_some(&blas_lib)) {
+        mri.push_str(&format!("addlib {}\n", lib.display()));
+    }
+    mri.push_str("save\nend\n");
+
+    let mut ar = Command::new("ar")
+        .arg("-M")
+        .stdin(Stdio::piped())
+        .spawn()
+        .expect("ar non trouvé");
+    ar.stdin.take().unwrap().write_all(mri.as_bytes()).unwrap();
+    if !ar.wait().map(|s| s.success()).unwrap_or(false) {
+        panic!("ar failed: impossible de fusionner les libs whisper+ggml");
+    }
+
+    println!("cargo:rustc-link-search=native={out_dir}");
+    println!("cargo:rustc-link-lib=static=whisper_full");
+    println!("cargo:rustc-link-lib=dylib=stdc++");
+    if cmake_option("GGML_OPENMP") {
+        println!("cargo:rustc-link-lib=dylib=gomp");
+    }
+    if blas {
+        println!("cargo:rustc-link-lib=dylib=openblas");
+        println!("cargo:rustc-cfg=whisper_blas");
+    }
+
+    println!("cargo:rustc-cfg=whisper_native");
+    println!(
+        "cargo:warning=whisper.cpp natif activé (CPU{})",
+        if blas { " + OpenBLAS" } else { "" }
+    );
+}
+
 /// Generate tray menu translations from frontend locale files.
 ///
 /// Source of truth: src/i18n/locales/*/translation.json
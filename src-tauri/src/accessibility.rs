@@ -64,6 +64,47 @@ pub fn save_target_app() {
     unsafe { func() }
 }
 
+/// Nom de l'application memorisee par `save_target_app` (ex: "Slack").
+#[cfg(target_os = "macos")]
+pub fn target_app_name() -> Option<String> {
+    let sym = unsafe { libc::dlsym(libc::RTLD_DEFAULT, b"accessibility_target_app_name\0".as_ptr() as *const _) };
+    if sym.is_null() {
+        return None;
+    }
+    let func: unsafe extern "C" fn() -> *mut c_char = unsafe { std::mem::transmute(sym) };
+    let ptr = unsafe { func() };
+    if ptr.is_null() {
+        return None;
+    }
+    // Chaine allouee par strdup cote Swift
+    let name = unsafe { std::ffi::CStr::from_ptr(ptr) }
+        .to_string_lossy()
+        .into_owned();
+    unsafe { libc::free(ptr as *mut libc::c_void) };
+    Some(name)
+}
+
+/// Empreinte de l'element qui a le focus clavier (pid + hash AX), ou None si
+/// indisponible (permission absente, symbole non linke).
+#[cfg(target_os = "macos")]
+pub fn focused_element_id() -> Option<String> {
+    let sym = unsafe { libc::dlsym(libc::RTLD_DEFAULT, b"accessibility_focused_element_id\0".as_ptr() as *const _) };
+    if sym.is_null() {
+        return None;
+    }
+    let func: unsafe extern "C" fn() -> *mut c_char = unsafe { std::mem::transmute(sym) };
+    let ptr = unsafe { func() };
+    if ptr.is_null() {
+        return None;
+    }
+    // Chaine allouee par strdup cote Swift
+    let id = unsafe { std::ffi::CStr::from_ptr(ptr) }
+        .to_string_lossy()
+        .into_owned();
+    unsafe { libc::free(ptr as *mut libc::c_void) };
+    Some(id)
+}
+
 /// Demande les permissions Accessibility (affiche la dialog systeme macOS).
 #[cfg(target_os = "macos")]
 pub fn request_permission() {
@@ -83,6 +124,16 @@ pub fn paste_via_accessibility(_text: &str) -> Result<i32, String> {
 #[cfg(not(target_os = "macos"))]
 pub fn save_target_app() {}
 
+#[cfg(not(target_os = "macos"))]
+pub fn target_app_name() -> Option<String> {
+    None
+}
+
+#[cfg(not(target_os = "macos"))]
+pub fn focused_element_id() -> Option<String> {
+    None
+}
+
 #[cfg(not(target_os = "macos"))]
 pub fn check_permission() -> bool {
     false
@@ -1,9 +1,20 @@
 use crate::audio_feedback::{play_feedback_sound, play_feedback_sound_blocking, SoundType};
+use crate::audio_toolkit::constants::WHISPER_SAMPLE_RATE;
+use crate::llm::stream::{self, StreamCancel};
 use crate::managers::audio::AudioRecordingManager;
 use crate::managers::history::HistoryManager;
 use crate::managers::transcription::TranscriptionManager;
-use crate::settings::{get_settings, AppSettings};
+use crate::output::{self, OutputPayload};
+use crate::pipeline::context::{self as session_context, RecentDictation};
+use crate::pipeline::recipe::{self, RecipeStepOutput, RecipeVariables};
+use crate::pipeline::redaction::{self, RedactionSession, Redactor};
+use crate::settings::{
+    get_settings, is_custom_binding, AppSettings, LLMPrompt, LlmStreamMode, OutputSink,
+    PasteMethod, PostProcessProvider, PostProcessRecipe, RecipeStep, RedactionPolicy,
+    APPLE_INTELLIGENCE_PROVIDER_ID,
+};
 use crate::shortcut;
+use crate::stats::{DictationMetrics, Routing};
 use crate::tray::{change_tray_icon, TrayIconState};
 use crate::utils::{
     self, show_error_overlay, show_processing_overlay, show_recording_overlay,
@@ -13,22 +24,99 @@ use crate::TranscriptionCoordinator;
 use log::{debug, error, info, warn};
 use once_cell::sync::Lazy;
 use std::collections::HashMap;
-use std::sync::Arc;
-use std::time::Instant;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
 use tauri::{AppHandle, Emitter};
 use tauri::Manager;
+use tauri_plugin_clipboard_manager::ClipboardExt;
 
 /// Drop guard that notifies the [`TranscriptionCoordinator`] when the
 /// transcription pipeline finishes — whether it completes normally or panics.
-struct FinishGuard(AppHandle);
+/// Also closes the cancellable operation and, when streaming kept it alive
+/// past `stop`, the cancel shortcut.
+struct FinishGuard {
+    app: AppHandle,
+    cancel: StreamCancel,
+    owns_cancel_shortcut: bool,
+}
 impl Drop for FinishGuard {
     fn drop(&mut self) {
-        if let Some(c) = self.0.try_state::<TranscriptionCoordinator>() {
+        stream::end_operation(&self.cancel);
+        if self.owns_cancel_shortcut {
+            shortcut::unregister_cancel_shortcut(&self.app);
+        }
+        if let Some(c) = self.app.try_state::<TranscriptionCoordinator>() {
             c.notify_processing_finished();
         }
     }
 }
 
+/// Délai max sans nouveau token avant d'abandonner une génération en streaming
+const STREAM_IDLE_TIMEOUT: Duration = Duration::from_secs(15);
+
+/// Sortie progressive d'une génération LLM : aperçu dans l'overlay et, en
+/// mode `Type` avec le collage `Direct`, frappe au fil de l'eau.
+struct StreamOutput {
+    app: AppHandle,
+    enabled: bool,
+    type_text: bool,
+    /// Texte déjà envoyé à l'application cible
+    typed: Mutex<String>,
+}
+
+impl StreamOutput {
+    fn new(app: &AppHandle, settings: &AppSettings, allow_typing: bool) -> Self {
+        let mode = settings.llm_stream_mode;
+        Self {
+            app: app.clone(),
+            enabled: mode != LlmStreamMode::Off,
+            type_text: allow_typing
+                && mode == LlmStreamMode::Type
+                && settings.paste_method == PasteMethod::Direct
+                && settings.pastes_output(),
+            typed: Mutex::new(String::new()),
+        }
+    }
+
+    fn on_progress(&self, text: &str) {
+        utils::show_llm_preview(&self.app, text);
+        if !self.type_text {
+            return;
+        }
+
+        let mut typed = self.typed.lock().unwrap();
+        let chunk = match stream::untyped_suffix(text, &typed) {
+            Some(chunk) if !chunk.is_empty() => chunk.to_string(),
+            _ => return,
+        };
+        typed.push_str(&chunk);
+        let app = self.app.clone();
+        let _ = self.app.run_on_main_thread(move || {
+            if let Err(e) = utils::type_streamed_chunk(&chunk, &app) {
+                warn!("Frappe progressive impossible : {}", e);
+            }
+        });
+    }
+
+    fn typed(&self) -> String {
+        self.typed.lock().unwrap().clone()
+    }
+
+    /// Efface ce qui a été tapé (génération annulée ou en échec)
+    fn rollback(&self) {
+        let typed = std::mem::take(&mut *self.typed.lock().unwrap());
+        if typed.is_empty() {
+            return;
+        }
+        let app = self.app.clone();
+        let _ = self.app.run_on_main_thread(move || {
+            if let Err(e) = utils::erase_streamed_text(typed.chars().count(), &app) {
+                warn!("Effacement de la frappe progressive impossible : {}", e);
+            }
+        });
+    }
+}
+
 // Shortcut Action Trait
 pub trait ShortcutAction: Send + Sync {
     fn start(&self, app: &AppHandle, binding_id: &str, shortcut_str: &str);
@@ -68,7 +156,77 @@ fn get_rss_mb() -> u64 {
         .unwrap_or(0)
 }
 
-async fn post_process_transcription(settings: &AppSettings, transcription: &str) -> Option<String> {
+/// Prompt sélectionné pour le raccourci de post-traitement
+fn selected_prompt(settings: &AppSettings) -> Option<&LLMPrompt> {
+    let id = settings.post_process_selected_prompt_id.as_ref()?;
+    settings.post_process_prompts.iter().find(|p| &p.id == id)
+}
+
+/// Dictées récentes de la session pour le nettoyage LLM et le prompt Whisper
+/// (voir [`session_context`]), `None` si le réglage est désactivé
+fn recent_session_context(
+    hm: &HistoryManager,
+    settings: &AppSettings,
+    target_app: Option<&str>,
+) -> Option<String> {
+    let limits = settings.session_context_limits()?;
+    let now = chrono::Utc::now().timestamp();
+    let since = now - limits.inactivity_secs * limits.max_entries as i64;
+    let entries = match hm.get_recent_dictations(since, limits.max_entries) {
+        Ok(entries) => entries,
+        Err(e) => {
+            warn!("[Context] Historique illisible : {}", e);
+            return None;
+        }
+    };
+    let recent: Vec<RecentDictation> = entries
+        .into_iter()
+        .map(|entry| RecentDictation {
+            text: entry
+                .post_processed_text
+                .unwrap_or(entry.transcription_text),
+            timestamp: entry.timestamp,
+            app: entry.metrics.and_then(|m| m.target_app),
+        })
+        // Coffre verrouillé : le texte n'est pas disponible
+        .filter(|dictation| dictation.text != crate::vault::LOCKED_PLACEHOLDER)
+        .collect();
+    let context = session_context::build(&recent, target_app, now, &limits)?;
+    debug!(
+        "[Context] {} dictée(s), ~{} tokens",
+        context.lines().count(),
+        session_context::estimate_tokens(&context)
+    );
+    Some(context)
+}
+
+/// Variables de contexte des recettes et des prompts (`${raw}`, `${app}`…)
+fn recipe_variables(
+    app: &AppHandle,
+    settings: &AppSettings,
+    raw: &str,
+    read_clipboard: bool,
+) -> RecipeVariables {
+    RecipeVariables {
+        raw: raw.to_string(),
+        language: settings.selected_language.clone(),
+        app: crate::accessibility::target_app_name().unwrap_or_default(),
+        date: chrono::Local::now().format("%Y-%m-%d").to_string(),
+        clipboard: if read_clipboard {
+            app.clipboard().read_text().unwrap_or_default()
+        } else {
+            String::new()
+        },
+    }
+}
+
+async fn post_process_transcription(
+    settings: &AppSettings,
+    transcription: &str,
+    vars: &RecipeVariables,
+    cancel: &StreamCancel,
+    output: &StreamOutput,
+) -> Option<String> {
     let provider = match settings.active_post_process_provider().cloned() {
         Some(provider) => provider,
         None => {
@@ -119,22 +277,284 @@ async fn post_process_transcription(settings: &AppSettings, transcription: &str)
         return None;
     }
 
+    let prompt = recipe::expand_context(&prompt, vars);
+    run_llm_prompt(
+        settings,
+        &provider,
+        &model,
+        &prompt,
+        transcription,
+        cancel,
+        output,
+    )
+    .await
+}
+
+/// Fournisseur et modèle d'une étape LLM de recette, ceux actifs par défaut
+fn resolve_step_llm(
+    settings: &AppSettings,
+    provider_id: Option<&str>,
+    model: Option<&str>,
+) -> Option<(PostProcessProvider, String)> {
+    let provider = match provider_id {
+        Some(id) => settings.post_process_provider(id),
+        None => settings.active_post_process_provider(),
+    }?
+    .clone();
+    let model = model
+        .filter(|m| !m.trim().is_empty())
+        .map(str::to_string)
+        .or_else(|| settings.post_process_models.get(&provider.id).cloned())
+        .filter(|m| !m.trim().is_empty())?;
+    Some((provider, model))
+}
+
+/// Applique une recette étape par étape. Une étape en échec interrompt la
+/// chaîne : le texte final est la sortie de la dernière étape réussie.
+async fn run_recipe(
+    app: &AppHandle,
+    settings: &AppSettings,
+    recipe: &PostProcessRecipe,
+    input: &str,
+    vars: &RecipeVariables,
+    cancel: &StreamCancel,
+    output: &StreamOutput,
+) -> Vec<RecipeStepOutput> {
+    // Les étapes intermédiaires restent en aperçu : seule la dernière peut taper
+    let preview = StreamOutput::new(app, settings, false);
+    let mut outputs: Vec<RecipeStepOutput> = Vec::with_capacity(recipe.steps.len());
+
+    for (index, step) in recipe.steps.iter().enumerate() {
+        if cancel.is_cancelled() {
+            break;
+        }
+        let current = outputs.last().map_or(input, |o| o.output.as_str());
+        let mut detail = None;
+        let result = match step {
+            RecipeStep::Llm {
+                prompt,
+                provider_id,
+                model,
+            } => match resolve_step_llm(settings, provider_id.as_deref(), model.as_deref()) {
+                Some((provider, model)) => {
+                    detail = Some(format!("{} · {}", provider.label, model));
+                    let step_output = if index + 1 == recipe.steps.len() {
+                        output
+                    } else {
+                        &preview
+                    };
+                    let prompt = recipe::expand_context(prompt, vars);
+                    run_llm_prompt(
+                        settings,
+                        &provider,
+                        &model,
+                        &prompt,
+                        current,
+                        cancel,
+                        step_output,
+                    )
+                    .await
+                }
+                None => {
+                    warn!(
+                        "[Recette] Étape {} : fournisseur ou modèle introuvable",
+                        index + 1
+                    );
+                    None
+                }
+            },
+            _ => recipe::apply_local(step, current, vars),
+        };
+
+        match result {
+            Some(text) => outputs.push(RecipeStepOutput {
+                kind: recipe::step_kind(step).to_string(),
+                detail,
+                output: text,
+            }),
+            None => {
+                warn!(
+                    "[Recette] « {} » interrompue à l'étape {}/{}",
+                    recipe.name,
+                    index + 1,
+                    recipe.steps.len()
+                );
+                break;
+            }
+        }
+    }
+    outputs
+}
+
+/// Fichier d'audit du masquage, dans le dossier de données de l'app
+const REDACTION_AUDIT_FILE: &str = "pii_audit.jsonl";
+
+/// true si la dictée doit être masquée avant l'envoi à ce fournisseur
+fn redaction_applies(settings: &AppSettings, provider: &PostProcessProvider) -> bool {
+    match settings.redaction_policy(&provider.id) {
+        RedactionPolicy::Always => true,
+        RedactionPolicy::Never => false,
+        RedactionPolicy::CloudOnly => {
+            provider.id != APPLE_INTELLIGENCE_PROVIDER_ID && !crate::llm_client::is_local(provider)
+        }
+    }
+}
+
+fn audit_redaction(app: &AppHandle, provider: &PostProcessProvider, session: &RedactionSession) {
+    let path = match app.path().app_data_dir() {
+        Ok(dir) => dir.join(REDACTION_AUDIT_FILE),
+        Err(e) => {
+            warn!(
+                "[PII] Dossier de données introuvable, audit non écrit : {}",
+                e
+            );
+            return;
+        }
+    };
+    if let Err(e) = redaction::append_audit(&path, &provider.id, session) {
+        warn!("[PII] Écriture du journal d'audit impossible : {}", e);
+    }
+}
+
+/// Envoie `input` au LLM avec un prompt déjà développé (hors `${output}`).
+/// Selon la politique du fournisseur, les données personnelles du prompt et
+/// du texte sont masquées avant l'envoi puis rétablies dans la réponse.
+async fn run_llm_prompt(
+    settings: &AppSettings,
+    provider: &PostProcessProvider,
+    model: &str,
+    prompt: &str,
+    input: &str,
+    cancel: &StreamCancel,
+    output: &StreamOutput,
+) -> Option<String> {
+    if !crate::baseline::provider_allowed(provider) {
+        warn!(
+            "Post-processing skipped: provider '{}' is disallowed by the team baseline",
+            provider.id
+        );
+        return None;
+    }
+    if !redaction_applies(settings, provider) {
+        return request_llm_prompt(
+            settings, provider, model, prompt, input, cancel, output, None,
+        )
+        .await;
+    }
+
+    let redactor = Redactor::new(&settings.redaction_names);
+    let mut session = RedactionSession::default();
+    let redacted_input = redactor.redact(&mut session, input);
+    let redacted_prompt = redactor.redact(&mut session, prompt);
+    if session.is_empty() {
+        return request_llm_prompt(
+            settings, provider, model, prompt, input, cancel, output, None,
+        )
+        .await;
+    }
+
+    info!(
+        "[PII] {} élément(s) masqué(s) avant l'appel à '{}'",
+        session.len(),
+        provider.id
+    );
+    audit_redaction(&output.app, provider, &session);
+
+    request_llm_prompt(
+        settings,
+        provider,
+        model,
+        &redacted_prompt,
+        &redacted_input,
+        cancel,
+        output,
+        Some(&session),
+    )
+    .await
+    .map(|text| session.restore(&text))
+}
+
+/// Streaming si activé, sinon sortie structurée, sinon prompt legacy.
+/// `redaction` rétablit les valeurs masquées dans l'aperçu progressif.
+#[allow(clippy::too_many_arguments)]
+async fn request_llm_prompt(
+    settings: &AppSettings,
+    provider: &PostProcessProvider,
+    model: &str,
+    prompt: &str,
+    input: &str,
+    cancel: &StreamCancel,
+    output: &StreamOutput,
+    redaction: Option<&RedactionSession>,
+) -> Option<String> {
     debug!(
         "Starting LLM post-processing with provider '{}' (model: {})",
         provider.id, model
     );
 
-    let api_key = settings
-        .post_process_api_keys
-        .get(&provider.id)
-        .cloned()
-        .unwrap_or_default();
+    let api_key = settings.api_key(&provider.id);
+
+    if output.enabled {
+        // Sans structured outputs : un JSON partiel n'est pas affichable
+        let (user_content, system_prompt) = if provider.supports_structured_output {
+            (input.to_string(), Some(build_system_prompt(prompt)))
+        } else {
+            (prompt.replace("${output}", input), None)
+        };
+        let streamed = match crate::llm_client::stream_chat_completion(
+            provider,
+            api_key.clone(),
+            model,
+            user_content,
+            system_prompt,
+        )
+        .await
+        {
+            Ok(tokens) => {
+                stream::collect(
+                    tokens,
+                    cancel,
+                    STREAM_IDLE_TIMEOUT,
+                    |partial| match redaction {
+                        Some(session) => output.on_progress(&session.restore_partial(partial)),
+                        None => output.on_progress(partial),
+                    },
+                )
+                .await
+                .map_err(|e| e.to_string())
+            }
+            Err(e) => Err(e),
+        };
+
+        match streamed {
+            Ok(content) if !content.trim().is_empty() => {
+                let content = strip_invisible_chars(content.trim());
+                debug!(
+                    "Streaming post-processing succeeded for provider '{}'. Output length: {} chars",
+                    provider.id,
+                    content.len()
+                );
+                return Some(content);
+            }
+            _ if cancel.is_cancelled() => return None,
+            Ok(_) => {
+                warn!("Streaming response from '{}' was empty", provider.id);
+            }
+            Err(e) => {
+                warn!(
+                    "Streaming failed for provider '{}': {}. Retrying without streaming.",
+                    provider.id, e
+                );
+            }
+        }
+        output.rollback();
+    }
 
     if provider.supports_structured_output {
         debug!("Using structured outputs for provider '{}'", provider.id);
 
-        let system_prompt = build_system_prompt(&prompt);
-        let user_content = transcription.to_string();
+        let system_prompt = build_system_prompt(prompt);
+        let user_content = input.to_string();
 
         // Define JSON schema for transcription output
         let json_schema = serde_json::json!({
@@ -150,9 +570,9 @@ async fn post_process_transcription(settings: &AppSettings, transcription: &str)
         });
 
         match crate::llm_client::send_chat_completion_with_schema(
-            &provider,
+            provider,
             api_key.clone(),
-            &model,
+            model,
             user_content,
             Some(system_prompt),
             Some(json_schema),
@@ -202,11 +622,10 @@ async fn post_process_transcription(settings: &AppSettings, transcription: &str)
     }
 
     // Legacy mode: Replace ${output} variable in the prompt with the actual text
-    let processed_prompt = prompt.replace("${output}", transcription);
+    let processed_prompt = prompt.replace("${output}", input);
     debug!("Processed prompt length: {} chars", processed_prompt.len());
 
-    match crate::llm_client::send_chat_completion(&provider, api_key, &model, processed_prompt)
-        .await
+    match crate::llm_client::send_chat_completion(provider, api_key, model, processed_prompt).await
     {
         Ok(Some(content)) => {
             let content = strip_invisible_chars(&content);
@@ -307,8 +726,13 @@ impl ShortcutAction for TranscribeAction {
     }
 
     fn stop(&self, app: &AppHandle, binding_id: &str, _shortcut_str: &str) {
-        // Unregister the cancel shortcut when transcription stops
-        shortcut::unregister_cancel_shortcut(app);
+        // Unregister the cancel shortcut when transcription stops — unless LLM
+        // output is streamed, where it stays active to interrupt generation
+        // (released by FinishGuard).
+        let keep_cancel_shortcut = get_settings(app).llm_stream_mode != LlmStreamMode::Off;
+        if !keep_cancel_shortcut {
+            shortcut::unregister_cancel_shortcut(app);
+        }
 
         let stop_time = Instant::now();
         debug!("TranscribeAction::stop called for binding: {}", binding_id);
@@ -330,8 +754,14 @@ impl ShortcutAction for TranscribeAction {
         let binding_id = binding_id.to_string(); // Clone binding_id for the async task
         let post_process = self.post_process;
 
+        let cancel = stream::begin_operation();
+
         tauri::async_runtime::spawn(async move {
-            let _guard = FinishGuard(ah.clone());
+            let _guard = FinishGuard {
+                app: ah.clone(),
+                cancel: cancel.clone(),
+                owns_cancel_shortcut: keep_cancel_shortcut,
+            };
             let pipeline_start = Instant::now();
             info!("[BENCH] 🔄 Pipeline STT démarré — RAM: {}MB", get_rss_mb());
             let binding_id = binding_id.clone(); // Clone for the inner async task
@@ -350,12 +780,23 @@ impl ShortcutAction for TranscribeAction {
                 );
 
                 let transcription_time = Instant::now();
-                let samples_clone = samples.clone(); // Clone for history saving
-                match tm.transcribe(samples) {
+                // Langue du profil de raccourci, à défaut celle des réglages
+                let binding_settings = get_settings(&ah).for_binding(&binding_id);
+                let language = binding_settings.selected_language.clone();
+                let target_app = crate::accessibility::target_app_name();
+                let recent_context =
+                    recent_session_context(&hm, &binding_settings, target_app.as_deref());
+                // Emprunt : le buffer est ensuite déplacé vers l'historique sans copie
+                match tm.transcribe_with_context(
+                    &samples,
+                    Some(&language),
+                    recent_context.as_deref(),
+                ) {
                     Ok(output) => {
                         let raw_transcription = output.text;
                         let confidence = output.confidence;
                         let stt_duration_ms = output.duration_ms;
+                        let hallucinations = output.hallucinations;
                         info!(
                             "[STT] Transcription brute : «{}» (confiance: {:.2}, durée: {:?})",
                             raw_transcription,
@@ -365,17 +806,59 @@ impl ShortcutAction for TranscribeAction {
 
                         // Pipeline hybride FR : règles locales → [LLM conditionnel]
                         // Routing : confiance >= 0.85 + ≤30 mots + Chat/Code → règles seules
-                        let settings_for_pipeline = get_settings(&ah);
+                        // Le profil du raccourci remplace mode, prompt, langue et collage
+                        let settings_for_pipeline = get_settings(&ah).for_binding(&binding_id);
+                        // Une recette associée au raccourci active le post-traitement
+                        let recipe = settings_for_pipeline
+                            .recipe_for_binding(&binding_id)
+                            .cloned();
+                        let post_process = post_process
+                            || recipe.is_some()
+                            || settings_for_pipeline.binding_post_process(&binding_id);
                         let write_mode = settings_for_pipeline
                             .write_mode
                             .parse::<crate::pipeline::modes::WriteMode>()
                             .unwrap_or_default();
+                        // La frappe progressive est réservée à la dernière étape LLM
+                        let cleanup_output =
+                            StreamOutput::new(&ah, &settings_for_pipeline, !post_process);
+                        let cleanup = |text: &str,
+                                       mode: crate::pipeline::modes::WriteMode,
+                                       hint: crate::pipeline::rules::StructureHint|
+                         -> anyhow::Result<String> {
+                            if cleanup_output.enabled {
+                                crate::llm::cleanup::run_streaming(
+                                    text,
+                                    mode,
+                                    hint,
+                                    recent_context.as_deref(),
+                                    &cancel,
+                                    &|partial| cleanup_output.on_progress(partial),
+                                )
+                            } else {
+                                crate::llm::cleanup::run_with_context(
+                                    text,
+                                    mode,
+                                    hint,
+                                    recent_context.as_deref(),
+                                )
+                            }
+                        };
                         let pipeline_result = crate::pipeline::orchestrator::process(
                             &raw_transcription,
                             confidence,
                             write_mode,
-                            Some(&crate::llm::cleanup::run),
+                            Some(&cleanup),
                         );
+                        if cancel.is_cancelled() {
+                            info!("[Pipeline] Dictée annulée pendant le traitement");
+                            cleanup_output.rollback();
+                            return;
+                        }
+                        if pipeline_result.llm_fallback {
+                            // Génération interrompue : la frappe partielle est remplacée
+                            cleanup_output.rollback();
+                        }
                         info!(
                             "[Pipeline] Texte final : «{}» | {}ms | {}",
                             pipeline_result.text,
@@ -385,13 +868,21 @@ impl ShortcutAction for TranscribeAction {
 
                         // Émettre un event si le LLM a échoué (fallback mid-session)
                         if pipeline_result.llm_fallback {
-                            let _ = ah.emit("llm-fallback", ());
+                            let _ = ah.emit("llm-fallback", crate::llm::cleanup::fallback_event());
                         }
 
+                        let llm_fallback = pipeline_result.llm_fallback;
+                        let routing = if pipeline_result.rules_only && !llm_fallback {
+                            Routing::RulesOnly
+                        } else {
+                            Routing::Llm
+                        };
+                        let (rules_ms, pipeline_llm_ms) =
+                            (pipeline_result.rules_ms, pipeline_result.llm_ms);
                         let transcription = pipeline_result.text;
 
                         if !transcription.is_empty() {
-                            let settings = get_settings(&ah);
+                            let settings = get_settings(&ah).for_binding(&binding_id);
                             let mut final_text = transcription.clone();
                             let mut post_processed_text: Option<String> = None;
                             let mut post_process_prompt: Option<String> = None;
@@ -400,39 +891,106 @@ impl ShortcutAction for TranscribeAction {
                             if post_process {
                                 show_processing_overlay(&ah);
                             }
-                            let processed = if post_process {
-                                post_process_transcription(&settings, &final_text).await
+                            let post_output = StreamOutput::new(&ah, &settings, true);
+                            let mut recipe_steps: Vec<RecipeStepOutput> = Vec::new();
+                            let post_process_time = Instant::now();
+                            let processed = match &recipe {
+                                Some(recipe) => {
+                                    let vars = recipe_variables(
+                                        &ah,
+                                        &settings,
+                                        &raw_transcription,
+                                        recipe::uses_clipboard(&recipe.steps),
+                                    );
+                                    recipe_steps = run_recipe(
+                                        &ah,
+                                        &settings,
+                                        recipe,
+                                        &final_text,
+                                        &vars,
+                                        &cancel,
+                                        &post_output,
+                                    )
+                                    .await;
+                                    recipe_steps.last().map(|step| step.output.clone())
+                                }
+                                None if post_process => {
+                                    let read_clipboard = selected_prompt(&settings)
+                                        .is_some_and(|p| p.prompt.contains("${clipboard}"));
+                                    let vars = recipe_variables(
+                                        &ah,
+                                        &settings,
+                                        &raw_transcription,
+                                        read_clipboard,
+                                    );
+                                    post_process_transcription(
+                                        &settings,
+                                        &final_text,
+                                        &vars,
+                                        &cancel,
+                                        &post_output,
+                                    )
+                                    .await
+                                }
+                                None => None,
+                            };
+                            if cancel.is_cancelled() {
+                                info!("[Pipeline] Dictée annulée pendant le post-traitement");
+                                post_output.rollback();
+                                return;
+                            }
+                            // Texte déjà tapé par la frappe progressive
+                            let typed = if post_process {
+                                post_output.typed()
                             } else {
-                                None
+                                cleanup_output.typed()
                             };
                             if let Some(processed_text) = processed {
                                 post_processed_text = Some(processed_text.clone());
                                 final_text = processed_text;
 
-                                // Get the prompt that was used
-                                if let Some(prompt_id) = &settings.post_process_selected_prompt_id {
-                                    if let Some(prompt) = settings
-                                        .post_process_prompts
-                                        .iter()
-                                        .find(|p| &p.id == prompt_id)
-                                    {
-                                        post_process_prompt = Some(prompt.prompt.clone());
-                                    }
+                                // Get the prompt that was used (recipes keep per-step outputs)
+                                if recipe.is_none() {
+                                    post_process_prompt =
+                                        selected_prompt(&settings).map(|p| p.prompt.clone());
                                 }
                             }
 
+                            let mut metrics = DictationMetrics {
+                                audio_ms: samples.len() as u64 * 1000 / WHISPER_SAMPLE_RATE as u64,
+                                stt_ms: stt_duration_ms,
+                                rules_ms,
+                                llm_ms: pipeline_llm_ms
+                                    + post_process_time.elapsed().as_millis() as u64,
+                                paste_ms: None,
+                                routing,
+                                llm_fallback,
+                                model_id: tm.get_current_model(),
+                                write_mode: settings_for_pipeline.write_mode.clone(),
+                                confidence,
+                                word_count: final_text.split_whitespace().count(),
+                                hallucinations,
+                                target_app: target_app.clone(),
+                            };
+                            // Durée du collage, connue seulement une fois celui-ci terminé
+                            let (paste_tx, paste_rx) = tokio::sync::oneshot::channel::<u64>();
+
                             // Save to history with post-processed text, prompt, and write mode
                             let hm_clone = Arc::clone(&hm);
                             let transcription_for_history = transcription.clone();
                             let write_mode_str = settings_for_pipeline.write_mode.clone();
                             tauri::async_runtime::spawn(async move {
+                                metrics.paste_ms = paste_rx.await.ok();
                                 if let Err(e) = hm_clone
                                     .save_transcription(
-                                        samples_clone,
+                                        samples,
                                         transcription_for_history,
                                         post_processed_text,
                                         post_process_prompt,
                                         Some(write_mode_str),
+                                        None,
+                                        recipe_steps,
+                                        Some(metrics),
                                     )
                                     .await
                                 {
@@ -440,16 +998,70 @@ impl ShortcutAction for TranscribeAction {
                                 }
                             });
 
+                            // Journal, fichier, stdout, webhook : en parallèle du collage
+                            let other_sinks: Vec<OutputSink> = settings
+                                .output_sinks
+                                .iter()
+                                .filter(|sink| **sink != OutputSink::Paste)
+                                .cloned()
+                                .collect();
+                            let delivery = (!other_sinks.is_empty()).then(|| {
+                                let payload = OutputPayload {
+                                    text: final_text.clone(),
+                                    raw_text: raw_transcription.clone(),
+                                    binding_id: binding_id.clone(),
+                                    language: settings.selected_language.clone(),
+                                    timestamp: chrono::Local::now(),
+                                };
+                                tauri::async_runtime::spawn_blocking(move || {
+                                    output::deliver(&other_sinks, &payload)
+                                })
+                            });
+                            if !settings.pastes_output() {
+                                let failed = match delivery {
+                                    Some(handle) => {
+                                        !matches!(handle.await, Ok(errors) if errors.is_empty())
+                                    }
+                                    None => false,
+                                };
+                                if failed {
+                                    show_error_overlay(&ah, "Échec de l'envoi");
+                                } else {
+                                    show_success_overlay(&ah);
+                                }
+                                change_tray_icon(&ah, TrayIconState::Idle);
+                                return;
+                            }
+
                             // Paste the final text (either processed or original)
                             let ah_clone = ah.clone();
                             let paste_time = Instant::now();
+                            let paste_method = settings.paste_method;
+                            let paste_format = settings.paste_format_for_app(
+                                crate::accessibility::target_app_name().as_deref(),
+                            );
                             ah.run_on_main_thread(move || {
-                                match utils::paste(final_text, ah_clone.clone()) {
+                                let pasted = if typed.is_empty() {
+                                    utils::paste_with_method(
+                                        final_text,
+                                        ah_clone.clone(),
+                                        paste_method,
+                                        paste_format,
+                                    )
+                                } else {
+                                    utils::finish_streamed_typing(
+                                        final_text,
+                                        &typed,
+                                        ah_clone.clone(),
+                                    )
+                                };
+                                match pasted {
                                     Ok(()) => {
                                         debug!(
                                             "Text pasted successfully in {:?}",
                                             paste_time.elapsed()
                                         );
+                                        let _ = paste_tx.send(paste_time.elapsed().as_millis() as u64);
                                         info!(
                                             "[BENCH] ✅ Pipeline complet: {}ms total | STT: {}ms | RAM: {}MB",
                                             pipeline_start.elapsed().as_millis(),
@@ -509,6 +1121,23 @@ impl ShortcutAction for CancelAction {
     }
 }
 
+struct UndoAction;
+
+impl ShortcutAction for UndoAction {
+    fn start(&self, _app: &AppHandle, _binding_id: &str, _shortcut_str: &str) {
+        // Attendre le relâchement : Shift+Gauche ne doit pas hériter des
+        // modificateurs du raccourci
+    }
+
+    fn stop(&self, app: &AppHandle, _binding_id: &str, _shortcut_str: &str) {
+        let app = app.clone();
+        std::thread::spawn(move || {
+            std::thread::sleep(Duration::from_millis(150));
+            utils::undo_last_dictation(&app, utils::UndoTrigger::Shortcut);
+        });
+    }
+}
+
 // Test Action
 struct TestAction;
 
@@ -532,6 +1161,20 @@ impl ShortcutAction for TestAction {
     }
 }
 
+/// Action des raccourcis de dictée personnalisés (`custom_*`)
+const CUSTOM_TRANSCRIBE_ACTION: &str = "custom";
+
+/// Action d'un raccourci : son entrée dans `ACTION_MAP`, ou la dictée
+/// personnalisée pour un raccourci créé par l'utilisateur
+pub fn action_for_binding(binding_id: &str) -> Option<&'static Arc<dyn ShortcutAction>> {
+    let key = if is_custom_binding(binding_id) {
+        CUSTOM_TRANSCRIBE_ACTION
+    } else {
+        binding_id
+    };
+    ACTION_MAP.get(key)
+}
+
 // Static Action Map
 pub static ACTION_MAP: Lazy<HashMap<String, Arc<dyn ShortcutAction>>> = Lazy::new(|| {
     let mut map = HashMap::new();
@@ -545,10 +1188,21 @@ pub static ACTION_MAP: Lazy<HashMap<String, Arc<dyn ShortcutAction>>> = Lazy::ne
         "transcribe_with_post_process".to_string(),
         Arc::new(TranscribeAction { post_process: true }) as Arc<dyn ShortcutAction>,
     );
+    // Le post-traitement dépend du profil du raccourci (voir `for_binding`)
+    map.insert(
+        CUSTOM_TRANSCRIBE_ACTION.to_string(),
+        Arc::new(TranscribeAction {
+            post_process: false,
+        }) as Arc<dyn ShortcutAction>,
+    );
     map.insert(
         "cancel".to_string(),
         Arc::new(CancelAction) as Arc<dyn ShortcutAction>,
     );
+    map.insert(
+        "undo_last_dictation".to_string(),
+        Arc::new(UndoAction) as Arc<dyn ShortcutAction>,
+    );
     map.insert(
         "test".to_string(),
         Arc::new(TestAction) as Arc<dyn ShortcutAction>,
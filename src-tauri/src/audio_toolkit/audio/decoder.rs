@@ -0,0 +1,227 @@
+use anyhow::{anyhow, Result};
+use log::{debug, warn};
+use std::fs::File;
+use std::io::Cursor;
+use std::path::Path;
+use std::time::Duration;
+use symphonia::core::audio::SampleBuffer;
+use symphonia::core::codecs::{DecoderOptions, CODEC_TYPE_NULL};
+use symphonia::core::errors::Error as SymphoniaError;
+use symphonia::core::formats::FormatOptions;
+use symphonia::core::io::MediaSourceStream;
+use symphonia::core::meta::MetadataOptions;
+use symphonia::core::probe::Hint;
+
+use super::resampler::FrameResampler;
+use crate::audio_toolkit::constants::WHISPER_SAMPLE_RATE;
+
+/// Extensions acceptées pour l'import (audio + pistes audio des vidéos MP4/MOV)
+pub const SUPPORTED_IMPORT_EXTENSIONS: &[&str] = &[
+    "wav", "mp3", "m4a", "aac", "mp4", "mov", "ogg", "oga", "flac",
+];
+
+pub fn is_supported_import_file(path: &Path) -> bool {
+    path.extension()
+        .and_then(|e| e.to_str())
+        .map(|e| SUPPORTED_IMPORT_EXTENSIONS.contains(&e.to_lowercase().as_str()))
+        .unwrap_or(false)
+}
+
+/// Moyenne des canaux d'un buffer entrelacé
+pub fn downmix_to_mono(interleaved: &[f32], channels: usize) -> Vec<f32> {
+    if channels <= 1 {
+        return interleaved.to_vec();
+    }
+    interleaved
+        .chunks_exact(channels)
+        .map(|frame| frame.iter().sum::<f32>() / channels as f32)
+        .collect()
+}
+
+/// Décode un fichier audio/vidéo et le convertit en PCM f32 16 kHz mono.
+///
+/// `on_progress` reçoit l'avancement [0.0, 1.0] quand la durée est connue
+/// dans l'en-tête du conteneur, au plus une fois par point de pourcentage.
+pub fn decode_audio_file(path: &Path, on_progress: impl FnMut(f32)) -> Result<Vec<f32>> {
+    let file = File::open(path)?;
+    let mss = MediaSourceStream::new(Box::new(file), Default::default());
+    let extension = path.extension().and_then(|e| e.to_str());
+    decode_media(mss, extension, &path.display().to_string(), on_progress)
+}
+
+/// Décode un enregistrement déjà chargé en mémoire (déchiffré au besoin).
+///
+/// L'extension sert d'indice de format ; `opus` passe par le décodeur Ogg
+/// Opus (feature `opus`), les autres formats par symphonia.
+pub fn decode_audio_bytes(data: Vec<u8>, extension: Option<&str>) -> Result<Vec<f32>> {
+    if extension == Some("opus") {
+        #[cfg(feature = "opus")]
+        return super::opus::decode_ogg_opus(&data);
+        #[cfg(not(feature = "opus"))]
+        return Err(anyhow!("Opus support is not enabled in this build"));
+    }
+    let mss = MediaSourceStream::new(Box::new(Cursor::new(data)), Default::default());
+    decode_media(mss, extension, "<mémoire>", |_| {})
+}
+
+fn decode_media(
+    mss: MediaSourceStream,
+    extension: Option<&str>,
+    label: &str,
+    mut on_progress: impl FnMut(f32),
+) -> Result<Vec<f32>> {
+    let mut hint = Hint::new();
+    if let Some(ext) = extension {
+        hint.with_extension(ext);
+    }
+
+    let probed = symphonia::default::get_probe().format(
+        &hint,
+        mss,
+        &FormatOptions::default(),
+        &MetadataOptions::default(),
+    )?;
+    let mut format = probed.format;
+
+    let track = format
+        .tracks()
+        .iter()
+        .find(|t| t.codec_params.codec != CODEC_TYPE_NULL)
+        .ok_or_else(|| anyhow!("Aucune piste audio dans {}", label))?;
+    let track_id = track.id;
+    let sample_rate = track
+        .codec_params
+        .sample_rate
+        .ok_or_else(|| anyhow!("Fréquence d'échantillonnage inconnue"))?;
+    let total_frames = track.codec_params.n_frames;
+
+    let mut decoder =
+        symphonia::default::get_codecs().make(&track.codec_params, &DecoderOptions::default())?;
+
+    let mut resampler = FrameResampler::new(
+        sample_rate as usize,
+        WHISPER_SAMPLE_RATE as usize,
+        Duration::from_millis(30),
+    );
+    let mut output = Vec::new();
+    let mut sample_buf: Option<SampleBuffer<f32>> = None;
+    let mut decoded_frames: u64 = 0;
+    // Un appel par paquet inonderait l'IPC sur les fichiers longs
+    let mut last_percent = None;
+
+    loop {
+        let packet = match format.next_packet() {
+            Ok(packet) => packet,
+            Err(SymphoniaError::IoError(e)) if e.kind() == std::io::ErrorKind::UnexpectedEof => {
+                break
+            }
+            Err(SymphoniaError::ResetRequired) => break,
+            Err(e) => return Err(e.into()),
+        };
+        if packet.track_id() != track_id {
+            continue;
+        }
+
+        let decoded = match decoder.decode(&packet) {
+            Ok(decoded) => decoded,
+            // Paquet corrompu : on l'ignore plutôt que d'abandonner tout le fichier
+            Err(SymphoniaError::DecodeError(e)) => {
+                warn!("Paquet audio ignoré ({}): {}", label, e);
+                continue;
+            }
+            Err(e) => return Err(e.into()),
+        };
+
+        let spec = *decoded.spec();
+        let channels = spec.channels.count();
+        let needed = decoded.capacity() * channels;
+        if !matches!(&sample_buf, Some(b) if b.capacity() >= needed) {
+            sample_buf = Some(SampleBuffer::<f32>::new(decoded.capacity() as u64, spec));
+        }
+        let buf = sample_buf.as_mut().unwrap();
+        buf.copy_interleaved_ref(decoded);
+
+        let mono = downmix_to_mono(buf.samples(), channels);
+        decoded_frames += mono.len() as u64;
+        resampler.push(&mono, |frame| output.extend_from_slice(frame));
+
+        if let Some(total) = total_frames.filter(|t| *t > 0) {
+            let percent = (decoded_frames * 100 / total).min(100);
+            if last_percent != Some(percent) {
+                last_percent = Some(percent);
+                on_progress(percent as f32 / 100.0);
+            }
+        }
+    }
+
+    resampler.finish(|frame| output.extend_from_slice(frame));
+    if last_percent != Some(100) {
+        on_progress(1.0);
+    }
+
+    debug!(
+        "Décodé {} ({} Hz, {:.1}s) → {} samples 16 kHz",
+        label,
+        sample_rate,
+        decoded_frames as f32 / sample_rate as f32,
+        output.len()
+    );
+    Ok(output)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use hound::{SampleFormat, WavSpec, WavWriter};
+
+    #[test]
+    fn downmix_averages_channels() {
+        let stereo = [1.0, 0.0, 0.5, 0.5, -1.0, 1.0];
+        assert_eq!(downmix_to_mono(&stereo, 2), vec![0.5, 0.5, 0.0]);
+        assert_eq!(downmix_to_mono(&[0.1, 0.2], 1), vec![0.1, 0.2]);
+    }
+
+    #[test]
+    fn supported_extensions_are_case_insensitive() {
+        assert!(is_supported_import_file(Path::new("/tmp/memo.M4A")));
+        assert!(is_supported_import_file(Path::new("note.mp3")));
+        assert!(!is_supported_import_file(Path::new("notes.txt")));
+        assert!(!is_supported_import_file(Path::new("sans_extension")));
+    }
+
+    #[test]
+    fn decode_stereo_48k_wav_to_16k_mono() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("memo.wav");
+        let spec = WavSpec {
+            channels: 2,
+            sample_rate: 48000,
+            bits_per_sample: 16,
+            sample_format: SampleFormat::Int,
+        };
+        let mut writer = WavWriter::create(&path, spec).unwrap();
+        for i in 0..48000 {
+            let s = ((i as f32 * 440.0 * 2.0 * std::f32::consts::PI / 48000.0).sin()
+                * i16::MAX as f32
+                * 0.5) as i16;
+            writer.write_sample(s).unwrap();
+            writer.write_sample(s).unwrap();
+        }
+        writer.finalize().unwrap();
+
+        let mut progress = Vec::new();
+        let samples = decode_audio_file(&path, |p| progress.push(p)).unwrap();
+
+        // 1 s à 16 kHz, à une trame de padding du resampler près
+        assert!(
+            (15_000..=17_500).contains(&samples.len()),
+            "got {} samples",
+            samples.len()
+        );
+        // Au plus un appel par point de pourcentage, en progression stricte
+        assert_eq!(progress.last(), Some(&1.0));
+        assert!(progress.len() <= 101, "{} progress calls", progress.len());
+        assert!(progress.windows(2).all(|w| w[0] < w[1]), "{:?}", progress);
+        assert!(samples.iter().any(|s| s.abs() > 0.1));
+    }
+}
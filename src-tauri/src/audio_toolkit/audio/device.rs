@@ -29,6 +29,15 @@ pub fn list_input_devices() -> Result<Vec<CpalDeviceInfo>, Box<dyn std::error::E
     Ok(out)
 }
 
+/// Premier périphérique de la liste de préférence (ordre décroissant) présent
+/// parmi les périphériques branchés. `None` = aucun : utiliser le défaut système.
+pub fn pick_preferred_device<'a>(available: &[String], preferred: &'a [String]) -> Option<&'a str> {
+    preferred
+        .iter()
+        .find(|name| available.iter().any(|a| a == *name))
+        .map(|name| name.as_str())
+}
+
 pub fn list_output_devices() -> Result<Vec<CpalDeviceInfo>, Box<dyn std::error::Error>> {
     let host = crate::audio_toolkit::get_cpal_host();
     let default_name = host.default_output_device().and_then(|d| d.name().ok());
@@ -50,3 +59,32 @@ pub fn list_output_devices() -> Result<Vec<CpalDeviceInfo>, Box<dyn std::error::
 
     Ok(out)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn names(list: &[&str]) -> Vec<String> {
+        list.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn picks_first_available_preference() {
+        let available = names(&["MacBook Pro Microphone", "Jabra Evolve"]);
+        let preferred = names(&["Shure MV7", "Jabra Evolve", "MacBook Pro Microphone"]);
+        assert_eq!(
+            pick_preferred_device(&available, &preferred),
+            Some("Jabra Evolve")
+        );
+    }
+
+    #[test]
+    fn falls_back_to_system_default_when_nothing_matches() {
+        let available = names(&["MacBook Pro Microphone"]);
+        assert_eq!(
+            pick_preferred_device(&available, &names(&["Shure MV7"])),
+            None
+        );
+        assert_eq!(pick_preferred_device(&available, &[]), None);
+    }
+}
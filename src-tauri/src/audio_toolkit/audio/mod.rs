@@ -1,12 +1,22 @@
 // Re-export all audio components
+mod decoder;
 mod device;
+mod flac;
+#[cfg(feature = "opus")]
+mod opus;
 mod recorder;
 mod resampler;
 mod utils;
 mod visualizer;
 
-pub use device::{list_input_devices, list_output_devices, CpalDeviceInfo};
+pub use decoder::{
+    decode_audio_bytes, decode_audio_file, downmix_to_mono, is_supported_import_file,
+};
+pub use device::{list_input_devices, list_output_devices, pick_preferred_device, CpalDeviceInfo};
+pub use flac::encode_flac;
+#[cfg(feature = "opus")]
+pub use opus::encode_ogg_opus;
 pub use recorder::AudioRecorder;
 pub use resampler::FrameResampler;
-pub use utils::save_wav_file;
+pub use utils::{encode_wav, save_wav_file};
 pub use visualizer::AudioVisualiser;
@@ -0,0 +1,186 @@
+//! Enregistrements en Ogg Opus (RFC 7845), mono 16 kHz
+//!
+//! Trames de 20 ms encodées avec le profil VoIP de libopus ; la position de
+//! granule de la dernière page borne la durée exacte et le `pre-skip` de
+//! l'en-tête retire le délai de l'encodeur au décodage.
+
+use anyhow::{anyhow, bail, Result};
+use audiopus::coder::{Decoder, Encoder};
+use audiopus::packet::Packet;
+use audiopus::{Application, Bitrate, Channels, MutSignals, SampleRate};
+use ogg::writing::PacketWriteEndInfo;
+use ogg::{PacketReader, PacketWriter};
+use std::io::Cursor;
+
+use crate::audio_toolkit::constants::WHISPER_SAMPLE_RATE;
+
+/// 20 ms à 16 kHz
+const FRAME_SAMPLES: usize = WHISPER_SAMPLE_RATE as usize / 50;
+/// Les granules Ogg Opus sont toujours exprimées à 48 kHz
+const GRANULE_RATIO: u64 = 48_000 / WHISPER_SAMPLE_RATE as u64;
+/// Plus longue trame Opus (120 ms) à 16 kHz
+const MAX_DECODED_FRAME: usize = WHISPER_SAMPLE_RATE as usize * 120 / 1000;
+const MAX_PACKET_BYTES: usize = 4000;
+const STREAM_SERIAL: u32 = 0x6469_6100;
+const VENDOR: &str = "dictation-ia";
+
+fn opus_head(pre_skip: u16) -> Vec<u8> {
+    let mut head = b"OpusHead".to_vec();
+    head.push(1); // version
+    head.push(1); // canaux
+    head.extend_from_slice(&pre_skip.to_le_bytes());
+    head.extend_from_slice(&WHISPER_SAMPLE_RATE.to_le_bytes());
+    head.extend_from_slice(&0i16.to_le_bytes()); // gain de sortie
+    head.push(0); // famille de mapping : mono/stéréo
+    head
+}
+
+fn opus_tags() -> Vec<u8> {
+    let mut tags = b"OpusTags".to_vec();
+    tags.extend_from_slice(&(VENDOR.len() as u32).to_le_bytes());
+    tags.extend_from_slice(VENDOR.as_bytes());
+    tags.extend_from_slice(&0u32.to_le_bytes()); // aucun commentaire
+    tags
+}
+
+/// Encode des échantillons f32 mono 16 kHz en Ogg Opus
+pub fn encode_ogg_opus(samples: &[f32], bitrate_kbps: u32) -> Result<Vec<u8>> {
+    let mut encoder = Encoder::new(SampleRate::Hz16000, Channels::Mono, Application::Voip)?;
+    encoder.set_bitrate(Bitrate::BitsPerSecond(bitrate_kbps as i32 * 1000))?;
+    let lookahead = encoder.lookahead()? as usize;
+    let pre_skip = lookahead as u64 * GRANULE_RATIO;
+
+    let mut writer = PacketWriter::new(Vec::new());
+    writer.write_packet(
+        opus_head(pre_skip as u16).into_boxed_slice(),
+        STREAM_SERIAL,
+        PacketWriteEndInfo::EndPage,
+        0,
+    )?;
+    writer.write_packet(
+        opus_tags().into_boxed_slice(),
+        STREAM_SERIAL,
+        PacketWriteEndInfo::EndPage,
+        0,
+    )?;
+
+    // Le délai de l'encodeur doit lui aussi sortir : on complète par du silence
+    let frames = (samples.len() + lookahead).div_ceil(FRAME_SAMPLES).max(1);
+    let final_granule = pre_skip + samples.len() as u64 * GRANULE_RATIO;
+    let mut frame = vec![0.0f32; FRAME_SAMPLES];
+    let mut packet = vec![0u8; MAX_PACKET_BYTES];
+
+    for index in 0..frames {
+        let start = (index * FRAME_SAMPLES).min(samples.len());
+        let end = (start + FRAME_SAMPLES).min(samples.len());
+        frame.fill(0.0);
+        frame[..end - start].copy_from_slice(&samples[start..end]);
+
+        let len = encoder.encode_float(&frame, &mut packet)?;
+        let last = index + 1 == frames;
+        let granule = if last {
+            final_granule
+        } else {
+            (index as u64 + 1) * FRAME_SAMPLES as u64 * GRANULE_RATIO
+        };
+        writer.write_packet(
+            packet[..len].to_vec().into_boxed_slice(),
+            STREAM_SERIAL,
+            if last {
+                PacketWriteEndInfo::EndStream
+            } else {
+                PacketWriteEndInfo::NormalPacket
+            },
+            granule,
+        )?;
+    }
+
+    Ok(writer.into_inner())
+}
+
+/// Décode un flux Ogg Opus mono en f32 16 kHz
+pub fn decode_ogg_opus(data: &[u8]) -> Result<Vec<f32>> {
+    let mut reader = PacketReader::new(Cursor::new(data));
+
+    let head = reader
+        .read_packet()?
+        .ok_or_else(|| anyhow!("Flux Ogg vide"))?;
+    if !head.data.starts_with(b"OpusHead") || head.data.len() < 19 {
+        bail!("En-tête OpusHead absent");
+    }
+    if head.data[9] != 1 {
+        bail!("Seuls les enregistrements Opus mono sont pris en charge");
+    }
+    let pre_skip = u16::from_le_bytes([head.data[10], head.data[11]]) as u64;
+
+    let tags = reader.read_packet()?;
+    if !tags.is_some_and(|p| p.data.starts_with(b"OpusTags")) {
+        bail!("En-tête OpusTags absent");
+    }
+
+    let mut decoder = Decoder::new(SampleRate::Hz16000, Channels::Mono)?;
+    let mut output = Vec::new();
+    let mut buffer = vec![0.0f32; MAX_DECODED_FRAME];
+    let mut final_granule = None;
+
+    while let Some(packet) = reader.read_packet()? {
+        let len = decoder.decode_float(
+            Some(Packet::try_from(packet.data.as_slice())?),
+            MutSignals::try_from(buffer.as_mut_slice())?,
+            false,
+        )?;
+        output.extend_from_slice(&buffer[..len]);
+        if packet.last_in_stream() {
+            final_granule = Some(packet.absgp_page());
+        }
+    }
+
+    let skip = (pre_skip / GRANULE_RATIO) as usize;
+    let end = match final_granule {
+        Some(granule) => (granule.saturating_sub(pre_skip) / GRANULE_RATIO) as usize + skip,
+        None => output.len(),
+    };
+    output.truncate(end.min(output.len()));
+    output.drain(..skip.min(output.len()));
+    Ok(output)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tone(len: usize) -> Vec<f32> {
+        (0..len)
+            .map(|i| 0.4 * (i as f32 * 300.0 * std::f32::consts::TAU / 16000.0).sin())
+            .collect()
+    }
+
+    fn rms(samples: &[f32]) -> f32 {
+        (samples.iter().map(|s| s * s).sum::<f32>() / samples.len() as f32).sqrt()
+    }
+
+    #[test]
+    fn round_trip_keeps_length_and_energy() {
+        let samples = tone(16000 + 123);
+        let encoded = encode_ogg_opus(&samples, 24).unwrap();
+        assert!(encoded.starts_with(b"OggS"));
+        // 24 kb/s ≈ 3 ko par seconde, loin des 32 ko du WAV 16 bits
+        assert!(encoded.len() < 8000, "{} octets", encoded.len());
+
+        let decoded = decode_ogg_opus(&encoded).unwrap();
+        assert_eq!(decoded.len(), samples.len());
+        let ratio = rms(&decoded) / rms(&samples);
+        assert!((0.8..1.2).contains(&ratio), "ratio d'énergie {}", ratio);
+    }
+
+    #[test]
+    fn empty_recording_round_trips() {
+        let encoded = encode_ogg_opus(&[], 24).unwrap();
+        assert!(decode_ogg_opus(&encoded).unwrap().is_empty());
+    }
+
+    #[test]
+    fn rejects_non_opus_streams() {
+        assert!(decode_ogg_opus(b"RIFF....WAVE").is_err());
+    }
+}
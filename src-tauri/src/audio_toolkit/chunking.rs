@@ -0,0 +1,249 @@
+//! Découpage des dictées longues pour Whisper (fenêtre native de 30 s).
+//!
+//! Le buffer capturé est découpé en segments d'environ `chunk_secs`, en coupant
+//! dans la trame la plus silencieuse proche de la limite (le recorder a déjà
+//! retiré les longs silences via la VAD, il reste les pauses courtes entre mots).
+//! Chaque segment recouvre le précédent de `overlap_secs` pour ne pas couper un
+//! mot en deux ; les mots dupliqués à la jonction sont retirés par [`stitch`].
+
+use std::ops::Range;
+
+use super::constants::WHISPER_SAMPLE_RATE;
+
+/// Trame d'analyse d'énergie : 30 ms, comme le resampler/VAD du recorder.
+const FRAME_SAMPLES: usize = WHISPER_SAMPLE_RATE as usize * 30 / 1000;
+
+/// Nombre max de mots comparés à la jonction de deux segments.
+pub const MAX_STITCH_WORDS: usize = 8;
+
+#[derive(Debug, Clone, Copy)]
+pub struct ChunkConfig {
+    /// Durée cible d'un segment (doit rester < 30 s pour Whisper)
+    pub chunk_secs: f32,
+    /// Recouvrement entre deux segments consécutifs
+    pub overlap_secs: f32,
+    /// Fenêtre (avant la limite) dans laquelle chercher une pause
+    pub search_secs: f32,
+}
+
+impl Default for ChunkConfig {
+    fn default() -> Self {
+        Self {
+            chunk_secs: 25.0,
+            overlap_secs: 1.0,
+            search_secs: 5.0,
+        }
+    }
+}
+
+impl ChunkConfig {
+    fn samples(secs: f32) -> usize {
+        (secs * WHISPER_SAMPLE_RATE as f32) as usize
+    }
+
+    /// true si le buffer dépasse un seul segment et doit être découpé
+    pub fn needs_chunking(&self, n_samples: usize) -> bool {
+        n_samples > Self::samples(self.chunk_secs)
+    }
+}
+
+/// Énergie moyenne (RMS²) d'une trame
+fn frame_energy(frame: &[f32]) -> f32 {
+    if frame.is_empty() {
+        return 0.0;
+    }
+    frame.iter().map(|s| s * s).sum::<f32>() / frame.len() as f32
+}
+
+/// Cherche la trame la plus silencieuse dans `[start, end)` et retourne
+/// l'index de sample au milieu de cette trame.
+fn quietest_point(samples: &[f32], start: usize, end: usize) -> usize {
+    let mut best = end;
+    let mut best_energy = f32::MAX;
+    let mut pos = start;
+    while pos + FRAME_SAMPLES <= end {
+        let energy = frame_energy(&samples[pos..pos + FRAME_SAMPLES]);
+        // `<=` : à énergie égale, on préfère la coupe la plus tardive
+        if energy <= best_energy {
+            best_energy = energy;
+            best = pos + FRAME_SAMPLES / 2;
+        }
+        pos += FRAME_SAMPLES;
+    }
+    best
+}
+
+/// Découpe `samples` en plages qui se recouvrent, coupées sur des pauses.
+///
+/// Retourne une seule plage couvrant tout le buffer si celui-ci tient dans
+/// un segment.
+pub fn split_into_chunks(samples: &[f32], config: &ChunkConfig) -> Vec<Range<usize>> {
+    let total = samples.len();
+    let chunk = ChunkConfig::samples(config.chunk_secs).max(FRAME_SAMPLES * 2);
+    let overlap = ChunkConfig::samples(config.overlap_secs).min(chunk / 2);
+    let search = ChunkConfig::samples(config.search_secs).min(chunk / 2);
+
+    if total <= chunk {
+        return vec![0..total];
+    }
+
+    let mut ranges = Vec::new();
+    let mut start = 0;
+    loop {
+        let limit = start + chunk;
+        if limit >= total {
+            ranges.push(start..total);
+            break;
+        }
+        let cut = quietest_point(samples, limit - search, limit);
+        ranges.push(start..cut);
+        // Le segment suivant démarre `overlap` avant la coupe pour ne pas
+        // perdre un mot tronqué ; il avance toujours d'au moins une trame.
+        start = cut.saturating_sub(overlap).max(start + FRAME_SAMPLES);
+    }
+    ranges
+}
+
+fn normalize_word(word: &str) -> String {
+    word.trim_matches(|c: char| !c.is_alphanumeric())
+        .to_lowercase()
+}
+
+/// Recolle le texte d'un nouveau segment à la suite du texte déjà transcrit
+/// en retirant les mots répétés à cause du recouvrement audio.
+///
+/// On cherche le plus long suffixe de `previous` (≤ `max_words` mots) égal au
+/// préfixe de `next`, comparaison insensible à la casse et à la ponctuation.
+pub fn stitch(previous: &str, next: &str, max_words: usize) -> String {
+    let next = next.trim();
+    let previous = previous.trim_end();
+    if previous.is_empty() {
+        return next.to_string();
+    }
+    if next.is_empty() {
+        return previous.to_string();
+    }
+
+    let prev_words: Vec<String> = previous.split_whitespace().map(normalize_word).collect();
+    let next_raw: Vec<&str> = next.split_whitespace().collect();
+    let next_words: Vec<String> = next_raw.iter().map(|w| normalize_word(w)).collect();
+
+    let max_k = max_words.min(prev_words.len()).min(next_words.len());
+    let overlap = (1..=max_k)
+        .rev()
+        .find(|&k| {
+            let tail = &prev_words[prev_words.len() - k..];
+            let head = &next_words[..k];
+            tail == head && head.iter().any(|w| !w.is_empty())
+        })
+        .unwrap_or(0);
+
+    let mut rest = next_raw[overlap..].join(" ");
+    // Le mot qui suivait la jonction était en milieu de phrase dans `next` ;
+    // il en commence une nouvelle si `previous` se termine par un point.
+    if overlap > 0 && ends_sentence(previous) {
+        rest = capitalize_first(&rest);
+    }
+    if rest.is_empty() {
+        previous.to_string()
+    } else {
+        format!("{} {}", previous, rest)
+    }
+}
+
+fn ends_sentence(text: &str) -> bool {
+    text.ends_with(['.', '!', '?', '…'])
+}
+
+fn capitalize_first(text: &str) -> String {
+    let mut chars = text.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+/// Derniers mots du texte déjà transcrit, passés en prompt initial au segment
+/// suivant pour conserver le contexte (noms propres, ponctuation).
+pub fn context_tail(text: &str, max_words: usize) -> String {
+    let words: Vec<&str> = text.split_whitespace().collect();
+    let start = words.len().saturating_sub(max_words);
+    words[start..].join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SR: usize = WHISPER_SAMPLE_RATE as usize;
+
+    #[test]
+    fn short_audio_is_single_chunk() {
+        let samples = vec![0.1; SR * 10];
+        let chunks = split_into_chunks(&samples, &ChunkConfig::default());
+        assert_eq!(chunks, vec![0..samples.len()]);
+        assert!(!ChunkConfig::default().needs_chunking(samples.len()));
+    }
+
+    #[test]
+    fn long_audio_chunks_cover_everything_with_overlap() {
+        let samples = vec![0.1; SR * 70];
+        let config = ChunkConfig::default();
+        let chunks = split_into_chunks(&samples, &config);
+        assert!(chunks.len() >= 3, "got {} chunks", chunks.len());
+        assert_eq!(chunks.first().unwrap().start, 0);
+        assert_eq!(chunks.last().unwrap().end, samples.len());
+        for pair in chunks.windows(2) {
+            assert!(pair[1].start < pair[0].end, "chunks must overlap");
+            assert!(pair[1].start > pair[0].start);
+        }
+        for c in &chunks {
+            assert!(c.len() <= SR * 25);
+        }
+    }
+
+    #[test]
+    fn cut_lands_in_silence() {
+        // 40 s de signal avec une pause de 300 ms vers 22 s
+        let mut samples = vec![0.5; SR * 40];
+        let pause = SR * 22..SR * 22 + SR * 3 / 10;
+        for s in &mut samples[pause.clone()] {
+            *s = 0.0;
+        }
+        let chunks = split_into_chunks(&samples, &ChunkConfig::default());
+        let cut = chunks[0].end;
+        assert!(pause.contains(&cut), "cut {} not in pause {:?}", cut, pause);
+    }
+
+    #[test]
+    fn stitch_removes_duplicated_boundary_words() {
+        let out = stitch(
+            "on se retrouve demain pour la réunion",
+            "pour la réunion du projet",
+            MAX_STITCH_WORDS,
+        );
+        assert_eq!(out, "on se retrouve demain pour la réunion du projet");
+    }
+
+    #[test]
+    fn stitch_ignores_case_and_punctuation() {
+        let out = stitch("Il faut valider le budget.", "Le budget, puis le planning", 8);
+        assert_eq!(out, "Il faut valider le budget. Puis le planning");
+
+        let out = stitch("on valide le budget", "Le budget, puis le planning", 8);
+        assert_eq!(out, "on valide le budget puis le planning");
+    }
+
+    #[test]
+    fn stitch_without_overlap_concatenates() {
+        assert_eq!(stitch("bonjour", "tout le monde", 8), "bonjour tout le monde");
+        assert_eq!(stitch("", "tout le monde", 8), "tout le monde");
+        assert_eq!(stitch("bonjour", "", 8), "bonjour");
+    }
+
+    #[test]
+    fn context_tail_keeps_last_words() {
+        assert_eq!(context_tail("un deux trois quatre", 2), "trois quatre");
+        assert_eq!(context_tail("un", 5), "un");
+    }
+}
@@ -1,11 +1,16 @@
 pub mod audio;
+pub mod chunking;
 pub mod constants;
 pub mod text;
 pub mod utils;
 pub mod vad;
 
+#[cfg(feature = "opus")]
+pub use audio::encode_ogg_opus;
 pub use audio::{
-    list_input_devices, list_output_devices, save_wav_file, AudioRecorder, CpalDeviceInfo,
+    decode_audio_bytes, decode_audio_file, encode_flac, encode_wav, is_supported_import_file,
+    list_input_devices, list_output_devices, pick_preferred_device, save_wav_file, AudioRecorder,
+    CpalDeviceInfo,
 };
 pub use text::{apply_custom_words, filter_transcription_output};
 pub use utils::get_cpal_host;
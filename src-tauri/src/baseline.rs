@@ -0,0 +1,340 @@
+//! Réglages d'équipe (« baseline ») — `team_baseline.json`
+//!
+//! Un fichier distribué par l'équipe (déploiement ou installation depuis les
+//! réglages) fixe des valeurs de départ et peut en verrouiller certaines :
+//!
+//! ```json
+//! {
+//!   "name": "Équipe support",
+//!   "settings": { "post_process_provider_id": "custom", "history_limit": 50 },
+//!   "locked": ["post_process_provider_id"],
+//!   "disallowed_providers": ["openai"],
+//!   "disallow_cloud_providers": false,
+//!   "allow_user_removal": false
+//! }
+//! ```
+//!
+//! Les valeurs de `settings` sont appliquées à l'installation ; celles listées
+//! dans `locked` sont rétablies au chargement des réglages, à l'installation
+//! et à chaque écriture. Un fournisseur interdit n'est ni sélectionnable ni
+//! appelé par les recettes.
+//!
+//! L'utilisateur ne peut retirer la baseline depuis l'application que si
+//! `allow_user_removal` l'y autorise ; sinon seul l'administrateur qui a
+//! déployé `team_baseline.json` dans le dossier de données peut le retirer.
+
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use specta::Type;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::RwLock;
+
+use crate::settings::{
+    get_default_settings, AppSettings, PostProcessProvider, APPLE_INTELLIGENCE_PROVIDER_ID,
+};
+
+const BASELINE_FILE: &str = "team_baseline.json";
+
+/// Contenu de `team_baseline.json`
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct TeamBaseline {
+    pub name: String,
+    /// Valeurs de l'équipe, par clé de `AppSettings`
+    #[serde(default)]
+    pub settings: Map<String, Value>,
+    /// Clés de `settings` que l'utilisateur ne peut pas modifier
+    #[serde(default)]
+    pub locked: Vec<String>,
+    /// Identifiants de fournisseurs de post-traitement interdits
+    #[serde(default)]
+    pub disallowed_providers: Vec<String>,
+    /// Interdit tout fournisseur qui ne tourne pas sur la machine
+    #[serde(default)]
+    pub disallow_cloud_providers: bool,
+    /// L'utilisateur peut retirer la baseline depuis les réglages
+    #[serde(default)]
+    pub allow_user_removal: bool,
+}
+
+/// Résumé affiché dans les réglages
+#[derive(Serialize, Debug, Clone, Type)]
+pub struct BaselineInfo {
+    pub name: String,
+    pub locked: Vec<String>,
+    pub disallowed_providers: Vec<String>,
+    pub disallow_cloud_providers: bool,
+    pub allow_user_removal: bool,
+}
+
+#[derive(Default)]
+struct BaselineState {
+    dir: Option<PathBuf>,
+    baseline: Option<TeamBaseline>,
+}
+
+static STATE: Lazy<RwLock<BaselineState>> = Lazy::new(|| RwLock::new(BaselineState::default()));
+
+impl TeamBaseline {
+    /// Lit et valide un fichier de baseline : clés connues, valeurs du bon
+    /// type, clés verrouillées présentes dans `settings`
+    pub fn parse(json: &str) -> Result<TeamBaseline, String> {
+        let baseline: TeamBaseline =
+            serde_json::from_str(json).map_err(|e| format!("Invalid team baseline: {}", e))?;
+        if baseline.name.trim().is_empty() {
+            return Err("Team baseline needs a name".to_string());
+        }
+        if baseline.settings.contains_key("settings_version") {
+            return Err("Team baseline cannot set 'settings_version'".to_string());
+        }
+        if let Some(key) = baseline
+            .locked
+            .iter()
+            .find(|key| !baseline.settings.contains_key(*key))
+        {
+            return Err(format!("Locked setting '{}' has no value", key));
+        }
+        get_default_settings().with_fields(&baseline.settings)?;
+        Ok(baseline)
+    }
+
+    pub fn info(&self) -> BaselineInfo {
+        BaselineInfo {
+            name: self.name.clone(),
+            locked: self.locked.clone(),
+            disallowed_providers: self.disallowed_providers.clone(),
+            disallow_cloud_providers: self.disallow_cloud_providers,
+            allow_user_removal: self.allow_user_removal,
+        }
+    }
+
+    /// Refuse le retrait depuis l'application si la baseline ne l'autorise pas
+    pub fn check_removable(&self) -> Result<(), String> {
+        if self.allow_user_removal {
+            Ok(())
+        } else {
+            Err(format!(
+                "Team baseline '{}' can only be removed by an administrator",
+                self.name
+            ))
+        }
+    }
+
+    pub fn is_locked(&self, key: &str) -> bool {
+        self.locked.iter().any(|locked| locked == key)
+    }
+
+    /// true si le fournisseur peut recevoir des dictées
+    pub fn allows(&self, provider: &PostProcessProvider) -> bool {
+        if self.disallowed_providers.contains(&provider.id) {
+            return false;
+        }
+        !self.disallow_cloud_providers
+            || provider.id == APPLE_INTELLIGENCE_PROVIDER_ID
+            || crate::llm_client::is_local(provider)
+    }
+
+    /// Applique toutes les valeurs de l'équipe (installation)
+    pub fn apply_defaults(&self, settings: &mut AppSettings) -> Result<(), String> {
+        *settings = settings.with_fields(&self.settings)?;
+        self.enforce(settings);
+        Ok(())
+    }
+
+    /// Rétablit les valeurs verrouillées et quitte un fournisseur interdit.
+    /// Renvoie true si `settings` a changé.
+    pub fn enforce(&self, settings: &mut AppSettings) -> bool {
+        let mut changed = false;
+
+        let current = settings.to_fields();
+        let locked: Map<String, Value> = self
+            .locked
+            .iter()
+            .filter_map(|key| Some((key.clone(), self.settings.get(key)?.clone())))
+            .filter(|(key, value)| current.get(key) != Some(value))
+            .collect();
+        if !locked.is_empty() {
+            match settings.with_fields(&locked) {
+                Ok(updated) => {
+                    *settings = updated;
+                    changed = true;
+                }
+                Err(e) => log::error!("Failed to apply team baseline locks: {}", e),
+            }
+        }
+
+        let active_allowed = settings
+            .active_post_process_provider()
+            .is_none_or(|provider| self.allows(provider));
+        if !active_allowed {
+            let fallback = settings
+                .post_process_providers
+                .iter()
+                .find(|provider| self.allows(provider))
+                .map(|provider| provider.id.clone());
+            log::warn!(
+                "Provider '{}' is disallowed by the team baseline, switching to {:?}",
+                settings.post_process_provider_id,
+                fallback
+            );
+            match fallback {
+                Some(id) => settings.post_process_provider_id = id,
+                None => settings.post_process_enabled = false,
+            }
+            changed = true;
+        }
+
+        changed
+    }
+}
+
+fn baseline_path(dir: &Path) -> PathBuf {
+    dir.join(BASELINE_FILE)
+}
+
+/// Charge `team_baseline.json` s'il existe ; à appeler avant la première
+/// lecture des réglages
+pub fn init(app_data_dir: &Path) {
+    let mut state = STATE.write().unwrap();
+    state.dir = Some(app_data_dir.to_path_buf());
+    state.baseline = fs::read_to_string(baseline_path(app_data_dir))
+        .ok()
+        .and_then(|json| match TeamBaseline::parse(&json) {
+            Ok(baseline) => Some(baseline),
+            Err(e) => {
+                log::error!("Ignoring {}: {}", BASELINE_FILE, e);
+                None
+            }
+        });
+    if let Some(baseline) = &state.baseline {
+        log::info!(
+            "Team baseline '{}' loaded ({} locked setting(s))",
+            baseline.name,
+            baseline.locked.len()
+        );
+    }
+}
+
+pub fn current() -> Option<TeamBaseline> {
+    STATE.read().unwrap().baseline.clone()
+}
+
+/// Valide puis installe `json` comme baseline de l'équipe
+pub fn install(json: &str) -> Result<TeamBaseline, String> {
+    let baseline = TeamBaseline::parse(json)?;
+    let mut state = STATE.write().unwrap();
+    let dir = state
+        .dir
+        .clone()
+        .ok_or_else(|| "App data directory is not available".to_string())?;
+    fs::write(baseline_path(&dir), json)
+        .map_err(|e| format!("Failed to write {}: {}", BASELINE_FILE, e))?;
+    state.baseline = Some(baseline.clone());
+    Ok(baseline)
+}
+
+/// Retire la baseline si elle l'autorise ; les réglages gardent leurs valeurs
+/// actuelles
+pub fn remove() -> Result<(), String> {
+    let mut state = STATE.write().unwrap();
+    if let Some(baseline) = &state.baseline {
+        baseline.check_removable()?;
+    }
+    if let Some(dir) = &state.dir {
+        let path = baseline_path(dir);
+        if path.exists() {
+            fs::remove_file(&path)
+                .map_err(|e| format!("Failed to remove {}: {}", BASELINE_FILE, e))?;
+        }
+    }
+    state.baseline = None;
+    Ok(())
+}
+
+/// Applique la baseline installée, s'il y en a une ; true si `settings` a changé
+pub fn enforce(settings: &mut AppSettings) -> bool {
+    STATE
+        .read()
+        .unwrap()
+        .baseline
+        .as_ref()
+        .is_some_and(|baseline| baseline.enforce(settings))
+}
+
+/// true si aucune baseline n'interdit ce fournisseur
+pub fn provider_allowed(provider: &PostProcessProvider) -> bool {
+    STATE
+        .read()
+        .unwrap()
+        .baseline
+        .as_ref()
+        .is_none_or(|baseline| baseline.allows(provider))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn baseline(json: &str) -> TeamBaseline {
+        TeamBaseline::parse(json).expect("valid baseline")
+    }
+
+    #[test]
+    fn rejects_unknown_keys_and_unset_locks() {
+        assert!(TeamBaseline::parse(r#"{"name":"x","settings":{"nope":1}}"#).is_err());
+        assert!(TeamBaseline::parse(r#"{"name":"x","locked":["history_limit"]}"#).is_err());
+        assert!(
+            TeamBaseline::parse(r#"{"name":"x","settings":{"history_limit":"lots"}}"#).is_err()
+        );
+        assert!(TeamBaseline::parse(r#"{"name":" "}"#).is_err());
+    }
+
+    #[test]
+    fn locked_values_are_restored() {
+        let baseline = baseline(
+            r#"{"name":"Support","settings":{"history_limit":20,"push_to_talk":false},
+                "locked":["history_limit"]}"#,
+        );
+        let mut settings = get_default_settings();
+        baseline.apply_defaults(&mut settings).unwrap();
+        assert_eq!(settings.history_limit, 20);
+        assert!(!settings.push_to_talk);
+
+        settings.history_limit = 500;
+        settings.push_to_talk = true;
+        assert!(baseline.enforce(&mut settings));
+        assert_eq!(settings.history_limit, 20);
+        // Valeur de départ non verrouillée : le choix de l'utilisateur reste
+        assert!(settings.push_to_talk);
+        assert!(!baseline.enforce(&mut settings));
+    }
+
+    #[test]
+    fn disallowed_cloud_provider_is_replaced() {
+        let baseline = baseline(r#"{"name":"Local","disallow_cloud_providers":true}"#);
+        let mut settings = get_default_settings();
+        settings.post_process_provider_id = "openai".to_string();
+
+        assert!(!baseline.allows(settings.active_post_process_provider().unwrap()));
+        assert!(baseline.enforce(&mut settings));
+        let active = settings.active_post_process_provider().unwrap();
+        assert!(baseline.allows(active), "{}", active.id);
+    }
+
+    #[test]
+    fn listed_provider_is_disallowed() {
+        let baseline = baseline(r#"{"name":"x","disallowed_providers":["groq"]}"#);
+        let settings = get_default_settings();
+        assert!(!baseline.allows(settings.post_process_provider("groq").unwrap()));
+        assert!(baseline.allows(settings.post_process_provider("openai").unwrap()));
+    }
+
+    #[test]
+    fn removal_needs_permission() {
+        assert!(baseline(r#"{"name":"x"}"#).check_removable().is_err());
+        assert!(baseline(r#"{"name":"x","allow_user_removal":true}"#)
+            .check_removable()
+            .is_ok());
+    }
+}
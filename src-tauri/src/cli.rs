@@ -1,4 +1,5 @@
 use clap::Parser;
+use std::path::PathBuf;
 
 #[derive(Parser, Debug, Clone, Default)]
 #[command(name = "handy", about = "Handy - Speech to Text")]
@@ -23,6 +24,14 @@ pub struct CliArgs {
     #[arg(long)]
     pub cancel: bool,
 
+    /// Transcribe an audio/video file and save it to history (sent to running instance)
+    #[arg(long, value_name = "PATH")]
+    pub transcribe_file: Option<PathBuf>,
+
+    /// Print usage statistics for the last DAYS days (default 30) and exit
+    #[arg(long, value_name = "DAYS", num_args = 0..=1, default_missing_value = "30")]
+    pub stats_report: Option<u32>,
+
     /// Enable debug mode with verbose logging
     #[arg(long)]
     pub debug: bool,
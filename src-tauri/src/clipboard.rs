@@ -1,10 +1,14 @@
+use crate::clipboard_snapshot::{self, ClipboardSnapshot};
 use crate::input::{self, EnigoState};
+use crate::pipeline::rich_text;
 #[cfg(target_os = "linux")]
 use crate::settings::TypingTool;
-use crate::settings::{get_settings, AutoSubmitKey, ClipboardHandling, PasteMethod};
+use crate::settings::{get_settings, AutoSubmitKey, ClipboardHandling, PasteFormat, PasteMethod};
 use enigo::{Direction, Enigo, Key, Keyboard};
-use log::info;
+use log::{info, warn};
+use once_cell::sync::Lazy;
 use std::process::Command;
+use std::sync::Mutex;
 use std::time::Duration;
 use tauri::{AppHandle, Manager};
 use tauri_plugin_clipboard_manager::ClipboardExt;
@@ -12,21 +16,29 @@ use tauri_plugin_clipboard_manager::ClipboardExt;
 #[cfg(target_os = "linux")]
 use crate::utils::{is_kde_wayland, is_wayland};
 
-/// Pastes text using the clipboard: saves current content, writes text, sends paste keystroke, restores clipboard.
+/// Pastes text using the clipboard: saves current content, writes text, sends paste keystroke.
+/// With `html`, the clipboard holds both `text/html` and `text/plain` (RTF isn't exposed by the clipboard backend).
+/// Returns the saved content; the caller restores it (see [`clipboard_snapshot::restore_later`]).
 fn paste_via_clipboard(
     enigo: &mut Enigo,
     text: &str,
+    html: Option<&str>,
     app_handle: &AppHandle,
     paste_method: &PasteMethod,
     paste_delay_ms: u64,
-) -> Result<(), String> {
+) -> Result<ClipboardSnapshot, String> {
     let clipboard = app_handle.clipboard();
-    let clipboard_content = clipboard.read_text().unwrap_or_default();
+    let snapshot = clipboard_snapshot::capture_for_paste(app_handle);
 
     // Write text to clipboard first
-    // On Wayland, prefer wl-copy for better compatibility (especially with umlauts)
+    // On Wayland, prefer wl-copy for better compatibility (especially with umlauts),
+    // except for rich text: wl-copy only offers a single MIME type per call
     #[cfg(target_os = "linux")]
-    let write_result = if is_wayland() && is_wl_copy_available() {
+    let write_result = if let Some(html) = html {
+        clipboard
+            .write_html(html, Some(text))
+            .map_err(|e| format!("Failed to write HTML to clipboard: {}", e))
+    } else if is_wayland() && is_wl_copy_available() {
         info!("Using wl-copy for clipboard write on Wayland");
         write_clipboard_via_wl_copy(text)
     } else {
@@ -36,9 +48,14 @@ fn paste_via_clipboard(
     };
 
     #[cfg(not(target_os = "linux"))]
-    let write_result = clipboard
-        .write_text(text)
-        .map_err(|e| format!("Failed to write to clipboard: {}", e));
+    let write_result = match html {
+        Some(html) => clipboard
+            .write_html(html, Some(text))
+            .map_err(|e| format!("Failed to write HTML to clipboard: {}", e)),
+        None => clipboard
+            .write_text(text)
+            .map_err(|e| format!("Failed to write to clipboard: {}", e)),
+    };
 
     write_result?;
 
@@ -61,24 +78,7 @@ fn paste_via_clipboard(
         }
     }
 
-    // Delai avant restauration du presse-papier.
-    // Note : le chemin Swift (AccessibilityPaste.swift) utilise 150ms via DispatchQueue.
-    // Ce chemin Enigo utilise 50ms car le keystroke est synchrone (pas de CGEvent async).
-    std::thread::sleep(std::time::Duration::from_millis(50));
-
-    // Restore original clipboard content
-    // On Wayland, prefer wl-copy for better compatibility
-    #[cfg(target_os = "linux")]
-    if is_wayland() && is_wl_copy_available() {
-        let _ = write_clipboard_via_wl_copy(&clipboard_content);
-    } else {
-        let _ = clipboard.write_text(&clipboard_content);
-    }
-
-    #[cfg(not(target_os = "linux"))]
-    let _ = clipboard.write_text(&clipboard_content);
-
-    Ok(())
+    Ok(snapshot)
 }
 
 /// Attempts to send a key combination using Linux-native tools.
@@ -201,6 +201,56 @@ fn try_direct_typing_linux(text: &str, preferred_tool: TypingTool) -> Result<boo
     Ok(false)
 }
 
+/// Attempts to send `chars` backspaces using Linux-native tools, picked like
+/// [`try_direct_typing_linux`]. Returns `Ok(false)` to fall back to enigo.
+#[cfg(target_os = "linux")]
+fn try_erase_linux(chars: usize, preferred_tool: TypingTool) -> Result<bool, String> {
+    let tool = if preferred_tool != TypingTool::Auto {
+        let available = match preferred_tool {
+            TypingTool::Wtype => is_wtype_available(),
+            TypingTool::Kwtype => is_kwtype_available(),
+            TypingTool::Dotool => is_dotool_available(),
+            TypingTool::Ydotool => is_ydotool_available(),
+            TypingTool::Xdotool => is_xdotool_available(),
+            TypingTool::Auto => false,
+        };
+        if !available {
+            return Err(format!(
+                "Typing tool {:?} is not available on this system",
+                preferred_tool
+            ));
+        }
+        preferred_tool
+    } else if is_wayland() {
+        if !is_kde_wayland() && is_wtype_available() {
+            TypingTool::Wtype
+        } else if is_dotool_available() {
+            TypingTool::Dotool
+        } else if is_ydotool_available() {
+            TypingTool::Ydotool
+        } else {
+            return Ok(false);
+        }
+    } else if is_xdotool_available() {
+        TypingTool::Xdotool
+    } else if is_ydotool_available() {
+        TypingTool::Ydotool
+    } else {
+        return Ok(false);
+    };
+
+    info!("Using {:?} for backspaces", tool);
+    match tool {
+        TypingTool::Wtype => erase_via_wtype(chars)?,
+        TypingTool::Dotool => erase_via_dotool(chars)?,
+        TypingTool::Ydotool => erase_via_ydotool(chars)?,
+        TypingTool::Xdotool => erase_via_xdotool(chars)?,
+        // kwtype ne sait que taper du texte
+        TypingTool::Kwtype | TypingTool::Auto => return Ok(false),
+    }
+    Ok(true)
+}
+
 /// Returns the list of available typing tools on this system.
 /// Always includes "auto" as the first entry.
 #[cfg(target_os = "linux")]
@@ -502,6 +552,90 @@ fn send_key_combo_via_xdotool(paste_method: &PasteMethod) -> Result<(), String>
     Ok(())
 }
 
+/// Send `chars` backspaces via wtype on Wayland.
+#[cfg(target_os = "linux")]
+fn erase_via_wtype(chars: usize) -> Result<(), String> {
+    let output = Command::new("wtype")
+        .args(std::iter::repeat(["-k", "BackSpace"]).take(chars).flatten())
+        .output()
+        .map_err(|e| format!("Failed to execute wtype: {}", e))?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(format!("wtype failed: {}", stderr));
+    }
+
+    Ok(())
+}
+
+/// Send `chars` backspaces via dotool.
+#[cfg(target_os = "linux")]
+fn erase_via_dotool(chars: usize) -> Result<(), String> {
+    use std::io::Write;
+    use std::process::Stdio;
+
+    let mut child = Command::new("dotool")
+        .stdin(Stdio::piped())
+        .spawn()
+        .map_err(|e| format!("Failed to spawn dotool: {}", e))?;
+
+    if let Some(mut stdin) = child.stdin.take() {
+        for _ in 0..chars {
+            writeln!(stdin, "key backspace")
+                .map_err(|e| format!("Failed to write to dotool stdin: {}", e))?;
+        }
+    }
+
+    let output = child
+        .wait_with_output()
+        .map_err(|e| format!("Failed to wait for dotool: {}", e))?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(format!("dotool failed: {}", stderr));
+    }
+
+    Ok(())
+}
+
+/// Send `chars` backspaces via ydotool (requires ydotoold daemon).
+#[cfg(target_os = "linux")]
+fn erase_via_ydotool(chars: usize) -> Result<(), String> {
+    // Keycode 14 = backspace, same <keycode>:<pressed> format as the key combos
+    let output = Command::new("ydotool")
+        .arg("key")
+        .args(std::iter::repeat(["14:1", "14:0"]).take(chars).flatten())
+        .output()
+        .map_err(|e| format!("Failed to execute ydotool: {}", e))?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(format!("ydotool failed: {}", stderr));
+    }
+
+    Ok(())
+}
+
+/// Send `chars` backspaces via xdotool on X11.
+#[cfg(target_os = "linux")]
+fn erase_via_xdotool(chars: usize) -> Result<(), String> {
+    let output = Command::new("xdotool")
+        .arg("key")
+        .arg("--clearmodifiers")
+        .arg("--repeat")
+        .arg(chars.to_string())
+        .arg("BackSpace")
+        .output()
+        .map_err(|e| format!("Failed to execute xdotool: {}", e))?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(format!("xdotool failed: {}", stderr));
+    }
+
+    Ok(())
+}
+
 /// Pastes text by invoking an external script.
 /// The script receives the text to paste as a single argument.
 fn paste_via_external_script(text: &str, script_path: &str) -> Result<(), String> {
@@ -591,9 +725,213 @@ fn should_send_auto_submit(auto_submit: bool, paste_method: PasteMethod) -> bool
     auto_submit && paste_method != PasteMethod::None
 }
 
+/// Contenu du presse-papiers remplacé par `ClipboardHandling::CopyToClipboard`
+#[derive(Debug, Clone)]
+struct ClipboardSwap {
+    previous: ClipboardSnapshot,
+    copied: String,
+}
+
+/// Dernière insertion faite par [`paste_with_method`] ou
+/// [`finish_streamed_typing`], pour [`undo_last_paste`].
+#[derive(Debug, Clone)]
+struct LastPaste {
+    /// Texte réellement inséré, espace final compris
+    text: String,
+    method: PasteMethod,
+    auto_submitted: bool,
+    /// Collé en HTML : l'application a rendu les listes à sa façon, le
+    /// nombre de caractères insérés n'est plus celui de `text`
+    rich: bool,
+    /// Empreinte de la fenêtre qui avait le focus juste après l'insertion
+    focus: Option<String>,
+    clipboard_swap: Option<ClipboardSwap>,
+}
+
+static LAST_PASTE: Lazy<Mutex<Option<LastPaste>>> = Lazy::new(|| Mutex::new(None));
+
+fn remember_paste(last: LastPaste) {
+    if let Ok(mut slot) = LAST_PASTE.lock() {
+        *slot = Some(last);
+    }
+}
+
+/// Empreinte de la fenêtre (ou de l'élément) qui a le focus clavier.
+/// `None` si la plateforme ne permet pas de le savoir (Wayland, Windows).
+fn focused_window_id() -> Option<String> {
+    #[cfg(target_os = "macos")]
+    {
+        crate::accessibility::focused_element_id()
+    }
+
+    #[cfg(target_os = "linux")]
+    {
+        if is_wayland() || !is_xdotool_available() {
+            return None;
+        }
+        Command::new("xdotool")
+            .arg("getactivewindow")
+            .output()
+            .ok()
+            .filter(|output| output.status.success())
+            .map(|output| String::from_utf8_lossy(&output.stdout).trim().to_string())
+            .filter(|id| !id.is_empty())
+    }
+
+    #[cfg(not(any(target_os = "macos", target_os = "linux")))]
+    {
+        None
+    }
+}
+
+/// Origine d'une demande d'annulation
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UndoTrigger {
+    /// Raccourci clavier : les touches partent là où l'utilisateur tape
+    Shortcut,
+    /// Menu de la barre système : le focus a pu passer à la barre des tâches
+    TrayMenu,
+}
+
+/// Vérifie que la dernière insertion peut être annulée depuis la fenêtre
+/// `current_focus`. Un focus inconnu (Wayland, Windows) n'empêche que
+/// l'annulation depuis la barre système, dont les touches pourraient
+/// atterrir ailleurs.
+fn check_undo<'a>(
+    last: Option<&'a LastPaste>,
+    current_focus: Option<&str>,
+    trigger: UndoTrigger,
+) -> Result<&'a LastPaste, &'static str> {
+    let last = last
+        .filter(|last| !last.text.is_empty())
+        .ok_or("No dictation to undo")?;
+    if last.auto_submitted {
+        return Err("Last dictation was already submitted");
+    }
+    if matches!(last.method, PasteMethod::None | PasteMethod::ExternalScript) {
+        return Err("Last dictation was not inserted by the app");
+    }
+    if last.rich {
+        return Err("Last dictation was pasted as formatted text");
+    }
+    match (last.focus.as_deref(), current_focus) {
+        (Some(before), Some(now)) if before != now => Err("Focus changed since the last dictation"),
+        (Some(_), Some(_)) => Ok(last),
+        _ if trigger == UndoTrigger::TrayMenu => {
+            Err("Cannot check the focused window; use the undo shortcut instead")
+        }
+        _ => Ok(last),
+    }
+}
+
+/// Annule la dernière dictée : efface le texte inséré (retours arrière pour
+/// la frappe directe, sélection puis suppression pour les collages) et
+/// remet le presse-papiers d'avant si la dictée l'avait remplacé.
+pub fn undo_last_paste(app_handle: &AppHandle, trigger: UndoTrigger) -> Result<(), String> {
+    let last = LAST_PASTE
+        .lock()
+        .map_err(|e| format!("Failed to lock last paste: {}", e))?
+        .clone();
+    let current_focus = focused_window_id();
+    let last = check_undo(last.as_ref(), current_focus.as_deref(), trigger)?;
+    if last.focus.is_none() || current_focus.is_none() {
+        warn!("[UNDO] Focus unknown, undoing without focus check");
+    }
+
+    let chars = last.text.chars().count();
+    info!("[UNDO] Removing {} chars ({:?})", chars, last.method);
+    {
+        let enigo_state = app_handle
+            .try_state::<EnigoState>()
+            .ok_or("Enigo state not initialized")?;
+        let mut enigo = enigo_state
+            .0
+            .lock()
+            .map_err(|e| format!("Failed to lock Enigo: {}", e))?;
+
+        if last.method == PasteMethod::Direct {
+            erase_direct(
+                &mut enigo,
+                chars,
+                #[cfg(target_os = "linux")]
+                get_settings(app_handle).typing_tool,
+            )?;
+        } else {
+            delete_backwards_selection(&mut enigo, chars)?;
+        }
+    }
+
+    if let Some(swap) = &last.clipboard_swap {
+        // Ne pas écraser ce que l'utilisateur a copié depuis
+        if clipboard_snapshot::read_text(app_handle).as_deref() == Some(swap.copied.as_str()) {
+            clipboard_snapshot::restore(app_handle, &swap.previous)?;
+        }
+    }
+
+    if let Ok(mut slot) = LAST_PASTE.lock() {
+        *slot = None;
+    }
+    Ok(())
+}
+
+/// Efface `chars` caractères tapés, via l'outil de frappe Linux si possible.
+fn erase_direct(
+    enigo: &mut Enigo,
+    chars: usize,
+    #[cfg(target_os = "linux")] typing_tool: TypingTool,
+) -> Result<(), String> {
+    #[cfg(target_os = "linux")]
+    {
+        if try_erase_linux(chars, typing_tool)? {
+            return Ok(());
+        }
+        info!("Falling back to enigo for backspaces");
+    }
+
+    for _ in 0..chars {
+        enigo
+            .key(Key::Backspace, Direction::Click)
+            .map_err(|e| format!("Failed to send Backspace: {}", e))?;
+    }
+    Ok(())
+}
+
+/// Sélectionne les `chars` caractères avant le curseur (Shift+Gauche) puis
+/// les supprime, pour les textes collés d'un bloc.
+fn delete_backwards_selection(enigo: &mut Enigo, chars: usize) -> Result<(), String> {
+    enigo
+        .key(Key::Shift, Direction::Press)
+        .map_err(|e| format!("Failed to press Shift key: {}", e))?;
+    let selected = (0..chars).try_for_each(|_| {
+        enigo
+            .key(Key::LeftArrow, Direction::Click)
+            .map_err(|e| format!("Failed to send Left arrow: {}", e))
+    });
+    enigo
+        .key(Key::Shift, Direction::Release)
+        .map_err(|e| format!("Failed to release Shift key: {}", e))?;
+    selected?;
+    enigo
+        .key(Key::Backspace, Direction::Click)
+        .map_err(|e| format!("Failed to send Backspace: {}", e))
+}
+
 pub fn paste(text: String, app_handle: AppHandle) -> Result<(), String> {
     let settings = get_settings(&app_handle);
-    let paste_method = settings.paste_method;
+    let paste_format =
+        settings.paste_format_for_app(crate::accessibility::target_app_name().as_deref());
+    paste_with_method(text, app_handle, settings.paste_method, paste_format)
+}
+
+/// Comme [`paste`], avec une méthode et un format de collage imposés (profil
+/// de raccourci, application cible)
+pub fn paste_with_method(
+    text: String,
+    app_handle: AppHandle,
+    paste_method: PasteMethod,
+    paste_format: PasteFormat,
+) -> Result<(), String> {
+    let settings = get_settings(&app_handle);
     let paste_delay_ms = settings.paste_delay_ms;
 
     // Append trailing space if setting is enabled
@@ -608,6 +946,15 @@ pub fn paste(text: String, app_handle: AppHandle) -> Result<(), String> {
         paste_method, paste_delay_ms
     );
 
+    // HTML seulement pour les collages via presse-papier et un texte
+    // structuré ; Ctrl+Shift+V sert justement à coller en texte brut
+    let html = match paste_method {
+        PasteMethod::CtrlV | PasteMethod::ShiftInsert if paste_format == PasteFormat::Rich => {
+            rich_text::to_html(&text)
+        }
+        _ => None,
+    };
+
     // Get the managed Enigo instance
     let enigo_state = app_handle
         .try_state::<EnigoState>()
@@ -620,8 +967,9 @@ pub fn paste(text: String, app_handle: AppHandle) -> Result<(), String> {
     // Perform the paste operation
     // On macOS, try Accessibility API first (AXUIElement direct or Cmd+V via Swift FFI)
     // before falling back to the Enigo-based paste methods.
+    // L'insertion AXUIElement est en texte brut : le HTML passe par Cmd+V (Enigo)
     #[cfg(target_os = "macos")]
-    let accessibility_handled = if paste_method != PasteMethod::None {
+    let accessibility_handled = if paste_method != PasteMethod::None && html.is_none() {
         match crate::accessibility::paste_via_accessibility(&text) {
             Ok(0) => {
                 info!("[PASTE] Success via AXUIElement direct (~2ms)");
@@ -647,6 +995,8 @@ pub fn paste(text: String, app_handle: AppHandle) -> Result<(), String> {
     #[cfg(not(target_os = "macos"))]
     let accessibility_handled = false;
 
+    // Contenu remplacé par un collage via presse-papiers, à restaurer
+    let mut saved_clipboard = None;
     if !accessibility_handled {
         match paste_method {
             PasteMethod::None => {
@@ -662,13 +1012,14 @@ pub fn paste(text: String, app_handle: AppHandle) -> Result<(), String> {
             }
             PasteMethod::CtrlV | PasteMethod::CtrlShiftV | PasteMethod::ShiftInsert => {
                 info!("[PASTE] Using Enigo clipboard paste method: {:?}", paste_method);
-                paste_via_clipboard(
+                saved_clipboard = Some(paste_via_clipboard(
                     &mut enigo,
                     &text,
+                    html.as_deref(),
                     &app_handle,
                     &paste_method,
                     paste_delay_ms,
-                )?
+                )?);
             }
             PasteMethod::ExternalScript => {
                 let script_path = settings
@@ -681,19 +1032,150 @@ pub fn paste(text: String, app_handle: AppHandle) -> Result<(), String> {
         }
     }
 
-    if should_send_auto_submit(settings.auto_submit, paste_method) {
+    let auto_submitted = should_send_auto_submit(settings.auto_submit, paste_method);
+    if auto_submitted {
         std::thread::sleep(Duration::from_millis(50));
         send_return_key(&mut enigo, settings.auto_submit_key)?;
     }
 
-    // After pasting, optionally copy to clipboard based on settings
+    // After pasting, optionally copy to clipboard based on settings; otherwise
+    // give back what the clipboard paste replaced
+    let mut clipboard_swap = None;
     if settings.clipboard_handling == ClipboardHandling::CopyToClipboard {
-        let clipboard = app_handle.clipboard();
-        clipboard
+        let previous =
+            saved_clipboard.unwrap_or_else(|| clipboard_snapshot::capture_for_paste(&app_handle));
+        app_handle
+            .clipboard()
+            .write_text(&text)
+            .map_err(|e| format!("Failed to copy to clipboard: {}", e))?;
+        clipboard_swap = Some(ClipboardSwap {
+            previous,
+            copied: text.clone(),
+        });
+    } else if let Some(snapshot) = saved_clipboard {
+        clipboard_snapshot::restore_later(
+            &app_handle,
+            snapshot,
+            text.clone(),
+            settings.clipboard_restore_delay_ms,
+        );
+    }
+
+    remember_paste(LastPaste {
+        text,
+        method: paste_method,
+        auto_submitted,
+        rich: html.is_some(),
+        focus: focused_window_id(),
+        clipboard_swap,
+    });
+
+    Ok(())
+}
+
+/// Tape un fragment de réponse LLM en cours de génération (frappe
+/// progressive, chemin `Direct`). Ni espace final, ni auto-submit : voir
+/// [`finish_streamed_typing`].
+pub fn type_streamed_chunk(text: &str, app_handle: &AppHandle) -> Result<(), String> {
+    #[cfg(target_os = "linux")]
+    let typing_tool = get_settings(app_handle).typing_tool;
+    let enigo_state = app_handle
+        .try_state::<EnigoState>()
+        .ok_or("Enigo state not initialized")?;
+    let mut enigo = enigo_state
+        .0
+        .lock()
+        .map_err(|e| format!("Failed to lock Enigo: {}", e))?;
+
+    paste_direct(
+        &mut enigo,
+        text,
+        #[cfg(target_os = "linux")]
+        typing_tool,
+    )
+}
+
+/// Efface `chars` caractères tapés par la frappe progressive (génération
+/// annulée ou en échec).
+pub fn erase_streamed_text(chars: usize, app_handle: &AppHandle) -> Result<(), String> {
+    let enigo_state = app_handle
+        .try_state::<EnigoState>()
+        .ok_or("Enigo state not initialized")?;
+    let mut enigo = enigo_state
+        .0
+        .lock()
+        .map_err(|e| format!("Failed to lock Enigo: {}", e))?;
+
+    for _ in 0..chars {
+        enigo
+            .key(Key::Backspace, Direction::Click)
+            .map_err(|e| format!("Failed to send Backspace: {}", e))?;
+    }
+    Ok(())
+}
+
+/// Termine une dictée dont le début a été tapé pendant la génération :
+/// tape le reste de `text`, puis applique espace final, auto-submit et copie
+/// presse-papiers comme [`paste`]. Si le texte final ne prolonge pas ce qui
+/// a été tapé, efface la frappe et colle normalement.
+pub fn finish_streamed_typing(
+    text: String,
+    typed: &str,
+    app_handle: AppHandle,
+) -> Result<(), String> {
+    let Some(remainder) = text.strip_prefix(typed) else {
+        info!("[PASTE] Texte final différent de la frappe progressive, recollage");
+        erase_streamed_text(typed.chars().count(), &app_handle)?;
+        // La frappe progressive n'existe qu'avec le collage Direct
+        return paste_with_method(text, app_handle, PasteMethod::Direct, PasteFormat::Plain);
+    };
+
+    let settings = get_settings(&app_handle);
+    let remainder = if settings.append_trailing_space {
+        format!("{} ", remainder)
+    } else {
+        remainder.to_string()
+    };
+    if !remainder.is_empty() {
+        type_streamed_chunk(&remainder, &app_handle)?;
+    }
+
+    if settings.auto_submit {
+        let enigo_state = app_handle
+            .try_state::<EnigoState>()
+            .ok_or("Enigo state not initialized")?;
+        let mut enigo = enigo_state
+            .0
+            .lock()
+            .map_err(|e| format!("Failed to lock Enigo: {}", e))?;
+        std::thread::sleep(Duration::from_millis(50));
+        send_return_key(&mut enigo, settings.auto_submit_key)?;
+    }
+
+    let mut clipboard_swap = None;
+    if settings.clipboard_handling == ClipboardHandling::CopyToClipboard {
+        let previous = clipboard_snapshot::capture_for_paste(&app_handle);
+        app_handle
+            .clipboard()
             .write_text(&text)
             .map_err(|e| format!("Failed to copy to clipboard: {}", e))?;
+        clipboard_swap = Some(ClipboardSwap {
+            previous,
+            copied: text,
+        });
     }
 
+    // Tout le texte est désormais dans le champ, frappe progressive comprise
+    let inserted = format!("{}{}", typed, remainder);
+    remember_paste(LastPaste {
+        text: inserted,
+        method: PasteMethod::Direct,
+        auto_submitted: settings.auto_submit,
+        rich: false,
+        focus: focused_window_id(),
+        clipboard_swap,
+    });
+
     Ok(())
 }
 
@@ -712,6 +1194,67 @@ mod tests {
         assert!(!should_send_auto_submit(true, PasteMethod::None));
     }
 
+    fn last_paste(method: PasteMethod, focus: Option<&str>) -> LastPaste {
+        LastPaste {
+            text: "bonjour ".to_string(),
+            method,
+            auto_submitted: false,
+            rich: false,
+            focus: focus.map(str::to_string),
+            clipboard_swap: None,
+        }
+    }
+
+    #[test]
+    fn undo_requires_a_previous_paste() {
+        assert!(check_undo(None, None, UndoTrigger::Shortcut).is_err());
+        let mut empty = last_paste(PasteMethod::CtrlV, None);
+        empty.text.clear();
+        assert!(check_undo(Some(&empty), None, UndoTrigger::Shortcut).is_err());
+    }
+
+    #[test]
+    fn undo_refuses_submitted_or_untracked_pastes() {
+        let mut submitted = last_paste(PasteMethod::Direct, None);
+        submitted.auto_submitted = true;
+        assert!(check_undo(Some(&submitted), None, UndoTrigger::Shortcut).is_err());
+        let none = last_paste(PasteMethod::None, None);
+        assert!(check_undo(Some(&none), None, UndoTrigger::Shortcut).is_err());
+        let script = last_paste(PasteMethod::ExternalScript, None);
+        assert!(check_undo(Some(&script), None, UndoTrigger::Shortcut).is_err());
+    }
+
+    #[test]
+    fn undo_refuses_rich_pastes() {
+        let mut rich = last_paste(PasteMethod::CtrlV, Some("42"));
+        rich.rich = true;
+        assert!(check_undo(Some(&rich), Some("42"), UndoTrigger::Shortcut).is_err());
+    }
+
+    #[test]
+    fn undo_refuses_when_focus_changed() {
+        let last = last_paste(PasteMethod::CtrlV, Some("42"));
+        assert!(check_undo(Some(&last), Some("43"), UndoTrigger::Shortcut).is_err());
+        assert!(check_undo(Some(&last), Some("42"), UndoTrigger::Shortcut).is_ok());
+    }
+
+    #[test]
+    fn undo_proceeds_when_focus_unknown() {
+        let last = last_paste(PasteMethod::Direct, None);
+        assert!(check_undo(Some(&last), Some("42"), UndoTrigger::Shortcut).is_ok());
+        let last = last_paste(PasteMethod::Direct, Some("42"));
+        assert!(check_undo(Some(&last), None, UndoTrigger::Shortcut).is_ok());
+    }
+
+    #[test]
+    fn tray_undo_requires_a_known_focus() {
+        let last = last_paste(PasteMethod::CtrlV, None);
+        assert!(check_undo(Some(&last), Some("42"), UndoTrigger::TrayMenu).is_err());
+        let last = last_paste(PasteMethod::CtrlV, Some("42"));
+        assert!(check_undo(Some(&last), None, UndoTrigger::TrayMenu).is_err());
+        assert!(check_undo(Some(&last), Some("42"), UndoTrigger::TrayMenu).is_ok());
+    }
+
     #[test]
     fn auto_submit_runs_for_active_paste_methods() {
         assert!(should_send_auto_submit(true, PasteMethod::CtrlV));
@@ -0,0 +1,537 @@
+//! Instantané du presse-papiers autour d'un collage
+//!
+//! Un collage par presse-papiers remplace son contenu le temps du raccourci
+//! clavier. Ce module sauvegarde avant le collage tous les formats que l'on
+//! sait remettre (fichiers copiés, image, HTML, texte), puis les restaure
+//! après un délai, sauf si l'utilisateur a copié autre chose entre-temps.
+//!
+//! - Linux : `wl-paste`/`wl-copy` (Wayland) ou `xclip` (X11) lisent chaque
+//!   type MIME. Ces outils ne publient qu'un type par processus : la
+//!   restauration remet le format le plus riche (HTML avec son texte brut via
+//!   le plugin).
+//! - Ailleurs, ou sans ces outils : le plugin ne lit que le texte et les
+//!   images. Les autres contenus (fichiers sous Windows) ne sont pas restaurés :
+//!   comme pour un presse-papiers vide, il est vidé après le collage.
+//!   Sur macOS, le collage passe d'abord par `AccessibilityPaste.swift`, qui
+//!   sauvegarde lui-même tous les types du presse-papiers.
+
+use log::{info, warn};
+use once_cell::sync::Lazy;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::time::Duration;
+use tauri::AppHandle;
+use tauri_plugin_clipboard_manager::ClipboardExt;
+
+#[cfg(target_os = "linux")]
+use crate::utils::is_wayland;
+#[cfg(target_os = "linux")]
+use std::process::{Command, Stdio};
+
+/// Listes de fichiers (KDE/standard, puis GNOME)
+#[cfg(any(target_os = "linux", test))]
+const FILE_TYPES: [&str; 2] = ["text/uri-list", "x-special/gnome-copied-files"];
+const HTML_TYPE: &str = "text/html";
+/// Texte brut, du nom MIME aux cibles X11 historiques
+const TEXT_TYPES: [&str; 4] = [
+    "text/plain;charset=utf-8",
+    "UTF8_STRING",
+    "text/plain",
+    "STRING",
+];
+/// Au-delà, un format n'est pas sauvegardé (vidéo copiée depuis un navigateur…)
+#[cfg(target_os = "linux")]
+const MAX_FORMAT_BYTES: usize = 64 * 1024 * 1024;
+
+/// Un format du presse-papiers et ses octets
+#[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct MimeData {
+    pub mime: String,
+    pub data: Vec<u8>,
+}
+
+/// Contenu du presse-papiers avant un collage
+#[derive(Debug, Clone, PartialEq)]
+pub enum ClipboardSnapshot {
+    /// Presse-papiers vide (pour le plugin : sans texte ni image) : restauré
+    /// en le vidant
+    Empty,
+    /// Formats lus par `wl-paste`/`xclip`, du plus riche au plus pauvre
+    #[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+    Mime(Vec<MimeData>),
+    Text(String),
+    /// Image lue par le plugin (RGBA)
+    Image {
+        rgba: Vec<u8>,
+        width: u32,
+        height: u32,
+    },
+    /// Contenu illisible (presse-papiers occupé par une autre application…) :
+    /// laissé tel quel
+    Unsupported,
+}
+
+/// Restauration programmée par [`restore_later`]
+struct PendingRestore {
+    id: u64,
+    snapshot: ClipboardSnapshot,
+    /// Texte collé : s'il n'est plus dans le presse-papiers, l'utilisateur a copié autre chose
+    pasted: String,
+}
+
+static PENDING: Lazy<Mutex<Option<PendingRestore>>> = Lazy::new(|| Mutex::new(None));
+static NEXT_ID: AtomicU64 = AtomicU64::new(0);
+
+/// Types à sauvegarder parmi `available`, du plus riche au plus pauvre :
+/// fichiers, image (PNG de préférence), HTML, texte brut.
+#[cfg(any(target_os = "linux", test))]
+fn wanted_types(available: &[String]) -> Vec<String> {
+    let has = |mime: &str| available.iter().any(|t| t == mime);
+    let mut wanted = Vec::new();
+    if let Some(files) = FILE_TYPES.iter().find(|t| has(t)) {
+        wanted.push(files.to_string());
+    }
+    let image = available
+        .iter()
+        .find(|t| *t == "image/png")
+        .or_else(|| available.iter().find(|t| t.starts_with("image/")));
+    if let Some(image) = image {
+        wanted.push(image.clone());
+    }
+    if has(HTML_TYPE) {
+        wanted.push(HTML_TYPE.to_string());
+    }
+    if let Some(text) = TEXT_TYPES.iter().find(|t| has(t)) {
+        wanted.push(text.to_string());
+    }
+    wanted
+}
+
+fn is_text_type(mime: &str) -> bool {
+    TEXT_TYPES.contains(&mime)
+}
+
+/// Texte brut d'un instantané multi-format, s'il y en a un
+fn plain_text(formats: &[MimeData]) -> Option<String> {
+    formats
+        .iter()
+        .find(|f| is_text_type(&f.mime))
+        .map(|f| String::from_utf8_lossy(&f.data).into_owned())
+}
+
+/// Outil Linux de lecture/écriture par type MIME
+#[cfg(target_os = "linux")]
+#[derive(Clone, Copy)]
+enum LinuxTool {
+    WlClipboard,
+    Xclip,
+}
+
+#[cfg(target_os = "linux")]
+fn is_tool_available(name: &str) -> bool {
+    Command::new("which")
+        .arg(name)
+        .output()
+        .map(|output| output.status.success())
+        .unwrap_or(false)
+}
+
+#[cfg(target_os = "linux")]
+fn linux_tool() -> Option<LinuxTool> {
+    if is_wayland() {
+        (is_tool_available("wl-paste") && is_tool_available("wl-copy"))
+            .then_some(LinuxTool::WlClipboard)
+    } else {
+        is_tool_available("xclip").then_some(LinuxTool::Xclip)
+    }
+}
+
+#[cfg(target_os = "linux")]
+impl LinuxTool {
+    fn run(command: &mut Command) -> Option<Vec<u8>> {
+        command
+            .stderr(Stdio::null())
+            .output()
+            .ok()
+            .filter(|output| output.status.success())
+            .map(|output| output.stdout)
+    }
+
+    /// Types proposés ; vide si le presse-papiers l'est
+    fn list_types(self) -> Vec<String> {
+        let output = match self {
+            Self::WlClipboard => Self::run(Command::new("wl-paste").arg("--list-types")),
+            Self::Xclip => Self::run(Command::new("xclip").args([
+                "-selection",
+                "clipboard",
+                "-t",
+                "TARGETS",
+                "-o",
+            ])),
+        };
+        output
+            .map(|stdout| {
+                String::from_utf8_lossy(&stdout)
+                    .lines()
+                    .map(str::trim)
+                    .filter(|line| !line.is_empty())
+                    .map(str::to_string)
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    fn read(self, mime: &str) -> Option<Vec<u8>> {
+        match self {
+            Self::WlClipboard => {
+                Self::run(Command::new("wl-paste").args(["--no-newline", "--type", mime]))
+            }
+            Self::Xclip => {
+                Self::run(Command::new("xclip").args(["-selection", "clipboard", "-t", mime, "-o"]))
+            }
+        }
+    }
+
+    fn read_text(self) -> Option<String> {
+        let stdout = match self {
+            Self::WlClipboard => {
+                Self::run(Command::new("wl-paste").args(["--no-newline", "--type", "text"]))
+            }
+            Self::Xclip => Self::run(Command::new("xclip").args(["-selection", "clipboard", "-o"])),
+        }?;
+        Some(String::from_utf8_lossy(&stdout).into_owned())
+    }
+
+    /// Publie `data` sous le type `mime`. stdout/stderr à null : `wl-copy` et
+    /// `xclip` laissent un processus en arrière-plan qui hérite des descripteurs.
+    fn write(self, mime: &str, data: &[u8]) -> Result<(), String> {
+        use std::io::Write;
+        let mut command = match self {
+            Self::WlClipboard => {
+                let mut command = Command::new("wl-copy");
+                command.args(["--type", mime]);
+                command
+            }
+            Self::Xclip => {
+                let mut command = Command::new("xclip");
+                command.args(["-selection", "clipboard", "-t", mime, "-i"]);
+                command
+            }
+        };
+        let mut child = command
+            .stdin(Stdio::piped())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn()
+            .map_err(|e| format!("Failed to run clipboard tool: {}", e))?;
+        if let Some(mut stdin) = child.stdin.take() {
+            stdin
+                .write_all(data)
+                .map_err(|e| format!("Failed to write to clipboard tool: {}", e))?;
+        }
+        let status = child
+            .wait()
+            .map_err(|e| format!("Clipboard tool failed: {}", e))?;
+        if !status.success() {
+            return Err(format!("Clipboard tool exited with {}", status));
+        }
+        Ok(())
+    }
+
+    fn capture(self) -> ClipboardSnapshot {
+        let available = self.list_types();
+        if available.is_empty() {
+            return ClipboardSnapshot::Empty;
+        }
+        let formats: Vec<MimeData> = wanted_types(&available)
+            .into_iter()
+            .filter_map(|mime| {
+                let data = self.read(&mime)?;
+                if data.len() > MAX_FORMAT_BYTES {
+                    warn!(
+                        "[Clipboard] Format {} trop volumineux ({} octets), non sauvegardé",
+                        mime,
+                        data.len()
+                    );
+                    return None;
+                }
+                Some(MimeData { mime, data })
+            })
+            .collect();
+        if formats.is_empty() {
+            ClipboardSnapshot::Unsupported
+        } else {
+            ClipboardSnapshot::Mime(formats)
+        }
+    }
+}
+
+/// Fin du message d'arboard `Error::ContentNotAvailable`, que le plugin ne
+/// transmet que sous forme de texte
+const CONTENT_NOT_AVAILABLE: &str = "or the clipboard is empty.";
+
+fn is_content_not_available(error: &str) -> bool {
+    error.ends_with(CONTENT_NOT_AVAILABLE)
+}
+
+/// Instantané d'après les lectures du plugin ; l'image n'est lue que s'il
+/// n'y a pas de texte. Ni texte ni image équivaut à un presse-papiers vide.
+fn plugin_snapshot(
+    text: Result<String, String>,
+    read_image: impl FnOnce() -> Result<ClipboardSnapshot, String>,
+) -> ClipboardSnapshot {
+    let text_error = match text {
+        Ok(text) if !text.is_empty() => return ClipboardSnapshot::Text(text),
+        Ok(_) => None,
+        Err(e) => Some(e),
+    };
+    match read_image() {
+        Ok(image) => image,
+        Err(image_error) => {
+            let empty = text_error.map_or(true, |e| is_content_not_available(&e))
+                && is_content_not_available(&image_error);
+            if empty {
+                ClipboardSnapshot::Empty
+            } else {
+                ClipboardSnapshot::Unsupported
+            }
+        }
+    }
+}
+
+fn capture_with_plugin(app: &AppHandle) -> ClipboardSnapshot {
+    let clipboard = app.clipboard();
+    plugin_snapshot(clipboard.read_text().map_err(|e| e.to_string()), || {
+        clipboard
+            .read_image()
+            .map(|image| ClipboardSnapshot::Image {
+                rgba: image.rgba().to_vec(),
+                width: image.width(),
+                height: image.height(),
+            })
+            .map_err(|e| e.to_string())
+    })
+}
+
+/// Sauvegarde le contenu actuel du presse-papiers
+pub fn capture(app: &AppHandle) -> ClipboardSnapshot {
+    #[cfg(target_os = "linux")]
+    if let Some(tool) = linux_tool() {
+        return tool.capture();
+    }
+    capture_with_plugin(app)
+}
+
+/// Texte brut actuellement dans le presse-papiers
+pub fn read_text(app: &AppHandle) -> Option<String> {
+    #[cfg(target_os = "linux")]
+    if let Some(tool) = linux_tool() {
+        return tool.read_text();
+    }
+    app.clipboard().read_text().ok()
+}
+
+fn write_text(app: &AppHandle, text: &str) -> Result<(), String> {
+    #[cfg(target_os = "linux")]
+    if is_wayland() {
+        if let Some(tool) = linux_tool() {
+            return tool.write("text/plain;charset=utf-8", text.as_bytes());
+        }
+    }
+    app.clipboard()
+        .write_text(text)
+        .map_err(|e| format!("Failed to restore clipboard: {}", e))
+}
+
+/// Remet `snapshot` dans le presse-papiers
+pub fn restore(app: &AppHandle, snapshot: &ClipboardSnapshot) -> Result<(), String> {
+    let clipboard = app.clipboard();
+    match snapshot {
+        ClipboardSnapshot::Empty => {
+            #[cfg(target_os = "linux")]
+            if let Some(LinuxTool::WlClipboard) = linux_tool() {
+                let _ = Command::new("wl-copy")
+                    .arg("--clear")
+                    .stdout(Stdio::null())
+                    .stderr(Stdio::null())
+                    .status();
+                return Ok(());
+            }
+            clipboard
+                .clear()
+                .map_err(|e| format!("Failed to clear clipboard: {}", e))
+        }
+        ClipboardSnapshot::Text(text) => write_text(app, text),
+        ClipboardSnapshot::Image {
+            rgba,
+            width,
+            height,
+        } => clipboard
+            .write_image(&tauri::image::Image::new(rgba, *width, *height))
+            .map_err(|e| format!("Failed to restore clipboard image: {}", e)),
+        ClipboardSnapshot::Mime(formats) => {
+            let Some(primary) = formats.first() else {
+                return Ok(());
+            };
+            if is_text_type(&primary.mime) {
+                return write_text(app, &String::from_utf8_lossy(&primary.data));
+            }
+            if primary.mime == HTML_TYPE {
+                let html = String::from_utf8_lossy(&primary.data).into_owned();
+                let alt = plain_text(formats).unwrap_or_else(|| html.clone());
+                return clipboard
+                    .write_html(html, Some(alt))
+                    .map_err(|e| format!("Failed to restore clipboard HTML: {}", e));
+            }
+            #[cfg(target_os = "linux")]
+            if let Some(tool) = linux_tool() {
+                return tool.write(&primary.mime, &primary.data);
+            }
+            Err(format!("Cannot restore clipboard format {}", primary.mime))
+        }
+        ClipboardSnapshot::Unsupported => {
+            warn!("[Clipboard] Contenu précédent illisible, presse-papiers laissé tel quel");
+            Ok(())
+        }
+    }
+}
+
+/// Contenu à restaurer après un collage. Si la restauration d'une dictée
+/// précédente est encore en attente (son texte est toujours dans le
+/// presse-papiers), c'est son instantané qui est repris : il contient le
+/// contenu de l'utilisateur, pas notre texte.
+pub fn capture_for_paste(app: &AppHandle) -> ClipboardSnapshot {
+    let pending = PENDING.lock().ok().and_then(|mut pending| pending.take());
+    match pending {
+        Some(pending) if read_text(app).as_deref() == Some(pending.pasted.as_str()) => {
+            pending.snapshot
+        }
+        _ => capture(app),
+    }
+}
+
+/// Restaure `snapshot` après `delay_ms`, sur un thread à part, si le
+/// presse-papiers contient toujours `pasted`.
+pub fn restore_later(app: &AppHandle, snapshot: ClipboardSnapshot, pasted: String, delay_ms: u64) {
+    let id = NEXT_ID.fetch_add(1, Ordering::Relaxed);
+    if let Ok(mut pending) = PENDING.lock() {
+        *pending = Some(PendingRestore {
+            id,
+            snapshot,
+            pasted,
+        });
+    }
+
+    let app = app.clone();
+    std::thread::spawn(move || {
+        std::thread::sleep(Duration::from_millis(delay_ms));
+        let pending = PENDING.lock().ok().and_then(|mut pending| {
+            // Reprise par un collage plus récent
+            if pending.as_ref().is_some_and(|p| p.id == id) {
+                pending.take()
+            } else {
+                None
+            }
+        });
+        let Some(pending) = pending else {
+            return;
+        };
+        if read_text(&app).as_deref() != Some(pending.pasted.as_str()) {
+            info!("[Clipboard] Nouveau contenu copié pendant le collage, pas de restauration");
+            return;
+        }
+        if let Err(e) = restore(&app, &pending.snapshot) {
+            warn!("[Clipboard] {}", e);
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn types(list: &[&str]) -> Vec<String> {
+        list.iter().map(|t| t.to_string()).collect()
+    }
+
+    #[test]
+    fn wanted_types_rank_files_image_html_text() {
+        let available = types(&[
+            "TARGETS",
+            "TIMESTAMP",
+            "UTF8_STRING",
+            "text/plain;charset=utf-8",
+            "text/html",
+            "image/jpeg",
+            "image/png",
+            "x-special/gnome-copied-files",
+            "text/uri-list",
+        ]);
+        assert_eq!(
+            wanted_types(&available),
+            types(&[
+                "text/uri-list",
+                "image/png",
+                "text/html",
+                "text/plain;charset=utf-8"
+            ])
+        );
+    }
+
+    #[test]
+    fn wanted_types_fall_back_to_x11_targets() {
+        assert_eq!(
+            wanted_types(&types(&["TARGETS", "STRING", "image/bmp"])),
+            types(&["image/bmp", "STRING"])
+        );
+        assert!(wanted_types(&types(&["TARGETS", "application/x-foo"])).is_empty());
+    }
+
+    #[test]
+    fn html_restore_uses_plain_text_companion() {
+        let formats = vec![
+            MimeData {
+                mime: "text/html".to_string(),
+                data: b"<b>gras</b>".to_vec(),
+            },
+            MimeData {
+                mime: "UTF8_STRING".to_string(),
+                data: "gras é".as_bytes().to_vec(),
+            },
+        ];
+        assert_eq!(plain_text(&formats).as_deref(), Some("gras é"));
+        assert_eq!(plain_text(&formats[..1]), None);
+    }
+
+    const NOT_AVAILABLE: &str = "The clipboard contents were not available in the requested format or the clipboard is empty.";
+
+    #[test]
+    fn plugin_snapshot_treats_missing_content_as_empty() {
+        let snapshot = plugin_snapshot(Err(NOT_AVAILABLE.to_string()), || {
+            Err(NOT_AVAILABLE.to_string())
+        });
+        assert_eq!(snapshot, ClipboardSnapshot::Empty);
+        let snapshot = plugin_snapshot(Ok(String::new()), || Err(NOT_AVAILABLE.to_string()));
+        assert_eq!(snapshot, ClipboardSnapshot::Empty);
+    }
+
+    #[test]
+    fn plugin_snapshot_keeps_text_image_and_unreadable_content() {
+        let snapshot = plugin_snapshot(Ok("copié".to_string()), || unreachable!());
+        assert_eq!(snapshot, ClipboardSnapshot::Text("copié".to_string()));
+
+        let image = ClipboardSnapshot::Image {
+            rgba: vec![0; 4],
+            width: 1,
+            height: 1,
+        };
+        let snapshot = plugin_snapshot(Err(NOT_AVAILABLE.to_string()), || Ok(image.clone()));
+        assert_eq!(snapshot, image);
+
+        let occupied = "The native clipboard is not accessible due to being held by another party.";
+        let snapshot =
+            plugin_snapshot(Err(occupied.to_string()), || Err(NOT_AVAILABLE.to_string()));
+        assert_eq!(snapshot, ClipboardSnapshot::Unsupported);
+    }
+}
@@ -194,6 +194,39 @@ pub fn get_clamshell_microphone(app: AppHandle) -> Result<String, String> {
         .unwrap_or_else(|| "default".to_string()))
 }
 
+#[tauri::command]
+#[specta::specta]
+pub fn set_microphone_priority(app: AppHandle, devices: Vec<String>) -> Result<(), String> {
+    let mut settings = get_settings(&app);
+    settings.microphone_priority = devices
+        .into_iter()
+        .filter(|name| name != "default" && !name.trim().is_empty())
+        .collect();
+    write_settings(&app, settings);
+
+    // Applique la nouvelle priorité si un micro mieux classé est branché
+    let rm = app.state::<Arc<AudioRecordingManager>>();
+    rm.update_selected_device()
+        .map_err(|e| format!("Failed to update selected device: {}", e))?;
+
+    Ok(())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn get_microphone_priority(app: AppHandle) -> Result<Vec<String>, String> {
+    Ok(get_settings(&app).microphone_priority)
+}
+
+/// Micro réellement ouvert (peut différer du micro sélectionné après un
+/// débranchement). None si le flux est fermé.
+#[tauri::command]
+#[specta::specta]
+pub fn get_active_microphone(app: AppHandle) -> Option<String> {
+    let rm = app.state::<Arc<AudioRecordingManager>>();
+    rm.active_microphone()
+}
+
 #[tauri::command]
 #[specta::specta]
 pub fn is_recording(app: AppHandle) -> bool {
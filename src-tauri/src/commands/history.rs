@@ -1,6 +1,9 @@
 use crate::managers::history::{HistoryEntry, HistoryManager};
+use crate::settings::RecordingCodec;
+use crate::stats::{self, UsageStats};
 use std::sync::Arc;
-use tauri::{AppHandle, State};
+use tauri::http::{header, Request, Response, StatusCode};
+use tauri::{AppHandle, Manager, State};
 
 #[tauri::command]
 #[specta::specta]
@@ -27,17 +30,42 @@ pub async fn toggle_history_entry_saved(
         .map_err(|e| e.to_string())
 }
 
+/// Protocole `recording://localhost/<fichier>` du lecteur audio de
+/// l'historique : l'enregistrement en clair, servi depuis la mémoire
+pub fn recording_protocol(app: &AppHandle, request: &Request<Vec<u8>>) -> Response<Vec<u8>> {
+    let file_name = request.uri().path().trim_start_matches('/');
+    let history_manager = app.state::<Arc<HistoryManager>>();
+    let (status, mime_type, body) = match history_manager.playable_audio(file_name) {
+        Ok((data, mime_type)) => (StatusCode::OK, mime_type, data),
+        Err(e) => {
+            log::warn!("Failed to serve recording '{}': {}", file_name, e);
+            (
+                StatusCode::NOT_FOUND,
+                "text/plain",
+                e.to_string().into_bytes(),
+            )
+        }
+    };
+    Response::builder()
+        .status(status)
+        .header(header::CONTENT_TYPE, mime_type)
+        .header(header::ACCESS_CONTROL_ALLOW_ORIGIN, "*")
+        .body(body)
+        .expect("valid recording response")
+}
+
+/// Statistiques d'usage des `days` derniers jours
 #[tauri::command]
 #[specta::specta]
-pub async fn get_audio_file_path(
-    _app: AppHandle,
+pub async fn get_usage_stats(
     history_manager: State<'_, Arc<HistoryManager>>,
-    file_name: String,
-) -> Result<String, String> {
-    let path = history_manager.get_audio_file_path(&file_name);
-    path.to_str()
-        .ok_or_else(|| "Invalid file path".to_string())
-        .map(|s| s.to_string())
+    days: u32,
+) -> Result<UsageStats, String> {
+    let since = chrono::Utc::now().timestamp() - days as i64 * 24 * 60 * 60;
+    let records = history_manager
+        .get_dictation_metrics(since)
+        .map_err(|e| e.to_string())?;
+    Ok(stats::aggregate(&records))
 }
 
 #[tauri::command]
@@ -86,6 +114,7 @@ pub async fn update_recording_retention_period(
         "days3" => RecordingRetentionPeriod::Days3,
         "weeks2" => RecordingRetentionPeriod::Weeks2,
         "months3" => RecordingRetentionPeriod::Months3,
+        "disk_quota" => RecordingRetentionPeriod::DiskQuota,
         _ => return Err(format!("Invalid retention period: {}", period)),
     };
 
@@ -99,3 +128,71 @@ pub async fn update_recording_retention_period(
 
     Ok(())
 }
+
+#[tauri::command]
+#[specta::specta]
+pub async fn update_recording_quota_mb(
+    app: AppHandle,
+    history_manager: State<'_, Arc<HistoryManager>>,
+    quota_mb: u32,
+) -> Result<(), String> {
+    if quota_mb == 0 {
+        return Err("Recording quota must be greater than 0 MB".to_string());
+    }
+
+    let mut settings = crate::settings::get_settings(&app);
+    settings.recording_quota_mb = quota_mb;
+    crate::settings::write_settings(&app, settings);
+
+    history_manager
+        .cleanup_old_entries()
+        .map_err(|e| e.to_string())?;
+
+    Ok(())
+}
+
+/// Formats de stockage compilés dans cette build
+#[tauri::command]
+#[specta::specta]
+pub fn get_available_recording_codecs() -> Vec<RecordingCodec> {
+    RecordingCodec::available()
+}
+
+/// Change le format de stockage ; les enregistrements WAV existants sont
+/// convertis en arrière-plan, vers un format avec perte seulement si
+/// `convert_lossy` (conversion irréversible confirmée par l'utilisateur)
+#[tauri::command]
+#[specta::specta]
+pub fn change_recording_codec_setting(
+    app: AppHandle,
+    history_manager: State<'_, Arc<HistoryManager>>,
+    codec: RecordingCodec,
+    convert_lossy: bool,
+) -> Result<(), String> {
+    if !codec.is_available() {
+        return Err(format!(
+            "Recording format {:?} is not enabled in this build",
+            codec
+        ));
+    }
+
+    let mut settings = crate::settings::get_settings(&app);
+    settings.recording_codec = codec;
+    crate::settings::write_settings(&app, settings);
+
+    history_manager.spawn_recording_migration(convert_lossy);
+    Ok(())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn change_recording_opus_bitrate_setting(app: AppHandle, kbps: u32) -> Result<(), String> {
+    if !(6..=128).contains(&kbps) {
+        return Err(format!("Invalid Opus bitrate: {} kb/s", kbps));
+    }
+
+    let mut settings = crate::settings::get_settings(&app);
+    settings.recording_opus_bitrate_kbps = kbps;
+    crate::settings::write_settings(&app, settings);
+    Ok(())
+}
@@ -1,7 +1,9 @@
 pub mod audio;
 pub mod history;
 pub mod models;
+pub mod settings_transfer;
 pub mod transcription;
+pub mod vault;
 
 use crate::settings::{get_settings, write_settings, AppSettings, LogLevel};
 use crate::utils::cancel_current_operation;
@@ -28,7 +30,12 @@ pub fn get_app_dir_path(app: AppHandle) -> Result<String, String> {
 #[tauri::command]
 #[specta::specta]
 pub fn get_app_settings(app: AppHandle) -> Result<AppSettings, String> {
-    Ok(get_settings(&app))
+    let mut settings = get_settings(&app);
+    // Keys are shown in clear in the settings UI, never as ciphertext
+    for key in settings.post_process_api_keys.values_mut() {
+        *key = crate::vault::open_secret(key).unwrap_or_default();
+    }
+    Ok(settings)
 }
 
 #[tauri::command]
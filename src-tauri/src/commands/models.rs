@@ -1,6 +1,8 @@
 use crate::managers::model::{ModelInfo, ModelManager};
 use crate::managers::transcription::TranscriptionManager;
+use crate::model_benchmark::{self, BenchmarkSummary};
 use crate::settings::{get_settings, write_settings};
+use crate::whisper_ffi::WhisperPreset;
 use std::sync::Arc;
 use tauri::{AppHandle, State};
 
@@ -24,13 +26,16 @@ pub async fn get_model_info(
 #[tauri::command]
 #[specta::specta]
 pub async fn download_model(
+    app_handle: AppHandle,
     model_manager: State<'_, Arc<ModelManager>>,
     model_id: String,
 ) -> Result<(), String> {
     model_manager
         .download_model(&model_id)
         .await
-        .map_err(|e| e.to_string())
+        .map_err(|e| e.to_string())?;
+    model_benchmark::notify_if_stale(&app_handle);
+    Ok(())
 }
 
 #[tauri::command]
@@ -55,7 +60,9 @@ pub async fn delete_model(
 
     model_manager
         .delete_model(&model_id)
-        .map_err(|e| e.to_string())
+        .map_err(|e| e.to_string())?;
+    model_benchmark::notify_if_stale(&app_handle);
+    Ok(())
 }
 
 #[tauri::command]
@@ -142,3 +149,41 @@ pub async fn cancel_download(
         .cancel_download(&model_id)
         .map_err(|e| e.to_string())
 }
+
+/// Preset de décodage Whisper d'un modèle, appliqué à la prochaine dictée
+#[tauri::command]
+#[specta::specta]
+pub async fn set_whisper_preset(
+    app_handle: AppHandle,
+    model_manager: State<'_, Arc<ModelManager>>,
+    model_id: String,
+    preset: WhisperPreset,
+) -> Result<(), String> {
+    model_manager
+        .get_model_info(&model_id)
+        .ok_or_else(|| format!("Model not found: {}", model_id))?;
+
+    let mut settings = get_settings(&app_handle);
+    settings.whisper_presets.insert(model_id, preset);
+    write_settings(&app_handle, settings);
+    Ok(())
+}
+
+/// Mesure chaque modèle téléchargé sur l'extrait de référence et recommande
+/// le plus précis qui tient l'objectif de latence
+#[tauri::command]
+#[specta::specta]
+pub async fn run_model_benchmark(app_handle: AppHandle) -> Result<BenchmarkSummary, String> {
+    tauri::async_runtime::spawn_blocking(move || model_benchmark::run(&app_handle))
+        .await
+        .map_err(|e| format!("Model benchmark interrupted: {}", e))?
+}
+
+/// Dernières mesures, avec la recommandation pour l'objectif actuel
+#[tauri::command]
+#[specta::specta]
+pub async fn get_model_benchmark(
+    app_handle: AppHandle,
+) -> Result<Option<BenchmarkSummary>, String> {
+    Ok(model_benchmark::current_summary(&app_handle))
+}
@@ -0,0 +1,107 @@
+//! Commandes d'export/import des réglages et de baseline d'équipe (voir
+//! [`crate::settings_bundle`] et [`crate::baseline`])
+
+use crate::baseline::{self, BaselineInfo};
+use crate::settings::{get_settings, write_settings, AppSettings};
+use crate::settings_bundle::{self, ImportPreview};
+use crate::shortcut;
+use std::fs;
+use tauri::AppHandle;
+
+fn read_file(path: &str) -> Result<String, String> {
+    fs::read_to_string(path).map_err(|e| format!("Failed to read {}: {}", path, e))
+}
+
+/// Réglages et aperçu de l'import du fichier `path`
+fn prepare_import(app: &AppHandle, path: &str) -> Result<(AppSettings, ImportPreview), String> {
+    let bundle = settings_bundle::parse(&read_file(path)?)?;
+    settings_bundle::prepare_import(&get_settings(app), &bundle, baseline::current().as_ref())
+}
+
+/// Réenregistre les raccourcis modifiés par l'import
+fn reregister_bindings(
+    app: &AppHandle,
+    before: &AppSettings,
+    after: &AppSettings,
+    warnings: &mut Vec<String>,
+) {
+    for (id, binding) in &after.bindings {
+        // Le raccourci d'annulation n'est enregistré que pendant une dictée
+        let previous = before.bindings.get(id);
+        if id == "cancel" || previous.map(|b| &b.current_binding) == Some(&binding.current_binding)
+        {
+            continue;
+        }
+        if let Some(previous) = previous {
+            if let Err(e) = shortcut::unregister_shortcut(app, previous.clone()) {
+                log::warn!("Failed to unregister shortcut '{}': {}", id, e);
+            }
+        }
+        if let Err(e) = shortcut::register_shortcut(app, binding.clone()) {
+            warnings.push(format!("Shortcut '{}' could not be registered: {}", id, e));
+        }
+    }
+}
+
+/// Écrit les réglages dans `path` ; les clés d'API seulement si demandé
+#[tauri::command]
+#[specta::specta]
+pub fn export_settings(app: AppHandle, path: String, include_api_keys: bool) -> Result<(), String> {
+    let bundle = settings_bundle::export(
+        &get_settings(&app),
+        include_api_keys,
+        chrono::Utc::now().timestamp(),
+    )?;
+    let json = serde_json::to_string_pretty(&bundle).map_err(|e| e.to_string())?;
+    fs::write(&path, json).map_err(|e| format!("Failed to write {}: {}", path, e))
+}
+
+/// Réglages que l'import de `path` modifierait, sans rien appliquer
+#[tauri::command]
+#[specta::specta]
+pub fn preview_settings_import(app: AppHandle, path: String) -> Result<ImportPreview, String> {
+    prepare_import(&app, &path).map(|(_, preview)| preview)
+}
+
+/// Applique l'import de `path` ; renvoie les changements et avertissements
+#[tauri::command]
+#[specta::specta]
+pub fn import_settings(app: AppHandle, path: String) -> Result<ImportPreview, String> {
+    let before = get_settings(&app);
+    let (after, mut preview) = prepare_import(&app, &path)?;
+    write_settings(&app, after.clone());
+    reregister_bindings(&app, &before, &after, &mut preview.warnings);
+    log::info!(
+        "Imported {} setting change(s) from {}",
+        preview.changes.len(),
+        path
+    );
+    Ok(preview)
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn get_team_baseline() -> Option<BaselineInfo> {
+    baseline::current().map(|baseline| baseline.info())
+}
+
+/// Installe le fichier de baseline `path` et applique ses valeurs
+#[tauri::command]
+#[specta::specta]
+pub fn install_team_baseline(app: AppHandle, path: String) -> Result<BaselineInfo, String> {
+    let baseline = baseline::install(&read_file(&path)?)?;
+    let before = get_settings(&app);
+    let mut after = before.clone();
+    baseline.apply_defaults(&mut after)?;
+    write_settings(&app, after.clone());
+    reregister_bindings(&app, &before, &after, &mut Vec::new());
+    log::info!("Team baseline '{}' installed", baseline.name);
+    Ok(baseline.info())
+}
+
+/// Retire la baseline si elle l'autorise ; les réglages restent tels quels
+#[tauri::command]
+#[specta::specta]
+pub fn remove_team_baseline() -> Result<(), String> {
+    baseline::remove()
+}
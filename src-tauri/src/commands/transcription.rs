@@ -38,3 +38,12 @@ pub fn unload_model_manually(
         .unload_model()
         .map_err(|e| format!("Failed to unload model: {}", e))
 }
+
+/// Transcrit un fichier audio/vidéo (m4a, mp3, ogg, wav, mp4…) et l'ajoute à l'historique
+#[tauri::command]
+#[specta::specta]
+pub async fn transcribe_audio_file(app: AppHandle, path: String) -> Result<String, String> {
+    crate::file_import::transcribe_file(&app, std::path::PathBuf::from(path))
+        .await
+        .map_err(|e| e.to_string())
+}
@@ -0,0 +1,96 @@
+//! Commandes du coffre de chiffrement au repos (voir [`crate::vault`])
+
+use crate::managers::history::HistoryManager;
+use crate::settings::{get_settings, write_settings};
+use crate::vault::{self, VaultError, VaultMode, VaultStatus};
+use std::sync::Arc;
+use tauri::{AppHandle, Emitter, State};
+
+/// Chiffre (`seal`) ou remet en clair les clés d'API, l'historique et les
+/// enregistrements ; ce qui est déjà dans l'état voulu n'est pas réécrit
+fn reseal_everything(
+    app: &AppHandle,
+    history_manager: &HistoryManager,
+    seal: bool,
+) -> Result<(), String> {
+    let mut settings = get_settings(app);
+    for key in settings.post_process_api_keys.values_mut() {
+        if key.is_empty() || vault::is_sealed_secret(key) == seal {
+            continue;
+        }
+        let plain = vault::open_secret(key).ok_or_else(|| VaultError::Locked.to_string())?;
+        *key = if seal {
+            vault::seal_secret(&plain).map_err(|e| e.to_string())?
+        } else {
+            plain
+        };
+    }
+    write_settings(app, settings);
+
+    history_manager
+        .reseal_all(seal)
+        .map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn get_vault_status() -> VaultStatus {
+    vault::status()
+}
+
+/// Crée la clé maître puis chiffre les données existantes
+#[tauri::command]
+#[specta::specta]
+pub async fn enable_vault(
+    app: AppHandle,
+    history_manager: State<'_, Arc<HistoryManager>>,
+    mode: VaultMode,
+    passphrase: Option<String>,
+) -> Result<VaultStatus, String> {
+    vault::enable(mode, passphrase.as_deref()).map_err(|e| e.to_string())?;
+    reseal_everything(&app, &history_manager, true)?;
+    Ok(vault::status())
+}
+
+/// Charge la clé ; reprend aussi les migrations interrompues
+#[tauri::command]
+#[specta::specta]
+pub async fn unlock_vault(
+    app: AppHandle,
+    history_manager: State<'_, Arc<HistoryManager>>,
+    passphrase: Option<String>,
+) -> Result<VaultStatus, String> {
+    vault::unlock(passphrase.as_deref()).map_err(|e| e.to_string())?;
+    reseal_everything(&app, &history_manager, true)?;
+    // Dictées reçues pendant le verrouillage, gardées en mémoire
+    history_manager.flush_pending().map_err(|e| e.to_string())?;
+    // Conversion des enregistrements reportée tant que le coffre était verrouillé
+    history_manager.spawn_recording_migration(false);
+    Ok(vault::status())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn lock_vault(app: AppHandle) -> Result<VaultStatus, String> {
+    vault::lock().map_err(|e| e.to_string())?;
+    if let Err(e) = app.emit("history-updated", ()) {
+        log::error!("Failed to emit history-updated event: {}", e);
+    }
+    Ok(vault::status())
+}
+
+/// Remet toutes les données en clair puis supprime la clé maître
+#[tauri::command]
+#[specta::specta]
+pub async fn disable_vault(
+    app: AppHandle,
+    history_manager: State<'_, Arc<HistoryManager>>,
+) -> Result<VaultStatus, String> {
+    if !vault::status().unlocked {
+        return Err(VaultError::Locked.to_string());
+    }
+    reseal_everything(&app, &history_manager, false)?;
+    vault::disable().map_err(|e| e.to_string())?;
+    Ok(vault::status())
+}
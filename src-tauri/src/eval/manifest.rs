@@ -0,0 +1,107 @@
+//! Manifeste du corpus d'évaluation.
+//!
+//! Format JSON Lines (une paire audio/référence par ligne, `#` = commentaire)
+//! ou tableau JSON :
+//!
+//! ```json
+//! {"id": "reunion-01", "audio": "audio/reunion-01.wav", "reference": "On se retrouve demain à 10h.", "structure": "SingleMessage"}
+//! ```
+//!
+//! `audio` (alias `wav`) est relatif au dossier du manifeste. `structure` est
+//! optionnel : sans lui, l'utterance ne compte pas dans la précision StructureHint.
+
+use crate::pipeline::rules::StructureHint;
+use anyhow::{anyhow, Context, Result};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ManifestEntry {
+    /// Identifiant affiché dans les rapports (défaut : nom du fichier audio)
+    #[serde(default)]
+    pub id: String,
+    #[serde(alias = "wav")]
+    pub audio: PathBuf,
+    /// Transcription de référence, ponctuée et structurée comme attendu
+    pub reference: String,
+    /// Structure attendue du texte dicté
+    #[serde(default)]
+    pub structure: Option<StructureHint>,
+}
+
+/// Charge un manifeste `.jsonl` ou `.json` et résout les chemins audio.
+pub fn load_manifest(path: &Path) -> Result<Vec<ManifestEntry>> {
+    let content = std::fs::read_to_string(path)
+        .with_context(|| format!("Lecture du manifeste {}", path.display()))?;
+    let is_jsonl = path
+        .extension()
+        .and_then(|e| e.to_str())
+        .map(|e| e.eq_ignore_ascii_case("jsonl"))
+        .unwrap_or(false);
+    let base_dir = path.parent().unwrap_or_else(|| Path::new("."));
+    parse_manifest(&content, is_jsonl, base_dir)
+}
+
+fn parse_manifest(content: &str, is_jsonl: bool, base_dir: &Path) -> Result<Vec<ManifestEntry>> {
+    let mut entries: Vec<ManifestEntry> = if is_jsonl {
+        content
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| {
+                let line = line.trim();
+                !line.is_empty() && !line.starts_with('#')
+            })
+            .map(|(i, line)| {
+                serde_json::from_str(line).with_context(|| format!("Manifeste, ligne {}", i + 1))
+            })
+            .collect::<Result<_>>()?
+    } else {
+        serde_json::from_str(content).context("Manifeste JSON invalide")?
+    };
+
+    if entries.is_empty() {
+        return Err(anyhow!("Manifeste vide"));
+    }
+
+    for entry in &mut entries {
+        if entry.audio.is_relative() {
+            entry.audio = base_dir.join(&entry.audio);
+        }
+        if entry.id.is_empty() {
+            entry.id = entry
+                .audio
+                .file_stem()
+                .map(|s| s.to_string_lossy().to_string())
+                .unwrap_or_default();
+        }
+    }
+    Ok(entries)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_jsonl_with_comments_and_relative_paths() {
+        let content = r#"
+# corpus de test
+{"audio": "a/memo.wav", "reference": "Bonjour.", "structure": "SingleMessage"}
+{"id": "liste", "wav": "/abs/liste.wav", "reference": "- un\n- deux"}
+"#;
+        let entries = parse_manifest(content, true, Path::new("/corpus")).unwrap();
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].id, "memo");
+        assert_eq!(entries[0].audio, PathBuf::from("/corpus/a/memo.wav"));
+        assert_eq!(entries[0].structure, Some(StructureHint::SingleMessage));
+        assert_eq!(entries[1].id, "liste");
+        assert_eq!(entries[1].audio, PathBuf::from("/abs/liste.wav"));
+        assert_eq!(entries[1].structure, None);
+    }
+
+    #[test]
+    fn rejects_empty_or_invalid_manifest() {
+        assert!(parse_manifest("[]", false, Path::new(".")).is_err());
+        assert!(parse_manifest("{\"audio\": 1}", true, Path::new(".")).is_err());
+    }
+}
@@ -0,0 +1,362 @@
+//! Métriques d'évaluation : WER, CER, F1 ponctuation.
+//!
+//! Les textes sont normalisés avant comparaison (minuscules, ponctuation
+//! retirée, espaces fusionnés) : on mesure les mots, pas la typographie.
+//! La ponctuation est évaluée à part, alignée sur les mots de la référence.
+
+use serde::Serialize;
+
+/// Signes de ponctuation évalués par le F1
+const SCORED_PUNCTUATION: &[char] = &['.', ',', '?', '!', ':', ';'];
+
+/// Mot normalisé suivi des signes de ponctuation qui le terminent
+#[derive(Debug, Clone, PartialEq)]
+struct Token {
+    word: String,
+    punct: Vec<char>,
+}
+
+fn is_word_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '\'' || c == '’' || c == '-'
+}
+
+fn flush_word(current: &mut String, tokens: &mut Vec<Token>) {
+    let word = current.trim_matches(|c| c == '\'' || c == '-').to_string();
+    current.clear();
+    if !word.is_empty() {
+        tokens.push(Token {
+            word,
+            punct: Vec::new(),
+        });
+    }
+}
+
+/// Découpe en mots normalisés. Les apostrophes et traits d'union internes
+/// sont conservés (« l'équipe », « peut-être ») ; un signe de ponctuation est
+/// rattaché au dernier mot rencontré (« bonjour ? » → bonjour + '?').
+fn tokenize(text: &str) -> Vec<Token> {
+    let mut tokens = Vec::new();
+    let mut current = String::new();
+    for c in text.chars() {
+        if is_word_char(c) {
+            let c = if c == '’' { '\'' } else { c };
+            current.extend(c.to_lowercase());
+            continue;
+        }
+        flush_word(&mut current, &mut tokens);
+        if SCORED_PUNCTUATION.contains(&c) {
+            if let Some(last) = tokens.last_mut() {
+                if !last.punct.contains(&c) {
+                    last.punct.push(c);
+                }
+            }
+        }
+    }
+    flush_word(&mut current, &mut tokens);
+    tokens
+}
+
+/// Texte normalisé utilisé pour le WER/CER
+pub fn normalize_text(text: &str) -> String {
+    tokenize(text)
+        .into_iter()
+        .map(|t| t.word)
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum EditOp {
+    Match,
+    Substitute,
+    Insert,
+    Delete,
+}
+
+/// Une étape de l'alignement référence ↔ hypothèse
+#[derive(Debug, Clone, Copy)]
+struct AlignStep {
+    op: EditOp,
+    reference: Option<usize>,
+    hypothesis: Option<usize>,
+}
+
+/// Alignement de Levenshtein avec backtrace
+fn align<T: PartialEq>(reference: &[T], hypothesis: &[T]) -> Vec<AlignStep> {
+    let (n, m) = (reference.len(), hypothesis.len());
+    let mut dist = vec![vec![0usize; m + 1]; n + 1];
+    for (i, row) in dist.iter_mut().enumerate() {
+        row[0] = i;
+    }
+    for (j, cell) in dist[0].iter_mut().enumerate() {
+        *cell = j;
+    }
+    for i in 1..=n {
+        for j in 1..=m {
+            let cost = usize::from(reference[i - 1] != hypothesis[j - 1]);
+            dist[i][j] = (dist[i - 1][j - 1] + cost)
+                .min(dist[i - 1][j] + 1)
+                .min(dist[i][j - 1] + 1);
+        }
+    }
+
+    let mut steps = Vec::with_capacity(n.max(m));
+    let (mut i, mut j) = (n, m);
+    while i > 0 || j > 0 {
+        if i > 0 && j > 0 {
+            let same = reference[i - 1] == hypothesis[j - 1];
+            if dist[i][j] == dist[i - 1][j - 1] + usize::from(!same) {
+                steps.push(AlignStep {
+                    op: if same { EditOp::Match } else { EditOp::Substitute },
+                    reference: Some(i - 1),
+                    hypothesis: Some(j - 1),
+                });
+                i -= 1;
+                j -= 1;
+                continue;
+            }
+        }
+        if i > 0 && dist[i][j] == dist[i - 1][j] + 1 {
+            steps.push(AlignStep {
+                op: EditOp::Delete,
+                reference: Some(i - 1),
+                hypothesis: None,
+            });
+            i -= 1;
+        } else {
+            steps.push(AlignStep {
+                op: EditOp::Insert,
+                reference: None,
+                hypothesis: Some(j - 1),
+            });
+            j -= 1;
+        }
+    }
+    steps.reverse();
+    steps
+}
+
+/// Compteurs d'erreurs d'édition — agrégeables sur tout un corpus
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct ErrorCounts {
+    pub substitutions: usize,
+    pub deletions: usize,
+    pub insertions: usize,
+    /// Nombre d'unités (mots ou caractères) de la référence
+    pub reference_len: usize,
+}
+
+impl ErrorCounts {
+    fn from_alignment(steps: &[AlignStep], reference_len: usize) -> Self {
+        let mut counts = ErrorCounts {
+            reference_len,
+            ..Default::default()
+        };
+        for step in steps {
+            match step.op {
+                EditOp::Match => {}
+                EditOp::Substitute => counts.substitutions += 1,
+                EditOp::Insert => counts.insertions += 1,
+                EditOp::Delete => counts.deletions += 1,
+            }
+        }
+        counts
+    }
+
+    pub fn errors(&self) -> usize {
+        self.substitutions + self.deletions + self.insertions
+    }
+
+    /// Taux d'erreur (S + D + I) / N. Une référence vide donne 0 si
+    /// l'hypothèse l'est aussi, 1 sinon.
+    pub fn rate(&self) -> f64 {
+        if self.reference_len == 0 {
+            return if self.errors() == 0 { 0.0 } else { 1.0 };
+        }
+        self.errors() as f64 / self.reference_len as f64
+    }
+
+    pub fn add(&mut self, other: &ErrorCounts) {
+        self.substitutions += other.substitutions;
+        self.deletions += other.deletions;
+        self.insertions += other.insertions;
+        self.reference_len += other.reference_len;
+    }
+}
+
+/// Word Error Rate (compteurs) sur textes normalisés
+pub fn word_errors(reference: &str, hypothesis: &str) -> ErrorCounts {
+    let reference: Vec<String> = tokenize(reference).into_iter().map(|t| t.word).collect();
+    let hypothesis: Vec<String> = tokenize(hypothesis).into_iter().map(|t| t.word).collect();
+    ErrorCounts::from_alignment(&align(&reference, &hypothesis), reference.len())
+}
+
+/// Character Error Rate (compteurs) sur textes normalisés, espaces compris
+pub fn char_errors(reference: &str, hypothesis: &str) -> ErrorCounts {
+    let reference: Vec<char> = normalize_text(reference).chars().collect();
+    let hypothesis: Vec<char> = normalize_text(hypothesis).chars().collect();
+    ErrorCounts::from_alignment(&align(&reference, &hypothesis), reference.len())
+}
+
+/// Vrais/faux positifs de ponctuation — agrégeables sur tout un corpus
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct PunctuationCounts {
+    pub true_positives: usize,
+    pub false_positives: usize,
+    pub false_negatives: usize,
+}
+
+impl PunctuationCounts {
+    pub fn precision(&self) -> f64 {
+        ratio(self.true_positives, self.true_positives + self.false_positives)
+    }
+
+    pub fn recall(&self) -> f64 {
+        ratio(self.true_positives, self.true_positives + self.false_negatives)
+    }
+
+    /// F1 ; vaut 1 quand ni la référence ni l'hypothèse ne sont ponctuées
+    pub fn f1(&self) -> f64 {
+        if self.true_positives + self.false_positives + self.false_negatives == 0 {
+            return 1.0;
+        }
+        let (p, r) = (self.precision(), self.recall());
+        if p + r == 0.0 {
+            0.0
+        } else {
+            2.0 * p * r / (p + r)
+        }
+    }
+
+    pub fn add(&mut self, other: &PunctuationCounts) {
+        self.true_positives += other.true_positives;
+        self.false_positives += other.false_positives;
+        self.false_negatives += other.false_negatives;
+    }
+}
+
+fn ratio(num: usize, den: usize) -> f64 {
+    if den == 0 {
+        0.0
+    } else {
+        num as f64 / den as f64
+    }
+}
+
+/// Compare la ponctuation après chaque mot, en s'appuyant sur l'alignement
+/// des mots : un signe compte comme correct s'il suit le mot aligné
+/// (identique ou substitué) de la référence.
+pub fn punctuation_counts(reference: &str, hypothesis: &str) -> PunctuationCounts {
+    let reference = tokenize(reference);
+    let hypothesis = tokenize(hypothesis);
+    let ref_words: Vec<&str> = reference.iter().map(|t| t.word.as_str()).collect();
+    let hyp_words: Vec<&str> = hypothesis.iter().map(|t| t.word.as_str()).collect();
+
+    let mut counts = PunctuationCounts::default();
+    for step in align(&ref_words, &hyp_words) {
+        let ref_punct = step.reference.map_or(&[][..], |i| &reference[i].punct[..]);
+        let hyp_punct = step.hypothesis.map_or(&[][..], |j| &hypothesis[j].punct[..]);
+        for c in hyp_punct {
+            if ref_punct.contains(c) {
+                counts.true_positives += 1;
+            } else {
+                counts.false_positives += 1;
+            }
+        }
+        counts.false_negatives += ref_punct.iter().filter(|c| !hyp_punct.contains(c)).count();
+    }
+    counts
+}
+
+/// Diff mot à mot façon wdiff : `[-supprimé-]` / `{+inséré+}`
+pub fn word_diff(reference: &str, hypothesis: &str) -> String {
+    let reference: Vec<String> = tokenize(reference).into_iter().map(|t| t.word).collect();
+    let hypothesis: Vec<String> = tokenize(hypothesis).into_iter().map(|t| t.word).collect();
+    align(&reference, &hypothesis)
+        .into_iter()
+        .map(|step| {
+            let r = step.reference.map(|i| reference[i].as_str());
+            let h = step.hypothesis.map(|j| hypothesis[j].as_str());
+            match step.op {
+                EditOp::Match => r.unwrap_or_default().to_string(),
+                EditOp::Substitute => {
+                    format!("[-{}-]{{+{}+}}", r.unwrap_or_default(), h.unwrap_or_default())
+                }
+                EditOp::Delete => format!("[-{}-]", r.unwrap_or_default()),
+                EditOp::Insert => format!("{{+{}+}}", h.unwrap_or_default()),
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn normalization_ignores_case_punctuation_and_spacing() {
+        assert_eq!(
+            normalize_text("Bonjour,  l’équipe !\n- Peut-être demain ?"),
+            "bonjour l'équipe peut-être demain"
+        );
+    }
+
+    #[test]
+    fn wer_counts_each_edit_kind() {
+        let counts = word_errors("le chat dort bien", "le chien dort bien là");
+        assert_eq!(counts.substitutions, 1);
+        assert_eq!(counts.insertions, 1);
+        assert_eq!(counts.deletions, 0);
+        assert_eq!(counts.reference_len, 4);
+        assert!((counts.rate() - 0.5).abs() < 1e-9);
+
+        let counts = word_errors("le chat dort bien", "le dort bien");
+        assert_eq!(counts.deletions, 1);
+        assert_eq!(counts.errors(), 1);
+    }
+
+    #[test]
+    fn identical_texts_have_zero_error() {
+        let text = "Réunion demain, 10h.";
+        assert_eq!(word_errors(text, "réunion demain 10h").rate(), 0.0);
+        assert_eq!(char_errors(text, "RÉUNION DEMAIN 10H").rate(), 0.0);
+        assert_eq!(word_errors("", "").rate(), 0.0);
+        assert_eq!(word_errors("", "bonjour").rate(), 1.0);
+    }
+
+    #[test]
+    fn cer_counts_characters() {
+        let counts = char_errors("chat", "chats");
+        assert_eq!(counts.insertions, 1);
+        assert_eq!(counts.reference_len, 4);
+    }
+
+    #[test]
+    fn punctuation_is_aligned_on_words() {
+        // La virgule après « alors » est correcte malgré la substitution
+        // « on → nous » ; le point final manque, le « ? » est en trop.
+        let counts = punctuation_counts(
+            "Alors, on y va demain.",
+            "alors, nous y va demain ?",
+        );
+        assert_eq!(counts.true_positives, 1);
+        assert_eq!(counts.false_positives, 1);
+        assert_eq!(counts.false_negatives, 1);
+        assert!((counts.f1() - 0.5).abs() < 1e-9);
+    }
+
+    #[test]
+    fn unpunctuated_texts_have_perfect_f1() {
+        assert_eq!(punctuation_counts("bonjour", "bonjour").f1(), 1.0);
+        assert_eq!(punctuation_counts("bonjour.", "bonjour").f1(), 0.0);
+    }
+
+    #[test]
+    fn diff_marks_edits() {
+        assert_eq!(
+            word_diff("le chat dort", "le chien dort bien"),
+            "le [-chat-]{+chien+} dort {+bien+}"
+        );
+    }
+}
@@ -0,0 +1,137 @@
+//! Harnais d'évaluation qualité : STT + pipeline FR sur un corpus annoté
+//!
+//! Pour chaque paire (audio, référence) du manifeste :
+//!   décodage 16 kHz → STT (une seule fois) → pipeline dans chaque WriteMode,
+//!   avec et sans LLM → WER / CER / F1 ponctuation / précision StructureHint
+//!
+//! La configuration `stt` mesure la sortie Whisper brute, avant règles, pour
+//! isoler l'apport du pipeline. Le STT est injecté par l'appelant (closure),
+//! ce qui permet d'évaluer n'importe quel moteur ou un transcripteur simulé.
+//!
+//! Lancement sur un vrai corpus : voir `tests/evaluation.rs`.
+
+pub mod manifest;
+pub mod metrics;
+pub mod report;
+
+use crate::audio_toolkit::decode_audio_file;
+use crate::pipeline::modes::WriteMode;
+use crate::pipeline::orchestrator;
+use crate::pipeline::rules::StructureHint;
+use anyhow::Result;
+
+pub use manifest::{load_manifest, ManifestEntry};
+pub use report::{EvalFailure, EvalReport, RunSummary, UtteranceResult};
+
+/// Sortie du moteur STT évalué
+#[derive(Debug, Clone)]
+pub struct Transcript {
+    pub text: String,
+    /// Confiance [0.0, 1.0], utilisée par le routage du pipeline
+    pub confidence: f32,
+}
+
+/// Callback LLM, même signature que celle attendue par `orchestrator::process`
+pub type LlmCleanupFn = dyn Fn(&str, WriteMode, StructureHint) -> Result<String>;
+
+/// Une configuration évaluée : STT brut, ou pipeline dans un mode ± LLM
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RunConfig {
+    /// None = sortie STT brute, sans pipeline
+    pub mode: Option<WriteMode>,
+    pub llm: bool,
+}
+
+impl RunConfig {
+    pub fn label(&self) -> String {
+        match self.mode {
+            None => "stt".to_string(),
+            Some(mode) if self.llm => format!("{}+llm", mode),
+            Some(mode) => mode.to_string(),
+        }
+    }
+}
+
+/// Configurations à évaluer : STT brut, puis chaque mode sans LLM et,
+/// si un LLM est fourni, avec LLM.
+pub fn run_configs(modes: &[WriteMode], with_llm: bool) -> Vec<RunConfig> {
+    let mut configs = vec![RunConfig {
+        mode: None,
+        llm: false,
+    }];
+    for &mode in modes {
+        configs.push(RunConfig {
+            mode: Some(mode),
+            llm: false,
+        });
+        if with_llm {
+            configs.push(RunConfig {
+                mode: Some(mode),
+                llm: true,
+            });
+        }
+    }
+    configs
+}
+
+/// Évalue le corpus. Les utterances dont le décodage ou le STT échoue sont
+/// listées dans `failures` et exclues des agrégats.
+pub fn evaluate(
+    model: &str,
+    entries: &[ManifestEntry],
+    modes: &[WriteMode],
+    llm: Option<&LlmCleanupFn>,
+    mut transcribe: impl FnMut(&[f32]) -> Result<Transcript>,
+) -> EvalReport {
+    let configs = run_configs(modes, llm.is_some());
+    let mut utterances = Vec::new();
+    let mut failures = Vec::new();
+
+    for entry in entries {
+        let transcript = decode_audio_file(&entry.audio, |_| {})
+            .and_then(|samples| transcribe(&samples));
+        let transcript = match transcript {
+            Ok(t) => t,
+            Err(e) => {
+                log::warn!("[Eval] {} ignoré : {}", entry.id, e);
+                failures.push(EvalFailure {
+                    id: entry.id.clone(),
+                    audio: entry.audio.to_string_lossy().to_string(),
+                    error: e.to_string(),
+                });
+                continue;
+            }
+        };
+
+        for config in &configs {
+            let (hypothesis, structure, llm_fallback, duration_ms) = match config.mode {
+                None => (transcript.text.trim().to_string(), None, false, 0),
+                Some(mode) => {
+                    let result = orchestrator::process(
+                        &transcript.text,
+                        transcript.confidence,
+                        mode,
+                        if config.llm { llm } else { None },
+                    );
+                    (
+                        result.text,
+                        Some(result.structure_hint),
+                        // Sans LLM, le fallback structure est le comportement attendu
+                        config.llm && result.llm_fallback,
+                        result.duration_ms,
+                    )
+                }
+            };
+            utterances.push(UtteranceResult::new(
+                entry,
+                config,
+                hypothesis,
+                structure,
+                llm_fallback,
+                duration_ms,
+            ));
+        }
+    }
+
+    EvalReport::new(model, &configs, utterances, failures)
+}
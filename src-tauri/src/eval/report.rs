@@ -0,0 +1,275 @@
+//! Rapports d'évaluation : résultats par utterance + agrégats par configuration,
+//! sérialisables en JSON et rendus en Markdown.
+//!
+//! Les agrégats WER/CER/F1 sont calculés au niveau corpus (somme des erreurs
+//! / somme des longueurs), pas en moyenne des scores par utterance.
+
+use super::manifest::ManifestEntry;
+use super::metrics::{self, ErrorCounts, PunctuationCounts};
+use super::RunConfig;
+use crate::pipeline::modes::WriteMode;
+use crate::pipeline::rules::StructureHint;
+use anyhow::Result;
+use serde::Serialize;
+use std::fmt::Write as _;
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, Clone, Serialize)]
+pub struct UtteranceResult {
+    pub id: String,
+    /// Libellé de la configuration (`stt`, `chat`, `pro+llm`…)
+    pub config: String,
+    pub mode: Option<WriteMode>,
+    pub llm: bool,
+    pub reference: String,
+    pub hypothesis: String,
+    pub wer: f64,
+    pub cer: f64,
+    pub punctuation_f1: f64,
+    pub word_errors: ErrorCounts,
+    pub char_errors: ErrorCounts,
+    pub punctuation: PunctuationCounts,
+    pub structure_expected: Option<StructureHint>,
+    /// Structure détectée par le pipeline (None pour la config `stt`)
+    pub structure_detected: Option<StructureHint>,
+    pub llm_fallback: bool,
+    pub duration_ms: u64,
+    /// Diff mot à mot normalisé : `[-référence-]{+hypothèse+}`
+    pub diff: String,
+}
+
+impl UtteranceResult {
+    pub(super) fn new(
+        entry: &ManifestEntry,
+        config: &RunConfig,
+        hypothesis: String,
+        structure_detected: Option<StructureHint>,
+        llm_fallback: bool,
+        duration_ms: u64,
+    ) -> Self {
+        let word_errors = metrics::word_errors(&entry.reference, &hypothesis);
+        let char_errors = metrics::char_errors(&entry.reference, &hypothesis);
+        let punctuation = metrics::punctuation_counts(&entry.reference, &hypothesis);
+        Self {
+            id: entry.id.clone(),
+            config: config.label(),
+            mode: config.mode,
+            llm: config.llm,
+            reference: entry.reference.clone(),
+            diff: metrics::word_diff(&entry.reference, &hypothesis),
+            hypothesis,
+            wer: word_errors.rate(),
+            cer: char_errors.rate(),
+            punctuation_f1: punctuation.f1(),
+            word_errors,
+            char_errors,
+            punctuation,
+            structure_expected: entry.structure,
+            structure_detected,
+            llm_fallback,
+            duration_ms,
+        }
+    }
+
+    /// None si la structure attendue n'est pas annotée ou non applicable
+    fn structure_correct(&self) -> Option<bool> {
+        match (self.structure_expected, self.structure_detected) {
+            (Some(expected), Some(detected)) => Some(expected == detected),
+            _ => None,
+        }
+    }
+}
+
+/// Agrégats d'une configuration sur tout le corpus
+#[derive(Debug, Clone, Serialize)]
+pub struct RunSummary {
+    pub config: String,
+    pub utterances: usize,
+    pub wer: f64,
+    pub cer: f64,
+    pub punctuation_precision: f64,
+    pub punctuation_recall: f64,
+    pub punctuation_f1: f64,
+    /// Précision StructureHint sur les utterances annotées (None si aucune)
+    pub structure_accuracy: Option<f64>,
+    pub structure_annotated: usize,
+    pub llm_fallbacks: usize,
+    pub mean_duration_ms: f64,
+}
+
+impl RunSummary {
+    fn from_results(config: String, results: &[&UtteranceResult]) -> Self {
+        let mut words = ErrorCounts::default();
+        let mut chars = ErrorCounts::default();
+        let mut punctuation = PunctuationCounts::default();
+        for r in results {
+            words.add(&r.word_errors);
+            chars.add(&r.char_errors);
+            punctuation.add(&r.punctuation);
+        }
+        let structure: Vec<bool> = results.iter().filter_map(|r| r.structure_correct()).collect();
+        let structure_accuracy = (!structure.is_empty()).then(|| {
+            structure.iter().filter(|ok| **ok).count() as f64 / structure.len() as f64
+        });
+        let mean_duration_ms = if results.is_empty() {
+            0.0
+        } else {
+            results.iter().map(|r| r.duration_ms as f64).sum::<f64>() / results.len() as f64
+        };
+
+        Self {
+            config,
+            utterances: results.len(),
+            wer: words.rate(),
+            cer: chars.rate(),
+            punctuation_precision: punctuation.precision(),
+            punctuation_recall: punctuation.recall(),
+            punctuation_f1: punctuation.f1(),
+            structure_accuracy,
+            structure_annotated: structure.len(),
+            llm_fallbacks: results.iter().filter(|r| r.llm_fallback).count(),
+            mean_duration_ms,
+        }
+    }
+}
+
+/// Utterance non évaluée (audio illisible, STT en échec)
+#[derive(Debug, Clone, Serialize)]
+pub struct EvalFailure {
+    pub id: String,
+    pub audio: String,
+    pub error: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct EvalReport {
+    /// Modèle STT évalué (nom ou chemin)
+    pub model: String,
+    pub generated_at: String,
+    pub summaries: Vec<RunSummary>,
+    pub utterances: Vec<UtteranceResult>,
+    pub failures: Vec<EvalFailure>,
+}
+
+impl EvalReport {
+    pub(super) fn new(
+        model: &str,
+        configs: &[RunConfig],
+        utterances: Vec<UtteranceResult>,
+        failures: Vec<EvalFailure>,
+    ) -> Self {
+        let summaries = configs
+            .iter()
+            .map(|config| {
+                let label = config.label();
+                let results: Vec<&UtteranceResult> =
+                    utterances.iter().filter(|u| u.config == label).collect();
+                RunSummary::from_results(label, &results)
+            })
+            .collect();
+        Self {
+            model: model.to_string(),
+            generated_at: chrono::Local::now().to_rfc3339(),
+            summaries,
+            utterances,
+            failures,
+        }
+    }
+
+    pub fn summary(&self, config: &str) -> Option<&RunSummary> {
+        self.summaries.iter().find(|s| s.config == config)
+    }
+
+    pub fn to_json(&self) -> Result<String> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+
+    pub fn to_markdown(&self) -> String {
+        let mut md = String::new();
+        let _ = writeln!(md, "# Évaluation — {}\n", self.model);
+        let _ = writeln!(md, "Généré le {}\n", self.generated_at);
+
+        md.push_str("## Agrégats\n\n");
+        md.push_str(
+            "| Config | N | WER | CER | Ponct. P | Ponct. R | Ponct. F1 | Structure | Fallback LLM | ms moy. |\n",
+        );
+        md.push_str("|---|---:|---:|---:|---:|---:|---:|---:|---:|---:|\n");
+        for s in &self.summaries {
+            let structure = match s.structure_accuracy {
+                Some(acc) => format!("{} ({})", pct(acc), s.structure_annotated),
+                None => "—".to_string(),
+            };
+            let _ = writeln!(
+                md,
+                "| {} | {} | {} | {} | {} | {} | {} | {} | {} | {:.1} |",
+                s.config,
+                s.utterances,
+                pct(s.wer),
+                pct(s.cer),
+                pct(s.punctuation_precision),
+                pct(s.punctuation_recall),
+                pct(s.punctuation_f1),
+                structure,
+                s.llm_fallbacks,
+                s.mean_duration_ms
+            );
+        }
+
+        if !self.failures.is_empty() {
+            md.push_str("\n## Échecs\n\n");
+            for f in &self.failures {
+                let _ = writeln!(md, "- `{}` ({}) : {}", f.id, f.audio, escape(&f.error));
+            }
+        }
+
+        md.push_str("\n## Détail par utterance\n");
+        let mut current_id: Option<&str> = None;
+        for u in &self.utterances {
+            if current_id != Some(u.id.as_str()) {
+                current_id = Some(u.id.as_str());
+                let _ = writeln!(md, "\n### {}\n", u.id);
+                let _ = writeln!(md, "Référence : {}\n", escape(&u.reference));
+                md.push_str("| Config | WER | CER | Ponct. F1 | Structure | Diff |\n");
+                md.push_str("|---|---:|---:|---:|---|---|\n");
+            }
+            let structure = match (u.structure_expected, u.structure_detected) {
+                (_, None) => "—".to_string(),
+                (Some(expected), Some(detected)) if expected != detected => {
+                    format!("{:?} ≠ {:?}", detected, expected)
+                }
+                (_, Some(detected)) => format!("{:?}", detected),
+            };
+            let _ = writeln!(
+                md,
+                "| {} | {} | {} | {} | {} | `{}` |",
+                u.config,
+                pct(u.wer),
+                pct(u.cer),
+                pct(u.punctuation_f1),
+                structure,
+                u.diff
+            );
+        }
+        md
+    }
+
+    /// Écrit `eval-report.json` et `eval-report.md` dans `dir`.
+    /// Retourne les deux chemins.
+    pub fn write_to_dir(&self, dir: &Path) -> Result<(PathBuf, PathBuf)> {
+        std::fs::create_dir_all(dir)?;
+        let json_path = dir.join("eval-report.json");
+        let md_path = dir.join("eval-report.md");
+        std::fs::write(&json_path, self.to_json()?)?;
+        std::fs::write(&md_path, self.to_markdown())?;
+        Ok((json_path, md_path))
+    }
+}
+
+fn pct(value: f64) -> String {
+    format!("{:.1} %", value * 100.0)
+}
+
+/// Échappe les caractères qui casseraient un tableau Markdown
+fn escape(text: &str) -> String {
+    text.replace('|', "\\|").replace('\n', " ⏎ ")
+}
@@ -0,0 +1,165 @@
+/// Import de fichiers audio/vidéo (mémos vocaux, enregistrements de réunion)
+///
+/// Même chaîne que la dictée micro, sans collage :
+///   décodage + resampling 16 kHz mono → STT (découpé si long) → pipeline FR
+///   → historique (lien vers le fichier d'origine, sans copie de l'audio)
+///
+/// Points d'entrée : commande `transcribe_audio_file`, flag CLI `--transcribe-file`
+/// et glisser-déposer sur la fenêtre principale.
+use crate::audio_toolkit::{decode_audio_file, is_supported_import_file};
+use crate::managers::history::HistoryManager;
+use crate::managers::transcription::TranscriptionManager;
+use crate::pipeline::modes::WriteMode;
+use crate::settings::get_settings;
+use anyhow::{anyhow, Result};
+use log::{error, info};
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use tauri::{AppHandle, Emitter, Manager};
+
+/// Progression d'un import, émise sur `file-import-progress`
+#[derive(Clone, Debug, Serialize)]
+pub struct FileImportProgress {
+    pub file: String,
+    /// "decoding" | "transcribing" | "processing" | "done" | "error"
+    pub stage: String,
+    pub percentage: f64,
+    pub text: Option<String>,
+    pub error: Option<String>,
+}
+
+fn emit_progress(app: &AppHandle, file: &Path, stage: &str, percentage: f64) {
+    let _ = app.emit(
+        "file-import-progress",
+        FileImportProgress {
+            file: file.to_string_lossy().to_string(),
+            stage: stage.to_string(),
+            percentage,
+            text: None,
+            error: None,
+        },
+    );
+}
+
+/// Transcrit un fichier et l'enregistre dans l'historique.
+/// Retourne le texte final (après pipeline).
+pub async fn transcribe_file(app: &AppHandle, path: PathBuf) -> Result<String> {
+    let result = run_import(app, &path).await;
+
+    let (text, error) = match &result {
+        Ok(text) => (Some(text.clone()), None),
+        Err(e) => (None, Some(e.to_string())),
+    };
+    let _ = app.emit(
+        "file-import-progress",
+        FileImportProgress {
+            file: path.to_string_lossy().to_string(),
+            stage: if error.is_none() { "done" } else { "error" }.to_string(),
+            percentage: 100.0,
+            text,
+            error,
+        },
+    );
+
+    result
+}
+
+async fn run_import(app: &AppHandle, path: &Path) -> Result<String> {
+    if !path.is_file() {
+        return Err(anyhow!("Fichier introuvable : {}", path.display()));
+    }
+    if !is_supported_import_file(path) {
+        return Err(anyhow!("Format non supporté : {}", path.display()));
+    }
+    info!("[Import] {}", path.display());
+
+    let tm = Arc::clone(&app.state::<Arc<TranscriptionManager>>());
+    let hm = Arc::clone(&app.state::<Arc<HistoryManager>>());
+    tm.initiate_model_load();
+
+    // Étape 1 : décodage (0 → 30 %)
+    emit_progress(app, path, "decoding", 0.0);
+    let samples = {
+        let app = app.clone();
+        let path = path.to_path_buf();
+        tauri::async_runtime::spawn_blocking(move || {
+            decode_audio_file(&path, |p| {
+                emit_progress(&app, &path, "decoding", p as f64 * 30.0)
+            })
+        })
+        .await
+        .map_err(|e| anyhow!("Décodage interrompu : {}", e))??
+    };
+    if samples.is_empty() {
+        return Err(anyhow!("Aucun audio décodé dans {}", path.display()));
+    }
+
+    // Étape 2 : STT (30 → 90 %) — la progression fine par segment arrive
+    // via `transcription-progress`
+    emit_progress(app, path, "transcribing", 30.0);
+    let output = {
+        let tm = Arc::clone(&tm);
+        tauri::async_runtime::spawn_blocking(move || tm.transcribe(&samples))
+            .await
+            .map_err(|e| anyhow!("Transcription interrompue : {}", e))??
+    };
+
+    // Étape 3 : pipeline FR (règles + LLM conditionnel)
+    emit_progress(app, path, "processing", 90.0);
+    let settings = get_settings(app);
+    let write_mode = settings.write_mode.parse::<WriteMode>().unwrap_or_default();
+    let raw_text = output.text;
+    let confidence = output.confidence;
+    let pipeline_result = tauri::async_runtime::spawn_blocking(move || {
+        crate::pipeline::orchestrator::process(
+            &raw_text,
+            confidence,
+            write_mode,
+            Some(&crate::llm::cleanup::run),
+        )
+    })
+    .await
+    .map_err(|e| anyhow!("Pipeline interrompu : {}", e))?;
+
+    if pipeline_result.llm_fallback {
+        let _ = app.emit("llm-fallback", crate::llm::cleanup::fallback_event());
+    }
+
+    let text = pipeline_result.text;
+    if text.is_empty() {
+        return Err(anyhow!("Aucune parole détectée dans {}", path.display()));
+    }
+
+    // Le fichier d'origine reste la référence : pas de copie de l'audio
+    hm.save_transcription(
+        Vec::new(),
+        text.clone(),
+        None,
+        None,
+        Some(settings.write_mode.clone()),
+        Some(path.to_string_lossy().to_string()),
+        Vec::new(),
+        None,
+    )
+    .await?;
+
+    info!("[Import] Terminé : {} mots", text.split_whitespace().count());
+    Ok(text)
+}
+
+/// Lance l'import en tâche de fond (CLI, glisser-déposer) — les erreurs sont
+/// loggées et remontées via `file-import-progress`.
+///
+/// Les fichiers sont traités l'un après l'autre : le moteur STT refuse les
+/// transcriptions concurrentes.
+pub fn spawn_transcribe_files(app: &AppHandle, paths: Vec<PathBuf>) {
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        for path in paths {
+            if let Err(e) = transcribe_file(&app, path.clone()).await {
+                error!("Import de {} échoué : {}", path.display(), e);
+            }
+        }
+    });
+}
@@ -3,23 +3,33 @@ mod actions;
 pub mod whisper_ffi;
 mod audio_feedback;
 pub mod audio_toolkit;
+mod baseline;
 pub mod cli;
 mod clipboard;
+mod clipboard_snapshot;
 mod commands;
+pub mod eval;
+mod file_import;
 mod helpers;
 mod input;
 pub mod llm;
 mod llm_client;
 mod managers;
+mod model_benchmark;
+mod output;
 mod overlay;
 pub mod pipeline;
 mod settings;
+mod settings_bundle;
+mod settings_migrations;
 mod shortcut;
 mod signal_handle;
+pub mod stats;
 mod transcription_coordinator;
 mod tray;
 mod tray_i18n;
 mod utils;
+mod vault;
 
 pub use cli::CliArgs;
 use specta_typescript::{BigIntExportBehavior, Typescript};
@@ -122,6 +132,11 @@ fn initialize_core_logic(app_handle: &AppHandle) {
         TranscriptionManager::new(app_handle, model_manager.clone())
             .expect("Failed to initialize transcription manager"),
     );
+    // The vault must be loaded before history rows are read or written
+    match app_handle.path().app_data_dir() {
+        Ok(app_data_dir) => vault::init(&app_data_dir),
+        Err(e) => log::error!("Failed to resolve app data dir for the vault: {}", e),
+    }
     let history_manager =
         Arc::new(HistoryManager::new(app_handle).expect("Failed to initialize history manager"));
 
@@ -131,6 +146,15 @@ fn initialize_core_logic(app_handle: &AppHandle) {
     app_handle.manage(transcription_manager.clone());
     app_handle.manage(history_manager.clone());
 
+    // Recordings kept in another format than the configured codec are converted
+    history_manager.spawn_recording_migration(false);
+
+    // Circuit breaker state changes of LLM backends are reported like fallbacks
+    let fallback_handle = app_handle.clone();
+    llm::resilience::set_listener(move |event| {
+        let _ = fallback_handle.emit("llm-fallback", event);
+    });
+
     // Note: Shortcuts are NOT initialized here.
     // The frontend is responsible for calling the `initialize_shortcuts` command
     // after permissions are confirmed (on macOS) or after onboarding completes.
@@ -182,6 +206,9 @@ fn initialize_core_logic(app_handle: &AppHandle) {
             "copy_last_transcript" => {
                 tray::copy_last_transcript(app);
             }
+            "undo_last_dictation" => {
+                crate::utils::undo_last_dictation(app, crate::utils::UndoTrigger::TrayMenu);
+            }
             "unload_model" => {
                 let transcription_manager = app.state::<Arc<TranscriptionManager>>();
                 if !transcription_manager.is_model_loaded() {
@@ -272,16 +299,27 @@ pub fn run(cli_args: CliArgs) {
         shortcut::change_overlay_position_setting,
         shortcut::change_debug_mode_setting,
         shortcut::change_word_correction_threshold_setting,
+        shortcut::change_max_recording_seconds_setting,
         shortcut::change_paste_method_setting,
+        shortcut::change_llm_stream_mode_setting,
         shortcut::get_available_typing_tools,
         shortcut::change_typing_tool_setting,
         shortcut::change_external_script_path_setting,
         shortcut::change_clipboard_handling_setting,
+        shortcut::change_output_sinks_setting,
+        shortcut::change_clipboard_restore_delay_setting,
+        shortcut::change_paste_format_setting,
+        shortcut::set_app_paste_format,
+        shortcut::change_session_context_enabled_setting,
+        shortcut::change_session_context_limits_setting,
+        shortcut::change_benchmark_target_rtf_setting,
+        shortcut::change_benchmark_auto_select_setting,
         shortcut::change_auto_submit_setting,
         shortcut::change_auto_submit_key_setting,
         shortcut::change_post_process_enabled_setting,
         shortcut::change_experimental_enabled_setting,
         shortcut::change_post_process_base_url_setting,
+        shortcut::change_post_process_kind_setting,
         shortcut::change_post_process_api_key_setting,
         shortcut::change_post_process_model_setting,
         shortcut::set_post_process_provider,
@@ -289,6 +327,15 @@ pub fn run(cli_args: CliArgs) {
         shortcut::add_post_process_prompt,
         shortcut::update_post_process_prompt,
         shortcut::delete_post_process_prompt,
+        shortcut::add_post_process_recipe,
+        shortcut::update_post_process_recipe,
+        shortcut::delete_post_process_recipe,
+        shortcut::set_binding_recipe,
+        shortcut::add_custom_binding,
+        shortcut::update_custom_binding,
+        shortcut::delete_custom_binding,
+        shortcut::set_redaction_policy,
+        shortcut::update_redaction_names,
         shortcut::set_post_process_selected_prompt,
         shortcut::update_custom_words,
         shortcut::suspend_binding,
@@ -329,6 +376,9 @@ pub fn run(cli_args: CliArgs) {
         commands::models::is_model_loading,
         commands::models::has_any_models_available,
         commands::models::has_any_models_or_downloads,
+        commands::models::set_whisper_preset,
+        commands::models::run_model_benchmark,
+        commands::models::get_model_benchmark,
         commands::audio::update_microphone_mode,
         commands::audio::get_microphone_mode,
         commands::audio::get_available_microphones,
@@ -341,16 +391,35 @@ pub fn run(cli_args: CliArgs) {
         commands::audio::check_custom_sounds,
         commands::audio::set_clamshell_microphone,
         commands::audio::get_clamshell_microphone,
+        commands::audio::set_microphone_priority,
+        commands::audio::get_microphone_priority,
+        commands::audio::get_active_microphone,
         commands::audio::is_recording,
         commands::transcription::set_model_unload_timeout,
         commands::transcription::get_model_load_status,
         commands::transcription::unload_model_manually,
+        commands::transcription::transcribe_audio_file,
         commands::history::get_history_entries,
         commands::history::toggle_history_entry_saved,
-        commands::history::get_audio_file_path,
+        commands::history::get_usage_stats,
         commands::history::delete_history_entry,
         commands::history::update_history_limit,
         commands::history::update_recording_retention_period,
+        commands::history::update_recording_quota_mb,
+        commands::history::get_available_recording_codecs,
+        commands::history::change_recording_codec_setting,
+        commands::history::change_recording_opus_bitrate_setting,
+        commands::vault::get_vault_status,
+        commands::vault::enable_vault,
+        commands::vault::unlock_vault,
+        commands::vault::lock_vault,
+        commands::vault::disable_vault,
+        commands::settings_transfer::export_settings,
+        commands::settings_transfer::preview_settings_import,
+        commands::settings_transfer::import_settings,
+        commands::settings_transfer::get_team_baseline,
+        commands::settings_transfer::install_team_baseline,
+        commands::settings_transfer::remove_team_baseline,
         helpers::clamshell::is_laptop,
     ]);
 
@@ -395,13 +464,20 @@ pub fn run(cli_args: CliArgs) {
     }
 
     builder
-        .plugin(tauri_plugin_single_instance::init(|app, args, _cwd| {
+        .plugin(tauri_plugin_single_instance::init(|app, args, cwd| {
             if args.iter().any(|a| a == "--toggle-transcription") {
                 signal_handle::send_transcription_input(app, "transcribe", "CLI");
             } else if args.iter().any(|a| a == "--toggle-post-process") {
                 signal_handle::send_transcription_input(app, "transcribe_with_post_process", "CLI");
             } else if args.iter().any(|a| a == "--cancel") {
                 crate::utils::cancel_current_operation(app);
+            } else if let Some(path) = args
+                .iter()
+                .position(|a| a == "--transcribe-file")
+                .and_then(|i| args.get(i + 1))
+            {
+                // Chemin relatif au répertoire courant de l'instance appelante
+                file_import::spawn_transcribe_files(app, vec![std::path::Path::new(&cwd).join(path)]);
             } else {
                 show_main_window(app);
             }
@@ -421,6 +497,11 @@ pub fn run(cli_args: CliArgs) {
         ))
         .manage(cli_args.clone())
         .setup(move |app| {
+            // Baseline d'équipe chargée avant la première lecture des réglages
+            match app.path().app_data_dir() {
+                Ok(app_data_dir) => baseline::init(&app_data_dir),
+                Err(e) => log::error!("Failed to resolve app data dir for the baseline: {}", e),
+            }
             let mut settings = get_settings(&app.handle());
 
             // CLI --debug flag overrides debug_mode and log level (runtime-only, not persisted)
@@ -438,6 +519,10 @@ pub fn run(cli_args: CliArgs) {
 
             initialize_core_logic(&app_handle);
 
+            if let Some(path) = cli_args.transcribe_file.clone() {
+                file_import::spawn_transcribe_files(&app_handle, vec![path]);
+            }
+
             // Note: Accessibility permissions are NOT checked here.
             // The frontend calls `check_accessibility_permission` after onboarding,
             // following the same pattern as initialize_enigo and initialize_shortcuts.
@@ -480,6 +565,16 @@ pub fn run(cli_args: CliArgs) {
                     }
                 }
             }
+            tauri::WindowEvent::DragDrop(tauri::DragDropEvent::Drop { paths, .. }) => {
+                let files: Vec<_> = paths
+                    .iter()
+                    .filter(|p| audio_toolkit::is_supported_import_file(p))
+                    .cloned()
+                    .collect();
+                if !files.is_empty() {
+                    file_import::spawn_transcribe_files(window.app_handle(), files);
+                }
+            }
             tauri::WindowEvent::ThemeChanged(theme) => {
                 log::info!("Theme changed to: {:?}", theme);
                 // Update tray icon to match new theme, maintaining idle state
@@ -487,6 +582,13 @@ pub fn run(cli_args: CliArgs) {
             }
             _ => {}
         })
+        .register_asynchronous_uri_scheme_protocol("recording", |ctx, request, responder| {
+            // Déchiffrement et décodage hors du thread de la WebView
+            let app = ctx.app_handle().clone();
+            tauri::async_runtime::spawn_blocking(move || {
+                responder.respond(commands::history::recording_protocol(&app, &request));
+            });
+        })
         .invoke_handler(specta_builder.invoke_handler())
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
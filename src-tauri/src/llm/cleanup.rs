@@ -6,8 +6,9 @@
 /// Architecture :
 /// - Backend : Ollama sur http://127.0.0.1:11434
 /// - Modèle  : qwen2.5:0.5b (auto-détecté, voir OLLAMA_MODEL)
-/// - HTTP    : reqwest::blocking dans un std::thread dédié (pas de conflit Tokio)
-/// - Timeout : 8s (LLM_TIMEOUT_SECS) + 2s marge thread
+/// - HTTP    : reqwest async sur un runtime Tokio local, dans un std::thread dédié
+/// - Timeout : 8s par tentative, 1 retry, disjoncteur (`resilience::RetryPolicy::local_cleanup`)
+/// - Streaming : `run_streaming` — NDJSON, timeout d'inactivité entre tokens
 /// - Params  : temperature=0.0 (greedy), top_k=1, num_predict adaptatif (Story 8.1)
 ///
 /// Installation Ollama :
@@ -15,6 +16,8 @@
 ///   ollama pull qwen2.5:0.5b
 ///   ollama serve
 
+use super::resilience::{self, CallError, Failure, LlmFallbackEvent, RetryPolicy};
+use super::stream::{self, StreamCancel, StreamFormat};
 use crate::pipeline::modes::WriteMode;
 use crate::pipeline::rules::StructureHint;
 use anyhow::Result;
@@ -26,8 +29,14 @@ use std::time::Duration;
 const OLLAMA_BASE_URL: &str = "http://127.0.0.1:11434";
 /// Modèle cible — qwen2.5:0.5b (~500 Mo, 200-300ms/50 tokens sur M1)
 const OLLAMA_MODEL: &str = "qwen2.5:0.5b";
-/// Timeout inférence LLM
-const LLM_TIMEOUT_SECS: u64 = 8;
+/// Timeout d'inactivité entre deux tokens (streaming)
+const STREAM_IDLE_TIMEOUT_SECS: u64 = 8;
+/// Attente minimale du thread d'appel, y compris hors streaming
+const CALL_TIMEOUT_SECS: u64 = 8;
+/// Disjoncteur d'Ollama local : un serveur arrêté n'est plus sondé à chaque dictée
+pub const BACKEND_ID: &str = "ollama-local";
+const BACKEND_LABEL: &str = "Ollama";
+const POLICY: RetryPolicy = RetryPolicy::local_cleanup();
 
 /// Calcule le nombre de tokens de sortie adaptatif selon la structure et la longueur (Story 8.1).
 ///
@@ -41,15 +50,32 @@ fn compute_num_predict(word_count: usize, hint: StructureHint) -> i64 {
     }
 }
 
+/// Prompt système du mode, suivi des dictées précédentes de la session s'il y en a
+fn system_prompt(mode: WriteMode, hint: StructureHint, context: Option<&str>) -> String {
+    let prompt = mode.system_prompt(hint);
+    match context.map(str::trim).filter(|c| !c.is_empty()) {
+        Some(context) => format!(
+            "{prompt}\n\nDictées précédentes de l'utilisateur, pour reprendre les noms et \
+             termes déjà employés. Ne les recopie pas, ne corrige que le texte reçu :\n{context}"
+        ),
+        None => prompt,
+    }
+}
+
 /// Corps de la requête Ollama /api/chat (Story 8.1 : hint intégré au prompt + tokens adaptatifs)
-fn build_ollama_payload(text: &str, mode: WriteMode, hint: StructureHint) -> serde_json::Value {
+fn build_ollama_payload(
+    text: &str,
+    mode: WriteMode,
+    hint: StructureHint,
+    context: Option<&str>,
+) -> serde_json::Value {
     let word_count = text.split_whitespace().count();
     let num_predict = compute_num_predict(word_count, hint);
 
     serde_json::json!({
         "model": OLLAMA_MODEL,
         "messages": [
-            {"role": "system", "content": mode.system_prompt(hint)},
+            {"role": "system", "content": system_prompt(mode, hint, context)},
             {"role": "user",   "content": text}
         ],
         "stream": false,
@@ -62,36 +88,93 @@ fn build_ollama_payload(text: &str, mode: WriteMode, hint: StructureHint) -> ser
     })
 }
 
-/// Appelle Ollama depuis un thread non-Tokio (évite le deadlock reqwest::blocking + async).
-fn call_ollama(text: &str, mode: WriteMode, hint: StructureHint) -> Result<String> {
+/// Événement `llm-fallback` d'une dictée retombée sur les règles
+pub fn fallback_event() -> LlmFallbackEvent {
+    LlmFallbackEvent::fallback(BACKEND_ID, BACKEND_LABEL)
+}
+
+/// Délai max d'attente du thread d'appel : échéance des retries + marge
+fn thread_timeout() -> Duration {
+    POLICY.deadline.max(Duration::from_secs(CALL_TIMEOUT_SECS)) + Duration::from_secs(2)
+}
+
+fn local_runtime() -> Result<tokio::runtime::Runtime> {
+    Ok(tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()?)
+}
+
+fn http_client() -> Result<reqwest::Client> {
+    Ok(reqwest::Client::builder()
+        .connect_timeout(Duration::from_secs(2))
+        .build()?)
+}
+
+/// POST /api/chat, échecs classés pour les retries et le disjoncteur
+async fn post_chat(
+    client: &reqwest::Client,
+    payload: &serde_json::Value,
+) -> std::result::Result<reqwest::Response, Failure> {
+    let response = client
+        .post(format!("{OLLAMA_BASE_URL}/api/chat"))
+        .json(payload)
+        .send()
+        .await
+        .map_err(|e| Failure::from_reqwest(&e))?;
+    resilience::check_status(response).await
+}
+
+/// Appelle Ollama depuis un thread dédié (runtime Tokio local, pour ne pas
+/// bloquer le runtime de l'appelant).
+fn call_ollama(
+    text: &str,
+    mode: WriteMode,
+    hint: StructureHint,
+    context: Option<&str>,
+) -> Result<String> {
     let (tx, rx) = mpsc::channel::<Result<String>>();
-    let payload = build_ollama_payload(text, mode, hint);
+    let payload = build_ollama_payload(text, mode, hint, context);
 
     thread::spawn(move || {
         let result = (|| -> Result<String> {
-            let client = reqwest::blocking::Client::builder()
-                .timeout(Duration::from_secs(LLM_TIMEOUT_SECS))
-                .build()?;
-
-            let resp: serde_json::Value = client
-                .post(format!("{OLLAMA_BASE_URL}/api/chat"))
-                .json(&payload)
-                .send()?
-                .json()?;
-
-            let content = resp["message"]["content"]
-                .as_str()
-                .ok_or_else(|| anyhow::anyhow!("Champ 'message.content' absent de la réponse Ollama"))?
-                .trim()
-                .to_string();
-
-            Ok(content)
+            local_runtime()?.block_on(async {
+                let client = http_client()?;
+                let resp: serde_json::Value =
+                    resilience::execute(BACKEND_ID, BACKEND_LABEL, &POLICY, || async {
+                        post_chat(&client, &payload)
+                            .await?
+                            .json()
+                            .await
+                            .map_err(|e| Failure::from_reqwest(&e))
+                    })
+                    .await?;
+
+                let content = resp["message"]["content"]
+                    .as_str()
+                    .ok_or_else(|| anyhow::anyhow!("Champ 'message.content' absent de la réponse Ollama"))?
+                    .trim()
+                    .to_string();
+
+                Ok(content)
+            })
         })();
         let _ = tx.send(result);
     });
 
-    rx.recv_timeout(Duration::from_secs(LLM_TIMEOUT_SECS + 2))
-        .unwrap_or_else(|_| Err(anyhow::anyhow!("LLM timeout après {}s", LLM_TIMEOUT_SECS)))
+    rx.recv_timeout(thread_timeout()).unwrap_or_else(|_| {
+        Err(anyhow::anyhow!(
+            "LLM timeout après {}s",
+            POLICY.deadline.as_secs()
+        ))
+    })
+}
+
+/// Échec attendu (backend ignoré) : inutile d'avertir à chaque dictée
+fn is_circuit_open(error: &anyhow::Error) -> bool {
+    matches!(
+        error.downcast_ref::<CallError>(),
+        Some(CallError::CircuitOpen { .. })
+    )
 }
 
 /// Nettoie le texte transcrit avec le LLM Qwen2.5:0.5b via Ollama.
@@ -104,15 +187,27 @@ fn call_ollama(text: &str, mode: WriteMode, hint: StructureHint) -> Result<Strin
 /// # Returns
 /// Texte nettoyé, ou `Err` si Ollama n'est pas disponible (le pipeline tombera sur les règles).
 pub fn run(text: &str, mode: WriteMode, hint: StructureHint) -> Result<String> {
+    run_with_context(text, mode, hint, None)
+}
+
+/// Comme [`run`], avec les dictées récentes de la session
+/// (voir [`crate::pipeline::context`]) ajoutées au prompt système
+pub fn run_with_context(
+    text: &str,
+    mode: WriteMode,
+    hint: StructureHint,
+    context: Option<&str>,
+) -> Result<String> {
     log::debug!(
-        "LLM cleanup : mode={}, structure={:?}, {} mots → Ollama {}",
+        "LLM cleanup : mode={}, structure={:?}, {} mots, contexte={} → Ollama {}",
         mode,
         hint,
         text.split_whitespace().count(),
+        context.is_some(),
         OLLAMA_MODEL
     );
 
-    let result = call_ollama(text, mode, hint);
+    let result = call_ollama(text, mode, hint, context);
 
     match &result {
         Ok(cleaned) => log::debug!(
@@ -120,6 +215,110 @@ pub fn run(text: &str, mode: WriteMode, hint: StructureHint) -> Result<String> {
             text.len(),
             cleaned.len()
         ),
+        Err(e) if is_circuit_open(e) => log::debug!("LLM cleanup ignoré ({}) — fallback règles", e),
+        Err(e) => log::warn!("LLM cleanup échec ({}) — fallback règles", e),
+    }
+
+    result
+}
+
+/// Message du thread de streaming vers l'appelant
+enum StreamEvent {
+    Progress(String),
+    Done(Result<String>),
+}
+
+/// Appelle Ollama en streaming depuis un thread dédié (runtime Tokio local,
+/// pour ne pas bloquer le runtime de l'appelant).
+fn call_ollama_streaming(
+    text: &str,
+    mode: WriteMode,
+    hint: StructureHint,
+    context: Option<&str>,
+    cancel: &StreamCancel,
+    on_progress: &dyn Fn(&str),
+) -> Result<String> {
+    let (tx, rx) = mpsc::channel::<StreamEvent>();
+    let mut payload = build_ollama_payload(text, mode, hint, context);
+    payload["stream"] = serde_json::Value::Bool(true);
+    let thread_cancel = cancel.clone();
+
+    thread::spawn(move || {
+        let progress_tx = tx.clone();
+        let result = (|| -> Result<String> {
+            local_runtime()?.block_on(async {
+                // Retries et disjoncteur jusqu'aux en-têtes seulement : une
+                // longue réécriture reste valide tant que les tokens arrivent
+                // (cf. timeout d'inactivité)
+                let client = http_client()?;
+                let response = resilience::execute(BACKEND_ID, BACKEND_LABEL, &POLICY, || {
+                    post_chat(&client, &payload)
+                })
+                .await?;
+
+                stream::collect(
+                    stream::token_stream(response, StreamFormat::Ndjson),
+                    &thread_cancel,
+                    Duration::from_secs(STREAM_IDLE_TIMEOUT_SECS),
+                    |partial| {
+                        let _ = progress_tx.send(StreamEvent::Progress(partial.to_string()));
+                    },
+                )
+                .await
+            })
+        })();
+        let _ = tx.send(StreamEvent::Done(result));
+    });
+
+    loop {
+        // Marge au-delà des timeouts gérés par le thread
+        match rx.recv_timeout(thread_timeout()) {
+            Ok(StreamEvent::Progress(partial)) => on_progress(&partial),
+            Ok(StreamEvent::Done(result)) => return result.map(|t| t.trim().to_string()),
+            Err(_) => {
+                cancel.cancel();
+                return Err(anyhow::anyhow!(
+                    "LLM timeout après {}s",
+                    thread_timeout().as_secs()
+                ));
+            }
+        }
+    }
+}
+
+/// Variante streaming de [`run`] : `on_progress` reçoit le texte généré au
+/// fil de l'eau (aperçu overlay, frappe progressive).
+///
+/// Retourne `Err` si `cancel` est déclenché en cours de génération (erreur
+/// [`stream::Cancelled`]) ; l'appelant doit alors abandonner la dictée plutôt
+/// que coller le fallback règles.
+pub fn run_streaming(
+    text: &str,
+    mode: WriteMode,
+    hint: StructureHint,
+    context: Option<&str>,
+    cancel: &StreamCancel,
+    on_progress: &dyn Fn(&str),
+) -> Result<String> {
+    log::debug!(
+        "LLM cleanup (streaming) : mode={}, structure={:?}, {} mots, contexte={} → Ollama {}",
+        mode,
+        hint,
+        text.split_whitespace().count(),
+        context.is_some(),
+        OLLAMA_MODEL
+    );
+
+    let result = call_ollama_streaming(text, mode, hint, context, cancel, on_progress);
+
+    match &result {
+        Ok(cleaned) => log::debug!(
+            "LLM cleanup OK : {} → {} chars",
+            text.len(),
+            cleaned.len()
+        ),
+        Err(e) if e.is::<stream::Cancelled>() => log::info!("LLM cleanup annulé"),
+        Err(e) if is_circuit_open(e) => log::debug!("LLM cleanup ignoré ({}) — fallback règles", e),
         Err(e) => log::warn!("LLM cleanup échec ({}) — fallback règles", e),
     }
 
@@ -161,7 +360,7 @@ mod tests {
 
     #[test]
     fn payload_has_required_fields() {
-        let p = build_ollama_payload("test", WriteMode::Chat, StructureHint::SingleMessage);
+        let p = build_ollama_payload("test", WriteMode::Chat, StructureHint::SingleMessage, None);
         assert_eq!(p["model"], OLLAMA_MODEL);
         assert_eq!(p["stream"], false);
         assert!(p["messages"].is_array());
@@ -174,8 +373,8 @@ mod tests {
 
     #[test]
     fn payload_uses_mode_system_prompt() {
-        let p_chat = build_ollama_payload("test", WriteMode::Chat, StructureHint::SingleMessage);
-        let p_pro  = build_ollama_payload("test", WriteMode::Pro, StructureHint::SingleMessage);
+        let p_chat = build_ollama_payload("test", WriteMode::Chat, StructureHint::SingleMessage, None);
+        let p_pro  = build_ollama_payload("test", WriteMode::Pro, StructureHint::SingleMessage, None);
         let sys_chat = p_chat["messages"][0]["content"].as_str().unwrap();
         let sys_pro  = p_pro["messages"][0]["content"].as_str().unwrap();
         assert_ne!(sys_chat, sys_pro);
@@ -183,14 +382,39 @@ mod tests {
 
     #[test]
     fn payload_list_hint_changes_prompt() {
-        let p_single = build_ollama_payload("test", WriteMode::Chat, StructureHint::SingleMessage);
-        let p_list   = build_ollama_payload("test", WriteMode::Chat, StructureHint::List);
+        let p_single = build_ollama_payload("test", WriteMode::Chat, StructureHint::SingleMessage, None);
+        let p_list   = build_ollama_payload("test", WriteMode::Chat, StructureHint::List, None);
         let sys_single = p_single["messages"][0]["content"].as_str().unwrap();
         let sys_list   = p_list["messages"][0]["content"].as_str().unwrap();
         assert_ne!(sys_single, sys_list, "List hint should produce different prompt");
         assert!(sys_list.contains("tirets"), "List prompt should mention tirets");
     }
 
+    #[test]
+    fn payload_appends_session_context() {
+        let p = build_ollama_payload(
+            "il valide le devis",
+            WriteMode::Chat,
+            StructureHint::SingleMessage,
+            Some("Le devis Orbeon est prêt."),
+        );
+        let sys = p["messages"][0]["content"].as_str().unwrap();
+        assert!(sys.starts_with(&WriteMode::Chat.system_prompt(StructureHint::SingleMessage)));
+        assert!(sys.ends_with("\nLe devis Orbeon est prêt."), "{sys}");
+        assert_eq!(p["messages"][1]["content"], "il valide le devis");
+
+        let empty = build_ollama_payload(
+            "test",
+            WriteMode::Chat,
+            StructureHint::SingleMessage,
+            Some("  "),
+        );
+        assert_eq!(
+            empty["messages"][0]["content"],
+            WriteMode::Chat.system_prompt(StructureHint::SingleMessage)
+        );
+    }
+
     #[test]
     fn num_predict_adaptive_short() {
         let n = compute_num_predict(5, StructureHint::SingleMessage);
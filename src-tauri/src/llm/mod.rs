@@ -6,3 +6,5 @@
 /// Latence cible : ~200-300ms pour ~50 tokens
 
 pub mod cleanup;
+pub mod resilience;
+pub mod stream;
@@ -0,0 +1,757 @@
+//! Résilience des appels LLM — timeouts, retries, disjoncteur
+//!
+//! Couche commune au nettoyage Ollama local et aux fournisseurs de
+//! post-traitement :
+//! - timeout par tentative et échéance globale ([`RetryPolicy`])
+//! - retries bornés avec backoff exponentiel à jitter sur 429 / 5xx /
+//!   timeout / connexion refusée, en respectant `Retry-After`
+//! - disjoncteur par backend : après `failure_threshold` appels en échec
+//!   (retries épuisés), le backend est ignoré pendant `open_for`, puis un
+//!   seul appel d'essai décide de sa réouverture
+//!
+//! Les changements d'état du disjoncteur sont transmis à l'écouteur installé
+//! par [`set_listener`] (événement `llm-fallback` côté application).
+
+use once_cell::sync::Lazy;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Mutex, RwLock};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+/// Politique d'appel d'un backend
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RetryPolicy {
+    /// Durée max d'une tentative (envoi → en-têtes de réponse)
+    pub attempt_timeout: Duration,
+    /// Durée max de l'appel, retries et attentes compris
+    pub deadline: Duration,
+    /// Nombre total de tentatives (1 = pas de retry)
+    pub max_attempts: u32,
+    /// Attente avant le premier retry, doublée à chaque tentative
+    pub base_delay: Duration,
+    /// Plafond d'une attente ; un `Retry-After` plus long abandonne l'appel
+    pub max_delay: Duration,
+    /// Appels consécutifs en échec avant ouverture du disjoncteur
+    pub failure_threshold: u32,
+    /// Durée pendant laquelle le backend est ignoré une fois le disjoncteur ouvert
+    pub open_for: Duration,
+}
+
+impl RetryPolicy {
+    /// Ollama local (nettoyage du pipeline) : la latence de dictée prime,
+    /// un serveur arrêté est ignoré dès le premier échec de connexion.
+    pub const fn local_cleanup() -> Self {
+        Self {
+            attempt_timeout: Duration::from_secs(8),
+            deadline: Duration::from_secs(10),
+            max_attempts: 2,
+            base_delay: Duration::from_millis(200),
+            max_delay: Duration::from_secs(1),
+            failure_threshold: 2,
+            open_for: Duration::from_secs(120),
+        }
+    }
+
+    /// Fournisseur cloud : quelques retries sur surcharge (429 / 5xx)
+    pub const fn cloud() -> Self {
+        Self {
+            attempt_timeout: Duration::from_secs(20),
+            deadline: Duration::from_secs(45),
+            max_attempts: 3,
+            base_delay: Duration::from_millis(500),
+            max_delay: Duration::from_secs(8),
+            failure_threshold: 3,
+            open_for: Duration::from_secs(300),
+        }
+    }
+
+    /// Serveur local compatible (LM Studio, Ollama…) : chargement du modèle
+    /// parfois long au premier appel, peu d'intérêt à insister ensuite
+    pub const fn local_server() -> Self {
+        Self {
+            attempt_timeout: Duration::from_secs(60),
+            deadline: Duration::from_secs(75),
+            max_attempts: 2,
+            base_delay: Duration::from_millis(300),
+            max_delay: Duration::from_secs(2),
+            failure_threshold: 2,
+            open_for: Duration::from_secs(120),
+        }
+    }
+
+    /// Même politique avec un autre timeout par tentative ; l'échéance
+    /// globale suit pour laisser la place aux retries
+    pub fn with_attempt_timeout(self, attempt_timeout: Duration) -> Self {
+        Self {
+            attempt_timeout,
+            deadline: attempt_timeout * self.max_attempts + self.max_delay,
+            ..self
+        }
+    }
+}
+
+/// Échec d'une tentative, classé par l'appelant
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Failure {
+    /// Surcharge ou erreur serveur (429, 5xx, timeout) : réessayable
+    Transient {
+        reason: String,
+        retry_after: Option<Duration>,
+    },
+    /// Backend injoignable (connexion refusée, DNS…) : réessayable, mais
+    /// ouvre le disjoncteur dès que les retries sont épuisés
+    Unavailable(String),
+    /// Erreur de requête (4xx, réponse invalide) : ni retry, ni disjoncteur
+    Fatal(String),
+}
+
+impl Failure {
+    pub fn transient(reason: impl Into<String>) -> Self {
+        Failure::Transient {
+            reason: reason.into(),
+            retry_after: None,
+        }
+    }
+
+    fn reason(&self) -> &str {
+        match self {
+            Failure::Transient { reason, .. } | Failure::Unavailable(reason) => reason,
+            Failure::Fatal(reason) => reason,
+        }
+    }
+
+    /// Classe une réponse HTTP en échec : 408 / 429 / 5xx réessayables
+    pub fn from_status(status: u16, retry_after: Option<&str>, reason: String) -> Self {
+        if status == 408 || status == 429 || (500..600).contains(&status) {
+            Failure::Transient {
+                reason,
+                retry_after: retry_after.and_then(parse_retry_after),
+            }
+        } else {
+            Failure::Fatal(reason)
+        }
+    }
+
+    /// Classe une erreur d'envoi reqwest
+    pub fn from_reqwest(error: &reqwest::Error) -> Self {
+        if error.is_connect() {
+            Failure::Unavailable(format!("Connexion impossible : {}", error))
+        } else if error.is_timeout() {
+            Failure::transient(format!("Timeout : {}", error))
+        } else if error.is_builder() || error.is_decode() {
+            Failure::Fatal(format!("Requête ou réponse invalide : {}", error))
+        } else {
+            Failure::transient(format!("HTTP request failed: {}", error))
+        }
+    }
+}
+
+/// Laisse passer une réponse HTTP en succès, classe les autres en échec
+/// (`Retry-After` compris)
+pub async fn check_status(response: reqwest::Response) -> Result<reqwest::Response, Failure> {
+    let status = response.status();
+    if status.is_success() {
+        return Ok(response);
+    }
+    let retry_after = response
+        .headers()
+        .get(reqwest::header::RETRY_AFTER)
+        .and_then(|v| v.to_str().ok())
+        .map(str::to_string);
+    let error_text = response
+        .text()
+        .await
+        .unwrap_or_else(|_| "Failed to read error response".to_string());
+    Err(Failure::from_status(
+        status.as_u16(),
+        retry_after.as_deref(),
+        format!("API request failed with status {}: {}", status, error_text),
+    ))
+}
+
+/// Erreur finale d'un appel protégé
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CallError {
+    /// Disjoncteur ouvert : le backend n'a pas été contacté
+    CircuitOpen { backend: String, retry_in: Duration },
+    /// Dernier échec après épuisement des tentatives (ou échec non réessayable)
+    Failed(String),
+}
+
+impl std::fmt::Display for CallError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CallError::CircuitOpen { backend, retry_in } => write!(
+                f,
+                "{} ignoré après des échecs répétés (nouvel essai dans {}s)",
+                backend,
+                retry_in.as_secs()
+            ),
+            CallError::Failed(reason) => f.write_str(reason),
+        }
+    }
+}
+
+impl std::error::Error for CallError {}
+
+/// Motif d'un événement `llm-fallback`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FallbackReason {
+    /// L'appel a échoué, la dictée utilise la sortie sans LLM
+    Error,
+    /// Le backend est ignoré (disjoncteur ouvert), la dictée se fait sans LLM
+    CircuitOpen,
+    /// Le disjoncteur vient de s'ouvrir
+    BreakerOpened,
+    /// Le backend répond de nouveau
+    BreakerClosed,
+}
+
+/// Charge utile de l'événement `llm-fallback`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct LlmFallbackEvent {
+    pub backend: String,
+    pub label: String,
+    pub reason: FallbackReason,
+    /// Dernière erreur, si connue
+    pub detail: Option<String>,
+    /// Délai avant le prochain essai quand le disjoncteur est ouvert
+    pub retry_in_secs: Option<u64>,
+}
+
+impl LlmFallbackEvent {
+    /// Événement de repli d'une dictée : `circuit_open` si le backend est
+    /// actuellement ignoré, `error` sinon
+    pub fn fallback(backend: &str, label: &str) -> Self {
+        let retry_in = breaker_retry_in(backend);
+        Self {
+            backend: backend.to_string(),
+            label: label.to_string(),
+            reason: if retry_in.is_some() {
+                FallbackReason::CircuitOpen
+            } else {
+                FallbackReason::Error
+            },
+            detail: None,
+            retry_in_secs: retry_in.map(|d| d.as_secs()),
+        }
+    }
+}
+
+type Listener = Box<dyn Fn(&LlmFallbackEvent) + Send + Sync>;
+
+static LISTENER: Lazy<RwLock<Option<Listener>>> = Lazy::new(|| RwLock::new(None));
+
+/// Installe l'écouteur des changements d'état du disjoncteur
+pub fn set_listener(listener: impl Fn(&LlmFallbackEvent) + Send + Sync + 'static) {
+    *LISTENER.write().unwrap() = Some(Box::new(listener));
+}
+
+fn notify(event: LlmFallbackEvent) {
+    match event.reason {
+        FallbackReason::BreakerOpened => log::warn!(
+            "[LLM] {} ignoré pendant {}s : {}",
+            event.label,
+            event.retry_in_secs.unwrap_or_default(),
+            event.detail.as_deref().unwrap_or_default()
+        ),
+        _ => log::info!("[LLM] {} : {:?}", event.label, event.reason),
+    }
+    if let Some(listener) = LISTENER.read().unwrap().as_ref() {
+        listener(&event);
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Disjoncteur
+// ─────────────────────────────────────────────────────────────────────────────
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum BreakerState {
+    Closed {
+        failures: u32,
+    },
+    Open {
+        until: Instant,
+    },
+    /// Délai écoulé : un seul appel d'essai est en cours
+    HalfOpen {
+        since: Instant,
+    },
+}
+
+/// Transition à signaler
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Transition {
+    Opened,
+    Closed,
+}
+
+#[derive(Debug)]
+struct Breaker {
+    state: BreakerState,
+}
+
+impl Breaker {
+    fn new() -> Self {
+        Self {
+            state: BreakerState::Closed { failures: 0 },
+        }
+    }
+
+    /// `Err(délai restant)` si le backend doit être ignoré
+    fn check(&mut self, policy: &RetryPolicy, now: Instant) -> Result<(), Duration> {
+        match self.state {
+            BreakerState::Closed { .. } => Ok(()),
+            BreakerState::Open { until } if now < until => Err(until - now),
+            // Un essai est déjà en cours : les autres appels attendent son
+            // issue, sauf s'il a été abandonné (dictée annulée)
+            BreakerState::HalfOpen { since } if now < since + policy.deadline => {
+                Err(Duration::ZERO)
+            }
+            BreakerState::Open { .. } | BreakerState::HalfOpen { .. } => {
+                self.state = BreakerState::HalfOpen { since: now };
+                Ok(())
+            }
+        }
+    }
+
+    fn on_success(&mut self) -> Option<Transition> {
+        let reopened = matches!(self.state, BreakerState::HalfOpen { .. });
+        self.state = BreakerState::Closed { failures: 0 };
+        reopened.then_some(Transition::Closed)
+    }
+
+    fn on_failure(
+        &mut self,
+        failure: &Failure,
+        policy: &RetryPolicy,
+        now: Instant,
+    ) -> Option<Transition> {
+        let failures = match (self.state, failure) {
+            // Le backend répond, même si la requête est refusée
+            (_, Failure::Fatal(_)) => return self.on_success(),
+            (BreakerState::HalfOpen { .. }, _) | (_, Failure::Unavailable(_)) => {
+                policy.failure_threshold
+            }
+            (BreakerState::Closed { failures }, _) => failures + 1,
+            (BreakerState::Open { .. }, _) => return None,
+        };
+        if failures >= policy.failure_threshold {
+            self.state = BreakerState::Open {
+                until: now + policy.open_for,
+            };
+            Some(Transition::Opened)
+        } else {
+            self.state = BreakerState::Closed { failures };
+            None
+        }
+    }
+}
+
+static BREAKERS: Lazy<Mutex<HashMap<String, Breaker>>> = Lazy::new(|| Mutex::new(HashMap::new()));
+
+fn with_breaker<R>(backend: &str, f: impl FnOnce(&mut Breaker) -> R) -> R {
+    let mut breakers = BREAKERS.lock().unwrap();
+    f(breakers
+        .entry(backend.to_string())
+        .or_insert_with(Breaker::new))
+}
+
+/// Délai avant réouverture si le disjoncteur du backend est ouvert
+pub fn breaker_retry_in(backend: &str) -> Option<Duration> {
+    let breakers = BREAKERS.lock().unwrap();
+    match breakers.get(backend)?.state {
+        BreakerState::Open { until } => Some(until.saturating_duration_since(Instant::now())),
+        _ => None,
+    }
+}
+
+/// Referme le disjoncteur (configuration du backend modifiée par l'utilisateur)
+pub fn reset(backend: &str) {
+    BREAKERS.lock().unwrap().remove(backend);
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Retries
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Valeur de `Retry-After` : secondes ou date HTTP
+pub fn parse_retry_after(value: &str) -> Option<Duration> {
+    let value = value.trim();
+    if let Ok(secs) = value.parse::<u64>() {
+        return Some(Duration::from_secs(secs));
+    }
+    let date = chrono::DateTime::parse_from_rfc2822(value).ok()?;
+    let delta = date.with_timezone(&chrono::Utc) - chrono::Utc::now();
+    Some(delta.to_std().unwrap_or(Duration::ZERO))
+}
+
+/// Fraction pseudo-aléatoire dans [0, 1) — le jitter n'a pas besoin d'un
+/// vrai générateur, seulement de désynchroniser les clients
+fn jitter_fraction() -> f64 {
+    static COUNTER: AtomicU64 = AtomicU64::new(0);
+    let nanos = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_nanos() as u64)
+        .unwrap_or_default();
+    // splitmix64
+    let mut x = nanos ^ COUNTER.fetch_add(0x9E37_79B9_7F4A_7C15, Ordering::Relaxed);
+    x = (x ^ (x >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    x = (x ^ (x >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    x ^= x >> 31;
+    (x >> 11) as f64 / (1u64 << 53) as f64
+}
+
+/// Attente avant la tentative `attempt + 1` (`attempt` ≥ 1) : backoff
+/// exponentiel plafonné, tiré dans [50 %, 100 %] de sa valeur
+fn backoff_delay(policy: &RetryPolicy, attempt: u32, jitter: f64) -> Duration {
+    let exp = policy
+        .base_delay
+        .saturating_mul(1u32 << (attempt - 1).min(16))
+        .min(policy.max_delay);
+    exp.mul_f64(0.5 + 0.5 * jitter.clamp(0.0, 1.0))
+}
+
+/// Exécute `attempt` sous la politique du backend : disjoncteur, timeout par
+/// tentative, retries avec backoff et échéance globale.
+pub async fn execute<T, F, Fut>(
+    backend: &str,
+    label: &str,
+    policy: &RetryPolicy,
+    mut attempt: F,
+) -> Result<T, CallError>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T, Failure>>,
+{
+    if let Err(retry_in) = with_breaker(backend, |b| b.check(policy, Instant::now())) {
+        return Err(CallError::CircuitOpen {
+            backend: label.to_string(),
+            retry_in,
+        });
+    }
+
+    let start = Instant::now();
+    let mut tries = 0;
+    let failure = loop {
+        tries += 1;
+        let remaining = policy.deadline.saturating_sub(start.elapsed());
+        let timeout = policy.attempt_timeout.min(remaining);
+        let result = match tokio::time::timeout(timeout, attempt()).await {
+            Ok(result) => result,
+            Err(_) => Err(Failure::transient(format!(
+                "Timeout après {:.1}s",
+                timeout.as_secs_f64()
+            ))),
+        };
+
+        let failure = match result {
+            Ok(value) => {
+                if with_breaker(backend, Breaker::on_success) == Some(Transition::Closed) {
+                    notify(LlmFallbackEvent {
+                        backend: backend.to_string(),
+                        label: label.to_string(),
+                        reason: FallbackReason::BreakerClosed,
+                        detail: None,
+                        retry_in_secs: None,
+                    });
+                }
+                return Ok(value);
+            }
+            Err(failure) => failure,
+        };
+
+        let retry_after = match &failure {
+            Failure::Fatal(_) => break failure,
+            Failure::Transient { retry_after, .. } => *retry_after,
+            Failure::Unavailable(_) => None,
+        };
+        if tries >= policy.max_attempts {
+            break failure;
+        }
+        let delay = match retry_after {
+            // Le serveur demande d'attendre plus que ce qu'une dictée peut supporter
+            Some(wait) if wait > policy.max_delay => break failure,
+            Some(wait) => wait,
+            None => backoff_delay(policy, tries, jitter_fraction()),
+        };
+        if start.elapsed() + delay >= policy.deadline {
+            break failure;
+        }
+        log::debug!(
+            "[LLM] {} : tentative {}/{} échouée ({}), nouvel essai dans {}ms",
+            label,
+            tries,
+            policy.max_attempts,
+            failure.reason(),
+            delay.as_millis()
+        );
+        tokio::time::sleep(delay).await;
+    };
+
+    let transition = with_breaker(backend, |b| b.on_failure(&failure, policy, Instant::now()));
+    if transition == Some(Transition::Opened) {
+        notify(LlmFallbackEvent {
+            backend: backend.to_string(),
+            label: label.to_string(),
+            reason: FallbackReason::BreakerOpened,
+            detail: Some(failure.reason().to_string()),
+            retry_in_secs: Some(policy.open_for.as_secs()),
+        });
+    }
+    Err(CallError::Failed(failure.reason().to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::AtomicU32;
+
+    fn run<F: Future>(future: F) -> F::Output {
+        tokio::runtime::Builder::new_current_thread()
+            .enable_time()
+            .build()
+            .unwrap()
+            .block_on(future)
+    }
+
+    fn fast_policy() -> RetryPolicy {
+        RetryPolicy {
+            attempt_timeout: Duration::from_millis(200),
+            deadline: Duration::from_secs(2),
+            max_attempts: 3,
+            base_delay: Duration::from_millis(1),
+            max_delay: Duration::from_millis(20),
+            failure_threshold: 2,
+            open_for: Duration::from_secs(60),
+        }
+    }
+
+    #[test]
+    fn retries_transient_failures_then_succeeds() {
+        let calls = AtomicU32::new(0);
+        let result = run(execute("test-retry", "Test", &fast_policy(), || async {
+            match calls.fetch_add(1, Ordering::SeqCst) {
+                0 => Err(Failure::from_status(503, None, "503".into())),
+                1 => Err(Failure::from_status(429, Some("0"), "429".into())),
+                _ => Ok("ok"),
+            }
+        }));
+        assert_eq!(result, Ok("ok"));
+        assert_eq!(calls.load(Ordering::SeqCst), 3);
+    }
+
+    #[test]
+    fn fatal_failures_are_not_retried_nor_counted() {
+        let calls = AtomicU32::new(0);
+        let policy = fast_policy();
+        for _ in 0..3 {
+            let result: Result<(), _> = run(execute("test-fatal", "Test", &policy, || async {
+                calls.fetch_add(1, Ordering::SeqCst);
+                Err(Failure::from_status(401, None, "401 Unauthorized".into()))
+            }));
+            assert_eq!(result, Err(CallError::Failed("401 Unauthorized".into())));
+        }
+        assert_eq!(calls.load(Ordering::SeqCst), 3);
+        assert_eq!(breaker_retry_in("test-fatal"), None);
+    }
+
+    #[test]
+    fn long_retry_after_gives_up_immediately() {
+        let calls = AtomicU32::new(0);
+        let result: Result<(), _> = run(execute(
+            "test-retry-after",
+            "Test",
+            &fast_policy(),
+            || async {
+                calls.fetch_add(1, Ordering::SeqCst);
+                Err(Failure::from_status(429, Some("120"), "429".into()))
+            },
+        ));
+        assert!(result.is_err());
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn attempt_timeout_is_a_transient_failure() {
+        let policy = RetryPolicy {
+            attempt_timeout: Duration::from_millis(10),
+            max_attempts: 1,
+            ..fast_policy()
+        };
+        let result: Result<(), _> = run(execute("test-timeout", "Test", &policy, || async {
+            tokio::time::sleep(Duration::from_secs(5)).await;
+            Ok(())
+        }));
+        match result {
+            Err(CallError::Failed(reason)) => assert!(reason.starts_with("Timeout"), "{}", reason),
+            other => panic!("{:?}", other),
+        }
+    }
+
+    #[test]
+    fn breaker_opens_after_threshold_and_skips_backend() {
+        let events = std::sync::Arc::new(Mutex::new(Vec::new()));
+        let sink = events.clone();
+        set_listener(move |event| {
+            if event.backend == "test-breaker" {
+                sink.lock().unwrap().push(event.reason);
+            }
+        });
+
+        let policy = RetryPolicy {
+            max_attempts: 1,
+            ..fast_policy()
+        };
+        let calls = AtomicU32::new(0);
+        let failing = || async {
+            calls.fetch_add(1, Ordering::SeqCst);
+            Err::<(), _>(Failure::from_status(500, None, "500".into()))
+        };
+
+        assert!(matches!(
+            run(execute("test-breaker", "Test", &policy, failing)),
+            Err(CallError::Failed(_))
+        ));
+        assert_eq!(breaker_retry_in("test-breaker"), None);
+        assert!(matches!(
+            run(execute("test-breaker", "Test", &policy, failing)),
+            Err(CallError::Failed(_))
+        ));
+        assert!(breaker_retry_in("test-breaker").is_some());
+
+        // Disjoncteur ouvert : le backend n'est plus contacté
+        let skipped = run(execute("test-breaker", "Test", &policy, failing));
+        assert!(matches!(skipped, Err(CallError::CircuitOpen { .. })));
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+        assert_eq!(
+            LlmFallbackEvent::fallback("test-breaker", "Test").reason,
+            FallbackReason::CircuitOpen
+        );
+        assert_eq!(*events.lock().unwrap(), [FallbackReason::BreakerOpened]);
+
+        reset("test-breaker");
+        assert_eq!(
+            run(execute("test-breaker", "Test", &policy, || async { Ok(1) })),
+            Ok(1)
+        );
+    }
+
+    #[test]
+    fn unavailable_backend_opens_breaker_at_once() {
+        let policy = RetryPolicy {
+            max_attempts: 2,
+            ..fast_policy()
+        };
+        let calls = AtomicU32::new(0);
+        let result: Result<(), _> = run(execute("test-unavailable", "Test", &policy, || async {
+            calls.fetch_add(1, Ordering::SeqCst);
+            Err(Failure::Unavailable("connection refused".into()))
+        }));
+        assert_eq!(result, Err(CallError::Failed("connection refused".into())));
+        // Un retry, puis disjoncteur ouvert sans attendre le seuil
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+        assert!(breaker_retry_in("test-unavailable").is_some());
+    }
+
+    #[test]
+    fn half_open_breaker_allows_a_single_trial() {
+        let policy = fast_policy();
+        let t0 = Instant::now();
+        let mut breaker = Breaker::new();
+        let failure = Failure::transient("503");
+
+        assert_eq!(breaker.on_failure(&failure, &policy, t0), None);
+        assert_eq!(
+            breaker.on_failure(&failure, &policy, t0),
+            Some(Transition::Opened)
+        );
+        assert!(breaker
+            .check(&policy, t0 + Duration::from_secs(30))
+            .is_err());
+
+        // Délai écoulé : un essai passe, les suivants attendent son issue
+        let later = t0 + policy.open_for + Duration::from_secs(1);
+        assert_eq!(breaker.check(&policy, later), Ok(()));
+        assert!(breaker.check(&policy, later).is_err());
+
+        // Essai raté : réouverture immédiate
+        assert_eq!(
+            breaker.on_failure(&failure, &policy, later),
+            Some(Transition::Opened)
+        );
+        let much_later = later + policy.open_for + Duration::from_secs(1);
+        assert_eq!(breaker.check(&policy, much_later), Ok(()));
+        assert_eq!(breaker.on_success(), Some(Transition::Closed));
+        assert_eq!(breaker.check(&policy, much_later), Ok(()));
+        assert_eq!(breaker.on_success(), None);
+
+        // Essai abandonné sans issue : un nouvel essai est permis après l'échéance
+        let mut stuck = Breaker {
+            state: BreakerState::Open { until: t0 },
+        };
+        assert_eq!(stuck.check(&policy, t0), Ok(()));
+        assert!(stuck.check(&policy, t0 + Duration::from_secs(1)).is_err());
+        assert_eq!(stuck.check(&policy, t0 + policy.deadline), Ok(()));
+
+        // Requête refusée pendant l'essai : le backend répond, il est réadmis
+        assert_eq!(
+            stuck.on_failure(&Failure::Fatal("400".into()), &policy, t0),
+            Some(Transition::Closed)
+        );
+    }
+
+    #[test]
+    fn backoff_is_exponential_capped_and_jittered() {
+        let policy = RetryPolicy {
+            base_delay: Duration::from_millis(100),
+            max_delay: Duration::from_millis(1000),
+            ..fast_policy()
+        };
+        assert_eq!(backoff_delay(&policy, 1, 1.0), Duration::from_millis(100));
+        assert_eq!(backoff_delay(&policy, 2, 1.0), Duration::from_millis(200));
+        assert_eq!(backoff_delay(&policy, 3, 0.0), Duration::from_millis(200));
+        assert_eq!(backoff_delay(&policy, 10, 1.0), Duration::from_millis(1000));
+        for _ in 0..100 {
+            let j = jitter_fraction();
+            assert!((0.0..1.0).contains(&j));
+        }
+    }
+
+    #[test]
+    fn retry_after_accepts_seconds_and_http_dates() {
+        assert_eq!(parse_retry_after(" 7 "), Some(Duration::from_secs(7)));
+        assert_eq!(
+            parse_retry_after("Wed, 21 Oct 2015 07:28:00 GMT"),
+            Some(Duration::ZERO)
+        );
+        let future = (chrono::Utc::now() + chrono::Duration::seconds(90)).to_rfc2822();
+        let wait = parse_retry_after(&future).unwrap();
+        assert!(wait > Duration::from_secs(80) && wait <= Duration::from_secs(90));
+        assert_eq!(parse_retry_after("bientôt"), None);
+    }
+
+    #[test]
+    fn status_classification() {
+        assert!(matches!(
+            Failure::from_status(429, None, String::new()),
+            Failure::Transient { .. }
+        ));
+        assert!(matches!(
+            Failure::from_status(502, None, String::new()),
+            Failure::Transient { .. }
+        ));
+        assert!(matches!(
+            Failure::from_status(400, None, String::new()),
+            Failure::Fatal(_)
+        ));
+        assert!(matches!(
+            Failure::from_status(404, None, String::new()),
+            Failure::Fatal(_)
+        ));
+    }
+}
@@ -0,0 +1,481 @@
+//! Streaming des réponses LLM — flux de tokens commun Ollama / OpenAI
+//!
+//! - Ollama `/api/chat` (`"stream": true`) : NDJSON, un objet par ligne,
+//!   `{"message": {"content": "…"}, "done": false}` puis `"done": true`
+//! - API compatibles OpenAI (`"stream": true`) : SSE, lignes `data: {…}` avec
+//!   `choices[0].delta.content`, terminées par `data: [DONE]`
+//! - API Anthropic Messages (`"stream": true`) : SSE, événements
+//!   `content_block_delta` (`delta.text`), terminés par `message_stop`
+//!
+//! Les formats sont exposés sous le même [`TokenStream`], consommé par
+//! [`collect`] qui gère l'annulation ([`StreamCancel`]) et le délai
+//! d'inactivité entre deux tokens.
+
+use anyhow::{anyhow, Result};
+use futures_util::{Stream, StreamExt};
+use once_cell::sync::Lazy;
+use std::collections::VecDeque;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::sync::Notify;
+
+/// Flux de fragments de texte (deltas) produits par le LLM
+pub type TokenStream = Pin<Box<dyn Stream<Item = Result<String>> + Send>>;
+
+/// Format de fil de la réponse HTTP
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StreamFormat {
+    /// Ollama natif
+    Ndjson,
+    /// Server-Sent Events (OpenAI et compatibles, Anthropic)
+    Sse,
+}
+
+/// Contenu utile d'une ligne
+#[derive(Debug, PartialEq)]
+enum Frame {
+    Token(String),
+    Done,
+    /// Ligne vide, commentaire SSE, keep-alive, delta sans texte…
+    Skip,
+}
+
+fn parse_ndjson_line(line: &str) -> Result<Frame> {
+    let line = line.trim();
+    if line.is_empty() {
+        return Ok(Frame::Skip);
+    }
+    let json: serde_json::Value = serde_json::from_str(line)
+        .map_err(|e| anyhow!("Ligne NDJSON invalide ({}) : {}", e, line))?;
+    if let Some(error) = json["error"].as_str() {
+        return Err(anyhow!("Ollama : {}", error));
+    }
+    let token = json["message"]["content"].as_str().unwrap_or_default();
+    if json["done"].as_bool() == Some(true) {
+        return Ok(if token.is_empty() {
+            Frame::Done
+        } else {
+            // Dernier objet porteur de texte : rare, mais autorisé
+            Frame::Token(token.to_string())
+        });
+    }
+    Ok(if token.is_empty() {
+        Frame::Skip
+    } else {
+        Frame::Token(token.to_string())
+    })
+}
+
+fn parse_sse_line(line: &str) -> Result<Frame> {
+    let line = line.trim_end_matches('\r');
+    // Seuls les champs `data:` nous intéressent (event:, id:, retry:, `:` commentaire)
+    let Some(data) = line.strip_prefix("data:") else {
+        return Ok(Frame::Skip);
+    };
+    let data = data.trim();
+    if data.is_empty() {
+        return Ok(Frame::Skip);
+    }
+    if data == "[DONE]" {
+        return Ok(Frame::Done);
+    }
+    let json: serde_json::Value = serde_json::from_str(data)
+        .map_err(|e| anyhow!("Événement SSE invalide ({}) : {}", e, data))?;
+    if let Some(error) = json.get("error") {
+        let message = error["message"].as_str().unwrap_or("erreur inconnue");
+        return Err(anyhow!("API : {}", message));
+    }
+    // Anthropic : le type d'événement est répété dans les données
+    let token = match json["type"].as_str() {
+        Some("message_stop") => return Ok(Frame::Done),
+        Some("content_block_delta") => json["delta"]["text"].as_str(),
+        _ => json["choices"][0]["delta"]["content"].as_str(),
+    };
+    match token {
+        Some(token) if !token.is_empty() => Ok(Frame::Token(token.to_string())),
+        _ => Ok(Frame::Skip),
+    }
+}
+
+fn parse_line(format: StreamFormat, line: &str) -> Result<Frame> {
+    match format {
+        StreamFormat::Ndjson => parse_ndjson_line(line),
+        StreamFormat::Sse => parse_sse_line(line),
+    }
+}
+
+/// Découpe un flux d'octets en lignes complètes. Les octets sont accumulés
+/// avant décodage : un caractère UTF-8 coupé entre deux chunks est conservé.
+#[derive(Default)]
+struct LineSplitter {
+    buf: Vec<u8>,
+}
+
+impl LineSplitter {
+    fn push(&mut self, bytes: &[u8]) -> Vec<String> {
+        self.buf.extend_from_slice(bytes);
+        let mut lines = Vec::new();
+        while let Some(pos) = self.buf.iter().position(|b| *b == b'\n') {
+            let line: Vec<u8> = self.buf.drain(..=pos).collect();
+            lines.push(String::from_utf8_lossy(&line[..pos]).into_owned());
+        }
+        lines
+    }
+
+    /// Dernière ligne sans `\n` final
+    fn finish(&mut self) -> Option<String> {
+        if self.buf.is_empty() {
+            return None;
+        }
+        let line = String::from_utf8_lossy(&self.buf).into_owned();
+        self.buf.clear();
+        Some(line)
+    }
+}
+
+/// Décode un flux d'octets (corps HTTP) en flux de tokens.
+pub fn decode_stream<S, B, E>(bytes: S, format: StreamFormat) -> TokenStream
+where
+    S: Stream<Item = std::result::Result<B, E>> + Send + 'static,
+    B: AsRef<[u8]>,
+    E: Into<anyhow::Error>,
+{
+    struct State<S> {
+        bytes: Pin<Box<S>>,
+        lines: LineSplitter,
+        pending: VecDeque<Result<String>>,
+        done: bool,
+    }
+
+    /// Met en file le token de la ligne ; true si le flux est terminé
+    fn queue(pending: &mut VecDeque<Result<String>>, format: StreamFormat, line: &str) -> bool {
+        match parse_line(format, line) {
+            Ok(Frame::Token(token)) => {
+                pending.push_back(Ok(token));
+                false
+            }
+            Ok(Frame::Skip) => false,
+            Ok(Frame::Done) => true,
+            Err(e) => {
+                pending.push_back(Err(e));
+                true
+            }
+        }
+    }
+
+    let state = State {
+        bytes: Box::pin(bytes),
+        lines: LineSplitter::default(),
+        pending: VecDeque::new(),
+        done: false,
+    };
+
+    Box::pin(futures_util::stream::unfold(
+        state,
+        move |mut state| async move {
+            loop {
+                if let Some(item) = state.pending.pop_front() {
+                    return Some((item, state));
+                }
+                if state.done {
+                    return None;
+                }
+                match state.bytes.next().await {
+                    Some(Ok(chunk)) => {
+                        for line in state.lines.push(chunk.as_ref()) {
+                            if queue(&mut state.pending, format, &line) {
+                                state.done = true;
+                                break;
+                            }
+                        }
+                    }
+                    Some(Err(e)) => {
+                        state.pending.push_back(Err(e.into()));
+                        state.done = true;
+                    }
+                    None => {
+                        if let Some(line) = state.lines.finish() {
+                            queue(&mut state.pending, format, &line);
+                        }
+                        state.done = true;
+                    }
+                }
+            }
+        },
+    ))
+}
+
+/// Flux de tokens d'une réponse HTTP en streaming. Le statut doit avoir été
+/// vérifié par l'appelant.
+pub fn token_stream(response: reqwest::Response, format: StreamFormat) -> TokenStream {
+    decode_stream(response.bytes_stream(), format)
+}
+
+/// Erreur renvoyée par [`collect`] quand l'utilisateur annule
+#[derive(Debug)]
+pub struct Cancelled;
+
+impl std::fmt::Display for Cancelled {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "génération LLM annulée")
+    }
+}
+
+impl std::error::Error for Cancelled {}
+
+#[derive(Default)]
+struct CancelInner {
+    cancelled: AtomicBool,
+    notify: Notify,
+}
+
+/// Jeton d'annulation partagé entre l'opération en cours et le raccourci
+/// d'annulation.
+#[derive(Clone, Default)]
+pub struct StreamCancel(Arc<CancelInner>);
+
+impl StreamCancel {
+    pub fn cancel(&self) {
+        self.0.cancelled.store(true, Ordering::SeqCst);
+        self.0.notify.notify_waiters();
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.0.cancelled.load(Ordering::SeqCst)
+    }
+
+    async fn cancelled(&self) {
+        let notified = self.0.notify.notified();
+        tokio::pin!(notified);
+        // Inscription avant la vérification : pas de réveil perdu
+        notified.as_mut().enable();
+        if self.is_cancelled() {
+            return;
+        }
+        notified.await;
+    }
+}
+
+/// Opération de dictée en cours (une seule à la fois, cf. coordinator)
+static ACTIVE: Lazy<Mutex<Option<StreamCancel>>> = Lazy::new(|| Mutex::new(None));
+
+/// Démarre une opération annulable et la rend visible à [`cancel_active`].
+pub fn begin_operation() -> StreamCancel {
+    let cancel = StreamCancel::default();
+    *ACTIVE.lock().unwrap() = Some(cancel.clone());
+    cancel
+}
+
+/// Termine l'opération (sans effet si une autre l'a remplacée entre-temps).
+pub fn end_operation(cancel: &StreamCancel) {
+    let mut active = ACTIVE.lock().unwrap();
+    if active
+        .as_ref()
+        .is_some_and(|c| Arc::ptr_eq(&c.0, &cancel.0))
+    {
+        *active = None;
+    }
+}
+
+/// Annule l'opération en cours, s'il y en a une (raccourci d'annulation).
+pub fn cancel_active() {
+    if let Some(cancel) = ACTIVE.lock().unwrap().as_ref() {
+        log::info!("Annulation de la génération LLM en cours");
+        cancel.cancel();
+    }
+}
+
+/// Consomme le flux jusqu'au bout. `on_progress` reçoit le texte accumulé
+/// après chaque token.
+///
+/// Erreurs : [`Cancelled`] si `cancel` est déclenché, délai d'inactivité
+/// dépassé (`idle_timeout` sans token, premier token compris), ou erreur du
+/// flux lui-même.
+pub async fn collect(
+    mut stream: TokenStream,
+    cancel: &StreamCancel,
+    idle_timeout: Duration,
+    mut on_progress: impl FnMut(&str),
+) -> Result<String> {
+    let mut text = String::new();
+    loop {
+        let next = tokio::select! {
+            _ = cancel.cancelled() => return Err(Cancelled.into()),
+            next = tokio::time::timeout(idle_timeout, stream.next()) => next,
+        };
+        match next {
+            Err(_) => {
+                return Err(anyhow!(
+                    "LLM muet depuis {}s — génération interrompue",
+                    idle_timeout.as_secs()
+                ))
+            }
+            Ok(None) => return Ok(text),
+            Ok(Some(Err(e))) => return Err(e),
+            Ok(Some(Ok(token))) => {
+                text.push_str(&token);
+                on_progress(&text);
+            }
+        }
+    }
+}
+
+/// Partie de `text` pas encore frappée, pour la frappe progressive.
+///
+/// Le texte est comparé sans ses blancs de début et de fin : un espace ou un
+/// retour à la ligne final n'est tapé qu'une fois suivi de texte, ce qui
+/// évite de laisser un blanc parasite si la génération s'arrête là.
+/// None si le texte a divergé de ce qui est déjà tapé.
+pub fn untyped_suffix<'a>(text: &'a str, typed: &str) -> Option<&'a str> {
+    text.trim().strip_prefix(typed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run<F: std::future::Future>(future: F) -> F::Output {
+        tokio::runtime::Builder::new_current_thread()
+            .enable_time()
+            .build()
+            .unwrap()
+            .block_on(future)
+    }
+
+    fn chunks(parts: &[&str]) -> impl Stream<Item = Result<Vec<u8>, std::io::Error>> + Send {
+        let owned: Vec<Result<Vec<u8>, std::io::Error>> =
+            parts.iter().map(|p| Ok(p.as_bytes().to_vec())).collect();
+        futures_util::stream::iter(owned)
+    }
+
+    fn collect_all(stream: TokenStream) -> Result<String> {
+        run(collect(
+            stream,
+            &StreamCancel::default(),
+            Duration::from_secs(5),
+            |_| {},
+        ))
+    }
+
+    #[test]
+    fn ndjson_tokens_across_chunk_boundaries() {
+        let body = [
+            "{\"message\":{\"content\":\"Bon\"},\"done\":false}\n{\"mess",
+            "age\":{\"content\":\"jour\"},\"done\":false}\n",
+            "{\"message\":{\"content\":\"\"},\"done\":true}\n",
+        ];
+        let text = collect_all(decode_stream(chunks(&body), StreamFormat::Ndjson)).unwrap();
+        assert_eq!(text, "Bonjour");
+    }
+
+    #[test]
+    fn ndjson_error_line_fails_stream() {
+        let body = ["{\"error\":\"model 'x' not found\"}\n"];
+        let err = collect_all(decode_stream(chunks(&body), StreamFormat::Ndjson)).unwrap_err();
+        assert!(err.to_string().contains("not found"));
+    }
+
+    #[test]
+    fn sse_tokens_until_done() {
+        let body = [
+            ": keep-alive\n\n",
+            "data: {\"choices\":[{\"delta\":{\"role\":\"assistant\"}}]}\n\n",
+            "data: {\"choices\":[{\"delta\":{\"content\":\"Ça \"}}]}\r\n\r\n",
+            "data: {\"choices\":[{\"delta\":{\"content\":\"va\"}}]}\n\ndata: [DONE]\n\n",
+            "data: {\"choices\":[{\"delta\":{\"content\":\"ignoré\"}}]}\n\n",
+        ];
+        let text = collect_all(decode_stream(chunks(&body), StreamFormat::Sse)).unwrap();
+        assert_eq!(text, "Ça va");
+    }
+
+    #[test]
+    fn anthropic_sse_events_until_message_stop() {
+        let body = [
+            "event: message_start\ndata: {\"type\":\"message_start\",\"message\":{}}\n\n",
+            "event: ping\ndata: {\"type\":\"ping\"}\n\n",
+            "event: content_block_delta\ndata: {\"type\":\"content_block_delta\",\"index\":0,\"delta\":{\"type\":\"text_delta\",\"text\":\"Ça \"}}\n\n",
+            "event: content_block_delta\ndata: {\"type\":\"content_block_delta\",\"index\":0,\"delta\":{\"type\":\"text_delta\",\"text\":\"va\"}}\n\n",
+            "event: message_stop\ndata: {\"type\":\"message_stop\"}\n\n",
+            "data: {\"type\":\"content_block_delta\",\"delta\":{\"text\":\"ignoré\"}}\n\n",
+        ];
+        let text = collect_all(decode_stream(chunks(&body), StreamFormat::Sse)).unwrap();
+        assert_eq!(text, "Ça va");
+
+        let error = ["event: error\ndata: {\"type\":\"error\",\"error\":{\"type\":\"overloaded_error\",\"message\":\"Overloaded\"}}\n\n"];
+        let err = collect_all(decode_stream(chunks(&error), StreamFormat::Sse)).unwrap_err();
+        assert!(err.to_string().contains("Overloaded"));
+    }
+
+    #[test]
+    fn utf8_split_between_chunks_is_preserved() {
+        let line = "data: {\"choices\":[{\"delta\":{\"content\":\"été\"}}]}\n";
+        let bytes = line.as_bytes();
+        // Coupe au milieu du « é » (2 octets)
+        let cut = line.find('é').unwrap() + 1;
+        let parts: Vec<Result<Vec<u8>, std::io::Error>> =
+            vec![Ok(bytes[..cut].to_vec()), Ok(bytes[cut..].to_vec())];
+        let stream = decode_stream(futures_util::stream::iter(parts), StreamFormat::Sse);
+        assert_eq!(collect_all(stream).unwrap(), "été");
+    }
+
+    #[test]
+    fn last_line_without_newline_is_parsed() {
+        let body = ["{\"message\":{\"content\":\"fin\"},\"done\":false}"];
+        let text = collect_all(decode_stream(chunks(&body), StreamFormat::Ndjson)).unwrap();
+        assert_eq!(text, "fin");
+    }
+
+    #[test]
+    fn progress_receives_accumulated_text() {
+        let body = [
+            "{\"message\":{\"content\":\"a\"}}\n",
+            "{\"message\":{\"content\":\"b\"}}\n",
+        ];
+        let mut seen = Vec::new();
+        run(collect(
+            decode_stream(chunks(&body), StreamFormat::Ndjson),
+            &StreamCancel::default(),
+            Duration::from_secs(5),
+            |text| seen.push(text.to_string()),
+        ))
+        .unwrap();
+        assert_eq!(seen, vec!["a", "ab"]);
+    }
+
+    #[test]
+    fn cancel_interrupts_pending_stream() {
+        let cancel = StreamCancel::default();
+        let stream: TokenStream = Box::pin(futures_util::stream::pending());
+        let canceller = cancel.clone();
+        let result = run(async move {
+            tokio::spawn(async move {
+                tokio::time::sleep(Duration::from_millis(20)).await;
+                canceller.cancel();
+            });
+            collect(stream, &cancel, Duration::from_secs(5), |_| {}).await
+        });
+        assert!(result.unwrap_err().is::<Cancelled>());
+    }
+
+    #[test]
+    fn idle_timeout_fails_stalled_stream() {
+        let stream: TokenStream = Box::pin(futures_util::stream::pending());
+        let err = run(collect(
+            stream,
+            &StreamCancel::default(),
+            Duration::from_millis(20),
+            |_| {},
+        ))
+        .unwrap_err();
+        assert!(!err.is::<Cancelled>());
+    }
+
+    #[test]
+    fn untyped_suffix_holds_back_trailing_whitespace() {
+        assert_eq!(untyped_suffix("  Bonjour ", ""), Some("Bonjour"));
+        assert_eq!(untyped_suffix("Bonjour à", "Bonjour"), Some(" à"));
+        assert_eq!(untyped_suffix("Salut", "Bonjour"), None);
+    }
+}
@@ -1,8 +1,21 @@
-use crate::settings::PostProcessProvider;
+use crate::llm::resilience::{self, Failure, RetryPolicy};
+use crate::llm::stream::{self, StreamFormat, TokenStream};
+use crate::settings::{PostProcessProvider, ProviderKind};
 use log::debug;
 use reqwest::header::{HeaderMap, HeaderValue, AUTHORIZATION, CONTENT_TYPE, REFERER, USER_AGENT};
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
+use std::time::Duration;
+
+/// Name of the JSON schema (OpenAI) / forced tool (Anthropic) used for structured outputs
+const STRUCTURED_OUTPUT_NAME: &str = "transcription_output";
+
+/// Anthropic requires an explicit output budget
+const ANTHROPIC_MAX_TOKENS: u32 = 4096;
+const ANTHROPIC_VERSION: &str = "2023-06-01";
+
+/// Model listing is user-initiated: no retries, but no endless spinner either
+const MODELS_TIMEOUT: Duration = Duration::from_secs(15);
 
 #[derive(Debug, Serialize)]
 struct ChatMessage {
@@ -30,6 +43,8 @@ struct ChatCompletionRequest {
     messages: Vec<ChatMessage>,
     #[serde(skip_serializing_if = "Option::is_none")]
     response_format: Option<ResponseFormat>,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    stream: bool,
 }
 
 #[derive(Debug, Deserialize)]
@@ -47,7 +62,66 @@ struct ChatMessageResponse {
     content: Option<String>,
 }
 
-/// Build headers for API requests based on provider type
+#[derive(Debug, Serialize)]
+struct AnthropicTool {
+    name: String,
+    description: String,
+    input_schema: Value,
+}
+
+#[derive(Debug, Serialize)]
+struct AnthropicToolChoice {
+    #[serde(rename = "type")]
+    choice_type: String,
+    name: String,
+}
+
+#[derive(Debug, Serialize)]
+struct AnthropicRequest {
+    model: String,
+    max_tokens: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    system: Option<String>,
+    messages: Vec<ChatMessage>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    tools: Vec<AnthropicTool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    tool_choice: Option<AnthropicToolChoice>,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    stream: bool,
+}
+
+#[derive(Debug, Deserialize)]
+struct AnthropicResponse {
+    content: Vec<AnthropicContentBlock>,
+}
+
+#[derive(Debug, Deserialize)]
+struct AnthropicContentBlock {
+    #[serde(rename = "type")]
+    block_type: String,
+    #[serde(default)]
+    text: Option<String>,
+    #[serde(default)]
+    input: Option<Value>,
+}
+
+#[derive(Debug, Serialize)]
+struct OllamaChatRequest {
+    model: String,
+    messages: Vec<ChatMessage>,
+    /// Always sent: Ollama streams by default
+    stream: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    format: Option<Value>,
+}
+
+#[derive(Debug, Deserialize)]
+struct OllamaChatResponse {
+    message: Option<ChatMessageResponse>,
+}
+
+/// Build headers for API requests based on provider kind
 fn build_headers(provider: &PostProcessProvider, api_key: &str) -> Result<HeaderMap, String> {
     let mut headers = HeaderMap::new();
 
@@ -63,37 +137,283 @@ fn build_headers(provider: &PostProcessProvider, api_key: &str) -> Result<Header
     );
     headers.insert("X-Title", HeaderValue::from_static("Handy"));
 
+    if provider.kind == ProviderKind::AnthropicMessages {
+        headers.insert(
+            "anthropic-version",
+            HeaderValue::from_static(ANTHROPIC_VERSION),
+        );
+    }
+
     // Provider-specific auth headers
     if !api_key.is_empty() {
-        if provider.id == "anthropic" {
-            headers.insert(
-                "x-api-key",
-                HeaderValue::from_str(api_key)
-                    .map_err(|e| format!("Invalid API key header value: {}", e))?,
-            );
-            headers.insert("anthropic-version", HeaderValue::from_static("2023-06-01"));
-        } else {
-            headers.insert(
-                AUTHORIZATION,
-                HeaderValue::from_str(&format!("Bearer {}", api_key))
-                    .map_err(|e| format!("Invalid authorization header value: {}", e))?,
-            );
+        match provider.kind {
+            ProviderKind::AnthropicMessages => {
+                headers.insert(
+                    "x-api-key",
+                    HeaderValue::from_str(api_key)
+                        .map_err(|e| format!("Invalid API key header value: {}", e))?,
+                );
+            }
+            // Ollama itself ignores auth, but reverse proxies in front of it often use Bearer
+            ProviderKind::OpenaiCompatible | ProviderKind::OllamaNative => {
+                headers.insert(
+                    AUTHORIZATION,
+                    HeaderValue::from_str(&format!("Bearer {}", api_key))
+                        .map_err(|e| format!("Invalid authorization header value: {}", e))?,
+                );
+            }
         }
     }
 
     Ok(headers)
 }
 
-/// Create an HTTP client with provider-specific headers
+/// System prompt (optional) followed by the user message
+fn build_messages(user_content: String, system_prompt: Option<String>) -> Vec<ChatMessage> {
+    let mut messages = Vec::new();
+
+    if let Some(system) = system_prompt {
+        messages.push(ChatMessage {
+            role: "system".to_string(),
+            content: system,
+        });
+    }
+
+    messages.push(ChatMessage {
+        role: "user".to_string(),
+        content: user_content,
+    });
+
+    messages
+}
+
+/// Create an HTTP client with provider-specific headers.
+/// Request timeouts are enforced per attempt by the resilience layer.
 fn create_client(provider: &PostProcessProvider, api_key: &str) -> Result<reqwest::Client, String> {
     let headers = build_headers(provider, api_key)?;
     reqwest::Client::builder()
         .default_headers(headers)
+        .connect_timeout(Duration::from_secs(5))
         .build()
         .map_err(|e| format!("Failed to build HTTP client: {}", e))
 }
 
-/// Send a chat completion request to an OpenAI-compatible API
+/// Whether the provider runs on this machine (Ollama, LM Studio…)
+pub fn is_local(provider: &PostProcessProvider) -> bool {
+    reqwest::Url::parse(&provider.base_url)
+        .ok()
+        .and_then(|url| url.host_str().map(str::to_string))
+        .is_some_and(|host| matches!(host.as_str(), "localhost" | "127.0.0.1" | "[::1]" | "::1"))
+}
+
+/// Call policy of the provider: local servers get long timeouts (model
+/// loading) and few retries, cloud APIs retry on overload. `timeout_secs`
+/// overrides the per-attempt timeout.
+pub fn retry_policy(provider: &PostProcessProvider) -> RetryPolicy {
+    let policy = if is_local(provider) {
+        RetryPolicy::local_server()
+    } else {
+        RetryPolicy::cloud()
+    };
+    match provider.timeout_secs {
+        Some(secs) if secs > 0 => policy.with_attempt_timeout(Duration::from_secs(secs)),
+        _ => policy,
+    }
+}
+
+fn endpoint(provider: &PostProcessProvider, path: &str) -> String {
+    format!("{}{}", provider.base_url.trim_end_matches('/'), path)
+}
+
+/// Chat endpoint of the provider kind
+fn chat_url(provider: &PostProcessProvider) -> String {
+    match provider.kind {
+        ProviderKind::OpenaiCompatible => endpoint(provider, "/chat/completions"),
+        ProviderKind::AnthropicMessages => endpoint(provider, "/messages"),
+        ProviderKind::OllamaNative => endpoint(provider, "/api/chat"),
+    }
+}
+
+/// Model listing endpoint of the provider kind
+fn models_url(provider: &PostProcessProvider) -> String {
+    match provider.kind {
+        ProviderKind::OpenaiCompatible | ProviderKind::AnthropicMessages => {
+            endpoint(provider, "/models")
+        }
+        ProviderKind::OllamaNative => endpoint(provider, "/api/tags"),
+    }
+}
+
+/// Build the request body for the provider kind.
+/// Structured outputs: `response_format` (OpenAI), a forced tool whose input
+/// is the schema (Anthropic), or `format` (Ollama).
+fn build_chat_body(
+    kind: ProviderKind,
+    model: &str,
+    user_content: String,
+    system_prompt: Option<String>,
+    json_schema: Option<Value>,
+    stream: bool,
+) -> Result<Value, String> {
+    let body = match kind {
+        ProviderKind::OpenaiCompatible => serde_json::to_value(ChatCompletionRequest {
+            model: model.to_string(),
+            messages: build_messages(user_content, system_prompt),
+            response_format: json_schema.map(|schema| ResponseFormat {
+                format_type: "json_schema".to_string(),
+                json_schema: JsonSchema {
+                    name: STRUCTURED_OUTPUT_NAME.to_string(),
+                    strict: true,
+                    schema,
+                },
+            }),
+            stream,
+        }),
+        ProviderKind::AnthropicMessages => {
+            // The system prompt is a top-level field, not a message
+            let (tools, tool_choice) = match json_schema {
+                Some(schema) => (
+                    vec![AnthropicTool {
+                        name: STRUCTURED_OUTPUT_NAME.to_string(),
+                        description: "Return the processed transcription".to_string(),
+                        input_schema: schema,
+                    }],
+                    Some(AnthropicToolChoice {
+                        choice_type: "tool".to_string(),
+                        name: STRUCTURED_OUTPUT_NAME.to_string(),
+                    }),
+                ),
+                None => (Vec::new(), None),
+            };
+            serde_json::to_value(AnthropicRequest {
+                model: model.to_string(),
+                max_tokens: ANTHROPIC_MAX_TOKENS,
+                system: system_prompt,
+                messages: build_messages(user_content, None),
+                tools,
+                tool_choice,
+                stream,
+            })
+        }
+        ProviderKind::OllamaNative => serde_json::to_value(OllamaChatRequest {
+            model: model.to_string(),
+            messages: build_messages(user_content, system_prompt),
+            stream,
+            format: json_schema,
+        }),
+    };
+    body.map_err(|e| format!("Failed to serialize request: {}", e))
+}
+
+/// Extract the message content from a non-streaming response.
+/// For Anthropic structured outputs, the forced tool input is returned as a
+/// JSON string, like the content of the other kinds.
+fn parse_chat_response(kind: ProviderKind, body: Value) -> Result<Option<String>, String> {
+    let parse_error = |e: serde_json::Error| format!("Failed to parse API response: {}", e);
+    match kind {
+        ProviderKind::OpenaiCompatible => {
+            let completion: ChatCompletionResponse =
+                serde_json::from_value(body).map_err(parse_error)?;
+            Ok(completion
+                .choices
+                .into_iter()
+                .next()
+                .and_then(|choice| choice.message.content))
+        }
+        ProviderKind::AnthropicMessages => {
+            let response: AnthropicResponse = serde_json::from_value(body).map_err(parse_error)?;
+            if let Some(input) = response
+                .content
+                .iter()
+                .find(|block| block.block_type == "tool_use")
+                .and_then(|block| block.input.as_ref())
+            {
+                return Ok(Some(input.to_string()));
+            }
+            let text: String = response
+                .content
+                .into_iter()
+                .filter(|block| block.block_type == "text")
+                .filter_map(|block| block.text)
+                .collect();
+            Ok(if text.is_empty() { None } else { Some(text) })
+        }
+        ProviderKind::OllamaNative => {
+            let response: OllamaChatResponse = serde_json::from_value(body).map_err(parse_error)?;
+            Ok(response.message.and_then(|message| message.content))
+        }
+    }
+}
+
+/// Extract model IDs from a model listing response
+fn parse_model_list(parsed: &Value) -> Vec<String> {
+    let mut models = Vec::new();
+
+    // Handle OpenAI / Anthropic format: { data: [ { id: "..." }, ... ] }
+    if let Some(data) = parsed.get("data").and_then(|d| d.as_array()) {
+        for entry in data {
+            if let Some(id) = entry.get("id").and_then(|i| i.as_str()) {
+                models.push(id.to_string());
+            } else if let Some(name) = entry.get("name").and_then(|n| n.as_str()) {
+                models.push(name.to_string());
+            }
+        }
+    }
+    // Handle Ollama format: { models: [ { name: "..." }, ... ] }
+    else if let Some(list) = parsed.get("models").and_then(|m| m.as_array()) {
+        for entry in list {
+            if let Some(name) = entry.get("name").and_then(|n| n.as_str()) {
+                models.push(name.to_string());
+            }
+        }
+    }
+    // Handle array format: [ "model1", "model2", ... ]
+    else if let Some(array) = parsed.as_array() {
+        for entry in array {
+            if let Some(model) = entry.as_str() {
+                models.push(model.to_string());
+            }
+        }
+    }
+
+    models
+}
+
+/// POST a JSON body, returning the response if the status is a success.
+/// Failures are classified for the retry / circuit breaker layer.
+async fn post_json(
+    client: &reqwest::Client,
+    url: &str,
+    body: &Value,
+) -> Result<reqwest::Response, Failure> {
+    let response = client
+        .post(url)
+        .json(body)
+        .send()
+        .await
+        .map_err(|e| Failure::from_reqwest(&e))?;
+
+    resilience::check_status(response).await
+}
+
+/// POST through the provider's resilience policy (timeouts, retries, breaker)
+async fn post_with_retries(
+    provider: &PostProcessProvider,
+    client: &reqwest::Client,
+    url: &str,
+    body: &Value,
+) -> Result<reqwest::Response, String> {
+    resilience::execute(
+        &provider.id,
+        &provider.label,
+        &retry_policy(provider),
+        || post_json(client, url, body),
+    )
+    .await
+    .map_err(|e| e.to_string())
+}
+
+/// Send a chat completion request to the provider
 /// Returns Ok(Some(content)) on success, Ok(None) if response has no content,
 /// or Err on actual errors (HTTP, parsing, etc.)
 pub async fn send_chat_completion(
@@ -106,7 +426,8 @@ pub async fn send_chat_completion(
 }
 
 /// Send a chat completion request with structured output support
-/// When json_schema is provided, uses structured outputs mode
+/// When json_schema is provided, uses the structured output strategy of the
+/// provider kind; the returned content is then a JSON document
 /// system_prompt is used as the system message when provided
 pub async fn send_chat_completion_with_schema(
     provider: &PostProcessProvider,
@@ -116,84 +437,76 @@ pub async fn send_chat_completion_with_schema(
     system_prompt: Option<String>,
     json_schema: Option<Value>,
 ) -> Result<Option<String>, String> {
-    let base_url = provider.base_url.trim_end_matches('/');
-    let url = format!("{}/chat/completions", base_url);
+    let url = chat_url(provider);
 
-    debug!("Sending chat completion request to: {}", url);
+    debug!(
+        "Sending chat completion request to: {} ({:?})",
+        url, provider.kind
+    );
 
     let client = create_client(provider, &api_key)?;
+    let body = build_chat_body(
+        provider.kind,
+        model,
+        user_content,
+        system_prompt,
+        json_schema,
+        false,
+    )?;
+
+    let response = post_with_retries(provider, &client, &url, &body).await?;
+    let parsed: Value = response
+        .json()
+        .await
+        .map_err(|e| format!("Failed to parse API response: {}", e))?;
 
-    // Build messages vector
-    let mut messages = Vec::new();
-
-    // Add system prompt if provided
-    if let Some(system) = system_prompt {
-        messages.push(ChatMessage {
-            role: "system".to_string(),
-            content: system,
-        });
-    }
+    parse_chat_response(provider.kind, parsed)
+}
 
-    // Add user message
-    messages.push(ChatMessage {
-        role: "user".to_string(),
-        content: user_content,
-    });
+/// Send a streaming chat completion request (`"stream": true`).
+/// Returns the token stream once the response headers are received; HTTP
+/// errors are reported here, mid-stream errors as stream items.
+/// Structured outputs are not used: partial JSON can't be previewed.
+pub async fn stream_chat_completion(
+    provider: &PostProcessProvider,
+    api_key: String,
+    model: &str,
+    user_content: String,
+    system_prompt: Option<String>,
+) -> Result<TokenStream, String> {
+    let url = chat_url(provider);
 
-    // Build response_format if schema is provided
-    let response_format = json_schema.map(|schema| ResponseFormat {
-        format_type: "json_schema".to_string(),
-        json_schema: JsonSchema {
-            name: "transcription_output".to_string(),
-            strict: true,
-            schema,
-        },
-    });
+    debug!(
+        "Sending streaming chat completion request to: {} ({:?})",
+        url, provider.kind
+    );
 
-    let request_body = ChatCompletionRequest {
-        model: model.to_string(),
-        messages,
-        response_format,
+    let client = create_client(provider, &api_key)?;
+    let body = build_chat_body(
+        provider.kind,
+        model,
+        user_content,
+        system_prompt,
+        None,
+        true,
+    )?;
+
+    let response = post_with_retries(provider, &client, &url, &body).await?;
+    let format = match provider.kind {
+        ProviderKind::OpenaiCompatible | ProviderKind::AnthropicMessages => StreamFormat::Sse,
+        ProviderKind::OllamaNative => StreamFormat::Ndjson,
     };
 
-    let response = client
-        .post(&url)
-        .json(&request_body)
-        .send()
-        .await
-        .map_err(|e| format!("HTTP request failed: {}", e))?;
-
-    let status = response.status();
-    if !status.is_success() {
-        let error_text = response
-            .text()
-            .await
-            .unwrap_or_else(|_| "Failed to read error response".to_string());
-        return Err(format!(
-            "API request failed with status {}: {}",
-            status, error_text
-        ));
-    }
-
-    let completion: ChatCompletionResponse = response
-        .json()
-        .await
-        .map_err(|e| format!("Failed to parse API response: {}", e))?;
-
-    Ok(completion
-        .choices
-        .first()
-        .and_then(|choice| choice.message.content.clone()))
+    Ok(stream::token_stream(response, format))
 }
 
-/// Fetch available models from an OpenAI-compatible API
+/// Fetch available models from the provider
 /// Returns a list of model IDs
 pub async fn fetch_models(
     provider: &PostProcessProvider,
     api_key: String,
 ) -> Result<Vec<String>, String> {
-    let base_url = provider.base_url.trim_end_matches('/');
-    let url = format!("{}/models", base_url);
+    let url = models_url(provider);
 
     debug!("Fetching models from: {}", url);
 
@@ -201,6 +514,7 @@ pub async fn fetch_models(
 
     let response = client
         .get(&url)
+        .timeout(MODELS_TIMEOUT)
         .send()
         .await
         .map_err(|e| format!("Failed to fetch models: {}", e))?;
@@ -222,26 +536,397 @@ pub async fn fetch_models(
         .await
         .map_err(|e| format!("Failed to parse response: {}", e))?;
 
-    let mut models = Vec::new();
+    Ok(parse_model_list(&parsed))
+}
 
-    // Handle OpenAI format: { data: [ { id: "..." }, ... ] }
-    if let Some(data) = parsed.get("data").and_then(|d| d.as_array()) {
-        for entry in data {
-            if let Some(id) = entry.get("id").and_then(|i| i.as_str()) {
-                models.push(id.to_string());
-            } else if let Some(name) = entry.get("name").and_then(|n| n.as_str()) {
-                models.push(name.to_string());
-            }
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures_util::StreamExt;
+    use serde_json::json;
+    use std::io::{BufRead, BufReader, Read, Write};
+    use std::net::TcpListener;
+    use std::thread::JoinHandle;
+
+    /// Request received by the mock server
+    struct Recorded {
+        request_line: String,
+        headers: Vec<(String, String)>,
+        body: String,
+    }
+
+    impl Recorded {
+        fn header(&self, name: &str) -> Option<&str> {
+            self.headers
+                .iter()
+                .find(|(k, _)| k.eq_ignore_ascii_case(name))
+                .map(|(_, v)| v.as_str())
+        }
+
+        fn json(&self) -> Value {
+            serde_json::from_str(&self.body).unwrap()
         }
     }
-    // Handle array format: [ "model1", "model2", ... ]
-    else if let Some(array) = parsed.as_array() {
-        for entry in array {
-            if let Some(model) = entry.as_str() {
-                models.push(model.to_string());
+
+    /// Canned response of the mock server: status, content type, extra headers, body
+    type MockResponse = (u16, &'static str, &'static str, &'static str);
+
+    /// Local HTTP server answering a single request with `status` and `body`.
+    /// Returns its base URL and a handle yielding the recorded request.
+    fn mock_server(
+        status: u16,
+        content_type: &'static str,
+        body: &'static str,
+    ) -> (String, JoinHandle<Recorded>) {
+        let (base_url, handle) = mock_server_sequence(vec![(status, content_type, "", body)]);
+        (
+            base_url,
+            std::thread::spawn(move || handle.join().unwrap().pop().unwrap()),
+        )
+    }
+
+    /// Local HTTP server answering one connection per response, in order
+    fn mock_server_sequence(responses: Vec<MockResponse>) -> (String, JoinHandle<Vec<Recorded>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+        let handle = std::thread::spawn(move || {
+            responses
+                .into_iter()
+                .map(|response| serve_one(&listener, response))
+                .collect()
+        });
+        (base_url, handle)
+    }
+
+    fn serve_one(listener: &TcpListener, response: MockResponse) -> Recorded {
+        let (status, content_type, extra_headers, body) = response;
+        let (stream, _) = listener.accept().unwrap();
+        let mut reader = BufReader::new(stream.try_clone().unwrap());
+
+        let mut request_line = String::new();
+        reader.read_line(&mut request_line).unwrap();
+        let mut headers = Vec::new();
+        loop {
+            let mut line = String::new();
+            reader.read_line(&mut line).unwrap();
+            let line = line.trim_end();
+            if line.is_empty() {
+                break;
+            }
+            if let Some((k, v)) = line.split_once(':') {
+                headers.push((k.trim().to_string(), v.trim().to_string()));
             }
         }
+        let length = headers
+            .iter()
+            .find(|(k, _)| k.eq_ignore_ascii_case("content-length"))
+            .map(|(_, v)| v.parse::<usize>().unwrap())
+            .unwrap_or(0);
+        let mut request_body = vec![0; length];
+        reader.read_exact(&mut request_body).unwrap();
+
+        let mut stream = stream;
+        write!(
+            stream,
+            "HTTP/1.1 {} Mock\r\nContent-Type: {}\r\nContent-Length: {}\r\n{}Connection: close\r\n\r\n{}",
+            status,
+            content_type,
+            body.len(),
+            extra_headers,
+            body
+        )
+        .unwrap();
+
+        Recorded {
+            request_line: request_line.trim_end().to_string(),
+            headers,
+            body: String::from_utf8(request_body).unwrap(),
+        }
     }
 
-    Ok(models)
+    fn provider(kind: ProviderKind, base_url: &str) -> PostProcessProvider {
+        // One provider per mock server: circuit breakers are keyed by id
+        PostProcessProvider {
+            id: base_url.to_string(),
+            label: "Test".to_string(),
+            base_url: base_url.to_string(),
+            kind,
+            allow_base_url_edit: true,
+            models_endpoint: None,
+            supports_structured_output: true,
+            timeout_secs: None,
+        }
+    }
+
+    fn schema() -> Value {
+        json!({
+            "type": "object",
+            "properties": { "transcription": { "type": "string" } },
+            "required": ["transcription"]
+        })
+    }
+
+    fn run<F: std::future::Future>(future: F) -> F::Output {
+        tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .unwrap()
+            .block_on(future)
+    }
+
+    #[test]
+    fn openai_compatible_uses_bearer_and_response_format() {
+        let (url, server) = mock_server(
+            200,
+            "application/json",
+            r#"{"choices":[{"message":{"content":"{\"transcription\":\"Bonjour.\"}"}}]}"#,
+        );
+        let provider = provider(ProviderKind::OpenaiCompatible, &format!("{}/v1/", url));
+
+        let content = run(send_chat_completion_with_schema(
+            &provider,
+            "sk-test".to_string(),
+            "gpt-test",
+            "bonjour".to_string(),
+            Some("Nettoie".to_string()),
+            Some(schema()),
+        ))
+        .unwrap();
+
+        assert_eq!(content.as_deref(), Some(r#"{"transcription":"Bonjour."}"#));
+        let request = server.join().unwrap();
+        assert_eq!(request.request_line, "POST /v1/chat/completions HTTP/1.1");
+        assert_eq!(request.header("authorization"), Some("Bearer sk-test"));
+        let body = request.json();
+        assert_eq!(body["messages"][0]["role"], "system");
+        assert_eq!(body["messages"][1]["content"], "bonjour");
+        assert_eq!(body["response_format"]["type"], "json_schema");
+        assert_eq!(body["response_format"]["json_schema"]["strict"], true);
+        assert!(body.get("stream").is_none());
+    }
+
+    #[test]
+    fn anthropic_uses_messages_api_and_forced_tool() {
+        let (url, server) = mock_server(
+            200,
+            "application/json",
+            r#"{"content":[{"type":"tool_use","id":"t1","name":"transcription_output","input":{"transcription":"Bonjour."}}]}"#,
+        );
+        let provider = provider(ProviderKind::AnthropicMessages, &url);
+
+        let content = run(send_chat_completion_with_schema(
+            &provider,
+            "sk-ant".to_string(),
+            "claude-test",
+            "bonjour".to_string(),
+            Some("Nettoie".to_string()),
+            Some(schema()),
+        ))
+        .unwrap()
+        .unwrap();
+
+        let output: Value = serde_json::from_str(&content).unwrap();
+        assert_eq!(output["transcription"], "Bonjour.");
+        let request = server.join().unwrap();
+        assert_eq!(request.request_line, "POST /messages HTTP/1.1");
+        assert_eq!(request.header("x-api-key"), Some("sk-ant"));
+        assert_eq!(request.header("anthropic-version"), Some(ANTHROPIC_VERSION));
+        assert_eq!(request.header("authorization"), None);
+        let body = request.json();
+        assert_eq!(body["system"], "Nettoie");
+        assert_eq!(body["messages"].as_array().unwrap().len(), 1);
+        assert_eq!(body["messages"][0]["role"], "user");
+        assert_eq!(body["max_tokens"], ANTHROPIC_MAX_TOKENS);
+        assert_eq!(body["tools"][0]["input_schema"], schema());
+        assert_eq!(body["tool_choice"]["name"], STRUCTURED_OUTPUT_NAME);
+    }
+
+    #[test]
+    fn anthropic_concatenates_text_blocks() {
+        let (url, server) = mock_server(
+            200,
+            "application/json",
+            r#"{"content":[{"type":"text","text":"Bon"},{"type":"text","text":"jour."}]}"#,
+        );
+        let provider = provider(ProviderKind::AnthropicMessages, &url);
+
+        let content = run(send_chat_completion(
+            &provider,
+            "sk-ant".to_string(),
+            "claude-test",
+            "bonjour".to_string(),
+        ))
+        .unwrap();
+
+        assert_eq!(content.as_deref(), Some("Bonjour."));
+        let body = server.join().unwrap().json();
+        assert!(body.get("tools").is_none());
+        assert!(body.get("system").is_none());
+    }
+
+    #[test]
+    fn ollama_native_uses_api_chat_and_format() {
+        let (url, server) = mock_server(
+            200,
+            "application/json",
+            r#"{"model":"qwen","message":{"role":"assistant","content":"{\"transcription\":\"Bonjour.\"}"},"done":true}"#,
+        );
+        let provider = provider(ProviderKind::OllamaNative, &url);
+
+        let content = run(send_chat_completion_with_schema(
+            &provider,
+            String::new(),
+            "qwen",
+            "bonjour".to_string(),
+            None,
+            Some(schema()),
+        ))
+        .unwrap();
+
+        assert_eq!(content.as_deref(), Some(r#"{"transcription":"Bonjour."}"#));
+        let request = server.join().unwrap();
+        assert_eq!(request.request_line, "POST /api/chat HTTP/1.1");
+        assert_eq!(request.header("authorization"), None);
+        let body = request.json();
+        assert_eq!(body["stream"], false);
+        assert_eq!(body["format"], schema());
+    }
+
+    #[test]
+    fn http_errors_include_status_and_body() {
+        let (url, server) = mock_server(400, "application/json", r#"{"error":"bad model"}"#);
+        let provider = provider(ProviderKind::OpenaiCompatible, &url);
+
+        let err = run(send_chat_completion(
+            &provider,
+            "sk".to_string(),
+            "m",
+            "x".to_string(),
+        ))
+        .unwrap_err();
+
+        assert!(err.contains("400"), "{}", err);
+        assert!(err.contains("bad model"), "{}", err);
+        server.join().unwrap();
+    }
+
+    #[test]
+    fn overloaded_provider_is_retried_with_retry_after() {
+        let (url, server) = mock_server_sequence(vec![
+            (429, "text/plain", "Retry-After: 0\r\n", "slow down"),
+            (
+                200,
+                "application/json",
+                "",
+                r#"{"choices":[{"message":{"content":"Bonjour."}}]}"#,
+            ),
+        ]);
+        let provider = provider(ProviderKind::OpenaiCompatible, &url);
+
+        let content = run(send_chat_completion(
+            &provider,
+            "sk".to_string(),
+            "m",
+            "x".to_string(),
+        ))
+        .unwrap();
+
+        assert_eq!(content.as_deref(), Some("Bonjour."));
+        assert_eq!(server.join().unwrap().len(), 2);
+    }
+
+    #[test]
+    fn local_and_cloud_providers_get_different_policies() {
+        let local = provider(ProviderKind::OllamaNative, "http://localhost:11434");
+        let cloud = provider(ProviderKind::OpenaiCompatible, "https://api.openai.com/v1");
+        assert_eq!(retry_policy(&local), RetryPolicy::local_server());
+        assert_eq!(retry_policy(&cloud), RetryPolicy::cloud());
+
+        let custom = PostProcessProvider {
+            timeout_secs: Some(5),
+            ..cloud
+        };
+        assert_eq!(
+            retry_policy(&custom).attempt_timeout,
+            Duration::from_secs(5)
+        );
+    }
+
+    #[test]
+    fn model_listing_per_kind() {
+        let cases = [
+            (
+                ProviderKind::OpenaiCompatible,
+                r#"{"data":[{"id":"gpt-a"},{"id":"gpt-b"}]}"#,
+                "GET /models HTTP/1.1",
+                vec!["gpt-a", "gpt-b"],
+            ),
+            (
+                ProviderKind::AnthropicMessages,
+                r#"{"data":[{"id":"claude-a","type":"model"}],"has_more":false}"#,
+                "GET /models HTTP/1.1",
+                vec!["claude-a"],
+            ),
+            (
+                ProviderKind::OllamaNative,
+                r#"{"models":[{"name":"qwen2.5:3b"},{"name":"llama3.2:1b"}]}"#,
+                "GET /api/tags HTTP/1.1",
+                vec!["qwen2.5:3b", "llama3.2:1b"],
+            ),
+        ];
+
+        for (kind, response, request_line, expected) in cases {
+            let (url, server) = mock_server(200, "application/json", response);
+            let models = run(fetch_models(&provider(kind, &url), "key".to_string())).unwrap();
+            assert_eq!(models, expected, "{:?}", kind);
+            assert_eq!(server.join().unwrap().request_line, request_line);
+        }
+    }
+
+    #[test]
+    fn streaming_per_kind() {
+        let cases = [
+            (
+                ProviderKind::OpenaiCompatible,
+                "text/event-stream",
+                "data: {\"choices\":[{\"delta\":{\"content\":\"Bon\"}}]}\n\n\
+                 data: {\"choices\":[{\"delta\":{\"content\":\"jour\"}}]}\n\ndata: [DONE]\n\n",
+            ),
+            (
+                ProviderKind::AnthropicMessages,
+                "text/event-stream",
+                "event: message_start\ndata: {\"type\":\"message_start\",\"message\":{}}\n\n\
+                 event: content_block_delta\ndata: {\"type\":\"content_block_delta\",\"index\":0,\"delta\":{\"type\":\"text_delta\",\"text\":\"Bon\"}}\n\n\
+                 event: content_block_delta\ndata: {\"type\":\"content_block_delta\",\"index\":0,\"delta\":{\"type\":\"text_delta\",\"text\":\"jour\"}}\n\n\
+                 event: message_stop\ndata: {\"type\":\"message_stop\"}\n\n",
+            ),
+            (
+                ProviderKind::OllamaNative,
+                "application/x-ndjson",
+                "{\"message\":{\"content\":\"Bon\"},\"done\":false}\n\
+                 {\"message\":{\"content\":\"jour\"},\"done\":false}\n\
+                 {\"message\":{\"content\":\"\"},\"done\":true}\n",
+            ),
+        ];
+
+        for (kind, content_type, response) in cases {
+            let (url, server) = mock_server(200, content_type, response);
+            let tokens: Vec<String> = run(async {
+                stream_chat_completion(
+                    &provider(kind, &url),
+                    "key".to_string(),
+                    "m",
+                    "bonjour".to_string(),
+                    None,
+                )
+                .await
+                .unwrap()
+                .map(|token| token.unwrap())
+                .collect()
+                .await
+            });
+            assert_eq!(tokens, ["Bon", "jour"], "{:?}", kind);
+            assert_eq!(server.join().unwrap().json()["stream"], true);
+        }
+    }
 }
@@ -6,5 +6,18 @@ use dictation_ia_lib::CliArgs;
 
 fn main() {
     let cli_args = CliArgs::parse();
+
+    // Rapport lu directement dans l'historique, sans démarrer l'application
+    if let Some(days) = cli_args.stats_report {
+        match dictation_ia_lib::stats::cli_report(days) {
+            Ok(report) => print!("{}", report),
+            Err(e) => {
+                eprintln!("{:#}", e);
+                std::process::exit(1);
+            }
+        }
+        return;
+    }
+
     dictation_ia_lib::run(cli_args)
 }
@@ -1,11 +1,16 @@
-use crate::audio_toolkit::{list_input_devices, vad::SmoothedVad, AudioRecorder, SileroVad};
+use crate::audio_toolkit::{
+    list_input_devices, pick_preferred_device, vad::SmoothedVad, AudioRecorder, SileroVad,
+};
 use crate::helpers::clamshell;
 use crate::settings::{get_settings, AppSettings};
 use crate::utils;
-use log::{debug, error, info};
+use log::{debug, error, info, warn};
+use serde::Serialize;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
 use std::sync::{Arc, Mutex};
-use std::time::Instant;
-use tauri::Manager;
+use std::thread;
+use std::time::{Duration, Instant};
+use tauri::{Emitter, Manager};
 
 // Gestionnaire d'enregistrement audio — macOS uniquement.
 // Pipeline : cpal capture f32 mono → SmoothedVad (Silero v4) → buffer PCM → transcription.
@@ -26,6 +31,37 @@ fn set_mute(mute: bool) {
 
 const WHISPER_SAMPLE_RATE: usize = 16000;
 
+/// Délai d'avertissement avant l'arrêt automatique (durée max atteinte)
+const RECORDING_LIMIT_WARNING_SECS: u64 = 30;
+
+/// Période de vérification des erreurs de flux signalées par cpal
+const DEVICE_MONITOR_TICK: Duration = Duration::from_millis(500);
+/// Énumération des périphériques toutes les N vérifications (~2 s) —
+/// cpal n'expose pas de notification de branchement.
+const DEVICE_SCAN_EVERY_TICKS: u32 = 4;
+
+/// Nom du micro par défaut du système, s'il y en a un
+fn default_input_name() -> Option<String> {
+    list_input_devices()
+        .ok()?
+        .into_iter()
+        .find(|d| d.is_default)
+        .map(|d| d.name)
+}
+
+/// Changement de micro, émis sur `microphone-switched`
+#[derive(Clone, Debug, Serialize)]
+pub struct MicrophoneSwitchEvent {
+    /// Micro précédent (None = défaut système inconnu)
+    pub from: Option<String>,
+    /// Nouveau micro (None = aucun micro disponible, flux fermé)
+    pub to: Option<String>,
+    /// "disconnected" | "preferred_available" | "device_available" | "no_device"
+    pub reason: String,
+    /// true si une dictée était en cours : l'audio déjà capté est conservé
+    pub recording: bool,
+}
+
 /* ──────────────────────────────────────────────────────────────── */
 
 #[derive(Clone, Debug)]
@@ -45,6 +81,7 @@ pub enum MicrophoneMode {
 fn create_audio_recorder(
     vad_path: &str,
     app_handle: &tauri::AppHandle,
+    stream_error: Arc<AtomicBool>,
 ) -> Result<AudioRecorder, anyhow::Error> {
     let silero = SileroVad::new(vad_path, 0.3)
         .map_err(|e| anyhow::anyhow!("Failed to create SileroVad: {}", e))?;
@@ -60,6 +97,12 @@ fn create_audio_recorder(
             move |levels| {
                 utils::emit_levels(&app_handle, &levels);
             }
+        })
+        // Appelé depuis le thread audio : on se contente de lever un drapeau,
+        // la reprise est faite par le moniteur de périphériques.
+        .with_error_callback(move |err| {
+            warn!("Erreur du flux micro : {}", err);
+            stream_error.store(true, Ordering::Relaxed);
         });
 
     Ok(recorder)
@@ -77,6 +120,16 @@ pub struct AudioRecordingManager {
     is_open: Arc<Mutex<bool>>,
     is_recording: Arc<Mutex<bool>>,
     did_mute: Arc<Mutex<bool>>,
+    /// Incrémenté à chaque début/fin d'enregistrement — invalide le watchdog
+    /// de durée max de la session précédente.
+    recording_session: Arc<AtomicU64>,
+    /// Nom du micro actuellement ouvert
+    active_device: Arc<Mutex<Option<String>>>,
+    /// Audio capté avant un changement de micro en cours de dictée,
+    /// préfixé au buffer rendu par `stop_recording`
+    carried_samples: Arc<Mutex<Vec<f32>>>,
+    /// Levé par le callback d'erreur cpal (périphérique perdu)
+    stream_error: Arc<AtomicBool>,
 }
 
 impl AudioRecordingManager {
@@ -99,6 +152,10 @@ impl AudioRecordingManager {
             is_open: Arc::new(Mutex::new(false)),
             is_recording: Arc::new(Mutex::new(false)),
             did_mute: Arc::new(Mutex::new(false)),
+            recording_session: Arc::new(AtomicU64::new(0)),
+            active_device: Arc::new(Mutex::new(None)),
+            carried_samples: Arc::new(Mutex::new(Vec::new())),
+            stream_error: Arc::new(AtomicBool::new(false)),
         };
 
         // Always-on?  Open immediately.
@@ -106,34 +163,58 @@ impl AudioRecordingManager {
             manager.start_microphone_stream()?;
         }
 
+        manager.spawn_device_monitor();
+
         Ok(manager)
     }
 
     /* ---------- helper methods --------------------------------------------- */
 
-    fn get_effective_microphone_device(&self, settings: &AppSettings) -> Option<cpal::Device> {
-        // Check if we're in clamshell mode and have a clamshell microphone configured
-        let use_clamshell_mic = if let Ok(is_clamshell) = clamshell::is_clamshell() {
-            is_clamshell && settings.clamshell_microphone.is_some()
-        } else {
-            false
-        };
+    /// Micros par ordre de préférence : micro « capot fermé » si applicable,
+    /// micro sélectionné, puis la liste de secours.
+    fn preferred_microphones(settings: &AppSettings) -> Vec<String> {
+        let is_clamshell = clamshell::is_clamshell().unwrap_or(false);
+        let mut preferred = Vec::new();
+        if is_clamshell {
+            preferred.extend(settings.clamshell_microphone.clone());
+        }
+        preferred.extend(settings.selected_microphone.clone());
+        preferred.extend(settings.microphone_priority.iter().cloned());
 
-        let device_name = if use_clamshell_mic {
-            settings.clamshell_microphone.as_ref().unwrap()
-        } else {
-            settings.selected_microphone.as_ref()?
-        };
+        let mut seen = std::collections::HashSet::new();
+        preferred.retain(|name| seen.insert(name.clone()));
+        preferred
+    }
 
-        // Find the device by name
-        match list_input_devices() {
-            Ok(devices) => devices
-                .into_iter()
-                .find(|d| d.name == *device_name)
-                .map(|d| d.device),
+    /// Résout le micro à ouvrir parmi les périphériques branchés.
+    /// Retourne `(None, nom du défaut système)` si aucun micro préféré n'est
+    /// présent.
+    fn get_effective_microphone_device(
+        &self,
+        settings: &AppSettings,
+    ) -> (Option<cpal::Device>, Option<String>) {
+        let devices = match list_input_devices() {
+            Ok(devices) => devices,
             Err(e) => {
                 debug!("Failed to list devices, using default: {}", e);
-                None
+                return (None, None);
+            }
+        };
+
+        let names: Vec<String> = devices.iter().map(|d| d.name.clone()).collect();
+        let preferred = Self::preferred_microphones(settings);
+        match pick_preferred_device(&names, &preferred) {
+            Some(name) => {
+                let name = name.to_string();
+                let device = devices
+                    .into_iter()
+                    .find(|d| d.name == name)
+                    .map(|d| d.device);
+                (device, Some(name))
+            }
+            None => {
+                let default_name = devices.into_iter().find(|d| d.is_default).map(|d| d.name);
+                (None, default_name)
             }
         }
     }
@@ -189,22 +270,39 @@ impl AudioRecordingManager {
             *recorder_opt = Some(create_audio_recorder(
                 vad_path.to_str().unwrap(),
                 &self.app_handle,
+                Arc::clone(&self.stream_error),
             )?);
         }
 
-        // Get the selected device from settings, considering clamshell mode
+        // Get the preferred available device, considering clamshell mode
         let settings = get_settings(&self.app_handle);
-        let selected_device = self.get_effective_microphone_device(&settings);
+        let (selected_device, mut device_name) = self.get_effective_microphone_device(&settings);
 
         if let Some(rec) = recorder_opt.as_mut() {
-            rec.open(selected_device)
-                .map_err(|e| anyhow::anyhow!("Failed to open recorder: {}", e))?;
+            let can_fall_back = selected_device.is_some();
+            if let Err(e) = rec.open(selected_device) {
+                // Micro préféré listé mais inutilisable (en cours de
+                // débranchement, occupé…) : dernier recours, le défaut système
+                if !can_fall_back {
+                    return Err(anyhow::anyhow!("Failed to open recorder: {}", e));
+                }
+                warn!(
+                    "Ouverture de {:?} impossible ({}), micro par défaut",
+                    device_name, e
+                );
+                rec.open(None)
+                    .map_err(|e| anyhow::anyhow!("Failed to open recorder: {}", e))?;
+                device_name = default_input_name();
+            }
         }
 
+        self.stream_error.store(false, Ordering::Relaxed);
+        *self.active_device.lock().unwrap() = device_name;
         *open_flag = true;
         info!(
-            "Microphone stream initialized in {:?}",
-            start_time.elapsed()
+            "Microphone stream initialized in {:?} ({:?})",
+            start_time.elapsed(),
+            self.active_device.lock().unwrap()
         );
         Ok(())
     }
@@ -231,9 +329,157 @@ impl AudioRecordingManager {
         }
 
         *open_flag = false;
+        *self.active_device.lock().unwrap() = None;
         debug!("Microphone stream stopped");
     }
 
+    /// Micro actuellement ouvert (None si le flux est fermé)
+    pub fn active_microphone(&self) -> Option<String> {
+        self.active_device.lock().unwrap().clone()
+    }
+
+    /* ---------- hot-plug ---------------------------------------------------- */
+
+    /// Rouvre le flux sur le meilleur micro disponible. Une dictée en cours
+    /// continue : l'audio déjà capté est mis de côté puis recollé à l'arrêt.
+    ///
+    /// L'appelant doit tenir le verrou `state` pour ne pas croiser un
+    /// démarrage/arrêt de dictée.
+    fn reopen_microphone_stream(&self, was_recording: bool) -> Result<(), anyhow::Error> {
+        if was_recording {
+            if let Some(rec) = self.recorder.lock().unwrap().as_ref() {
+                match rec.stop() {
+                    Ok(buf) => self.carried_samples.lock().unwrap().extend(buf),
+                    Err(e) => warn!("Audio partiel perdu au changement de micro : {}", e),
+                }
+            }
+            *self.is_recording.lock().unwrap() = false;
+        }
+
+        let was_muted = *self.did_mute.lock().unwrap();
+        self.stop_microphone_stream();
+        self.start_microphone_stream()?;
+
+        if was_recording {
+            if let Some(rec) = self.recorder.lock().unwrap().as_ref() {
+                rec.start()
+                    .map_err(|e| anyhow::anyhow!("Failed to resume recording: {}", e))?;
+            }
+            *self.is_recording.lock().unwrap() = true;
+            if was_muted {
+                self.apply_mute();
+            }
+        }
+        Ok(())
+    }
+
+    /// Compare le micro ouvert au meilleur micro branché et bascule si besoin.
+    /// `stream_failed` : cpal a signalé une erreur sur le flux courant.
+    fn handle_device_change(&self, available: &[String], stream_failed: bool) {
+        // Sérialise avec try_start_recording / stop_recording
+        let state = self.state.lock().unwrap();
+        let recording = matches!(*state, RecordingState::Recording { .. });
+        let is_open = *self.is_open.lock().unwrap();
+        let always_on = matches!(*self.mode.lock().unwrap(), MicrophoneMode::AlwaysOn);
+
+        let settings = get_settings(&self.app_handle);
+        let preferred = Self::preferred_microphones(&settings);
+        let wanted = pick_preferred_device(available, &preferred).map(str::to_string);
+        let active = self.active_device.lock().unwrap().clone();
+
+        let reason = if !is_open {
+            // Flux fermé après une perte de micro : on le rouvre dès qu'un
+            // périphérique revient (mode toujours actif uniquement)
+            if !always_on || available.is_empty() {
+                return;
+            }
+            "device_available"
+        } else if stream_failed
+            || active
+                .as_ref()
+                .is_some_and(|name| !available.contains(name))
+        {
+            "disconnected"
+        } else if wanted.is_some() && wanted != active {
+            // Un micro préféré vient d'être rebranché : on attend la fin de
+            // la dictée pour ne pas couper l'utilisateur
+            if recording {
+                return;
+            }
+            "preferred_available"
+        } else {
+            return;
+        };
+
+        info!(
+            "Changement de micro ({}) : {:?} → {:?}",
+            reason,
+            active,
+            wanted.as_deref().unwrap_or("défaut système")
+        );
+
+        let (to, reason) = match self.reopen_microphone_stream(recording) {
+            Ok(()) => (self.active_microphone(), reason),
+            Err(e) => {
+                error!("Aucun micro utilisable : {}", e);
+                self.stop_microphone_stream();
+                (None, "no_device")
+            }
+        };
+        drop(state);
+
+        let _ = self.app_handle.emit(
+            "microphone-switched",
+            MicrophoneSwitchEvent {
+                from: active,
+                to,
+                reason: reason.to_string(),
+                recording,
+            },
+        );
+    }
+
+    /// Surveille les branchements/débranchements : erreurs de flux cpal et
+    /// énumération périodique des entrées audio.
+    fn spawn_device_monitor(&self) {
+        let manager = self.clone();
+        thread::spawn(move || {
+            let mut known: Option<Vec<String>> = None;
+            let mut tick: u32 = 0;
+            loop {
+                thread::sleep(DEVICE_MONITOR_TICK);
+                let stream_failed = manager.stream_error.swap(false, Ordering::Relaxed);
+                tick = tick.wrapping_add(1);
+                if !stream_failed && tick % DEVICE_SCAN_EVERY_TICKS != 0 {
+                    continue;
+                }
+
+                let available: Vec<String> = match list_input_devices() {
+                    Ok(devices) => devices.into_iter().map(|d| d.name).collect(),
+                    Err(e) => {
+                        debug!("Énumération des micros impossible : {}", e);
+                        continue;
+                    }
+                };
+
+                let changed = known.as_ref() != Some(&available);
+                if changed {
+                    if known.is_some() {
+                        debug!("Périphériques d'entrée : {:?}", available);
+                        let _ = manager
+                            .app_handle
+                            .emit("microphone-devices-changed", available.clone());
+                    }
+                    known = Some(available.clone());
+                }
+
+                if changed || stream_failed {
+                    manager.handle_device_change(&available, stream_failed);
+                }
+            }
+        });
+    }
+
     /* ---------- mode switching --------------------------------------------- */
 
     pub fn update_mode(&self, new_mode: MicrophoneMode) -> Result<(), anyhow::Error> {
@@ -274,6 +520,7 @@ impl AudioRecordingManager {
                 }
             }
 
+            self.carried_samples.lock().unwrap().clear();
             if let Some(rec) = self.recorder.lock().unwrap().as_ref() {
                 if rec.start().is_ok() {
                     *self.is_recording.lock().unwrap() = true;
@@ -281,6 +528,7 @@ impl AudioRecordingManager {
                         binding_id: binding_id.to_string(),
                     };
                     debug!("Recording started for binding {binding_id}");
+                    self.spawn_recording_limit_watchdog(binding_id);
                     return true;
                 }
             }
@@ -291,11 +539,58 @@ impl AudioRecordingManager {
         }
     }
 
+    /// Surveille la durée de l'enregistrement en cours : avertit l'overlay
+    /// 30 s avant `max_recording_seconds`, puis arrête la dictée via le
+    /// coordinator (même chemin qu'un second appui sur le raccourci).
+    fn spawn_recording_limit_watchdog(&self, binding_id: &str) {
+        let settings = get_settings(&self.app_handle);
+        let max = Duration::from_secs(settings.max_recording_seconds.max(1) as u64);
+        let warn_at = max.saturating_sub(Duration::from_secs(RECORDING_LIMIT_WARNING_SECS));
+
+        let session = self.recording_session.fetch_add(1, Ordering::Relaxed) + 1;
+        let current_session = Arc::clone(&self.recording_session);
+        let app = self.app_handle.clone();
+        let binding_id = binding_id.to_string();
+
+        thread::spawn(move || {
+            let started = Instant::now();
+            let mut warned = false;
+            loop {
+                thread::sleep(Duration::from_millis(250));
+                if current_session.load(Ordering::Relaxed) != session {
+                    return;
+                }
+
+                let elapsed = started.elapsed();
+                if !warned && elapsed >= warn_at {
+                    warned = true;
+                    utils::show_recording_limit_warning(
+                        &app,
+                        max.saturating_sub(elapsed).as_secs(),
+                    );
+                }
+                if elapsed >= max {
+                    info!(
+                        "Durée max d'enregistrement atteinte ({}s) — arrêt automatique",
+                        max.as_secs()
+                    );
+                    // Arrêt explicite : un basculement relancerait un
+                    // enregistrement si l'utilisateur vient d'arrêter.
+                    if let Some(c) = app.try_state::<crate::TranscriptionCoordinator>() {
+                        c.request_stop(&binding_id, "max_recording_length");
+                    }
+                    return;
+                }
+            }
+        });
+    }
+
     pub fn update_selected_device(&self) -> Result<(), anyhow::Error> {
         // If currently open, restart the microphone stream to use the new device
+        let state = self.state.lock().unwrap();
         if *self.is_open.lock().unwrap() {
-            self.stop_microphone_stream();
-            self.start_microphone_stream()?;
+            let recording = matches!(*state, RecordingState::Recording { .. });
+            self.reopen_microphone_stream(recording)?;
         }
         Ok(())
     }
@@ -309,8 +604,9 @@ impl AudioRecordingManager {
             } if active == binding_id => {
                 *state = RecordingState::Idle;
                 drop(state);
+                self.recording_session.fetch_add(1, Ordering::Relaxed);
 
-                let samples = if let Some(rec) = self.recorder.lock().unwrap().as_ref() {
+                let captured = if let Some(rec) = self.recorder.lock().unwrap().as_ref() {
                     match rec.stop() {
                         Ok(buf) => buf,
                         Err(e) => {
@@ -322,6 +618,9 @@ impl AudioRecordingManager {
                     error!("Recorder not available");
                     Vec::new()
                 };
+                // Audio capté sur le micro précédent (changement en cours de dictée)
+                let mut samples = std::mem::take(&mut *self.carried_samples.lock().unwrap());
+                samples.extend(captured);
 
                 *self.is_recording.lock().unwrap() = false;
 
@@ -360,10 +659,12 @@ impl AudioRecordingManager {
         if let RecordingState::Recording { .. } = *state {
             *state = RecordingState::Idle;
             drop(state);
+            self.recording_session.fetch_add(1, Ordering::Relaxed);
 
             if let Some(rec) = self.recorder.lock().unwrap().as_ref() {
                 let _ = rec.stop(); // Discard the result
             }
+            self.carried_samples.lock().unwrap().clear();
 
             *self.is_recording.lock().unwrap() = false;
 
@@ -6,10 +6,19 @@ use rusqlite_migration::{Migrations, M};
 use serde::{Deserialize, Serialize};
 use specta::Type;
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
 use tauri::{AppHandle, Emitter, Manager};
 
-use crate::audio_toolkit::save_wav_file;
+use crate::audio_toolkit::constants::WHISPER_SAMPLE_RATE;
+#[cfg(feature = "opus")]
+use crate::audio_toolkit::encode_ogg_opus;
+use crate::audio_toolkit::{decode_audio_bytes, encode_flac, encode_wav};
+use crate::pipeline::recipe::RecipeStepOutput;
+use crate::settings::RecordingCodec;
+use crate::stats::DictationMetrics;
+use crate::vault;
 
 /// Database migrations for transcription history.
 /// Each migration is applied in order. The library tracks which migrations
@@ -32,11 +41,145 @@ static MIGRATIONS: &[M] = &[
     M::up("ALTER TABLE transcription_history ADD COLUMN post_processed_text TEXT;"),
     M::up("ALTER TABLE transcription_history ADD COLUMN post_process_prompt TEXT;"),
     M::up("ALTER TABLE transcription_history ADD COLUMN write_mode TEXT;"),
+    M::up("ALTER TABLE transcription_history ADD COLUMN source_file TEXT;"),
+    M::up("ALTER TABLE transcription_history ADD COLUMN post_process_steps TEXT;"),
+    M::up("ALTER TABLE transcription_history ADD COLUMN metrics TEXT;"),
 ];
 
+/// Extensions des enregistrements, tous formats de stockage confondus
+const RECORDING_EXTENSIONS: [&str; 3] = ["wav", "flac", "opus"];
+
+fn is_recording_file(path: &Path) -> bool {
+    path.extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| RECORDING_EXTENSIONS.contains(&ext))
+}
+
+/// Contenu lisible par la WebView et son type MIME. WebKit ne lit pas l'Ogg
+/// Opus : ces enregistrements sont décodés en WAV, en mémoire seulement.
+fn playable_recording(data: Vec<u8>, extension: Option<&str>) -> Result<(Vec<u8>, &'static str)> {
+    match extension {
+        Some("flac") => Ok((data, "audio/flac")),
+        Some("opus") => {
+            let samples = decode_audio_bytes(data, extension)?;
+            Ok((encode_wav(&samples)?, "audio/wav"))
+        }
+        _ => Ok((data, "audio/wav")),
+    }
+}
+
+fn encode_recording(
+    samples: &[f32],
+    codec: RecordingCodec,
+    opus_bitrate_kbps: u32,
+) -> Result<Vec<u8>> {
+    match codec {
+        RecordingCodec::Wav => encode_wav(samples),
+        RecordingCodec::Flac => Ok(encode_flac(samples, WHISPER_SAMPLE_RATE)),
+        #[cfg(feature = "opus")]
+        RecordingCodec::Opus => encode_ogg_opus(samples, opus_bitrate_kbps),
+        #[cfg(not(feature = "opus"))]
+        RecordingCodec::Opus => {
+            let _ = opus_bitrate_kbps;
+            Err(anyhow::anyhow!("Opus support is not enabled in this build"))
+        }
+    }
+}
+
+/// Format des nouveaux enregistrements : FLAC si le format choisi n'est pas
+/// compilé (réglages importés d'une build avec la feature `opus`)
+fn storage_codec(codec: RecordingCodec) -> RecordingCodec {
+    if codec.is_available() {
+        codec
+    } else {
+        RecordingCodec::Flac
+    }
+}
+
+/// Entrées à supprimer, de la plus ancienne à la plus récente, pour ramener
+/// l'espace occupé sous `quota_bytes`. `entries` (id, fichier, conservée,
+/// taille) va de la plus récente à la plus ancienne ; les entrées conservées
+/// comptent dans le total mais ne sont jamais supprimées.
+fn entries_over_quota(
+    entries: &[(i64, String, bool, u64)],
+    quota_bytes: u64,
+) -> Vec<(i64, String)> {
+    let mut total: u64 = entries.iter().map(|(_, _, _, size)| size).sum();
+    let mut to_delete = Vec::new();
+    for (id, file_name, saved, size) in entries.iter().rev() {
+        if total <= quota_bytes {
+            break;
+        }
+        if !saved {
+            total -= size;
+            to_delete.push((*id, file_name.clone()));
+        }
+    }
+    to_delete
+}
+
+/// Métriques des dictées enregistrées depuis `since` (timestamp Unix)
+pub fn read_metrics(conn: &Connection, since: i64) -> Result<Vec<(i64, DictationMetrics)>> {
+    let mut stmt = conn.prepare(
+        "SELECT timestamp, metrics FROM transcription_history
+         WHERE metrics IS NOT NULL AND timestamp >= ?1 ORDER BY timestamp",
+    )?;
+    let rows = stmt.query_map([since], |row| {
+        Ok((row.get::<_, i64>(0)?, row.get::<_, String>(1)?))
+    })?;
+
+    let mut records = Vec::new();
+    for row in rows {
+        let (timestamp, json) = row?;
+        // Mesures chiffrées et coffre verrouillé : dictée ignorée
+        let Some(json) = vault::open_secret(&json) else {
+            continue;
+        };
+        match serde_json::from_str(&json) {
+            Ok(metrics) => records.push((timestamp, metrics)),
+            Err(e) => error!("Ignoring unreadable dictation metrics: {}", e),
+        }
+    }
+    Ok(records)
+}
+
+/// Dictées micro (hors imports) depuis `since`, de la plus récente à la plus ancienne
+pub fn read_recent_dictations(
+    conn: &Connection,
+    since: i64,
+    limit: usize,
+) -> Result<Vec<HistoryEntry>> {
+    let mut stmt = conn.prepare(&format!(
+        "SELECT {ENTRY_COLUMNS} FROM transcription_history
+         WHERE source_file IS NULL AND timestamp >= ?1
+         ORDER BY timestamp DESC LIMIT ?2"
+    ))?;
+    let rows = stmt.query_map(params![since, limit as i64], HistoryEntry::from_row)?;
+
+    let mut entries = Vec::new();
+    for row in rows {
+        entries.push(row?);
+    }
+    Ok(entries)
+}
+
+/// Colonnes chiffrées quand le coffre est activé (voir [`crate::vault`])
+const SEALED_COLUMNS: [&str; 6] = [
+    "transcription_text",
+    "post_processed_text",
+    "post_process_prompt",
+    "source_file",
+    "post_process_steps",
+    "metrics",
+];
+
+/// Colonnes lues par toutes les requêtes qui construisent un [`HistoryEntry`]
+const ENTRY_COLUMNS: &str = "id, file_name, timestamp, saved, title, transcription_text, post_processed_text, post_process_prompt, write_mode, source_file, post_process_steps, metrics";
+
 #[derive(Clone, Debug, Serialize, Deserialize, Type)]
 pub struct HistoryEntry {
     pub id: i64,
+    /// Enregistrement dans `recordings/` — vide pour un import, qui n'en garde pas
     pub file_name: String,
     pub timestamp: i64,
     pub saved: bool,
@@ -45,12 +188,79 @@ pub struct HistoryEntry {
     pub post_processed_text: Option<String>,
     pub post_process_prompt: Option<String>,
     pub write_mode: Option<String>,
+    /// Chemin du fichier importé (mémo vocal, vidéo) — None pour une dictée micro
+    pub source_file: Option<String>,
+    /// Sortie de chaque étape quand une recette a été appliquée (JSON en base)
+    pub post_process_steps: Vec<RecipeStepOutput>,
+    /// Mesures de la dictée (JSON en base, chiffré comme le texte) — None pour un import
+    pub metrics: Option<DictationMetrics>,
+}
+
+/// Texte en clair d'une colonne chiffrée, ou un marqueur si le coffre est verrouillé
+fn open_column(value: String) -> String {
+    vault::open_secret(&value).unwrap_or_else(|| vault::LOCKED_PLACEHOLDER.to_string())
+}
+
+fn seal_column(value: Option<String>) -> Result<Option<String>> {
+    Ok(value.map(|v| vault::seal_secret(&v)).transpose()?)
+}
+
+impl HistoryEntry {
+    fn from_row(row: &rusqlite::Row<'_>) -> rusqlite::Result<Self> {
+        Ok(Self {
+            id: row.get("id")?,
+            file_name: row.get("file_name")?,
+            timestamp: row.get("timestamp")?,
+            saved: row.get("saved")?,
+            title: row.get("title")?,
+            transcription_text: open_column(row.get("transcription_text")?),
+            post_processed_text: row
+                .get::<_, Option<String>>("post_processed_text")?
+                .map(open_column),
+            post_process_prompt: row
+                .get::<_, Option<String>>("post_process_prompt")?
+                .map(open_column),
+            write_mode: row.get("write_mode")?,
+            source_file: row
+                .get::<_, Option<String>>("source_file")?
+                .map(open_column),
+            post_process_steps: row
+                .get::<_, Option<String>>("post_process_steps")?
+                .and_then(|json| vault::open_secret(&json))
+                .and_then(|json| serde_json::from_str(&json).ok())
+                .unwrap_or_default(),
+            metrics: row
+                .get::<_, Option<String>>("metrics")?
+                .and_then(|json| vault::open_secret(&json))
+                .and_then(|json| serde_json::from_str(&json).ok()),
+        })
+    }
+}
+
+/// Dictée prête à écrire : enregistrement déjà encodé, textes en clair
+struct PendingEntry {
+    file_name: String,
+    timestamp: i64,
+    title: String,
+    recording: Vec<u8>,
+    transcription_text: String,
+    post_processed_text: Option<String>,
+    post_process_prompt: Option<String>,
+    write_mode: Option<String>,
+    source_file: Option<String>,
+    post_process_steps: Vec<RecipeStepOutput>,
+    metrics: Option<DictationMetrics>,
 }
 
 pub struct HistoryManager {
     app_handle: AppHandle,
     recordings_dir: PathBuf,
     db_path: PathBuf,
+    /// Une seule migration de format à la fois
+    migrating: AtomicBool,
+    /// Dictées reçues coffre verrouillé : gardées en mémoire (jamais sur
+    /// disque en clair) jusqu'au déverrouillage, perdues si l'app est quittée
+    pending: Mutex<Vec<PendingEntry>>,
 }
 
 impl HistoryManager {
@@ -70,6 +280,8 @@ impl HistoryManager {
             app_handle: app_handle.clone(),
             recordings_dir,
             db_path,
+            migrating: AtomicBool::new(false),
+            pending: Mutex::new(Vec::new()),
         };
 
         // Initialize database and run migrations synchronously
@@ -179,6 +391,11 @@ impl HistoryManager {
     }
 
     /// Save a transcription to history (both database and WAV file)
+    ///
+    /// `source_file` : chemin du fichier d'origine pour un import (None = micro) ;
+    /// un import ne garde que ce lien, sans copie de l'audio (`file_name` vide)
+    /// `post_process_steps` : sorties intermédiaires d'une recette (vide sinon)
+    /// `metrics` : mesures de la dictée (None pour un import)
     pub async fn save_transcription(
         &self,
         audio_samples: Vec<f32>,
@@ -186,25 +403,44 @@ impl HistoryManager {
         post_processed_text: Option<String>,
         post_process_prompt: Option<String>,
         write_mode: Option<String>,
+        source_file: Option<String>,
+        post_process_steps: Vec<RecipeStepOutput>,
+        metrics: Option<DictationMetrics>,
     ) -> Result<()> {
+        let settings = crate::settings::get_settings(&self.app_handle);
+        let codec = storage_codec(settings.recording_codec);
         let timestamp = Utc::now().timestamp();
-        let file_name = format!("handy-{}.wav", timestamp);
         let title = self.format_timestamp_title(timestamp);
 
-        // Save WAV file
-        let file_path = self.recordings_dir.join(&file_name);
-        save_wav_file(file_path, &audio_samples).await?;
-
-        // Save to database
-        self.save_to_database(
+        // Save the recording in the configured storage format
+        let (file_name, recording) = if source_file.is_some() {
+            (String::new(), Vec::new())
+        } else {
+            (
+                format!("handy-{}.{}", timestamp, codec.extension()),
+                encode_recording(&audio_samples, codec, settings.recording_opus_bitrate_kbps)?,
+            )
+        };
+        let entry = PendingEntry {
             file_name,
             timestamp,
             title,
+            recording,
             transcription_text,
             post_processed_text,
             post_process_prompt,
             write_mode,
-        )?;
+            source_file,
+            post_process_steps,
+            metrics,
+        };
+
+        if vault::is_locked() {
+            self.pending.lock().unwrap().push(entry);
+            info!("Vault locked: dictation kept in memory until unlock");
+            return Ok(());
+        }
+        self.store_entry(&entry)?;
 
         // Clean up old entries
         self.cleanup_old_entries()?;
@@ -217,20 +453,74 @@ impl HistoryManager {
         Ok(())
     }
 
-    fn save_to_database(
-        &self,
-        file_name: String,
-        timestamp: i64,
-        title: String,
-        transcription_text: String,
-        post_processed_text: Option<String>,
-        post_process_prompt: Option<String>,
-        write_mode: Option<String>,
-    ) -> Result<()> {
+    /// Écrit les dictées gardées en mémoire pendant le verrouillage ; en cas
+    /// d'échec, la dictée fautive et les suivantes restent en attente.
+    /// Renvoie le nombre de dictées écrites.
+    pub fn flush_pending(&self) -> Result<usize> {
+        let entries = std::mem::take(&mut *self.pending.lock().unwrap());
+        if entries.is_empty() {
+            return Ok(0);
+        }
+
+        let mut failure = None;
+        for (index, entry) in entries.iter().enumerate() {
+            if let Err(e) = self.store_entry(entry) {
+                failure = Some((index, e));
+                break;
+            }
+        }
+        let stored = failure.as_ref().map_or(entries.len(), |(index, _)| *index);
+        if stored > 0 {
+            info!(
+                "Stored {} dictations kept while the vault was locked",
+                stored
+            );
+            self.cleanup_old_entries()?;
+            if let Err(e) = self.app_handle.emit("history-updated", ()) {
+                error!("Failed to emit history-updated event: {}", e);
+            }
+        }
+
+        match failure {
+            Some((index, e)) => {
+                let rest: Vec<PendingEntry> = entries.into_iter().skip(index).collect();
+                self.pending.lock().unwrap().splice(0..0, rest);
+                Err(e)
+            }
+            None => Ok(stored),
+        }
+    }
+
+    /// Enregistrement (chiffré si le coffre est activé) puis ligne en base
+    fn store_entry(&self, entry: &PendingEntry) -> Result<()> {
+        if let Some(file_path) = self.recording_path(&entry.file_name) {
+            vault::write_file(&file_path, &entry.recording)?;
+            debug!("Saved recording: {:?}", file_path);
+        }
+        self.save_to_database(entry)
+    }
+
+    fn save_to_database(&self, entry: &PendingEntry) -> Result<()> {
+        let metrics_json = entry
+            .metrics
+            .as_ref()
+            .map(serde_json::to_string)
+            .transpose()?;
+        let steps_json = if entry.post_process_steps.is_empty() {
+            None
+        } else {
+            Some(serde_json::to_string(&entry.post_process_steps)?)
+        };
+        let transcription_text = vault::seal_secret(&entry.transcription_text)?;
+        let post_processed_text = seal_column(entry.post_processed_text.clone())?;
+        let post_process_prompt = seal_column(entry.post_process_prompt.clone())?;
+        let source_file = seal_column(entry.source_file.clone())?;
+        let steps_json = seal_column(steps_json)?;
+        let metrics_json = seal_column(metrics_json)?;
         let conn = self.get_connection()?;
         conn.execute(
-            "INSERT INTO transcription_history (file_name, timestamp, saved, title, transcription_text, post_processed_text, post_process_prompt, write_mode) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
-            params![file_name, timestamp, false, title, transcription_text, post_processed_text, post_process_prompt, write_mode],
+            "INSERT INTO transcription_history (file_name, timestamp, saved, title, transcription_text, post_processed_text, post_process_prompt, write_mode, source_file, post_process_steps, metrics) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11)",
+            params![entry.file_name, entry.timestamp, false, entry.title, transcription_text, post_processed_text, post_process_prompt, entry.write_mode, source_file, steps_json, metrics_json],
         )?;
 
         debug!("Saved transcription to database");
@@ -250,6 +540,10 @@ impl HistoryManager {
                 let limit = crate::settings::get_history_limit(&self.app_handle);
                 return self.cleanup_by_count(limit);
             }
+            crate::settings::RecordingRetentionPeriod::DiskQuota => {
+                let quota_mb = crate::settings::get_settings(&self.app_handle).recording_quota_mb;
+                return self.cleanup_by_quota(quota_mb as u64 * 1024 * 1024);
+            }
             _ => {
                 // Use time-based logic
                 return self.cleanup_by_time(retention_period);
@@ -273,7 +567,9 @@ impl HistoryManager {
             )?;
 
             // Delete WAV file
-            let file_path = self.recordings_dir.join(file_name);
+            let Some(file_path) = self.recording_path(file_name) else {
+                continue;
+            };
             if file_path.exists() {
                 if let Err(e) = fs::remove_file(&file_path) {
                     error!("Failed to delete WAV file {}: {}", file_name, e);
@@ -316,6 +612,44 @@ impl HistoryManager {
         Ok(())
     }
 
+    fn cleanup_by_quota(&self, quota_bytes: u64) -> Result<()> {
+        let conn = self.get_connection()?;
+
+        let mut stmt = conn.prepare(
+            "SELECT id, file_name, saved FROM transcription_history ORDER BY timestamp DESC",
+        )?;
+
+        let rows = stmt.query_map([], |row| {
+            Ok((
+                row.get::<_, i64>("id")?,
+                row.get::<_, String>("file_name")?,
+                row.get::<_, bool>("saved")?,
+            ))
+        })?;
+
+        let mut entries = Vec::new();
+        for row in rows {
+            let (id, file_name, saved) = row?;
+            let size = self
+                .recording_path(&file_name)
+                .and_then(|path| fs::metadata(path).ok())
+                .map_or(0, |m| m.len());
+            entries.push((id, file_name, saved, size));
+        }
+
+        let entries_to_delete = entries_over_quota(&entries, quota_bytes);
+        let deleted_count = self.delete_entries_and_files(&entries_to_delete)?;
+
+        if deleted_count > 0 {
+            debug!(
+                "Cleaned up {} old history entries to stay under {} bytes",
+                deleted_count, quota_bytes
+            );
+        }
+
+        Ok(())
+    }
+
     fn cleanup_by_time(
         &self,
         retention_period: crate::settings::RecordingRetentionPeriod,
@@ -359,23 +693,11 @@ impl HistoryManager {
 
     pub async fn get_history_entries(&self) -> Result<Vec<HistoryEntry>> {
         let conn = self.get_connection()?;
-        let mut stmt = conn.prepare(
-            "SELECT id, file_name, timestamp, saved, title, transcription_text, post_processed_text, post_process_prompt, write_mode FROM transcription_history ORDER BY timestamp DESC"
-        )?;
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {ENTRY_COLUMNS} FROM transcription_history ORDER BY timestamp DESC"
+        ))?;
 
-        let rows = stmt.query_map([], |row| {
-            Ok(HistoryEntry {
-                id: row.get("id")?,
-                file_name: row.get("file_name")?,
-                timestamp: row.get("timestamp")?,
-                saved: row.get("saved")?,
-                title: row.get("title")?,
-                transcription_text: row.get("transcription_text")?,
-                post_processed_text: row.get("post_processed_text")?,
-                post_process_prompt: row.get("post_process_prompt")?,
-                write_mode: row.get("write_mode")?,
-            })
-        })?;
+        let rows = stmt.query_map([], HistoryEntry::from_row)?;
 
         let mut entries = Vec::new();
         for row in rows {
@@ -391,28 +713,14 @@ impl HistoryManager {
     }
 
     fn get_latest_entry_with_conn(conn: &Connection) -> Result<Option<HistoryEntry>> {
-        let mut stmt = conn.prepare(
-            "SELECT id, file_name, timestamp, saved, title, transcription_text, post_processed_text, post_process_prompt, write_mode
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {ENTRY_COLUMNS}
              FROM transcription_history
              ORDER BY timestamp DESC
-             LIMIT 1",
-        )?;
+             LIMIT 1"
+        ))?;
 
-        let entry = stmt
-            .query_row([], |row| {
-                Ok(HistoryEntry {
-                    id: row.get("id")?,
-                    file_name: row.get("file_name")?,
-                    timestamp: row.get("timestamp")?,
-                    saved: row.get("saved")?,
-                    title: row.get("title")?,
-                    transcription_text: row.get("transcription_text")?,
-                    post_processed_text: row.get("post_processed_text")?,
-                    post_process_prompt: row.get("post_process_prompt")?,
-                    write_mode: row.get("write_mode")?,
-                })
-            })
-            .optional()?;
+        let entry = stmt.query_row([], HistoryEntry::from_row).optional()?;
 
         Ok(entry)
     }
@@ -448,28 +756,231 @@ impl HistoryManager {
         self.recordings_dir.join(file_name)
     }
 
+    /// Chemin de l'enregistrement d'une entrée ; None pour un import, qui n'en
+    /// garde pas
+    fn recording_path(&self, file_name: &str) -> Option<PathBuf> {
+        (!file_name.is_empty()).then(|| self.get_audio_file_path(file_name))
+    }
+
+    pub fn get_dictation_metrics(&self, since: i64) -> Result<Vec<(i64, DictationMetrics)>> {
+        read_metrics(&self.get_connection()?, since)
+    }
+
+    /// Voir [`read_recent_dictations`]
+    pub fn get_recent_dictations(&self, since: i64, limit: usize) -> Result<Vec<HistoryEntry>> {
+        read_recent_dictations(&self.get_connection()?, since, limit)
+    }
+
+    /// Échantillons 16 kHz d'un enregistrement, quel que soit son format de
+    /// stockage et qu'il soit chiffré ou non
+    pub fn load_recording_samples(&self, file_name: &str) -> Result<Vec<f32>> {
+        let path = self
+            .recording_path(file_name)
+            .ok_or_else(|| anyhow::anyhow!("Entry has no recording"))?;
+        let data = vault::read_file(&path)?;
+        let extension = path.extension().and_then(|ext| ext.to_str());
+        decode_audio_bytes(data, extension)
+    }
+
+    /// Enregistrement en clair pour le lecteur audio de l'historique, avec
+    /// son type MIME. Il est déchiffré (et décodé s'il est en Opus) en mémoire
+    /// et servi par le protocole `recording://` : aucune copie n'est écrite
+    /// sur le disque.
+    pub fn playable_audio(&self, file_name: &str) -> Result<(Vec<u8>, &'static str)> {
+        let path = self
+            .recording_path(file_name)
+            // Un nom de fichier seul : pas de chemin hors de `recordings/`
+            .filter(|path| {
+                is_recording_file(path)
+                    && path.file_name().and_then(|name| name.to_str()) == Some(file_name)
+            })
+            .ok_or_else(|| anyhow::anyhow!("Invalid recording name '{}'", file_name))?;
+        let data = vault::read_file(&path)?;
+        playable_recording(data, path.extension().and_then(|ext| ext.to_str()))
+    }
+
+    /// Lance en arrière-plan la migration des enregistrements WAV vers le
+    /// format de stockage choisi ; vers un format avec perte, seulement si
+    /// l'utilisateur l'a confirmé (`allow_lossy`)
+    pub fn spawn_recording_migration(self: &Arc<Self>, allow_lossy: bool) {
+        let manager = Arc::clone(self);
+        tauri::async_runtime::spawn_blocking(move || {
+            if let Err(e) = manager.migrate_recordings(allow_lossy) {
+                error!("Recording migration failed: {}", e);
+            }
+        });
+    }
+
+    /// Réencode les enregistrements WAV au format `recording_codec` et met à
+    /// jour `file_name` ; les FLAC et Opus existants ne sont jamais touchés.
+    /// Renvoie le nombre de fichiers convertis.
+    pub fn migrate_recordings(&self, allow_lossy: bool) -> Result<usize> {
+        if self.migrating.swap(true, Ordering::SeqCst) {
+            return Ok(0);
+        }
+        let result = self.migrate_recordings_to_codec(allow_lossy);
+        self.migrating.store(false, Ordering::SeqCst);
+        result
+    }
+
+    fn migrate_recordings_to_codec(&self, allow_lossy: bool) -> Result<usize> {
+        let settings = crate::settings::get_settings(&self.app_handle);
+        let codec = storage_codec(settings.recording_codec);
+        if codec == RecordingCodec::Wav {
+            return Ok(0);
+        }
+        if codec.is_lossy() && !allow_lossy {
+            debug!(
+                "WAV recordings kept: conversion to {:?} not confirmed",
+                codec
+            );
+            return Ok(0);
+        }
+        let status = vault::status();
+        if status.enabled && !status.unlocked {
+            info!("Recording migration postponed until the vault is unlocked");
+            return Ok(0);
+        }
+
+        let conn = self.get_connection()?;
+        let entries = {
+            let mut stmt = conn.prepare("SELECT id, file_name FROM transcription_history")?;
+            let rows = stmt.query_map([], |row| {
+                Ok((row.get::<_, i64>("id")?, row.get::<_, String>("file_name")?))
+            })?;
+            rows.collect::<rusqlite::Result<Vec<_>>>()?
+        };
+
+        let mut migrated = 0;
+        for (id, file_name) in entries {
+            if !file_name.ends_with(".wav") {
+                continue;
+            }
+            let old_path = self.get_audio_file_path(&file_name);
+            let target_name = Path::new(&file_name)
+                .with_extension(codec.extension())
+                .to_string_lossy()
+                .to_string();
+            if target_name == file_name || !old_path.exists() {
+                continue;
+            }
+
+            let converted = self.load_recording_samples(&file_name).and_then(|samples| {
+                encode_recording(&samples, codec, settings.recording_opus_bitrate_kbps)
+            });
+            let encoded = match converted {
+                Ok(encoded) => encoded,
+                Err(e) => {
+                    error!("Failed to convert recording {}: {}", file_name, e);
+                    continue;
+                }
+            };
+
+            vault::write_file(&self.get_audio_file_path(&target_name), &encoded)?;
+            conn.execute(
+                "UPDATE transcription_history SET file_name = ?1 WHERE id = ?2",
+                params![target_name, id],
+            )?;
+            if let Err(e) = fs::remove_file(&old_path) {
+                error!("Failed to delete migrated recording {}: {}", file_name, e);
+            }
+            migrated += 1;
+        }
+
+        if migrated > 0 {
+            info!("Migrated {} recordings to {:?}", migrated, codec);
+            if let Err(e) = self.app_handle.emit("history-updated", ()) {
+                error!("Failed to emit history-updated event: {}", e);
+            }
+        }
+        Ok(migrated)
+    }
+
+    /// Chiffre (`seal`) ou déchiffre toutes les lignes et tous les
+    /// enregistrements ; seules les valeurs qui ne sont pas déjà dans l'état
+    /// voulu sont réécrites, une migration interrompue peut donc reprendre.
+    /// Renvoie le nombre de lignes et de fichiers modifiés.
+    pub fn reseal_all(&self, seal: bool) -> Result<usize> {
+        let mut conn = self.get_connection()?;
+        let changed_rows = Self::reseal_rows(&mut conn, seal)?;
+
+        let mut changed_files = 0;
+        for dir_entry in fs::read_dir(&self.recordings_dir)? {
+            let path = dir_entry?.path();
+            if is_recording_file(&path) {
+                let was_sealed = vault::is_sealed_file(&fs::read(&path)?);
+                vault::reseal_file(&path, seal)?;
+                if was_sealed != seal {
+                    changed_files += 1;
+                }
+            }
+        }
+
+        info!(
+            "History {}: {} rows, {} recordings",
+            if seal { "encrypted" } else { "decrypted" },
+            changed_rows,
+            changed_files
+        );
+        if let Err(e) = self.app_handle.emit("history-updated", ()) {
+            error!("Failed to emit history-updated event: {}", e);
+        }
+        Ok(changed_rows + changed_files)
+    }
+
+    fn reseal_rows(conn: &mut Connection, seal: bool) -> Result<usize> {
+        let tx = conn.transaction()?;
+        let mut changed = 0;
+        {
+            let mut stmt = tx.prepare(&format!(
+                "SELECT id, {} FROM transcription_history",
+                SEALED_COLUMNS.join(", ")
+            ))?;
+            let rows = stmt
+                .query_map([], |row| {
+                    let mut values = Vec::with_capacity(SEALED_COLUMNS.len());
+                    for column in SEALED_COLUMNS {
+                        values.push(row.get::<_, Option<String>>(column)?);
+                    }
+                    Ok((row.get::<_, i64>("id")?, values))
+                })?
+                .collect::<rusqlite::Result<Vec<_>>>()?;
+
+            for (id, values) in rows {
+                let needs_change = values
+                    .iter()
+                    .flatten()
+                    .any(|value| !value.is_empty() && vault::is_sealed_secret(value) != seal);
+                if !needs_change {
+                    continue;
+                }
+                for (column, value) in SEALED_COLUMNS.iter().zip(values) {
+                    let Some(value) = value else { continue };
+                    let plain = vault::open_secret(&value).ok_or(vault::VaultError::Locked)?;
+                    let value = if seal {
+                        vault::seal_secret(&plain)?
+                    } else {
+                        plain
+                    };
+                    tx.execute(
+                        &format!("UPDATE transcription_history SET {column} = ?1 WHERE id = ?2"),
+                        params![value, id],
+                    )?;
+                }
+                changed += 1;
+            }
+        }
+        tx.commit()?;
+        Ok(changed)
+    }
+
     pub async fn get_entry_by_id(&self, id: i64) -> Result<Option<HistoryEntry>> {
         let conn = self.get_connection()?;
-        let mut stmt = conn.prepare(
-            "SELECT id, file_name, timestamp, saved, title, transcription_text, post_processed_text, post_process_prompt, write_mode
-             FROM transcription_history WHERE id = ?1",
-        )?;
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {ENTRY_COLUMNS} FROM transcription_history WHERE id = ?1"
+        ))?;
 
-        let entry = stmt
-            .query_row([id], |row| {
-                Ok(HistoryEntry {
-                    id: row.get("id")?,
-                    file_name: row.get("file_name")?,
-                    timestamp: row.get("timestamp")?,
-                    saved: row.get("saved")?,
-                    title: row.get("title")?,
-                    transcription_text: row.get("transcription_text")?,
-                    post_processed_text: row.get("post_processed_text")?,
-                    post_process_prompt: row.get("post_process_prompt")?,
-                    write_mode: row.get("write_mode")?,
-                })
-            })
-            .optional()?;
+        let entry = stmt.query_row([id], HistoryEntry::from_row).optional()?;
 
         Ok(entry)
     }
@@ -480,11 +991,12 @@ impl HistoryManager {
         // Get the entry to find the file name
         if let Some(entry) = self.get_entry_by_id(id).await? {
             // Delete the audio file first
-            let file_path = self.get_audio_file_path(&entry.file_name);
-            if file_path.exists() {
-                if let Err(e) = fs::remove_file(&file_path) {
-                    error!("Failed to delete audio file {}: {}", entry.file_name, e);
-                    // Continue with database deletion even if file deletion fails
+            if let Some(file_path) = self.recording_path(&entry.file_name) {
+                if file_path.exists() {
+                    if let Err(e) = fs::remove_file(&file_path) {
+                        error!("Failed to delete audio file {}: {}", entry.file_name, e);
+                        // Continue with database deletion even if file deletion fails
+                    }
                 }
             }
         }
@@ -533,7 +1045,10 @@ mod tests {
                 transcription_text TEXT NOT NULL,
                 post_processed_text TEXT,
                 post_process_prompt TEXT,
-                write_mode TEXT
+                write_mode TEXT,
+                source_file TEXT,
+                post_process_steps TEXT,
+                metrics TEXT
             );",
         )
         .expect("create transcription_history table");
@@ -568,6 +1083,184 @@ mod tests {
         .expect("insert history entry");
     }
 
+    #[test]
+    fn imported_entry_keeps_source_file() {
+        let conn = setup_conn();
+        conn.execute(
+            "INSERT INTO transcription_history (file_name, timestamp, saved, title, transcription_text, source_file)
+             VALUES ('handy-100.wav', 100, 0, 'Recording 100', 'mémo importé', '/tmp/memo.m4a')",
+            [],
+        )
+        .expect("insert imported entry");
+
+        let entry = HistoryManager::get_latest_entry_with_conn(&conn)
+            .expect("fetch entry")
+            .expect("entry exists");
+
+        assert_eq!(entry.source_file.as_deref(), Some("/tmp/memo.m4a"));
+    }
+
+    #[test]
+    fn recipe_steps_round_trip() {
+        let conn = setup_conn();
+        let steps = vec![
+            RecipeStepOutput {
+                kind: "rules".to_string(),
+                detail: None,
+                output: "Bonjour.".to_string(),
+            },
+            RecipeStepOutput {
+                kind: "llm".to_string(),
+                detail: Some("openai · gpt-4o-mini".to_string()),
+                output: "Hello.".to_string(),
+            },
+        ];
+        conn.execute(
+            "INSERT INTO transcription_history (file_name, timestamp, saved, title, transcription_text, post_process_steps)
+             VALUES ('handy-100.wav', 100, 0, 'Recording 100', 'bonjour', ?1)",
+            params![serde_json::to_string(&steps).unwrap()],
+        )
+        .expect("insert recipe entry");
+        insert_entry(&conn, 50, "older", None);
+
+        let entry = HistoryManager::get_latest_entry_with_conn(&conn)
+            .expect("fetch entry")
+            .expect("entry exists");
+        assert_eq!(entry.post_process_steps, steps);
+    }
+
+    #[test]
+    fn entry_without_recipe_has_no_steps() {
+        let conn = setup_conn();
+        insert_entry(&conn, 100, "plain", None);
+
+        let entry = HistoryManager::get_latest_entry_with_conn(&conn)
+            .expect("fetch entry")
+            .expect("entry exists");
+        assert!(entry.post_process_steps.is_empty());
+    }
+
+    #[test]
+    fn sealed_columns_are_masked_while_vault_is_locked() {
+        let conn = setup_conn();
+        conn.execute(
+            "INSERT INTO transcription_history (file_name, timestamp, saved, title, transcription_text, post_processed_text, post_process_steps)
+             VALUES ('handy-100.wav', 100, 0, 'Recording 100', 'enc:v1:AAAA', 'en clair', 'enc:v1:BBBB')",
+            [],
+        )
+        .expect("insert sealed entry");
+
+        let entry = HistoryManager::get_latest_entry_with_conn(&conn)
+            .expect("fetch entry")
+            .expect("entry exists");
+
+        assert_eq!(entry.transcription_text, vault::LOCKED_PLACEHOLDER);
+        assert_eq!(entry.post_processed_text.as_deref(), Some("en clair"));
+        assert!(entry.post_process_steps.is_empty());
+    }
+
+    #[test]
+    fn quota_deletes_oldest_unsaved_entries_first() {
+        let mb = 1024 * 1024;
+        // Du plus récent au plus ancien
+        let entries = vec![
+            (4, "handy-4.flac".to_string(), false, 2 * mb),
+            (3, "handy-3.flac".to_string(), true, 2 * mb),
+            (2, "handy-2.flac".to_string(), false, 2 * mb),
+            (1, "handy-1.wav".to_string(), false, 2 * mb),
+        ];
+
+        assert!(entries_over_quota(&entries, 8 * mb).is_empty());
+        assert_eq!(
+            entries_over_quota(&entries, 5 * mb),
+            vec![
+                (1, "handy-1.wav".to_string()),
+                (2, "handy-2.flac".to_string())
+            ]
+        );
+        // L'entrée conservée n'est jamais supprimée, même au-delà du quota
+        assert_eq!(entries_over_quota(&entries, 0).len(), 3);
+    }
+
+    #[test]
+    fn read_metrics_keeps_entries_since_cutoff_with_metrics() {
+        let conn = setup_conn();
+        insert_entry(&conn, 100, "import", None);
+        let metrics = DictationMetrics {
+            audio_ms: 3_000,
+            stt_ms: 250,
+            rules_ms: 1,
+            llm_ms: 0,
+            paste_ms: Some(20),
+            routing: crate::stats::Routing::RulesOnly,
+            llm_fallback: false,
+            model_id: Some("small".to_string()),
+            write_mode: "chat".to_string(),
+            confidence: 0.9,
+            word_count: 8,
+            hallucinations: Vec::new(),
+            target_app: None,
+        };
+        for timestamp in [50, 200] {
+            conn.execute(
+                "INSERT INTO transcription_history (file_name, timestamp, title, transcription_text, metrics)
+                 VALUES ('handy.flac', ?1, 't', 'texte', ?2)",
+                params![timestamp, serde_json::to_string(&metrics).unwrap()],
+            )
+            .expect("insert entry with metrics");
+        }
+
+        let records = read_metrics(&conn, 100).expect("read metrics");
+        assert_eq!(records, vec![(200, metrics)]);
+    }
+
+    #[test]
+    fn sealed_metrics_are_skipped_while_vault_is_locked() {
+        let conn = setup_conn();
+        conn.execute(
+            "INSERT INTO transcription_history (file_name, timestamp, title, transcription_text, metrics)
+             VALUES ('handy-100.wav', 100, 't', 'enc:v1:AAAA', 'enc:v1:BBBB')",
+            [],
+        )
+        .expect("insert sealed entry");
+
+        assert!(read_metrics(&conn, 0).expect("read metrics").is_empty());
+        let entry = HistoryManager::get_latest_entry_with_conn(&conn)
+            .expect("fetch entry")
+            .expect("entry exists");
+        assert!(entry.metrics.is_none());
+    }
+
+    #[test]
+    fn recent_dictations_are_newest_first_without_imports() {
+        let conn = setup_conn();
+        insert_entry(&conn, 50, "trop ancienne", None);
+        insert_entry(&conn, 100, "première", None);
+        insert_entry(&conn, 300, "deuxième", Some("Deuxième."));
+        conn.execute(
+            "INSERT INTO transcription_history (file_name, timestamp, title, transcription_text, source_file)
+             VALUES ('handy-200.wav', 200, 't', 'mémo importé', '/tmp/memo.m4a')",
+            [],
+        )
+        .expect("insert imported entry");
+
+        let entries = read_recent_dictations(&conn, 100, 10).expect("read recent dictations");
+        let texts: Vec<_> = entries
+            .iter()
+            .map(|e| e.transcription_text.as_str())
+            .collect();
+        assert_eq!(texts, vec!["deuxième", "première"]);
+        assert_eq!(read_recent_dictations(&conn, 0, 1).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn recording_files_are_recognized_by_extension() {
+        assert!(is_recording_file(Path::new("/r/handy-1.wav")));
+        assert!(is_recording_file(Path::new("/r/handy-1.flac")));
+        assert!(is_recording_file(Path::new("/r/handy-1.opus")));
+        assert!(!is_recording_file(Path::new("/r/handy-1.tmp")));
+    }
+
     #[test]
     fn get_latest_entry_returns_none_when_empty() {
         let conn = setup_conn();
@@ -621,23 +1314,11 @@ mod tests {
         insert_entry_with_mode(&conn, 200, "code input", None, Some("code"));
 
         let mut stmt = conn.prepare(
-            "SELECT id, file_name, timestamp, saved, title, transcription_text, post_processed_text, post_process_prompt, write_mode
+            "SELECT id, file_name, timestamp, saved, title, transcription_text, post_processed_text, post_process_prompt, write_mode, source_file, post_process_steps, metrics
              FROM transcription_history ORDER BY timestamp DESC"
         ).expect("prepare stmt");
 
-        let entries: Vec<HistoryEntry> = stmt.query_map([], |row| {
-            Ok(HistoryEntry {
-                id: row.get("id")?,
-                file_name: row.get("file_name")?,
-                timestamp: row.get("timestamp")?,
-                saved: row.get("saved")?,
-                title: row.get("title")?,
-                transcription_text: row.get("transcription_text")?,
-                post_processed_text: row.get("post_processed_text")?,
-                post_process_prompt: row.get("post_process_prompt")?,
-                write_mode: row.get("write_mode")?,
-            })
-        }).expect("query_map")
+        let entries: Vec<HistoryEntry> = stmt.query_map([], HistoryEntry::from_row).expect("query_map")
           .collect::<Result<Vec<_>, _>>()
           .expect("collect entries");
 
@@ -668,23 +1349,11 @@ mod tests {
         ).expect("get id");
 
         let mut stmt = conn.prepare(
-            "SELECT id, file_name, timestamp, saved, title, transcription_text, post_processed_text, post_process_prompt, write_mode
+            "SELECT id, file_name, timestamp, saved, title, transcription_text, post_processed_text, post_process_prompt, write_mode, source_file, post_process_steps, metrics
              FROM transcription_history WHERE id = ?1"
         ).expect("prepare");
 
-        let entry = stmt.query_row([id], |row| {
-            Ok(HistoryEntry {
-                id: row.get("id")?,
-                file_name: row.get("file_name")?,
-                timestamp: row.get("timestamp")?,
-                saved: row.get("saved")?,
-                title: row.get("title")?,
-                transcription_text: row.get("transcription_text")?,
-                post_processed_text: row.get("post_processed_text")?,
-                post_process_prompt: row.get("post_process_prompt")?,
-                write_mode: row.get("write_mode")?,
-            })
-        }).expect("query entry");
+        let entry = stmt.query_row([id], HistoryEntry::from_row).expect("query entry");
 
         assert_eq!(entry.transcription_text, "test text");
         assert_eq!(entry.post_processed_text.as_deref(), Some("clean text"));
@@ -699,23 +1368,11 @@ mod tests {
         insert_entry(&conn, 200, "second", None);
 
         let mut stmt = conn.prepare(
-            "SELECT id, file_name, timestamp, saved, title, transcription_text, post_processed_text, post_process_prompt, write_mode
+            "SELECT id, file_name, timestamp, saved, title, transcription_text, post_processed_text, post_process_prompt, write_mode, source_file, post_process_steps, metrics
              FROM transcription_history ORDER BY timestamp DESC"
         ).expect("prepare");
 
-        let entries: Vec<HistoryEntry> = stmt.query_map([], |row| {
-            Ok(HistoryEntry {
-                id: row.get("id")?,
-                file_name: row.get("file_name")?,
-                timestamp: row.get("timestamp")?,
-                saved: row.get("saved")?,
-                title: row.get("title")?,
-                transcription_text: row.get("transcription_text")?,
-                post_processed_text: row.get("post_processed_text")?,
-                post_process_prompt: row.get("post_process_prompt")?,
-                write_mode: row.get("write_mode")?,
-            })
-        }).expect("query_map")
+        let entries: Vec<HistoryEntry> = stmt.query_map([], HistoryEntry::from_row).expect("query_map")
           .collect::<Result<Vec<_>, _>>()
           .expect("collect");
 
@@ -784,23 +1441,11 @@ mod tests {
         insert_entry(&conn, 400, "old entry no mode", None); // legacy entry without write_mode
 
         let mut stmt = conn.prepare(
-            "SELECT id, file_name, timestamp, saved, title, transcription_text, post_processed_text, post_process_prompt, write_mode
+            "SELECT id, file_name, timestamp, saved, title, transcription_text, post_processed_text, post_process_prompt, write_mode, source_file, post_process_steps, metrics
              FROM transcription_history ORDER BY timestamp DESC"
         ).expect("prepare");
 
-        let entries: Vec<HistoryEntry> = stmt.query_map([], |row| {
-            Ok(HistoryEntry {
-                id: row.get("id")?,
-                file_name: row.get("file_name")?,
-                timestamp: row.get("timestamp")?,
-                saved: row.get("saved")?,
-                title: row.get("title")?,
-                transcription_text: row.get("transcription_text")?,
-                post_processed_text: row.get("post_processed_text")?,
-                post_process_prompt: row.get("post_process_prompt")?,
-                write_mode: row.get("write_mode")?,
-            })
-        }).expect("query")
+        let entries: Vec<HistoryEntry> = stmt.query_map([], HistoryEntry::from_row).expect("query")
           .collect::<Result<Vec<_>, _>>()
           .expect("collect");
 
@@ -810,4 +1455,20 @@ mod tests {
         assert_eq!(entries[2].write_mode.as_deref(), Some("pro"));
         assert_eq!(entries[3].write_mode.as_deref(), Some("chat"));
     }
+
+    #[cfg(feature = "opus")]
+    #[test]
+    fn opus_recordings_are_served_as_wav() {
+        let samples = vec![0.0f32; WHISPER_SAMPLE_RATE as usize];
+        let opus = encode_ogg_opus(&samples, 24).expect("encode opus");
+
+        let (data, mime) = playable_recording(opus, Some("opus")).expect("playable");
+        assert_eq!(mime, "audio/wav");
+        assert_eq!(&data[..4], b"RIFF");
+
+        let flac = encode_flac(&samples, WHISPER_SAMPLE_RATE);
+        let (data, mime) = playable_recording(flac.clone(), Some("flac")).expect("playable");
+        assert_eq!(mime, "audio/flac");
+        assert_eq!(data, flac);
+    }
 }
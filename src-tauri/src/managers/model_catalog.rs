@@ -0,0 +1,467 @@
+//! Catalogue des modèles Whisper — `model_catalog.json`
+//!
+//! Le catalogue livré avec l'application (`resources/model_catalog.json`,
+//! embarqué dans le binaire) décrit les modèles téléchargeables. Un fichier du
+//! même nom dans le dossier de données le complète, par exemple pour une équipe
+//! sans accès à Internet qui héberge les modèles sur un miroir interne :
+//!
+//! ```json
+//! {
+//!   "mirror_base_url": "https://models.intranet.example/whisper",
+//!   "replace_builtin": false,
+//!   "models": [
+//!     {
+//!       "id": "large-v3-turbo-q5",
+//!       "name": "Whisper large-v3-turbo Q5",
+//!       "description": "Version validée par l'équipe",
+//!       "filename": "ggml-large-v3-turbo-q5_0.bin",
+//!       "size_mb": 805,
+//!       "sha256": "…",
+//!       "languages": ["fr", "en"],
+//!       "accuracy_score": 0.95,
+//!       "speed_score": 0.75,
+//!       "recommended": true
+//!     }
+//!   ]
+//! }
+//! ```
+//!
+//! Une entrée du fichier remplace l'entrée intégrée de même `id` ;
+//! `replace_builtin` retire toutes les entrées intégrées. Avec
+//! `mirror_base_url`, les modèles intégrés et les entrées sans `url` sont
+//! téléchargés depuis `<mirror_base_url>/<filename>`. Sans `languages`, un
+//! modèle couvre toutes les langues de Whisper. Les entrées invalides sont
+//! ignorées avec un avertissement ; un fichier illisible laisse le catalogue
+//! intégré en place. Les `sha256` du catalogue intégré sont renseignés par
+//! `scripts/model-checksums.sh`.
+
+use log::{info, warn};
+use serde::Deserialize;
+use sha2::{Digest, Sha256};
+use std::collections::HashSet;
+use std::fs::{self, File};
+use std::io::{self, Read};
+use std::path::Path;
+
+use crate::managers::model::{EngineType, ModelInfo};
+
+/// Nom du fichier de catalogue dans le dossier de données
+pub const CATALOG_FILE: &str = "model_catalog.json";
+
+const BUILTIN_CATALOG: &str = include_str!("../../resources/model_catalog.json");
+
+/// Langues du tokenizer Whisper (99), avec les variantes zh-Hans et zh-Hant
+/// utilisées par le frontend
+pub const WHISPER_LANGUAGES: &[&str] = &[
+    "en", "zh", "zh-Hans", "zh-Hant", "de", "es", "ru", "ko", "fr", "ja", "pt", "tr", "pl", "ca",
+    "nl", "ar", "sv", "it", "id", "hi", "fi", "vi", "he", "uk", "el", "ms", "cs", "ro", "da", "hu",
+    "ta", "no", "th", "ur", "hr", "bg", "lt", "la", "mi", "ml", "cy", "sk", "te", "fa", "lv", "bn",
+    "sr", "az", "sl", "kn", "et", "mk", "br", "eu", "is", "hy", "ne", "mn", "bs", "kk", "sq", "sw",
+    "gl", "mr", "pa", "si", "km", "sn", "yo", "so", "af", "oc", "ka", "be", "tg", "sd", "gu", "am",
+    "yi", "lo", "uz", "fo", "ht", "ps", "tk", "nn", "mt", "sa", "lb", "my", "bo", "tl", "mg", "as",
+    "tt", "haw", "ln", "ha", "ba", "jw", "su", "yue",
+];
+
+/// Contenu d'un fichier de catalogue
+#[derive(Deserialize, Debug, Default)]
+struct CatalogFile {
+    /// Base des URL de téléchargement : `<mirror_base_url>/<filename>`
+    #[serde(default)]
+    mirror_base_url: Option<String>,
+    /// Retire les modèles intégrés au lieu de les compléter
+    #[serde(default)]
+    replace_builtin: bool,
+    #[serde(default)]
+    models: Vec<CatalogEntry>,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+struct CatalogEntry {
+    id: String,
+    name: String,
+    #[serde(default)]
+    description: String,
+    filename: String,
+    #[serde(default)]
+    url: Option<String>,
+    /// Empreinte SHA-256 (hexadécimal) vérifiée après téléchargement
+    #[serde(default)]
+    sha256: Option<String>,
+    size_mb: u64,
+    /// Codes de langue ; absent = toutes les langues de Whisper
+    #[serde(default)]
+    languages: Option<Vec<String>>,
+    accuracy_score: f32,
+    speed_score: f32,
+    #[serde(default)]
+    supports_translation: bool,
+    #[serde(default)]
+    recommended: bool,
+}
+
+fn parse(json: &str) -> Result<CatalogFile, String> {
+    let catalog: CatalogFile =
+        serde_json::from_str(json).map_err(|e| format!("Invalid model catalog: {}", e))?;
+    if let Some(mirror) = &catalog.mirror_base_url {
+        check_url(mirror).map_err(|e| format!("mirror_base_url: {}", e))?;
+    }
+    Ok(catalog)
+}
+
+fn check_url(url: &str) -> Result<(), String> {
+    if url.starts_with("https://") || url.starts_with("http://") {
+        Ok(())
+    } else {
+        Err(format!("'{}' is not an http(s) URL", url))
+    }
+}
+
+/// Vérifie une entrée et normalise son empreinte en minuscules
+fn validate(entry: &mut CatalogEntry) -> Result<(), String> {
+    let id_ok = !entry.id.is_empty()
+        && entry
+            .id
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'));
+    if !id_ok {
+        return Err(format!("invalid id '{}'", entry.id));
+    }
+    if entry.name.trim().is_empty() {
+        return Err("missing name".to_string());
+    }
+    let filename = &entry.filename;
+    if filename.starts_with('.')
+        || filename.contains(['/', '\\'])
+        || !filename.ends_with(".bin")
+        || filename.len() <= ".bin".len()
+    {
+        return Err(format!("invalid filename '{}'", filename));
+    }
+    if entry.size_mb == 0 {
+        return Err("size_mb must be positive".to_string());
+    }
+    for (field, score) in [
+        ("accuracy_score", entry.accuracy_score),
+        ("speed_score", entry.speed_score),
+    ] {
+        if !(0.0..=1.0).contains(&score) {
+            return Err(format!("{} must be between 0 and 1", field));
+        }
+    }
+    if let Some(url) = &entry.url {
+        check_url(url)?;
+    }
+    if let Some(sha256) = &mut entry.sha256 {
+        if sha256.len() != 64 || !sha256.chars().all(|c| c.is_ascii_hexdigit()) {
+            return Err("sha256 must be 64 hexadecimal characters".to_string());
+        }
+        sha256.make_ascii_lowercase();
+    }
+    if let Some(languages) = &entry.languages {
+        if languages.is_empty() {
+            return Err("languages cannot be empty".to_string());
+        }
+        if let Some(unknown) = languages
+            .iter()
+            .find(|code| !WHISPER_LANGUAGES.contains(&code.as_str()))
+        {
+            return Err(format!("unknown language '{}'", unknown));
+        }
+    }
+    Ok(())
+}
+
+/// Entrées valides de `catalog`, sans doublons d'`id` ni de `filename`
+fn valid_entries(
+    catalog: CatalogFile,
+    source: &str,
+    warnings: &mut Vec<String>,
+) -> Vec<CatalogEntry> {
+    let mirror = catalog
+        .mirror_base_url
+        .as_deref()
+        .map(|url| url.trim_end_matches('/'));
+    let mut ids = HashSet::new();
+    let mut filenames = HashSet::new();
+    let mut entries = Vec::new();
+
+    for mut entry in catalog.models {
+        if let Err(e) = validate(&mut entry) {
+            warnings.push(format!("{}: model '{}' ignored: {}", source, entry.id, e));
+            continue;
+        }
+        if !ids.insert(entry.id.clone()) || !filenames.insert(entry.filename.clone()) {
+            warnings.push(format!(
+                "{}: model '{}' ignored: duplicate id or filename",
+                source, entry.id
+            ));
+            continue;
+        }
+        if entry.url.is_none() {
+            entry.url = mirror.map(|mirror| format!("{}/{}", mirror, entry.filename));
+        }
+        entries.push(entry);
+    }
+    entries
+}
+
+/// Catalogue intégré fusionné avec `custom` (contenu du fichier du dossier de
+/// données), plus les avertissements sur les entrées ignorées
+fn merge(builtin: CatalogFile, custom: Option<CatalogFile>) -> (Vec<ModelInfo>, Vec<String>) {
+    let mut warnings = Vec::new();
+    let custom = custom.unwrap_or_default();
+
+    let mut entries = if custom.replace_builtin {
+        Vec::new()
+    } else {
+        let mut builtin = valid_entries(builtin, "built-in catalog", &mut warnings);
+        if let Some(mirror) = &custom.mirror_base_url {
+            let mirror = mirror.trim_end_matches('/');
+            for entry in &mut builtin {
+                entry.url = Some(format!("{}/{}", mirror, entry.filename));
+            }
+        }
+        builtin
+    };
+
+    let overrides = valid_entries(custom, CATALOG_FILE, &mut warnings);
+    if overrides.iter().any(|entry| entry.recommended) {
+        for entry in &mut entries {
+            entry.recommended = false;
+        }
+    }
+    for entry in overrides {
+        let clash = entries
+            .iter()
+            .any(|other| other.id != entry.id && other.filename == entry.filename);
+        if clash {
+            warnings.push(format!(
+                "{}: model '{}' ignored: filename '{}' is already in the catalog",
+                CATALOG_FILE, entry.id, entry.filename
+            ));
+            continue;
+        }
+        match entries.iter_mut().find(|other| other.id == entry.id) {
+            Some(existing) => *existing = entry,
+            None => entries.push(entry),
+        }
+    }
+
+    // Un seul modèle recommandé : le premier du catalogue
+    let mut recommended = false;
+    let models = entries
+        .into_iter()
+        .map(|entry| {
+            let is_recommended = entry.recommended && !recommended;
+            recommended |= is_recommended;
+            to_model_info(entry, is_recommended)
+        })
+        .collect();
+    (models, warnings)
+}
+
+fn to_model_info(entry: CatalogEntry, is_recommended: bool) -> ModelInfo {
+    let supported_languages = entry.languages.unwrap_or_else(|| {
+        WHISPER_LANGUAGES
+            .iter()
+            .map(|code| code.to_string())
+            .collect()
+    });
+    ModelInfo {
+        id: entry.id,
+        name: entry.name,
+        description: entry.description,
+        filename: entry.filename,
+        url: entry.url,
+        sha256: entry.sha256,
+        size_mb: entry.size_mb,
+        is_downloaded: false,
+        is_downloading: false,
+        partial_size: 0,
+        is_directory: false,
+        engine_type: EngineType::Whisper,
+        accuracy_score: entry.accuracy_score,
+        speed_score: entry.speed_score,
+        supports_translation: entry.supports_translation,
+        is_recommended,
+        supported_languages,
+        is_custom: false,
+    }
+}
+
+/// Modèles du catalogue intégré, complété par `<app_data_dir>/model_catalog.json`
+/// s'il existe
+pub fn load(app_data_dir: &Path) -> Vec<ModelInfo> {
+    let builtin = parse(BUILTIN_CATALOG).expect("built-in model catalog is valid");
+    let path = app_data_dir.join(CATALOG_FILE);
+    let custom = match fs::read_to_string(&path) {
+        Ok(json) => match parse(&json) {
+            Ok(catalog) => {
+                info!("Using model catalog overrides from {:?}", path);
+                Some(catalog)
+            }
+            Err(e) => {
+                warn!("Ignoring {:?}: {}", path, e);
+                None
+            }
+        },
+        Err(e) if e.kind() == io::ErrorKind::NotFound => None,
+        Err(e) => {
+            warn!("Failed to read {:?}: {}", path, e);
+            None
+        }
+    };
+
+    let (models, warnings) = merge(builtin, custom);
+    for warning in warnings {
+        warn!("{}", warning);
+    }
+    models
+}
+
+/// Empreinte SHA-256 (hexadécimal minuscule) du fichier `path`
+pub fn file_sha256(path: &Path) -> io::Result<String> {
+    let mut file = File::open(path)?;
+    let mut hasher = Sha256::new();
+    let mut buffer = vec![0u8; 1024 * 1024];
+    loop {
+        let read = file.read(&mut buffer)?;
+        if read == 0 {
+            break;
+        }
+        hasher.update(&buffer[..read]);
+    }
+    Ok(format!("{:x}", hasher.finalize()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn catalog(json: &str) -> CatalogFile {
+        parse(json).expect("valid catalog")
+    }
+
+    fn builtin() -> CatalogFile {
+        catalog(BUILTIN_CATALOG)
+    }
+
+    fn model<'a>(models: &'a [ModelInfo], id: &str) -> &'a ModelInfo {
+        models.iter().find(|m| m.id == id).expect(id)
+    }
+
+    #[test]
+    fn builtin_catalog_is_valid() {
+        let (models, warnings) = merge(builtin(), None);
+        assert!(warnings.is_empty(), "{:?}", warnings);
+        assert_eq!(models.len(), 7);
+        let recommended: Vec<_> = models.iter().filter(|m| m.is_recommended).collect();
+        assert_eq!(recommended.len(), 1);
+        assert_eq!(recommended[0].id, "large-v3-turbo-q5");
+        assert_eq!(
+            model(&models, "small").supported_languages.len(),
+            WHISPER_LANGUAGES.len()
+        );
+    }
+
+    // Sans sha256, un modèle intégré — y compris servi par un miroir — est
+    // installé sans vérification. `scripts/model-checksums.sh` renseigne les
+    // empreintes ; il demande un accès aux URL du catalogue.
+    #[test]
+    fn builtin_models_have_checksums() {
+        let (models, _) = merge(builtin(), None);
+        let missing: Vec<_> = models
+            .iter()
+            .filter(|m| m.sha256.is_none())
+            .map(|m| m.id.as_str())
+            .collect();
+        assert!(
+            missing.is_empty(),
+            "no sha256 for {:?}, run scripts/model-checksums.sh",
+            missing
+        );
+    }
+
+    #[test]
+    fn mirror_rewrites_builtin_urls_and_overrides_replace_by_id() {
+        let sha = "AB".repeat(32);
+        let custom = catalog(&format!(
+            r#"{{"mirror_base_url":"https://mirror.local/models/","models":[
+                {{"id":"small","name":"Small (team)","filename":"ggml-small.bin","size_mb":487,
+                  "sha256":"{}","languages":["fr","en"],"accuracy_score":0.6,"speed_score":0.85}},
+                {{"id":"fr-medical","name":"Medical FR","filename":"fr-medical.bin","size_mb":900,
+                  "url":"https://other.local/fr-medical.bin","accuracy_score":0.9,
+                  "speed_score":0.5,"recommended":true}}
+            ]}}"#,
+            sha
+        ));
+        let (models, warnings) = merge(builtin(), Some(custom));
+        assert!(warnings.is_empty(), "{:?}", warnings);
+        assert_eq!(models.len(), 8);
+
+        let small = model(&models, "small");
+        assert_eq!(small.name, "Small (team)");
+        assert_eq!(small.sha256.as_deref(), Some("ab".repeat(32).as_str()));
+        assert_eq!(small.supported_languages, vec!["fr", "en"]);
+        assert_eq!(
+            small.url.as_deref(),
+            Some("https://mirror.local/models/ggml-small.bin")
+        );
+        assert_eq!(
+            model(&models, "large").url.as_deref(),
+            Some("https://mirror.local/models/ggml-large-v3-q5_0.bin")
+        );
+
+        let medical = model(&models, "fr-medical");
+        assert_eq!(
+            medical.url.as_deref(),
+            Some("https://other.local/fr-medical.bin")
+        );
+        assert!(medical.is_recommended);
+        assert!(!model(&models, "large-v3-turbo-q5").is_recommended);
+    }
+
+    #[test]
+    fn invalid_entries_are_skipped() {
+        let custom = catalog(
+            r#"{"replace_builtin":true,"models":[
+                {"id":"ok","name":"Ok","filename":"ok.bin","size_mb":1,"accuracy_score":0.5,"speed_score":0.5},
+                {"id":"bad score","name":"x","filename":"x.bin","size_mb":1,"accuracy_score":0.5,"speed_score":0.5},
+                {"id":"score","name":"x","filename":"y.bin","size_mb":1,"accuracy_score":1.5,"speed_score":0.5},
+                {"id":"path","name":"x","filename":"../z.bin","size_mb":1,"accuracy_score":0.5,"speed_score":0.5},
+                {"id":"sha","name":"x","filename":"s.bin","size_mb":1,"sha256":"1234","accuracy_score":0.5,"speed_score":0.5},
+                {"id":"lang","name":"x","filename":"l.bin","size_mb":1,"languages":["xx"],"accuracy_score":0.5,"speed_score":0.5},
+                {"id":"url","name":"x","filename":"u.bin","size_mb":1,"url":"ftp://host/u.bin","accuracy_score":0.5,"speed_score":0.5},
+                {"id":"ok","name":"Again","filename":"again.bin","size_mb":1,"accuracy_score":0.5,"speed_score":0.5}
+            ]}"#,
+        );
+        let (models, warnings) = merge(builtin(), Some(custom));
+        assert_eq!(models.len(), 1);
+        assert_eq!(models[0].id, "ok");
+        assert_eq!(models[0].url, None);
+        assert_eq!(warnings.len(), 7, "{:?}", warnings);
+    }
+
+    #[test]
+    fn override_cannot_reuse_another_models_filename() {
+        let custom = catalog(
+            r#"{"models":[{"id":"copy","name":"Copy","filename":"ggml-small.bin","size_mb":1,
+                "accuracy_score":0.5,"speed_score":0.5}]}"#,
+        );
+        let (models, warnings) = merge(builtin(), Some(custom));
+        assert_eq!(models.len(), 7);
+        assert_eq!(warnings.len(), 1);
+        assert!(parse(r#"{"mirror_base_url":"mirror.local","models":[]}"#).is_err());
+    }
+
+    #[test]
+    fn file_sha256_matches_known_digest() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("abc.bin");
+        fs::write(&path, b"abc").unwrap();
+        assert_eq!(
+            file_sha256(&path).unwrap(),
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+    }
+}
@@ -3,13 +3,19 @@
 /// Note architecture : ce module utilise transcribe-rs/WhisperEngine comme base.
 /// En Task 3-5, il sera migré vers whisper.cpp FFI direct (CoreML encoder + Metal decoder)
 /// pour accéder à l'ANE et réduire la latence de ~450ms → ~200ms sur l'encodeur.
+use crate::audio_toolkit::chunking::{
+    context_tail, split_into_chunks, stitch, ChunkConfig, MAX_STITCH_WORDS,
+};
 use crate::audio_toolkit::{apply_custom_words, filter_transcription_output};
 use crate::managers::model::ModelManager;
-use crate::settings::{get_settings, ModelUnloadTimeout};
+use crate::pipeline::hallucination::{self, HallucinationDecision};
+use crate::settings::{get_settings, AppSettings, ModelUnloadTimeout};
+use crate::whisper_ffi::WhisperParams;
 use anyhow::Result;
 use log::{debug, error, info, warn};
 use serde::Serialize;
 use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::path::Path;
 use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
 use std::sync::{Arc, Condvar, Mutex, MutexGuard};
 use std::thread;
@@ -20,6 +26,9 @@ use transcribe_rs::{
     TranscriptionEngine,
 };
 
+/// Nombre de mots du segment précédent passés en prompt initial au suivant
+const CONTEXT_PROMPT_WORDS: usize = 32;
+
 #[derive(Clone, Debug, Serialize)]
 pub struct ModelStateEvent {
     pub event_type: String,
@@ -28,6 +37,14 @@ pub struct ModelStateEvent {
     pub error: Option<String>,
 }
 
+/// Progression d'une transcription découpée en segments (dictées longues)
+#[derive(Clone, Debug, Serialize)]
+pub struct TranscriptionProgress {
+    pub chunk: usize,
+    pub total_chunks: usize,
+    pub percentage: f64,
+}
+
 /// Résultat de transcription enrichi avec métadonnées pour le pipeline hybride
 #[derive(Clone, Debug)]
 pub struct TranscriptionOutput {
@@ -36,6 +53,8 @@ pub struct TranscriptionOutput {
     /// pour router vers règles seules ou LLM conditionnel
     pub confidence: f32,
     pub duration_ms: u64,
+    /// Hallucinations écartées de la sortie brute de Whisper
+    pub hallucinations: Vec<HallucinationDecision>,
 }
 
 enum LoadedEngine {
@@ -43,6 +62,7 @@ enum LoadedEngine {
     Whisper(WhisperEngine),
     /// whisper.cpp FFI natif (activé quand whisper_native cfg = true)
     /// Offre CoreML encoder (ANE) + Metal decoder — latence ~2-3x inférieure
+    /// Sur Linux : backend CPU, avec le vrai `no_speech_prob` au lieu de l'heuristique
     #[cfg(whisper_native)]
     WhisperFfi(crate::whisper_ffi::WhisperContext),
 }
@@ -141,6 +161,10 @@ impl TranscriptionManager {
         })
     }
 
+    pub fn is_transcribing(&self) -> bool {
+        self.is_transcribing.load(Ordering::Acquire)
+    }
+
     pub fn is_model_loaded(&self) -> bool {
         self.lock_engine().is_some()
     }
@@ -222,15 +246,14 @@ impl TranscriptionManager {
 
         let model_path = self.model_manager.get_model_path(model_id)?;
 
-        // Essayer whisper_ffi natif (CoreML + Metal) si disponible
+        // Essayer whisper_ffi natif (CoreML + Metal, ou CPU sur Linux) si disponible
         #[cfg(whisper_native)]
         {
             match crate::whisper_ffi::WhisperContext::load(&model_path) {
                 Ok(ctx) => {
                     info!(
-                        "whisper.cpp natif chargé — CoreML: {}, Metal: {}",
-                        crate::whisper_ffi::is_coreml_available(),
-                        crate::whisper_ffi::is_metal_available()
+                        "whisper.cpp natif chargé — {}",
+                        crate::whisper_ffi::backend_description()
                     );
                     let mut engine_guard = self.lock_engine();
                     *engine_guard = Some(LoadedEngine::WhisperFfi(ctx));
@@ -379,9 +402,34 @@ impl TranscriptionManager {
     /// - language: "fr" (forcé — pas d'auto-detect pour réduire la latence)
     /// - beam_size: 1, temperature: 0.0 → greedy decoding, plus rapide
     ///
+    /// Au-delà d'un segment (~25 s), l'audio est découpé et décodé par morceaux
+    /// (voir `audio_toolkit::chunking`), avec un event `transcription-progress`
+    /// par segment.
+    ///
     /// TODO Task 3-5 : remplacer WhisperEngine par whisper_ffi::WhisperContext
     /// pour accéder au CoreML encoder (ANE 3x) + Metal decoder (3-4x)
-    pub fn transcribe(&self, audio: Vec<f32>) -> Result<TranscriptionOutput> {
+    pub fn transcribe(&self, audio: &[f32]) -> Result<TranscriptionOutput> {
+        self.transcribe_with_language(audio, None)
+    }
+
+    /// Comme [`Self::transcribe`], avec une langue imposée par le raccourci
+    /// (`None` : langue des réglages)
+    pub fn transcribe_with_language(
+        &self,
+        audio: &[f32],
+        language: Option<&str>,
+    ) -> Result<TranscriptionOutput> {
+        self.transcribe_with_context(audio, language, None)
+    }
+
+    /// Comme [`Self::transcribe_with_language`], avec les dictées récentes de
+    /// la session en prompt initial du premier segment (noms, termes)
+    pub fn transcribe_with_context(
+        &self,
+        audio: &[f32],
+        language: Option<&str>,
+        session_context: Option<&str>,
+    ) -> Result<TranscriptionOutput> {
         self.last_activity.store(
             SystemTime::now()
                 .duration_since(SystemTime::UNIX_EPOCH)
@@ -398,6 +446,7 @@ impl TranscriptionManager {
                 text: String::new(),
                 confidence: 1.0,
                 duration_ms: 0,
+                hallucinations: Vec::new(),
             });
         }
 
@@ -428,7 +477,10 @@ impl TranscriptionManager {
             }
         }
 
-        let settings = get_settings(&self.app_handle);
+        let mut settings = get_settings(&self.app_handle);
+        if let Some(language) = language {
+            settings.selected_language = language.to_string();
+        }
 
         let result = {
             let mut engine_guard = self.lock_engine();
@@ -440,45 +492,65 @@ impl TranscriptionManager {
             };
             drop(engine_guard);
 
+            // Dictées longues : découpage sur les pauses, décodage séquentiel avec
+            // la fin du segment précédent en prompt initial, puis recollage.
+            let ranges = split_into_chunks(audio, &ChunkConfig::default());
+            let total_chunks = ranges.len();
+            if total_chunks > 1 {
+                info!(
+                    "[Chunking] {:.1}s audio → {} segments",
+                    audio.len() as f32 / 16000.0,
+                    total_chunks
+                );
+            }
+
             // Retourne (text, no_speech_prob_optionnel)
-            // - WhisperFfi : no_speech_prob réel depuis whisper.cpp
+            // - WhisperFfi : no_speech_prob réel depuis whisper.cpp (moyenne des segments)
             // - Whisper    : None (heuristique utilisée plus bas)
             let transcribe_result: std::thread::Result<Result<(String, Option<f32>)>> =
                 catch_unwind(AssertUnwindSafe(|| {
-                    match &mut engine {
-                        #[cfg(whisper_native)]
-                        LoadedEngine::WhisperFfi(ctx) => {
-                            // Chemin natif : whisper.cpp CoreML encoder (ANE) + Metal decoder
-                            let params = crate::whisper_ffi::WhisperParams {
-                                language: if settings.selected_language == "auto" {
-                                    "fr".to_string()
-                                } else {
-                                    settings.selected_language.clone()
-                                },
-                                translate: settings.translate_to_english,
-                                ..Default::default()
-                            };
-                            ctx.transcribe(&audio, &params)
-                                .map(|r| (r.text, Some(r.no_speech_prob)))
-                                .map_err(|e| anyhow::anyhow!("whisper_ffi failed: {}", e))
+                    let mut text = String::new();
+                    let mut no_speech_sum = 0.0f32;
+                    let mut no_speech_count = 0usize;
+
+                    for (index, range) in ranges.iter().enumerate() {
+                        let previous = if index == 0 {
+                            session_context.unwrap_or_default()
+                        } else {
+                            text.as_str()
+                        };
+                        let initial_prompt = Some(context_tail(previous, CONTEXT_PROMPT_WORDS))
+                            .filter(|p| !p.is_empty());
+                        let (chunk_text, no_speech_prob) = run_engine(
+                            &mut engine,
+                            &audio[range.clone()],
+                            &settings,
+                            initial_prompt,
+                        )?;
+                        text = stitch(&text, &chunk_text, MAX_STITCH_WORDS);
+                        if let Some(nsp) = no_speech_prob {
+                            no_speech_sum += nsp;
+                            no_speech_count += 1;
                         }
-                        LoadedEngine::Whisper(whisper_engine) => {
-                            // Fallback : transcribe-rs (ADR-002 greedy FR)
-                            let params = WhisperInferenceParams {
-                                language: if settings.selected_language == "auto" {
-                                    Some("fr".to_string())
-                                } else {
-                                    Some(settings.selected_language.clone())
+
+                        if total_chunks > 1 {
+                            let _ = self.app_handle.emit(
+                                "transcription-progress",
+                                TranscriptionProgress {
+                                    chunk: index + 1,
+                                    total_chunks,
+                                    percentage: (index + 1) as f64 / total_chunks as f64 * 100.0,
                                 },
-                                translate: settings.translate_to_english,
-                                ..Default::default()
-                            };
-                            whisper_engine
-                                .transcribe_samples(audio, Some(params))
-                                .map(|o| (o.text, None::<f32>))
-                                .map_err(|e| anyhow::anyhow!("Whisper transcription failed: {}", e))
+                            );
                         }
                     }
+
+                    let no_speech_prob = if no_speech_count > 0 {
+                        Some(no_speech_sum / no_speech_count as f32)
+                    } else {
+                        None
+                    };
+                    Ok((text, no_speech_prob))
                 }));
 
             match transcribe_result {
@@ -523,6 +595,16 @@ impl TranscriptionManager {
         // result = (text: String, no_speech_prob: Option<f32>)
         let (raw_text, no_speech_prob_opt) = result;
 
+        // Artefacts de sous-titres, boucles et texte sur silence
+        let detection = hallucination::detect(&raw_text, &settings.selected_language, audio);
+        for decision in &detection.decisions {
+            info!(
+                "[Hallucination] {:?} : {} mot(s) retiré(s)",
+                decision.kind, decision.words_removed
+            );
+        }
+        let raw_text = detection.text;
+
         // Correction des mots personnalisés
         let corrected = if !settings.custom_words.is_empty() {
             apply_custom_words(
@@ -555,15 +637,108 @@ impl TranscriptionManager {
             text: filtered,
             confidence,
             duration_ms,
+            hallucinations: detection.decisions,
         })
     }
 
     /// API compatible avec l'existant — retourne seulement le texte
-    pub fn transcribe_text(&self, audio: Vec<f32>) -> Result<String> {
+    pub fn transcribe_text(&self, audio: &[f32]) -> Result<String> {
         self.transcribe(audio).map(|o| o.text)
     }
 }
 
+/// Banc d'essai : charge `model_path` dans un moteur à part (le modèle actif
+/// reste chargé) puis transcrit `audio` en français. Renvoie les durées de
+/// chargement et de transcription.
+pub fn measure_model(
+    model_id: &str,
+    model_path: &Path,
+    audio: &[f32],
+    settings: &AppSettings,
+) -> Result<(Duration, Duration)> {
+    let load_start = std::time::Instant::now();
+    let mut engine = load_engine(model_path)?;
+    let load_time = load_start.elapsed();
+
+    let mut settings = settings.clone();
+    settings.selected_model = model_id.to_string();
+    settings.selected_language = "fr".to_string();
+    settings.translate_to_english = false;
+
+    let transcribe_start = std::time::Instant::now();
+    run_engine(&mut engine, audio, &settings, None)?;
+    Ok((load_time, transcribe_start.elapsed()))
+}
+
+/// Moteur natif si disponible, sinon transcribe-rs (même ordre que `load_model`)
+fn load_engine(model_path: &Path) -> Result<LoadedEngine> {
+    #[cfg(whisper_native)]
+    {
+        match crate::whisper_ffi::WhisperContext::load(model_path) {
+            Ok(ctx) => return Ok(LoadedEngine::WhisperFfi(ctx)),
+            Err(e) => warn!("whisper_ffi::load() échoué, fallback transcribe-rs: {}", e),
+        }
+    }
+
+    let mut engine = WhisperEngine::new();
+    engine
+        .load_model(model_path)
+        .map_err(|e| anyhow::anyhow!("Failed to load whisper model: {}", e))?;
+    Ok(LoadedEngine::Whisper(engine))
+}
+
+/// Transcrit un segment audio avec le moteur chargé.
+/// Retourne (text, no_speech_prob_optionnel).
+fn run_engine(
+    engine: &mut LoadedEngine,
+    audio: &[f32],
+    settings: &AppSettings,
+    initial_prompt: Option<String>,
+) -> Result<(String, Option<f32>)> {
+    let params = whisper_params(settings, initial_prompt);
+    match engine {
+        #[cfg(whisper_native)]
+        LoadedEngine::WhisperFfi(ctx) => {
+            // Chemin natif : whisper.cpp CoreML encoder (ANE) + Metal decoder
+            ctx.transcribe(audio, &params)
+                .map(|r| (r.text, Some(r.no_speech_prob)))
+                .map_err(|e| anyhow::anyhow!("whisper_ffi failed: {}", e))
+        }
+        LoadedEngine::Whisper(whisper_engine) => {
+            // Fallback : transcribe-rs (ADR-002 greedy FR)
+            whisper_engine
+                .transcribe_samples(audio.to_vec(), Some(inference_params(params)))
+                .map(|o| (o.text, None::<f32>))
+                .map_err(|e| anyhow::anyhow!("Whisper transcription failed: {}", e))
+        }
+    }
+}
+
+/// Params Whisper des réglages : preset du modèle sélectionné, langue
+/// (`auto` → français) et traduction
+fn whisper_params(settings: &AppSettings, initial_prompt: Option<String>) -> WhisperParams {
+    let mut params = WhisperParams::for_preset(settings.whisper_preset(&settings.selected_model));
+    if settings.selected_language != "auto" {
+        params.language = settings.selected_language.clone();
+    }
+    params.translate = settings.translate_to_english;
+    params.initial_prompt = initial_prompt;
+    params
+}
+
+/// Équivalent transcribe-rs de `params`. transcribe-rs n'expose ni beam
+/// search ni repli en température : seuls les réglages communs s'appliquent.
+fn inference_params(params: WhisperParams) -> WhisperInferenceParams {
+    WhisperInferenceParams {
+        language: Some(params.language),
+        translate: params.translate,
+        initial_prompt: params.initial_prompt,
+        suppress_blank: params.suppress_blank,
+        no_speech_thold: params.no_speech_threshold,
+        ..Default::default()
+    }
+}
+
 impl Drop for TranscriptionManager {
     fn drop(&mut self) {
         self.shutdown_signal.store(true, Ordering::Relaxed);
@@ -0,0 +1,646 @@
+//! Banc d'essai des modèles sur la machine — `model_benchmark.json`
+//!
+//! Chaque modèle téléchargé transcrit un extrait de parole dans un moteur à
+//! part. On mesure le temps de chargement, le facteur temps réel (durée de
+//! transcription / durée audio) et la mémoire de pointe ajoutée. L'extrait est
+//! `resources/benchmark_fr.wav`, livré avec l'application pour que les mesures
+//! soient comparables d'un modèle et d'une machine à l'autre. En build de
+//! développement seulement, s'il manque, on prend la plus longue des dernières
+//! dictées de l'historique.
+//!
+//! Le modèle actif est déchargé pendant les mesures pour ne pas cumuler deux
+//! modèles en mémoire ; il est rechargé à la dictée suivante. Le banc d'essai
+//! refuse de démarrer pendant une dictée et s'arrête si une dictée commence.
+//!
+//! Le modèle recommandé est le plus précis (score du catalogue) dont le
+//! facteur temps réel tient dans `benchmark_target_rtf` ; à défaut, le plus
+//! rapide. Les mesures sont liées à la machine et aux modèles présents : quand
+//! l'un ou l'autre change, l'interface propose de relancer le banc d'essai.
+
+use log::{error, info, warn};
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tauri::{AppHandle, Emitter, Manager};
+
+use crate::audio_toolkit::constants::WHISPER_SAMPLE_RATE;
+use crate::audio_toolkit::decode_audio_file;
+use crate::managers::history::HistoryManager;
+use crate::managers::model::{ModelInfo, ModelManager};
+use crate::managers::transcription::{self, TranscriptionManager};
+use crate::settings::{get_settings, write_settings};
+use crate::TranscriptionCoordinator;
+
+const REPORT_FILE: &str = "model_benchmark.json";
+/// Extrait français d'une quinzaine de secondes, parole continue, en WAV
+/// mono 16 kHz
+const SAMPLE_CLIP: &str = "resources/benchmark_fr.wav";
+/// Dictées de l'historique examinées quand l'extrait manque (développement)
+const RECENT_SAMPLE_CANDIDATES: usize = 20;
+/// Durée minimale d'un extrait tiré de l'historique
+const MIN_SAMPLE_SECS: usize = 5;
+/// Un extrait plus long est tronqué
+const MAX_SAMPLE_SECS: usize = 30;
+/// Intervalle d'échantillonnage de la mémoire résidente
+const RSS_SAMPLE_INTERVAL: Duration = Duration::from_millis(20);
+
+static RUNNING: AtomicBool = AtomicBool::new(false);
+/// Dernier rapport lu ou écrit, pour ne pas relire le fichier à chaque appel
+static REPORT: Lazy<Mutex<Option<BenchmarkReport>>> = Lazy::new(|| Mutex::new(None));
+
+/// Machine sur laquelle les mesures ont été prises
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Type)]
+pub struct HardwareProfile {
+    pub os: String,
+    pub arch: String,
+    pub cpu_threads: u32,
+    /// Mémoire physique (0 si inconnue)
+    pub memory_mb: u64,
+    /// Accélérations de whisper.cpp (ex : "Metal + CoreML", "CPU AVX2")
+    pub backend: String,
+}
+
+impl HardwareProfile {
+    pub fn detect() -> Self {
+        Self {
+            os: std::env::consts::OS.to_string(),
+            arch: std::env::consts::ARCH.to_string(),
+            cpu_threads: std::thread::available_parallelism().map_or(1, |n| n.get() as u32),
+            memory_mb: total_memory_bytes().unwrap_or(0) / (1024 * 1024),
+            backend: crate::whisper_ffi::backend_description(),
+        }
+    }
+}
+
+/// Mesures d'un modèle
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Type)]
+pub struct ModelBenchmark {
+    pub model_id: String,
+    pub load_ms: u64,
+    pub transcribe_ms: u64,
+    /// Durée de transcription / durée de l'extrait (< 1 : plus rapide que le temps réel)
+    pub real_time_factor: f32,
+    /// Mémoire résidente de pointe ajoutée par le modèle (None si non mesurable)
+    pub peak_rss_mb: Option<u64>,
+    /// Échec du chargement ou de la transcription
+    pub error: Option<String>,
+}
+
+/// Contenu de `model_benchmark.json`
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct BenchmarkReport {
+    pub hardware: HardwareProfile,
+    /// Timestamp Unix (secondes)
+    pub ran_at: i64,
+    pub sample_ms: u64,
+    pub results: Vec<ModelBenchmark>,
+}
+
+/// Rapport et recommandation affichés dans les réglages
+#[derive(Serialize, Debug, Clone, Type)]
+pub struct BenchmarkSummary {
+    pub hardware: HardwareProfile,
+    pub ran_at: i64,
+    pub sample_ms: u64,
+    pub results: Vec<ModelBenchmark>,
+    pub recommended_model: Option<String>,
+    /// Machine ou modèles téléchargés différents depuis les mesures
+    pub stale: bool,
+}
+
+/// Progression envoyée avant chaque modèle (`model-benchmark-progress`)
+#[derive(Serialize, Debug, Clone, Type)]
+pub struct BenchmarkProgress {
+    pub model_id: String,
+    pub index: usize,
+    pub total: usize,
+}
+
+impl BenchmarkReport {
+    /// Modèle téléchargé le plus précis dont le facteur temps réel est sous
+    /// `target_rtf` ; à défaut le plus rapide
+    pub fn recommend(&self, models: &[ModelInfo], target_rtf: f32) -> Option<String> {
+        let mut candidates: Vec<(&ModelBenchmark, f32)> = self
+            .results
+            .iter()
+            .filter(|result| result.error.is_none())
+            .filter_map(|result| {
+                let model = models
+                    .iter()
+                    .find(|model| model.id == result.model_id && model.is_downloaded)?;
+                Some((result, model.accuracy_score))
+            })
+            .collect();
+        candidates.sort_by(|(a, _), (b, _)| a.real_time_factor.total_cmp(&b.real_time_factor));
+
+        candidates
+            .iter()
+            .filter(|(result, _)| result.real_time_factor <= target_rtf)
+            // max_by garde le dernier ex aequo : à précision égale, on veut
+            // le plus rapide, d'où le parcours à l'envers
+            .rev()
+            .max_by(|(_, a), (_, b)| a.total_cmp(b))
+            .or(candidates.first())
+            .map(|(result, _)| result.model_id.clone())
+    }
+
+    /// true si la machine a changé ou si les modèles téléchargés ne sont plus
+    /// ceux qui ont été mesurés
+    pub fn is_stale(&self, hardware: &HardwareProfile, downloaded: &[String]) -> bool {
+        let mut measured: Vec<&str> = self.results.iter().map(|r| r.model_id.as_str()).collect();
+        let mut current: Vec<&str> = downloaded.iter().map(String::as_str).collect();
+        measured.sort_unstable();
+        current.sort_unstable();
+        self.hardware != *hardware || measured != current
+    }
+
+    fn summary(&self, models: &[ModelInfo], target_rtf: f32) -> BenchmarkSummary {
+        BenchmarkSummary {
+            hardware: self.hardware.clone(),
+            ran_at: self.ran_at,
+            sample_ms: self.sample_ms,
+            results: self.results.clone(),
+            recommended_model: self.recommend(models, target_rtf),
+            stale: self.is_stale(&HardwareProfile::detect(), &downloaded_ids(models)),
+        }
+    }
+}
+
+fn downloaded_ids(models: &[ModelInfo]) -> Vec<String> {
+    models
+        .iter()
+        .filter(|model| model.is_downloaded)
+        .map(|model| model.id.clone())
+        .collect()
+}
+
+fn report_path(app: &AppHandle) -> Option<PathBuf> {
+    match app.path().app_data_dir() {
+        Ok(dir) => Some(dir.join(REPORT_FILE)),
+        Err(e) => {
+            error!("Failed to resolve app data dir: {}", e);
+            None
+        }
+    }
+}
+
+/// Dernier rapport enregistré, s'il y en a un
+fn load_report(app: &AppHandle) -> Option<BenchmarkReport> {
+    let mut cached = REPORT.lock().unwrap();
+    if cached.is_none() {
+        let json = fs::read_to_string(report_path(app)?).ok()?;
+        match serde_json::from_str(&json) {
+            Ok(report) => *cached = Some(report),
+            Err(e) => warn!("Ignoring {}: {}", REPORT_FILE, e),
+        }
+    }
+    cached.clone()
+}
+
+fn save_report(app: &AppHandle, report: &BenchmarkReport) {
+    *REPORT.lock().unwrap() = Some(report.clone());
+    let Some(path) = report_path(app) else {
+        return;
+    };
+    let result = serde_json::to_string_pretty(report)
+        .map_err(|e| e.to_string())
+        .and_then(|json| fs::write(&path, json).map_err(|e| e.to_string()));
+    if let Err(e) = result {
+        error!("Failed to write {}: {}", REPORT_FILE, e);
+    }
+}
+
+/// Résumé du dernier rapport, avec la recommandation pour les réglages actuels
+pub fn current_summary(app: &AppHandle) -> Option<BenchmarkSummary> {
+    let report = load_report(app)?;
+    let models = app.state::<Arc<ModelManager>>().get_available_models();
+    Some(report.summary(&models, get_settings(app).benchmark_target_rtf))
+}
+
+/// Modèle recommandé par le dernier rapport pour cette machine, parmi `models`
+pub fn recommended_model(app: &AppHandle, models: &[ModelInfo]) -> Option<String> {
+    let report = load_report(app)?;
+    if report.hardware != HardwareProfile::detect() {
+        return None;
+    }
+    report.recommend(models, get_settings(app).benchmark_target_rtf)
+}
+
+/// Lance le banc d'essai sur tous les modèles téléchargés (bloquant)
+pub fn run(app: &AppHandle) -> Result<BenchmarkSummary, String> {
+    if RUNNING.swap(true, Ordering::AcqRel) {
+        return Err("A model benchmark is already running".to_string());
+    }
+    struct RunningGuard;
+    impl Drop for RunningGuard {
+        fn drop(&mut self) {
+            RUNNING.store(false, Ordering::Release);
+        }
+    }
+    let _guard = RunningGuard;
+
+    let audio = load_sample(app)?;
+    let sample_ms = audio.len() as u64 * 1000 / WHISPER_SAMPLE_RATE as u64;
+
+    let model_manager = app.state::<Arc<ModelManager>>().inner().clone();
+    let mut models: Vec<ModelInfo> = model_manager
+        .get_available_models()
+        .into_iter()
+        .filter(|model| model.is_downloaded)
+        .collect();
+    if models.is_empty() {
+        return Err("No downloaded model to benchmark".to_string());
+    }
+    models.sort_by(|a, b| a.id.cmp(&b.id));
+
+    if dictation_active(app) {
+        return Err("Cannot run the model benchmark during a dictation".to_string());
+    }
+    let transcription_manager = app.state::<Arc<TranscriptionManager>>();
+    if transcription_manager.is_model_loaded() {
+        info!("Unloading the active model for the benchmark");
+        transcription_manager
+            .unload_model()
+            .map_err(|e| format!("Failed to unload the active model: {}", e))?;
+    }
+
+    let settings = get_settings(app);
+    let total = models.len();
+    let mut results = Vec::with_capacity(total);
+    for (index, model) in models.iter().enumerate() {
+        if dictation_active(app) {
+            return Err("Model benchmark stopped: a dictation started".to_string());
+        }
+        let _ = app.emit(
+            "model-benchmark-progress",
+            BenchmarkProgress {
+                model_id: model.id.clone(),
+                index,
+                total,
+            },
+        );
+        let result = model_manager.get_model_path(&model.id).and_then(|path| {
+            measure_with_rss(|| transcription::measure_model(&model.id, &path, &audio, &settings))
+        });
+        let benchmark = match result {
+            Ok(((load, transcribe), peak_rss_mb)) => ModelBenchmark {
+                model_id: model.id.clone(),
+                load_ms: load.as_millis() as u64,
+                transcribe_ms: transcribe.as_millis() as u64,
+                real_time_factor: transcribe.as_secs_f32() * 1000.0 / sample_ms as f32,
+                peak_rss_mb,
+                error: None,
+            },
+            Err(e) => {
+                warn!("Benchmark of '{}' failed: {}", model.id, e);
+                ModelBenchmark {
+                    model_id: model.id.clone(),
+                    load_ms: 0,
+                    transcribe_ms: 0,
+                    real_time_factor: 0.0,
+                    peak_rss_mb: None,
+                    error: Some(e.to_string()),
+                }
+            }
+        };
+        info!(
+            "Benchmark '{}': load {}ms, RTF {:.2}, peak RSS {:?} MB",
+            benchmark.model_id,
+            benchmark.load_ms,
+            benchmark.real_time_factor,
+            benchmark.peak_rss_mb
+        );
+        results.push(benchmark);
+    }
+
+    let report = BenchmarkReport {
+        hardware: HardwareProfile::detect(),
+        ran_at: chrono::Utc::now().timestamp(),
+        sample_ms,
+        results,
+    };
+    save_report(app, &report);
+
+    let all_models = model_manager.get_available_models();
+    let summary = report.summary(&all_models, settings.benchmark_target_rtf);
+    if settings.benchmark_auto_select {
+        if let Some(model_id) = &summary.recommended_model {
+            select_model(app, model_id);
+        }
+    }
+    let _ = app.emit("model-benchmark-completed", &summary);
+    Ok(summary)
+}
+
+/// Charge `model_id` et le sélectionne, comme `set_active_model` ; jamais
+/// pendant une dictée
+fn select_model(app: &AppHandle, model_id: &str) {
+    let mut settings = get_settings(app);
+    if settings.selected_model == model_id {
+        return;
+    }
+    if dictation_active(app) {
+        info!(
+            "Not switching to recommended model '{}' during a dictation",
+            model_id
+        );
+        return;
+    }
+    let transcription_manager = app.state::<Arc<TranscriptionManager>>();
+    if let Err(e) = transcription_manager.load_model(model_id) {
+        warn!("Failed to load recommended model '{}': {}", model_id, e);
+        return;
+    }
+    info!("Selecting benchmark-recommended model '{}'", model_id);
+    settings.selected_model = model_id.to_string();
+    write_settings(app, settings);
+}
+
+/// Signale à l'interface (`model-benchmark-stale`) qu'un rapport existe mais
+/// ne correspond plus à la machine ou aux modèles téléchargés ; le banc
+/// d'essai n'est relancé que si l'utilisateur le demande
+pub fn notify_if_stale(app: &AppHandle) {
+    let Some(report) = load_report(app) else {
+        return;
+    };
+    let models = app.state::<Arc<ModelManager>>().get_available_models();
+    let downloaded = downloaded_ids(&models);
+    if downloaded.is_empty() || !report.is_stale(&HardwareProfile::detect(), &downloaded) {
+        return;
+    }
+    info!("Hardware or models changed since the last benchmark");
+    let _ = app.emit("model-benchmark-stale", ());
+}
+
+/// true pendant un enregistrement, une transcription ou son traitement
+fn dictation_active(app: &AppHandle) -> bool {
+    app.try_state::<TranscriptionCoordinator>()
+        .is_some_and(|coordinator| coordinator.is_busy())
+        || app.state::<Arc<TranscriptionManager>>().is_transcribing()
+}
+
+/// Extrait livré avec l'application ; en développement, s'il manque, tiré
+/// des dernières dictées
+fn load_sample(app: &AppHandle) -> Result<Vec<f32>, String> {
+    let clip = app
+        .path()
+        .resolve(SAMPLE_CLIP, tauri::path::BaseDirectory::Resource)
+        .map_err(|e| format!("Failed to resolve benchmark sample: {}", e))?;
+    if clip.exists() {
+        let audio = decode_audio_file(&clip, |_| {})
+            .map_err(|e| format!("Failed to decode benchmark sample: {}", e))?;
+        if audio.is_empty() {
+            return Err("Benchmark sample is empty".to_string());
+        }
+        return Ok(audio);
+    }
+    if !cfg!(debug_assertions) {
+        return Err(format!("Benchmark sample not found: {}", clip.display()));
+    }
+
+    let history = app.state::<Arc<HistoryManager>>();
+    let entries = history
+        .get_recent_dictations(0, RECENT_SAMPLE_CANDIDATES)
+        .map_err(|e| format!("Failed to read history: {}", e))?;
+    // Coffre verrouillé ou fichier supprimé : la dictée est ignorée
+    let recordings = entries
+        .iter()
+        .filter_map(|entry| history.load_recording_samples(&entry.file_name).ok());
+    pick_sample(recordings).ok_or_else(|| {
+        format!(
+            "No benchmark sample: record a dictation of at least {} seconds first",
+            MIN_SAMPLE_SECS
+        )
+    })
+}
+
+/// Plus long enregistrement d'au moins [`MIN_SAMPLE_SECS`], tronqué à
+/// [`MAX_SAMPLE_SECS`]
+fn pick_sample(recordings: impl Iterator<Item = Vec<f32>>) -> Option<Vec<f32>> {
+    let sample_rate = WHISPER_SAMPLE_RATE as usize;
+    let mut longest = recordings
+        .filter(|samples| samples.len() >= MIN_SAMPLE_SECS * sample_rate)
+        .max_by_key(Vec::len)?;
+    longest.truncate(MAX_SAMPLE_SECS * sample_rate);
+    Some(longest)
+}
+
+/// Exécute `f` en relevant la mémoire résidente de pointe au-dessus de celle
+/// d'avant l'appel, en Mo
+fn measure_with_rss<T>(f: impl FnOnce() -> anyhow::Result<T>) -> anyhow::Result<(T, Option<u64>)> {
+    let Some(baseline) = resident_memory_bytes() else {
+        return f().map(|value| (value, None));
+    };
+    let peak = Arc::new(AtomicU64::new(baseline));
+    let done = Arc::new(AtomicBool::new(false));
+    let sampler = {
+        let peak = peak.clone();
+        let done = done.clone();
+        std::thread::spawn(move || {
+            while !done.load(Ordering::Acquire) {
+                if let Some(rss) = resident_memory_bytes() {
+                    peak.fetch_max(rss, Ordering::Relaxed);
+                }
+                std::thread::sleep(RSS_SAMPLE_INTERVAL);
+            }
+        })
+    };
+
+    let result = f();
+    done.store(true, Ordering::Release);
+    let _ = sampler.join();
+    let added = peak.load(Ordering::Relaxed).saturating_sub(baseline);
+    result.map(|value| (value, Some(added / (1024 * 1024))))
+}
+
+/// Mémoire résidente du processus
+#[cfg(target_os = "linux")]
+fn resident_memory_bytes() -> Option<u64> {
+    let statm = fs::read_to_string("/proc/self/statm").ok()?;
+    let pages: u64 = statm.split_whitespace().nth(1)?.parse().ok()?;
+    let page_size = unsafe { libc::sysconf(libc::_SC_PAGESIZE) };
+    (page_size > 0).then(|| pages * page_size as u64)
+}
+
+#[cfg(target_os = "macos")]
+fn resident_memory_bytes() -> Option<u64> {
+    let mut info: libc::proc_taskinfo = unsafe { std::mem::zeroed() };
+    let size = std::mem::size_of::<libc::proc_taskinfo>() as libc::c_int;
+    let written = unsafe {
+        libc::proc_pidinfo(
+            libc::getpid(),
+            libc::PROC_PIDTASKINFO,
+            0,
+            &mut info as *mut _ as *mut libc::c_void,
+            size,
+        )
+    };
+    (written == size).then_some(info.pti_resident_size)
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "macos")))]
+fn resident_memory_bytes() -> Option<u64> {
+    None
+}
+
+/// Mémoire physique de la machine
+#[cfg(target_os = "linux")]
+fn total_memory_bytes() -> Option<u64> {
+    let meminfo = fs::read_to_string("/proc/meminfo").ok()?;
+    let line = meminfo.lines().find(|line| line.starts_with("MemTotal:"))?;
+    let kib: u64 = line.split_whitespace().nth(1)?.parse().ok()?;
+    Some(kib * 1024)
+}
+
+#[cfg(target_os = "macos")]
+fn total_memory_bytes() -> Option<u64> {
+    let mut memsize: u64 = 0;
+    let mut len = std::mem::size_of::<u64>();
+    let result = unsafe {
+        libc::sysctlbyname(
+            c"hw.memsize".as_ptr(),
+            &mut memsize as *mut _ as *mut libc::c_void,
+            &mut len,
+            std::ptr::null_mut(),
+            0,
+        )
+    };
+    (result == 0).then_some(memsize)
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "macos")))]
+fn total_memory_bytes() -> Option<u64> {
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::managers::model::EngineType;
+
+    fn model(id: &str, accuracy: f32, downloaded: bool) -> ModelInfo {
+        ModelInfo {
+            id: id.to_string(),
+            name: id.to_string(),
+            description: String::new(),
+            filename: format!("{}.bin", id),
+            url: None,
+            sha256: None,
+            size_mb: 1,
+            is_downloaded: downloaded,
+            is_downloading: false,
+            partial_size: 0,
+            is_directory: false,
+            engine_type: EngineType::Whisper,
+            accuracy_score: accuracy,
+            speed_score: 0.5,
+            supports_translation: false,
+            is_recommended: false,
+            supported_languages: vec![],
+            is_custom: false,
+        }
+    }
+
+    fn result(id: &str, rtf: f32) -> ModelBenchmark {
+        ModelBenchmark {
+            model_id: id.to_string(),
+            load_ms: 500,
+            transcribe_ms: (rtf * 10_000.0) as u64,
+            real_time_factor: rtf,
+            peak_rss_mb: Some(600),
+            error: None,
+        }
+    }
+
+    fn report(results: Vec<ModelBenchmark>) -> BenchmarkReport {
+        BenchmarkReport {
+            hardware: HardwareProfile::detect(),
+            ran_at: 0,
+            sample_ms: 10_000,
+            results,
+        }
+    }
+
+    #[test]
+    fn recommends_most_accurate_model_within_target() {
+        let models = vec![
+            model("small", 0.6, true),
+            model("medium", 0.75, true),
+            model("turbo-q5", 0.95, true),
+        ];
+        let report = report(vec![
+            result("small", 0.1),
+            result("medium", 0.3),
+            result("turbo-q5", 0.9),
+        ]);
+        assert_eq!(report.recommend(&models, 0.5).as_deref(), Some("medium"));
+        assert_eq!(report.recommend(&models, 1.0).as_deref(), Some("turbo-q5"));
+        // Aucun modèle assez rapide : le plus rapide
+        assert_eq!(report.recommend(&models, 0.05).as_deref(), Some("small"));
+    }
+
+    #[test]
+    fn skips_failed_and_deleted_models() {
+        let models = vec![model("small", 0.6, true), model("large", 0.85, false)];
+        let mut failed = result("medium", 0.2);
+        failed.error = Some("load failed".to_string());
+        let report = report(vec![result("small", 0.4), result("large", 0.2), failed]);
+        assert_eq!(report.recommend(&models, 0.5).as_deref(), Some("small"));
+        assert_eq!(report.recommend(&[], 0.5), None);
+    }
+
+    #[test]
+    fn equal_accuracy_prefers_the_faster_model() {
+        let models = vec![model("q5", 0.9, true), model("q8", 0.9, true)];
+        let report = report(vec![result("q8", 0.3), result("q5", 0.2)]);
+        assert_eq!(report.recommend(&models, 0.5).as_deref(), Some("q5"));
+    }
+
+    #[test]
+    fn stale_when_hardware_or_models_change() {
+        let report = report(vec![result("small", 0.1), result("medium", 0.3)]);
+        let hardware = HardwareProfile::detect();
+        let ids = |ids: &[&str]| ids.iter().map(|id| id.to_string()).collect::<Vec<_>>();
+
+        assert!(!report.is_stale(&hardware, &ids(&["medium", "small"])));
+        assert!(report.is_stale(&hardware, &ids(&["small"])));
+        assert!(report.is_stale(&hardware, &ids(&["small", "medium", "large"])));
+
+        let other = HardwareProfile {
+            cpu_threads: hardware.cpu_threads + 4,
+            ..hardware
+        };
+        assert!(report.is_stale(&other, &ids(&["small", "medium"])));
+    }
+
+    #[test]
+    fn sample_is_the_longest_recording_within_bounds() {
+        let rate = WHISPER_SAMPLE_RATE as usize;
+        let secs = |n: usize| vec![0.0f32; n * rate];
+
+        let picked = pick_sample(vec![secs(6), secs(12), secs(8)].into_iter()).unwrap();
+        assert_eq!(picked.len(), 12 * rate);
+        let picked = pick_sample(vec![secs(45)].into_iter()).unwrap();
+        assert_eq!(picked.len(), MAX_SAMPLE_SECS * rate);
+        assert!(pick_sample(vec![secs(2), secs(4)].into_iter()).is_none());
+    }
+
+    #[test]
+    fn rss_is_measured_around_the_call() {
+        let (value, peak) = measure_with_rss(|| {
+            let buffer = vec![1u8; 64 * 1024 * 1024];
+            std::thread::sleep(RSS_SAMPLE_INTERVAL * 3);
+            Ok(buffer.iter().map(|&b| b as u64).sum::<u64>())
+        })
+        .unwrap();
+        assert_eq!(value, 64 * 1024 * 1024);
+        if cfg!(any(target_os = "linux", target_os = "macos")) {
+            assert!(peak.unwrap() >= 32, "{:?}", peak);
+        }
+    }
+}
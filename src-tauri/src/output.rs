@@ -0,0 +1,291 @@
+//! Destinations du texte dicté autres que le collage
+//!
+//! Une dictée part vers toutes les [`OutputSink`] du raccourci (ou des
+//! réglages globaux). Le collage reste géré par `actions` (thread principal,
+//! frappe progressive, annulation) ; ce module s'occupe du journal Markdown,
+//! des fichiers et tubes nommés, de la sortie standard et du webhook local.
+
+use crate::settings::OutputSink;
+use anyhow::{anyhow, bail, Context, Result};
+use chrono::{DateTime, Local};
+use log::{error, info};
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// Délai max d'un POST webhook — il ne doit jamais retenir une dictée
+const WEBHOOK_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Dictée à livrer
+#[derive(Debug, Clone)]
+pub struct OutputPayload {
+    /// Texte final (après règles et post-traitement)
+    pub text: String,
+    /// Sortie brute de Whisper
+    pub raw_text: String,
+    pub binding_id: String,
+    pub language: String,
+    pub timestamp: DateTime<Local>,
+}
+
+/// Vérifie une liste de destinations avant de l'enregistrer
+pub fn validate(sinks: &[OutputSink]) -> Result<(), String> {
+    if sinks.is_empty() {
+        return Err("At least one output is required".to_string());
+    }
+    for sink in sinks {
+        match sink {
+            OutputSink::Paste | OutputSink::Stdout => {}
+            OutputSink::MarkdownJournal {
+                directory,
+                template,
+            } => {
+                if directory.trim().is_empty() {
+                    return Err("Journal directory cannot be empty".to_string());
+                }
+                if template.trim().is_empty() {
+                    return Err("Journal template cannot be empty".to_string());
+                }
+            }
+            OutputSink::File { path } => {
+                if path.trim().is_empty() {
+                    return Err("Output file path cannot be empty".to_string());
+                }
+            }
+            OutputSink::Webhook { url } => {
+                local_url(url).map_err(|e| e.to_string())?;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Livre `payload` à chaque destination hors collage, sans s'arrêter à la
+/// première erreur. Bloquant (écritures disque, webhook) : à appeler hors du
+/// runtime async. Retourne les erreurs, déjà journalisées.
+pub fn deliver(sinks: &[OutputSink], payload: &OutputPayload) -> Vec<String> {
+    let mut errors = Vec::new();
+    for sink in sinks {
+        let result = match sink {
+            OutputSink::Paste => continue,
+            OutputSink::MarkdownJournal {
+                directory,
+                template,
+            } => append_journal(&expand_home(directory), template, payload).map(|path| {
+                info!("[Output] Dictée ajoutée au journal {}", path.display());
+            }),
+            OutputSink::File { path } => append_line(&expand_home(path), &payload.text),
+            OutputSink::Stdout => write_stdout(&payload.text),
+            OutputSink::Webhook { url } => post_webhook(url, payload),
+        };
+        if let Err(e) = result {
+            error!("[Output] {} : {:#}", sink_kind(sink), e);
+            errors.push(format!("{}: {:#}", sink_kind(sink), e));
+        }
+    }
+    errors
+}
+
+fn sink_kind(sink: &OutputSink) -> &'static str {
+    match sink {
+        OutputSink::Paste => "paste",
+        OutputSink::MarkdownJournal { .. } => "markdown_journal",
+        OutputSink::File { .. } => "file",
+        OutputSink::Stdout => "stdout",
+        OutputSink::Webhook { .. } => "webhook",
+    }
+}
+
+/// `~/Journal` → `$HOME/Journal`
+fn expand_home(path: &str) -> PathBuf {
+    let path = path.trim();
+    match (path.strip_prefix("~/"), dirs::home_dir()) {
+        (Some(rest), Some(home)) => home.join(rest),
+        _ => PathBuf::from(path),
+    }
+}
+
+/// Remplace `${date}`, `${time}`, `${text}` et `${binding}`
+fn render_template(template: &str, payload: &OutputPayload) -> String {
+    template
+        .replace("${date}", &payload.timestamp.format("%Y-%m-%d").to_string())
+        .replace("${time}", &payload.timestamp.format("%H:%M").to_string())
+        .replace("${binding}", &payload.binding_id)
+        .replace("${text}", &payload.text)
+}
+
+/// Ajoute l'entrée au journal du jour, créé avec un titre daté au besoin
+fn append_journal(directory: &Path, template: &str, payload: &OutputPayload) -> Result<PathBuf> {
+    std::fs::create_dir_all(directory)
+        .with_context(|| format!("création de {}", directory.display()))?;
+    let path = directory.join(payload.timestamp.format("%Y-%m-%d.md").to_string());
+
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&path)
+        .with_context(|| format!("ouverture de {}", path.display()))?;
+    let mut entry = String::new();
+    if file.metadata()?.len() == 0 {
+        entry.push_str(&format!("# {}\n\n", payload.timestamp.format("%Y-%m-%d")));
+    }
+    entry.push_str(render_template(template, payload).trim_end());
+    entry.push('\n');
+    file.write_all(entry.as_bytes())?;
+    Ok(path)
+}
+
+/// Ajoute `text` suivi d'un saut de ligne. Un tube nommé sans lecteur
+/// échoue aussitôt au lieu de bloquer la dictée.
+fn append_line(path: &Path, text: &str) -> Result<()> {
+    let mut options = OpenOptions::new();
+    options.create(true).append(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.custom_flags(libc::O_NONBLOCK);
+    }
+    let mut file = match options.open(path) {
+        Ok(file) => file,
+        #[cfg(unix)]
+        Err(e) if e.raw_os_error() == Some(libc::ENXIO) => {
+            bail!("aucun lecteur sur le tube {}", path.display())
+        }
+        Err(e) => return Err(e).with_context(|| format!("ouverture de {}", path.display())),
+    };
+    file.write_all(format!("{text}\n").as_bytes())?;
+    Ok(())
+}
+
+fn write_stdout(text: &str) -> Result<()> {
+    let mut stdout = std::io::stdout().lock();
+    writeln!(stdout, "{text}")?;
+    stdout.flush()?;
+    Ok(())
+}
+
+/// URL http(s) vers la machine locale uniquement : la dictée ne quitte pas le poste
+fn local_url(url: &str) -> Result<reqwest::Url> {
+    let parsed =
+        reqwest::Url::parse(url.trim()).map_err(|e| anyhow!("Invalid webhook URL: {e}"))?;
+    if !matches!(parsed.scheme(), "http" | "https") {
+        bail!("Webhook URL must use http or https");
+    }
+    let local = parsed.host_str().is_some_and(|host| {
+        host.eq_ignore_ascii_case("localhost")
+            || host
+                .trim_start_matches('[')
+                .trim_end_matches(']')
+                .parse::<std::net::IpAddr>()
+                .is_ok_and(|ip| ip.is_loopback())
+    });
+    if !local {
+        bail!("Webhook URL must point to localhost");
+    }
+    Ok(parsed)
+}
+
+fn post_webhook(url: &str, payload: &OutputPayload) -> Result<()> {
+    let url = local_url(url)?;
+    let body = serde_json::json!({
+        "text": payload.text,
+        "raw_text": payload.raw_text,
+        "binding_id": payload.binding_id,
+        "language": payload.language,
+        "timestamp": payload.timestamp.to_rfc3339(),
+    });
+    reqwest::blocking::Client::builder()
+        .timeout(WEBHOOK_TIMEOUT)
+        .build()?
+        .post(url)
+        .json(&body)
+        .send()?
+        .error_for_status()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn payload(text: &str) -> OutputPayload {
+        OutputPayload {
+            text: text.to_string(),
+            raw_text: text.to_string(),
+            binding_id: "custom_1".to_string(),
+            language: "fr".to_string(),
+            timestamp: Local.with_ymd_and_hms(2026, 3, 14, 9, 5, 0).unwrap(),
+        }
+    }
+
+    #[test]
+    fn template_fills_placeholders() {
+        let rendered = render_template("${date} ${time} [${binding}] ${text}", &payload("Bonjour"));
+        assert_eq!(rendered, "2026-03-14 09:05 [custom_1] Bonjour");
+    }
+
+    #[test]
+    fn journal_gets_dated_title_once() {
+        let dir = tempfile::tempdir().unwrap();
+        append_journal(dir.path(), "- ${text}", &payload("premier")).unwrap();
+        let path = append_journal(dir.path(), "- ${text}", &payload("second")).unwrap();
+
+        assert_eq!(path.file_name().unwrap(), "2026-03-14.md");
+        let content = std::fs::read_to_string(path).unwrap();
+        assert_eq!(content, "# 2026-03-14\n\n- premier\n- second\n");
+    }
+
+    #[test]
+    fn file_sink_appends_lines() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("out.txt");
+        let sinks = vec![
+            OutputSink::Paste,
+            OutputSink::File {
+                path: path.to_string_lossy().into_owned(),
+            },
+        ];
+        assert!(deliver(&sinks, &payload("un")).is_empty());
+        assert!(deliver(&sinks, &payload("deux")).is_empty());
+        assert_eq!(std::fs::read_to_string(path).unwrap(), "un\ndeux\n");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn fifo_without_reader_fails_fast() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("dictee.fifo");
+        let c_path = std::ffi::CString::new(path.to_str().unwrap()).unwrap();
+        assert_eq!(unsafe { libc::mkfifo(c_path.as_ptr(), 0o600) }, 0);
+
+        let err = append_line(&path, "texte").unwrap_err();
+        assert!(err.to_string().contains("aucun lecteur"));
+    }
+
+    #[test]
+    fn webhook_must_stay_local() {
+        assert!(local_url("http://localhost:8080/dictee").is_ok());
+        assert!(local_url("http://127.0.0.1/hook").is_ok());
+        assert!(local_url("http://[::1]:9000/").is_ok());
+        assert!(local_url("https://example.com/hook").is_err());
+        assert!(local_url("ftp://localhost/hook").is_err());
+    }
+
+    #[test]
+    fn validate_rejects_empty_and_incomplete_sinks() {
+        assert!(validate(&[]).is_err());
+        assert!(validate(&[OutputSink::File {
+            path: " ".to_string()
+        }])
+        .is_err());
+        assert!(validate(&[
+            OutputSink::Paste,
+            OutputSink::Webhook {
+                url: "http://localhost:3000".to_string()
+            }
+        ])
+        .is_ok());
+    }
+}
@@ -359,6 +359,28 @@ pub fn show_error_overlay(app_handle: &AppHandle, message: &str) {
     });
 }
 
+/// Aperçu de la réponse LLM en cours de génération (texte accumulé)
+pub fn show_llm_preview(app_handle: &AppHandle, text: &str) {
+    let settings = settings::get_settings(app_handle);
+    if settings.overlay_position == OverlayPosition::None {
+        return;
+    }
+
+    if let Some(overlay_window) = app_handle.get_webview_window("recording_overlay") {
+        let _ = overlay_window.show();
+        let _ = overlay_window.emit("llm-stream", text);
+    }
+}
+
+/// Avertit que la durée max d'enregistrement approche (secondes restantes)
+pub fn show_recording_limit_warning(app_handle: &AppHandle, remaining_secs: u64) {
+    let _ = app_handle.emit("recording-limit-warning", remaining_secs);
+
+    if let Some(overlay_window) = app_handle.get_webview_window("recording_overlay") {
+        let _ = overlay_window.emit("recording-limit-warning", remaining_secs);
+    }
+}
+
 /// Updates the overlay window position based on current settings
 pub fn update_overlay_position(app_handle: &AppHandle) {
     if let Some(overlay_window) = app_handle.get_webview_window("recording_overlay") {
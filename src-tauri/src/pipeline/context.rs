@@ -0,0 +1,190 @@
+//! Contexte de session : dictées récentes passées au nettoyage LLM et à Whisper
+//!
+//! Chaque dictée est traitée isolément ; une phrase de suite ne profite donc
+//! pas des noms et termes de la précédente. Ce module retient les dernières
+//! dictées de la session en cours (même application, sans longue pause) dans
+//! un budget de tokens, pour le prompt système du nettoyage et le prompt
+//! initial de Whisper.
+
+/// Dictée de l'historique candidate au contexte
+#[derive(Debug, Clone)]
+pub struct RecentDictation {
+    pub text: String,
+    /// Timestamp Unix (secondes)
+    pub timestamp: i64,
+    /// Application cible, si elle était connue
+    pub app: Option<String>,
+}
+
+/// Bornes du contexte (réglages `session_context_*`)
+#[derive(Debug, Clone, Copy)]
+pub struct ContextLimits {
+    pub max_entries: usize,
+    /// Au-delà de cette pause entre deux dictées, une nouvelle session commence
+    pub inactivity_secs: i64,
+    pub max_tokens: usize,
+}
+
+/// Estimation grossière du nombre de tokens (~4 caractères par token)
+pub fn estimate_tokens(text: &str) -> usize {
+    text.chars().count().div_ceil(4)
+}
+
+/// Dictées de la session en cours, de la plus récente à la plus ancienne.
+/// `recent` est trié de la plus récente à la plus ancienne ; la session
+/// s'arrête à la première pause trop longue ou au premier changement
+/// d'application (quand les deux applications sont connues).
+fn session<'a>(
+    recent: &'a [RecentDictation],
+    app: Option<&str>,
+    now: i64,
+    limits: &ContextLimits,
+) -> Vec<&'a RecentDictation> {
+    let mut session = Vec::new();
+    let mut last = now;
+    for dictation in recent {
+        if last - dictation.timestamp > limits.inactivity_secs {
+            break;
+        }
+        if let (Some(current), Some(previous)) = (app, dictation.app.as_deref()) {
+            if current != previous {
+                break;
+            }
+        }
+        if session.len() == limits.max_entries {
+            break;
+        }
+        last = dictation.timestamp;
+        if !dictation.text.trim().is_empty() {
+            session.push(dictation);
+        }
+    }
+    session
+}
+
+/// Fin de `text` tenant dans `max_tokens` (mots entiers)
+fn tail_within(text: &str, max_tokens: usize) -> String {
+    let mut words: Vec<&str> = Vec::new();
+    let mut tokens = 0;
+    for word in text.split_whitespace().rev() {
+        tokens += estimate_tokens(word) + 1;
+        if tokens > max_tokens {
+            break;
+        }
+        words.push(word);
+    }
+    words.reverse();
+    words.join(" ")
+}
+
+/// Texte de contexte pour la dictée en cours dans `app`, dans l'ordre
+/// chronologique, une dictée par ligne. Les plus récentes sont gardées en
+/// priorité ; la plus ancienne retenue peut être tronquée au budget.
+/// `None` hors session.
+pub fn build(
+    recent: &[RecentDictation],
+    app: Option<&str>,
+    now: i64,
+    limits: &ContextLimits,
+) -> Option<String> {
+    let mut lines = Vec::new();
+    let mut budget = limits.max_tokens;
+    for dictation in session(recent, app, now, limits) {
+        let text = dictation.text.trim();
+        let tokens = estimate_tokens(text);
+        if tokens <= budget {
+            lines.push(text.to_string());
+            budget -= tokens;
+        } else {
+            let tail = tail_within(text, budget);
+            if !tail.is_empty() {
+                lines.push(tail);
+            }
+            break;
+        }
+    }
+    lines.reverse();
+    Some(lines.join("\n")).filter(|context| !context.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LIMITS: ContextLimits = ContextLimits {
+        max_entries: 3,
+        inactivity_secs: 300,
+        max_tokens: 100,
+    };
+
+    fn dictation(text: &str, timestamp: i64, app: Option<&str>) -> RecentDictation {
+        RecentDictation {
+            text: text.to_string(),
+            timestamp,
+            app: app.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn keeps_recent_dictations_in_order() {
+        let recent = vec![
+            dictation("Envoie-le à Kévin Durand.", 990, Some("Slack")),
+            dictation("Le devis Orbeon est prêt.", 950, Some("Slack")),
+        ];
+        assert_eq!(
+            build(&recent, Some("Slack"), 1000, &LIMITS).as_deref(),
+            Some("Le devis Orbeon est prêt.\nEnvoie-le à Kévin Durand.")
+        );
+    }
+
+    #[test]
+    fn inactivity_starts_a_new_session() {
+        let recent = vec![
+            dictation("Récente", 900, None),
+            dictation("Trop ancienne", 500, None),
+        ];
+        assert_eq!(
+            build(&recent, None, 1000, &LIMITS).as_deref(),
+            Some("Récente")
+        );
+        assert_eq!(build(&recent, None, 1400, &LIMITS), None);
+    }
+
+    #[test]
+    fn app_change_starts_a_new_session() {
+        let recent = vec![
+            dictation("Dans Mail", 990, Some("Mail")),
+            dictation("Dans Slack", 980, Some("Slack")),
+        ];
+        assert_eq!(build(&recent, Some("Slack"), 1000, &LIMITS), None);
+        // Application inconnue d'un côté : pas de coupure
+        assert_eq!(
+            build(&recent, None, 1000, &LIMITS).as_deref(),
+            Some("Dans Slack\nDans Mail")
+        );
+    }
+
+    #[test]
+    fn entry_count_and_token_budget_are_respected() {
+        let recent: Vec<_> = (0..5)
+            .map(|i| dictation(&format!("phrase {i}"), 1000 - i, None))
+            .collect();
+        assert_eq!(
+            build(&recent, None, 1000, &LIMITS).as_deref(),
+            Some("phrase 2\nphrase 1\nphrase 0")
+        );
+
+        let long = "mot ".repeat(100);
+        let recent = vec![
+            dictation("Dernière phrase.", 999, None),
+            dictation(&long, 998, None),
+        ];
+        let limits = ContextLimits {
+            max_tokens: 20,
+            ..LIMITS
+        };
+        let context = build(&recent, None, 1000, &limits).unwrap();
+        assert!(context.ends_with("\nDernière phrase."), "{context}");
+        assert!(estimate_tokens(&context) <= 20 + 1, "{context}");
+    }
+}
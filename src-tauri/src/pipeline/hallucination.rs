@@ -0,0 +1,383 @@
+/// Détection des hallucinations Whisper, juste après la transcription
+///
+/// Sur du silence ou un souffle, Whisper recrache des artefacts de ses données
+/// d'entraînement (sous-titres de vidéos) ou boucle sur la même phrase. Trois
+/// contrôles, dans l'ordre :
+/// 1. phrases connues par langue — crédits de sous-titrage (retirés partout)
+///    et formules de fin de vidéo (retirées seulement en fin de texte) ;
+/// 2. boucles de répétition — un n-gramme répété ≥ 3 fois d'affilée est
+///    ramené à une seule occurrence ;
+/// 3. cohérence énergie audio / longueur du texte — du texte sur un audio
+///    quasi silencieux est entièrement écarté.
+///
+/// Les décisions ne contiennent aucun texte : elles sont stockées telles
+/// quelles dans les métriques de la dictée.
+use serde::{Deserialize, Serialize};
+use specta::Type;
+
+use crate::audio_toolkit::constants::WHISPER_SAMPLE_RATE;
+
+/// Crédits de sous-titrage : jamais dictés, la phrase qui les contient est retirée
+const SUBTITLE_MARKERS: &[(&str, &[&str])] = &[
+    (
+        "fr",
+        &[
+            "amara.org",
+            "sous-titres réalisés par",
+            "sous-titrage st'",
+            "sous-titrage société radio-canada",
+            "sous-titrage fr",
+        ],
+    ),
+    ("en", &["amara.org", "subtitles by", "captions by"]),
+    (
+        "de",
+        &["amara.org", "untertitel im auftrag", "untertitel von"],
+    ),
+    (
+        "es",
+        &["amara.org", "subtítulos realizados por", "subtítulos por"],
+    ),
+];
+
+/// Formules de fin de vidéo : retirées en fin de texte quand elles forment
+/// une phrase entière (une dictée peut légitimement les contenir ailleurs)
+const OUTRO_PHRASES: &[(&str, &[&str])] = &[
+    (
+        "fr",
+        &[
+            "merci d'avoir regardé cette vidéo",
+            "merci d'avoir regardé",
+            "n'oubliez pas de vous abonner",
+            "abonnez-vous",
+            "abonnez-vous à la chaîne",
+        ],
+    ),
+    (
+        "en",
+        &[
+            "thanks for watching",
+            "thank you for watching",
+            "please subscribe",
+            "like and subscribe",
+        ],
+    ),
+    (
+        "de",
+        &["vielen dank fürs zuschauen", "danke fürs zuschauen"],
+    ),
+    ("es", &["gracias por ver el video", "suscríbete al canal"]),
+];
+
+/// Taille max du n-gramme recherché dans les boucles
+const MAX_LOOP_NGRAM: usize = 8;
+/// Répétitions consécutives à partir desquelles on parle de boucle
+const MIN_LOOP_REPEATS: usize = 3;
+/// Nombre minimal de mots couverts par la boucle (épargne « non non non »)
+const MIN_LOOP_WORDS: usize = 8;
+
+/// Trame d'analyse énergétique (30 ms à 16 kHz)
+const ENERGY_FRAME: usize = WHISPER_SAMPLE_RATE as usize * 30 / 1000;
+/// RMS au-dessus duquel une trame est considérée comme sonore (~ -40 dBFS)
+const VOICED_RMS: f32 = 0.01;
+/// En dessous de cette durée sonore, l'audio est considéré comme silencieux
+const MIN_VOICED_SECONDS: f32 = 0.25;
+/// Quelques mots tolérés sur un audio silencieux (« oui », « ok »)
+const MAX_WORDS_ON_SILENCE: usize = 3;
+
+/// Type d'hallucination détecté
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize, Type)]
+#[serde(rename_all = "snake_case")]
+pub enum HallucinationKind {
+    /// Crédit de sous-titrage ou formule de fin de vidéo
+    KnownPhrase,
+    /// N-gramme répété en boucle
+    RepetitionLoop,
+    /// Texte produit sur un audio quasi silencieux
+    SilentAudio,
+}
+
+/// Décision prise sur la transcription brute
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, Type)]
+pub struct HallucinationDecision {
+    pub kind: HallucinationKind,
+    pub words_removed: usize,
+}
+
+/// Transcription nettoyée et décisions prises
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Detection {
+    pub text: String,
+    pub decisions: Vec<HallucinationDecision>,
+}
+
+/// Applique les trois contrôles à `text`, transcrit depuis `audio` (16 kHz
+/// mono). `language` suit les réglages : `"auto"` active les listes de
+/// toutes les langues.
+pub fn detect(text: &str, language: &str, audio: &[f32]) -> Detection {
+    let mut decisions = Vec::new();
+
+    let (text, removed) = remove_known_phrases(text, language);
+    if removed > 0 {
+        decisions.push(HallucinationDecision {
+            kind: HallucinationKind::KnownPhrase,
+            words_removed: removed,
+        });
+    }
+
+    let (text, removed) = collapse_loops(&text);
+    if removed > 0 {
+        decisions.push(HallucinationDecision {
+            kind: HallucinationKind::RepetitionLoop,
+            words_removed: removed,
+        });
+    }
+
+    let words = text.split_whitespace().count();
+    let text = if words > MAX_WORDS_ON_SILENCE && voiced_seconds(audio) < MIN_VOICED_SECONDS {
+        decisions.push(HallucinationDecision {
+            kind: HallucinationKind::SilentAudio,
+            words_removed: words,
+        });
+        String::new()
+    } else {
+        text
+    };
+
+    Detection { text, decisions }
+}
+
+/// Durée cumulée des trames sonores de `audio`, en secondes
+pub fn voiced_seconds(audio: &[f32]) -> f32 {
+    let voiced = audio
+        .chunks(ENERGY_FRAME)
+        .filter(|frame| {
+            let energy: f32 = frame.iter().map(|s| s * s).sum();
+            (energy / frame.len() as f32).sqrt() >= VOICED_RMS
+        })
+        .count();
+    (voiced * ENERGY_FRAME) as f32 / WHISPER_SAMPLE_RATE as f32
+}
+
+/// Listes applicables à `language` (préfixe avant `-`, toutes si `auto`)
+fn phrases_for<'a>(
+    table: &'a [(&'a str, &'a [&'a str])],
+    language: &str,
+) -> impl Iterator<Item = &'a str> + 'a {
+    let language = language
+        .split('-')
+        .next()
+        .unwrap_or_default()
+        .to_lowercase();
+    table
+        .iter()
+        .filter(move |(lang, _)| language == "auto" || *lang == language)
+        .flat_map(|(_, phrases)| phrases.iter().copied())
+}
+
+/// Minuscules, apostrophes typographiques unifiées, ponctuation de bord retirée
+fn normalize(sentence: &str) -> String {
+    sentence
+        .to_lowercase()
+        .replace('’', "'")
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .trim_matches(|c: char| !c.is_alphanumeric())
+        .to_string()
+}
+
+/// Découpe en phrases, ponctuation finale comprise. Un point suivi d'une
+/// lettre (« amara.org ») ne coupe pas.
+fn split_sentences(text: &str) -> Vec<&str> {
+    let mut sentences = Vec::new();
+    let mut start = 0;
+    let mut chars = text.char_indices().peekable();
+    while let Some((i, c)) = chars.next() {
+        if matches!(c, '.' | '!' | '?' | '…') {
+            let next = chars.peek().map(|&(_, n)| n);
+            if next.is_none_or(char::is_whitespace) {
+                let end = i + c.len_utf8();
+                sentences.push(&text[start..end]);
+                start = end;
+            }
+        }
+    }
+    if start < text.len() {
+        sentences.push(&text[start..]);
+    }
+    sentences.retain(|s| !s.trim().is_empty());
+    sentences
+}
+
+/// Retire les crédits de sous-titrage et les formules de fin de vidéo.
+/// Retourne le texte restant et le nombre de mots retirés.
+fn remove_known_phrases(text: &str, language: &str) -> (String, usize) {
+    let mut sentences = split_sentences(text);
+    let before = sentences.len();
+    let mut removed = 0;
+
+    sentences.retain(|sentence| {
+        let normalized = normalize(sentence);
+        let is_credit = phrases_for(SUBTITLE_MARKERS, language).any(|m| normalized.contains(m));
+        if is_credit {
+            removed += sentence.split_whitespace().count();
+        }
+        !is_credit
+    });
+
+    while let Some(last) = sentences.last() {
+        let normalized = normalize(last);
+        if !phrases_for(OUTRO_PHRASES, language).any(|p| normalized == p) {
+            break;
+        }
+        removed += last.split_whitespace().count();
+        sentences.pop();
+    }
+
+    if sentences.len() == before {
+        return (text.to_string(), 0);
+    }
+    let kept = sentences
+        .iter()
+        .map(|s| s.trim())
+        .collect::<Vec<_>>()
+        .join(" ");
+    (kept, removed)
+}
+
+/// Clé de comparaison d'un mot : minuscules, sans ponctuation de bord
+fn word_key(word: &str) -> String {
+    word.trim_matches(|c: char| !c.is_alphanumeric())
+        .to_lowercase()
+}
+
+/// Ramène chaque boucle de répétition à une seule occurrence.
+/// Retourne le texte et le nombre de mots retirés.
+fn collapse_loops(text: &str) -> (String, usize) {
+    let words: Vec<&str> = text.split_whitespace().collect();
+    let keys: Vec<String> = words.iter().map(|w| word_key(w)).collect();
+    let mut kept: Vec<&str> = Vec::with_capacity(words.len());
+    let mut removed = 0;
+    let mut i = 0;
+
+    while i < words.len() {
+        // Boucle couvrant le plus de mots à partir de i
+        let mut best: Option<(usize, usize)> = None;
+        for n in 1..=MAX_LOOP_NGRAM.min((words.len() - i) / MIN_LOOP_REPEATS) {
+            let gram = &keys[i..i + n];
+            let mut repeats = 1;
+            while i + (repeats + 1) * n <= words.len()
+                && keys[i + repeats * n..i + (repeats + 1) * n] == *gram
+            {
+                repeats += 1;
+            }
+            let covered = repeats * n;
+            if repeats >= MIN_LOOP_REPEATS
+                && covered >= MIN_LOOP_WORDS
+                && best.is_none_or(|(bn, br)| covered > bn * br)
+            {
+                best = Some((n, repeats));
+            }
+        }
+
+        match best {
+            Some((n, repeats)) => {
+                kept.extend_from_slice(&words[i..i + n]);
+                removed += n * (repeats - 1);
+                i += n * repeats;
+            }
+            None => {
+                kept.push(words[i]);
+                i += 1;
+            }
+        }
+    }
+
+    if removed == 0 {
+        return (text.to_string(), 0);
+    }
+    (kept.join(" "), removed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Une seconde de signal franc
+    fn speech(seconds: f32) -> Vec<f32> {
+        (0..(seconds * WHISPER_SAMPLE_RATE as f32) as usize)
+            .map(|i| if i % 2 == 0 { 0.2 } else { -0.2 })
+            .collect()
+    }
+
+    #[test]
+    fn removes_amara_credit_anywhere() {
+        let text = "Bonjour à tous. Sous-titres réalisés par la communauté d'Amara.org";
+        let d = detect(text, "fr", &speech(2.0));
+        assert_eq!(d.text, "Bonjour à tous.");
+        assert_eq!(d.decisions[0].kind, HallucinationKind::KnownPhrase);
+        assert_eq!(d.decisions[0].words_removed, 6);
+    }
+
+    #[test]
+    fn removes_trailing_outro_only() {
+        let d = detect(
+            "On se voit demain. Merci d'avoir regardé cette vidéo !",
+            "fr",
+            &speech(2.0),
+        );
+        assert_eq!(d.text, "On se voit demain.");
+
+        let kept = "Merci d'avoir regardé cette vidéo ! Elle m'a beaucoup aidé.";
+        assert_eq!(detect(kept, "fr", &speech(3.0)).text, kept);
+    }
+
+    #[test]
+    fn auto_language_checks_every_list() {
+        let d = detect("Thanks for watching!", "auto", &speech(1.0));
+        assert!(d.text.is_empty());
+        assert_eq!(
+            detect("Thanks for watching!", "fr", &speech(1.0)).text,
+            "Thanks for watching!"
+        );
+    }
+
+    #[test]
+    fn collapses_repetition_loops() {
+        let looped = "je pense que c'est bon je pense que c'est bon je pense que c'est bon";
+        let d = detect(looped, "fr", &speech(3.0));
+        assert_eq!(d.text, "je pense que c'est bon");
+        assert_eq!(d.decisions[0].kind, HallucinationKind::RepetitionLoop);
+        assert_eq!(d.decisions[0].words_removed, 10);
+    }
+
+    #[test]
+    fn keeps_short_emphatic_repeats() {
+        let text = "non non non je ne viendrai pas";
+        assert_eq!(detect(text, "fr", &speech(2.0)).text, text);
+    }
+
+    #[test]
+    fn drops_text_on_silent_audio() {
+        let silence = vec![0.001; WHISPER_SAMPLE_RATE as usize * 2];
+        let d = detect("ceci n'a jamais été dit ici", "fr", &silence);
+        assert!(d.text.is_empty());
+        assert_eq!(d.decisions[0].kind, HallucinationKind::SilentAudio);
+        // Un mot isolé est toléré
+        assert_eq!(detect("oui", "fr", &silence).text, "oui");
+    }
+
+    #[test]
+    fn clean_text_is_untouched() {
+        let text = "Réunion demain à 15 h. On valide le budget ?";
+        let d = detect(text, "fr", &speech(3.0));
+        assert_eq!(d.text, text);
+        assert!(d.decisions.is_empty());
+    }
+
+    #[test]
+    fn dot_inside_word_does_not_split() {
+        assert_eq!(split_sentences("Voir amara.org ici. Fin").len(), 2);
+    }
+}
@@ -3,11 +3,18 @@
 /// Architecture (ADR-009) :
 ///   Transcription Whisper
 ///     ↓
+///   [hallucination::detect] — artefacts de sous-titres, boucles, silence
+///     ↓
 ///   [rules::apply] — toujours, < 1ms
 ///     ↓
 ///   confidence >= 0.85 ET words <= 30 ET mode Chat ? → retourner direct
 ///   Sinon → [cleanup::run] — Qwen2.5-0.5B Q4 via llama.cpp
 
+pub mod context;
+pub mod hallucination;
 pub mod modes;
 pub mod orchestrator;
+pub mod recipe;
+pub mod redaction;
+pub mod rich_text;
 pub mod rules;
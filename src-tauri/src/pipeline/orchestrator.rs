@@ -25,6 +25,10 @@ pub struct PipelineResult {
     /// true = le LLM était requis mais a échoué (fallback sur règles)
     pub llm_fallback: bool,
     pub duration_ms: u64,
+    /// Temps passé dans les règles locales et la détection de structure
+    pub rules_ms: u64,
+    /// Temps passé dans le LLM (0 sur le fast-path)
+    pub llm_ms: u64,
     /// Structure détectée (Story 8.1)
     pub structure_hint: StructureHint,
 }
@@ -71,6 +75,7 @@ pub fn process(
 
     let word_count = rules_result.split_whitespace().count();
     let decision = route(confidence, word_count, mode, hint);
+    let rules_ms = start.elapsed().as_millis() as u64;
 
     log::info!(
         "[Routing] confiance={:.2} mots={} mode={:?} structure={:?} → {}",
@@ -79,6 +84,7 @@ pub fn process(
     );
 
     // Étape 2 : LLM conditionnel
+    let llm_start = std::time::Instant::now();
     let (final_text, rules_only, llm_fallback) = match (decision, llm_cleanup_fn) {
         (RoutingDecision::RulesAndLlm, Some(cleanup_fn)) => {
             match cleanup_fn(&rules_result, mode, hint) {
@@ -105,6 +111,8 @@ pub fn process(
         rules_only,
         llm_fallback,
         duration_ms: start.elapsed().as_millis() as u64,
+        rules_ms,
+        llm_ms: llm_start.elapsed().as_millis() as u64,
         structure_hint: hint,
     }
 }
@@ -185,6 +193,19 @@ mod tests {
         assert!(result.rules_only); // fallback sur règles
     }
 
+    #[test]
+    fn test_process_splits_rules_and_llm_time() {
+        let slow_llm =
+            |text: &str, _mode: WriteMode, _hint: StructureHint| -> anyhow::Result<String> {
+                std::thread::sleep(std::time::Duration::from_millis(20));
+                Ok(text.to_string())
+            };
+        let result = process("euh test", 0.60, WriteMode::Chat, Some(&slow_llm));
+        assert!(!result.rules_only);
+        assert!(result.llm_ms >= 20);
+        assert!(result.rules_ms + result.llm_ms <= result.duration_ms + 1);
+    }
+
     #[test]
     fn test_process_list_without_llm_gets_fallback() {
         // Story 8.1 : liste sans LLM → fallback structure appliqué
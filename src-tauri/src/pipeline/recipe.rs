@@ -0,0 +1,214 @@
+//! Recettes de post-traitement multi-étapes
+//!
+//! Une recette enchaîne des étapes locales (règles, structure, gabarit) et des
+//! appels LLM, chaque étape recevant la sortie de la précédente dans
+//! `${output}`. Ce module reste sans réseau : il développe les variables et
+//! exécute les étapes locales ; les étapes LLM sont confiées à l'appelant
+//! (actions.rs), qui gère fournisseurs, streaming et annulation.
+
+use once_cell::sync::Lazy;
+use regex::{Captures, Regex};
+use serde::{Deserialize, Serialize};
+use specta::Type;
+
+use crate::pipeline::rules;
+use crate::settings::RecipeStep;
+
+/// `${nom}` — les noms inconnus sont laissés tels quels
+static VARIABLE_RE: Lazy<Regex> = Lazy::new(|| Regex::new(r"\$\{(\w+)\}").unwrap());
+
+/// Valeurs des variables disponibles dans les gabarits et les prompts
+#[derive(Debug, Clone, Default)]
+pub struct RecipeVariables {
+    /// Transcription Whisper brute, avant les règles
+    pub raw: String,
+    /// Langue de dictée configurée (`auto` si détection automatique)
+    pub language: String,
+    /// Application cible de la dictée (vide si inconnue)
+    pub app: String,
+    /// Date locale au format AAAA-MM-JJ
+    pub date: String,
+    /// Presse-papier texte — lu seulement si une étape utilise `${clipboard}`
+    pub clipboard: String,
+}
+
+/// Sortie d'une étape, conservée dans l'historique
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Type)]
+pub struct RecipeStepOutput {
+    /// "rules" | "structure" | "template" | "llm"
+    pub kind: String,
+    /// Fournisseur et modèle d'une étape LLM
+    pub detail: Option<String>,
+    pub output: String,
+}
+
+fn substitute(template: &str, output: Option<&str>, vars: &RecipeVariables) -> String {
+    // Passe unique : une valeur contenant `${…}` n'est jamais ré-interprétée
+    VARIABLE_RE
+        .replace_all(template, |caps: &Captures| match &caps[1] {
+            "output" => output.map_or_else(|| caps[0].to_string(), str::to_string),
+            "raw" => vars.raw.clone(),
+            "language" => vars.language.clone(),
+            "app" => vars.app.clone(),
+            "date" => vars.date.clone(),
+            "clipboard" => vars.clipboard.clone(),
+            _ => caps[0].to_string(),
+        })
+        .into_owned()
+}
+
+/// Développe toutes les variables, `${output}` compris
+pub fn expand(template: &str, output: &str, vars: &RecipeVariables) -> String {
+    substitute(template, Some(output), vars)
+}
+
+/// Développe les variables de contexte mais laisse `${output}` en place :
+/// le prompt peut alors servir de system prompt (sortie structurée)
+pub fn expand_context(template: &str, vars: &RecipeVariables) -> String {
+    substitute(template, None, vars)
+}
+
+fn step_text(step: &RecipeStep) -> Option<&str> {
+    match step {
+        RecipeStep::Template { template } => Some(template),
+        RecipeStep::Llm { prompt, .. } => Some(prompt),
+        RecipeStep::Rules | RecipeStep::Structure => None,
+    }
+}
+
+/// true si une étape lit le presse-papier (évite de le lire pour rien)
+pub fn uses_clipboard(steps: &[RecipeStep]) -> bool {
+    steps
+        .iter()
+        .filter_map(step_text)
+        .any(|text| text.contains("${clipboard}"))
+}
+
+pub fn step_kind(step: &RecipeStep) -> &'static str {
+    match step {
+        RecipeStep::Rules => "rules",
+        RecipeStep::Structure => "structure",
+        RecipeStep::Template { .. } => "template",
+        RecipeStep::Llm { .. } => "llm",
+    }
+}
+
+/// Exécute une étape locale. `None` pour une étape LLM, laissée à l'appelant.
+pub fn apply_local(step: &RecipeStep, input: &str, vars: &RecipeVariables) -> Option<String> {
+    match step {
+        RecipeStep::Rules => Some(rules::apply(input)),
+        RecipeStep::Structure => Some(rules::apply_structure_fallback(
+            input,
+            rules::detect_structure(input),
+        )),
+        RecipeStep::Template { template } => Some(expand(template, input, vars)),
+        RecipeStep::Llm { .. } => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn vars() -> RecipeVariables {
+        RecipeVariables {
+            raw: "euh bonjour".to_string(),
+            language: "fr".to_string(),
+            app: "Slack".to_string(),
+            date: "2026-03-14".to_string(),
+            clipboard: "PROJ-42".to_string(),
+        }
+    }
+
+    #[test]
+    fn expand_replaces_every_variable() {
+        let out = expand(
+            "${output}|${raw}|${language}|${app}|${date}|${clipboard}",
+            "Bonjour.",
+            &vars(),
+        );
+        assert_eq!(out, "Bonjour.|euh bonjour|fr|Slack|2026-03-14|PROJ-42");
+    }
+
+    #[test]
+    fn unknown_variables_are_kept() {
+        assert_eq!(expand("${ticket} ${output}", "x", &vars()), "${ticket} x");
+    }
+
+    #[test]
+    fn values_are_not_expanded_twice() {
+        // Une dictée qui contient littéralement « ${raw} » ne doit pas être réinterprétée
+        assert_eq!(expand("[${output}]", "${raw}", &vars()), "[${raw}]");
+    }
+
+    #[test]
+    fn expand_context_keeps_output_placeholder() {
+        let out = expand_context("Traduis depuis ${language} : ${output}", &vars());
+        assert_eq!(out, "Traduis depuis fr : ${output}");
+    }
+
+    #[test]
+    fn template_step_is_local() {
+        let step = RecipeStep::Template {
+            template: "[${clipboard}] ${output}".to_string(),
+        };
+        assert_eq!(
+            apply_local(&step, "Corriger le login", &vars()).as_deref(),
+            Some("[PROJ-42] Corriger le login")
+        );
+    }
+
+    #[test]
+    fn rules_step_cleans_fillers() {
+        let out = apply_local(&RecipeStep::Rules, "euh bonjour à tous", &vars()).unwrap();
+        assert!(!out.contains("euh"), "got {out:?}");
+        assert!(out.starts_with('B'));
+    }
+
+    #[test]
+    fn llm_step_is_left_to_caller() {
+        let step = RecipeStep::Llm {
+            prompt: "Translate: ${output}".to_string(),
+            provider_id: None,
+            model: None,
+        };
+        assert_eq!(apply_local(&step, "x", &vars()), None);
+        assert_eq!(step_kind(&step), "llm");
+    }
+
+    #[test]
+    fn clipboard_is_only_read_when_referenced() {
+        let steps = vec![
+            RecipeStep::Rules,
+            RecipeStep::Llm {
+                prompt: "Clean: ${output}".to_string(),
+                provider_id: None,
+                model: None,
+            },
+        ];
+        assert!(!uses_clipboard(&steps));
+
+        let steps = vec![RecipeStep::Template {
+            template: "${clipboard} ${output}".to_string(),
+        }];
+        assert!(uses_clipboard(&steps));
+    }
+
+    #[test]
+    fn steps_deserialize_from_tagged_json() {
+        let steps: Vec<RecipeStep> = serde_json::from_str(
+            r#"[{"type":"rules"},{"type":"llm","prompt":"Translate: ${output}"},{"type":"template","template":"${output}"}]"#,
+        )
+        .unwrap();
+        assert_eq!(steps[0], RecipeStep::Rules);
+        assert_eq!(
+            steps[1],
+            RecipeStep::Llm {
+                prompt: "Translate: ${output}".to_string(),
+                provider_id: None,
+                model: None,
+            }
+        );
+        assert_eq!(step_kind(&steps[2]), "template");
+    }
+}
@@ -0,0 +1,415 @@
+//! Masquage des données personnelles avant un appel LLM cloud
+//!
+//! Emails, téléphones, IBAN, NIR, SIRET et noms d'une liste utilisateur sont
+//! remplacés par des balises stables (`[EMAIL_1]`, `[NOM_2]`…) avant l'envoi,
+//! puis rétablis dans la réponse. Les numéros structurés sont validés par leur
+//! clé de contrôle (mod 97, Luhn) pour limiter les faux positifs. Le journal
+//! d'audit ne contient que les types et les balises, jamais les valeurs.
+
+use std::collections::BTreeMap;
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::Path;
+
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde::Serialize;
+
+static EMAIL_RE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"(?i)\b[a-z0-9._%+-]+@[a-z0-9.-]+\.[a-z]{2,}\b").unwrap());
+
+/// Pays + clé + 11 à 30 caractères, par blocs de 4 éventuellement espacés
+static IBAN_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"\b[A-Z]{2}\d{2}(?: ?[A-Z0-9]{4}){2,7}(?: ?[A-Z0-9]{1,4})?\b").unwrap()
+});
+
+/// Sexe, année, mois, département (2A/2B pour la Corse), commune, ordre, clé
+static NIR_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"\b[12] ?\d{2} ?\d{2} ?(?:\d{2}|2[AB]) ?\d{3} ?\d{3} ?\d{2}\b").unwrap()
+});
+
+static SIRET_RE: Lazy<Regex> = Lazy::new(|| Regex::new(r"\b\d{3} ?\d{3} ?\d{3} ?\d{5}\b").unwrap());
+
+/// Numéros français (0X…, +33, 0033) puis internationaux au format E.164
+static PHONE_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r"(?:(?:\+33|0033)[ .-]?|\b0)[1-9](?:[ .-]?\d{2}){4}\b|\+\d{1,3}(?:[ .-]?\d){6,12}\b",
+    )
+    .unwrap()
+});
+
+/// Balise en cours de génération en fin de texte (`[EMA`, `[NOM_1`)
+static PARTIAL_PLACEHOLDER_RE: Lazy<Regex> = Lazy::new(|| Regex::new(r"\[[A-Z_0-9]*$").unwrap());
+
+#[derive(Serialize, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "snake_case")]
+pub enum PiiKind {
+    Email,
+    Phone,
+    Iban,
+    Nir,
+    Siret,
+    Name,
+}
+
+impl PiiKind {
+    fn label(self) -> &'static str {
+        match self {
+            PiiKind::Email => "EMAIL",
+            PiiKind::Phone => "TEL",
+            PiiKind::Iban => "IBAN",
+            PiiKind::Nir => "NIR",
+            PiiKind::Siret => "SIRET",
+            PiiKind::Name => "NOM",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct RedactedItem {
+    kind: PiiKind,
+    placeholder: String,
+    original: String,
+}
+
+/// Correspondances balise ↔ valeur d'un appel : partagée entre le prompt et
+/// le texte pour qu'une même valeur reçoive la même balise
+#[derive(Debug, Default)]
+pub struct RedactionSession {
+    items: Vec<RedactedItem>,
+}
+
+impl RedactionSession {
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    fn placeholder_for(&mut self, kind: PiiKind, original: &str) -> String {
+        if let Some(item) = self
+            .items
+            .iter()
+            .find(|i| i.kind == kind && i.original == original)
+        {
+            return item.placeholder.clone();
+        }
+        let index = self.items.iter().filter(|i| i.kind == kind).count() + 1;
+        let placeholder = format!("[{}_{}]", kind.label(), index);
+        self.items.push(RedactedItem {
+            kind,
+            placeholder: placeholder.clone(),
+            original: original.to_string(),
+        });
+        placeholder
+    }
+
+    /// Rétablit les valeurs d'origine dans la réponse du LLM
+    pub fn restore(&self, text: &str) -> String {
+        self.items.iter().fold(text.to_string(), |acc, item| {
+            acc.replace(&item.placeholder, &item.original)
+        })
+    }
+
+    /// Comme [`Self::restore`] pour un texte en cours de génération : une
+    /// balise incomplète en fin de texte est retenue jusqu'au token suivant
+    pub fn restore_partial(&self, text: &str) -> String {
+        let restored = self.restore(text);
+        match PARTIAL_PLACEHOLDER_RE.find(&restored) {
+            Some(m) => restored[..m.start()].to_string(),
+            None => restored,
+        }
+    }
+
+    /// Nombre d'éléments masqués par type
+    pub fn counts(&self) -> BTreeMap<PiiKind, usize> {
+        let mut counts = BTreeMap::new();
+        for item in &self.items {
+            *counts.entry(item.kind).or_insert(0) += 1;
+        }
+        counts
+    }
+}
+
+/// Ligne du journal d'audit (JSONL)
+#[derive(Serialize, Debug)]
+pub struct AuditEntry<'a> {
+    pub timestamp: String,
+    pub provider_id: &'a str,
+    pub counts: BTreeMap<PiiKind, usize>,
+    pub placeholders: Vec<&'a str>,
+}
+
+/// Ajoute une ligne au journal d'audit du masquage
+pub fn append_audit(
+    path: &Path,
+    provider_id: &str,
+    session: &RedactionSession,
+) -> std::io::Result<()> {
+    let entry = AuditEntry {
+        timestamp: chrono::Local::now().to_rfc3339(),
+        provider_id,
+        counts: session.counts(),
+        placeholders: session
+            .items
+            .iter()
+            .map(|i| i.placeholder.as_str())
+            .collect(),
+    };
+    let line = serde_json::to_string(&entry)?;
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    writeln!(file, "{}", line)
+}
+
+fn digits(s: &str) -> String {
+    s.chars().filter(|c| !c.is_whitespace()).collect()
+}
+
+/// Reste modulo 97 d'un grand nombre écrit en base 10
+fn mod97(number: &str) -> Option<u32> {
+    number
+        .chars()
+        .try_fold(0u32, |acc, c| c.to_digit(10).map(|d| (acc * 10 + d) % 97))
+}
+
+fn valid_iban(candidate: &str) -> bool {
+    let iban = digits(candidate);
+    if !(15..=34).contains(&iban.len()) {
+        return false;
+    }
+    let (head, tail) = iban.split_at(4);
+    let numeric: String = tail
+        .chars()
+        .chain(head.chars())
+        .map(|c| match c.to_digit(36) {
+            Some(v) => v.to_string(),
+            None => String::new(),
+        })
+        .collect();
+    mod97(&numeric) == Some(1)
+}
+
+fn valid_nir(candidate: &str) -> bool {
+    let nir = digits(candidate);
+    if nir.len() != 15 {
+        return false;
+    }
+    // Corse : 2A → 19, 2B → 18 pour le calcul de la clé
+    let body = nir[..13].replace("2A", "19").replace("2B", "18");
+    let key: u32 = match nir[13..].parse() {
+        Ok(key) => key,
+        Err(_) => return false,
+    };
+    mod97(&body).is_some_and(|r| 97 - r == key)
+}
+
+fn valid_siret(candidate: &str) -> bool {
+    let siret = digits(candidate);
+    if siret.len() != 14 {
+        return false;
+    }
+    let sum: u32 = siret
+        .chars()
+        .rev()
+        .enumerate()
+        .map(|(i, c)| {
+            let d = c.to_digit(10).unwrap_or(0);
+            if i % 2 == 1 {
+                let doubled = d * 2;
+                if doubled > 9 {
+                    doubled - 9
+                } else {
+                    doubled
+                }
+            } else {
+                d
+            }
+        })
+        .sum();
+    sum.is_multiple_of(10)
+}
+
+/// Détecteur configuré avec la liste de noms de l'utilisateur
+pub struct Redactor {
+    names: Option<Regex>,
+}
+
+impl Redactor {
+    pub fn new(names: &[String]) -> Self {
+        let mut names: Vec<&str> = names
+            .iter()
+            .map(|n| n.trim())
+            .filter(|n| !n.is_empty())
+            .collect();
+        // Les noms longs d'abord : « Marie Dupont » avant « Marie »
+        names.sort_by_key(|n| std::cmp::Reverse(n.chars().count()));
+        let names = (!names.is_empty()).then(|| {
+            let alternation = names
+                .iter()
+                .map(|n| regex::escape(n))
+                .collect::<Vec<_>>()
+                .join("|");
+            Regex::new(&format!(r"(?i)\b(?:{})\b", alternation)).unwrap()
+        });
+        Self { names }
+    }
+
+    /// Remplace les données personnelles de `text` par des balises de `session`
+    pub fn redact(&self, session: &mut RedactionSession, text: &str) -> String {
+        // (début, fin, type) — ordre de priorité en cas de chevauchement
+        let mut spans: Vec<(usize, usize, PiiKind)> = Vec::new();
+        let mut collect = |re: &Regex, kind: PiiKind, valid: &dyn Fn(&str) -> bool| {
+            for m in re.find_iter(text) {
+                let overlaps = spans.iter().any(|&(s, e, _)| m.start() < e && s < m.end());
+                if !overlaps && valid(m.as_str()) {
+                    spans.push((m.start(), m.end(), kind));
+                }
+            }
+        };
+        collect(&EMAIL_RE, PiiKind::Email, &|_| true);
+        collect(&IBAN_RE, PiiKind::Iban, &valid_iban);
+        collect(&NIR_RE, PiiKind::Nir, &valid_nir);
+        collect(&SIRET_RE, PiiKind::Siret, &valid_siret);
+        collect(&PHONE_RE, PiiKind::Phone, &|_| true);
+        if let Some(names) = &self.names {
+            collect(names, PiiKind::Name, &|_| true);
+        }
+
+        spans.sort_by_key(|&(start, _, _)| start);
+        let mut out = String::with_capacity(text.len());
+        let mut last = 0;
+        for (start, end, kind) in spans {
+            out.push_str(&text[last..start]);
+            out.push_str(&session.placeholder_for(kind, &text[start..end]));
+            last = end;
+        }
+        out.push_str(&text[last..]);
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn redact(text: &str) -> (String, RedactionSession) {
+        let mut session = RedactionSession::default();
+        let out = Redactor::new(&[]).redact(&mut session, text);
+        (out, session)
+    }
+
+    #[test]
+    fn masks_email_and_phone() {
+        let (out, session) = redact("Écris à jean.martin@exemple.fr ou appelle le 06 12 34 56 78.");
+        assert_eq!(out, "Écris à [EMAIL_1] ou appelle le [TEL_1].");
+        assert_eq!(session.len(), 2);
+    }
+
+    #[test]
+    fn masks_international_phone() {
+        let (out, _) = redact("Mon numéro : +33 6 12 34 56 78, sinon +44 20 7946 0958");
+        assert_eq!(out, "Mon numéro : [TEL_1], sinon [TEL_2]");
+    }
+
+    #[test]
+    fn masks_valid_iban_only() {
+        let (out, _) = redact("IBAN FR76 3000 6000 0112 3456 7890 189 merci");
+        assert_eq!(out, "IBAN [IBAN_1] merci");
+
+        // Clé fausse : laissé tel quel
+        let (out, session) = redact("IBAN FR77 3000 6000 0112 3456 7890 189");
+        assert!(session.is_empty(), "got {out:?}");
+    }
+
+    #[test]
+    fn masks_nir_with_valid_key() {
+        let (out, _) = redact("NIR 1 84 12 76 451 089 46 du salarié");
+        assert_eq!(out, "NIR [NIR_1] du salarié");
+
+        let (_, session) = redact("NIR 1 84 12 76 451 089 47");
+        assert!(session.is_empty());
+    }
+
+    #[test]
+    fn masks_corsican_nir() {
+        // 2A → 19 pour le calcul : 97 - (2841219451089 mod 97) = 80
+        let (out, _) = redact("2 84 12 2A 451 089 80");
+        assert_eq!(out, "[NIR_1]");
+    }
+
+    #[test]
+    fn masks_siret_with_luhn() {
+        let (out, _) = redact("SIRET 732 829 320 00074 du fournisseur");
+        assert_eq!(out, "SIRET [SIRET_1] du fournisseur");
+
+        let (_, session) = redact("SIRET 732 829 320 00075");
+        assert!(session.is_empty());
+    }
+
+    #[test]
+    fn masks_listed_names_case_insensitively() {
+        let mut session = RedactionSession::default();
+        let redactor = Redactor::new(&["Marie Dupont".to_string(), "Marie".to_string()]);
+        let out = redactor.redact(&mut session, "marie dupont a vu Marie hier");
+        assert_eq!(out, "[NOM_1] a vu [NOM_2] hier");
+        // Pas de correspondance au milieu d'un mot
+        let out = redactor.redact(&mut session, "Mariette");
+        assert_eq!(out, "Mariette");
+    }
+
+    #[test]
+    fn same_value_gets_same_placeholder_across_texts() {
+        let mut session = RedactionSession::default();
+        let redactor = Redactor::new(&[]);
+        let prompt = redactor.redact(&mut session, "Réponds à a@b.fr : ${output}");
+        let input = redactor.redact(&mut session, "merci a@b.fr et c@d.fr");
+        assert_eq!(prompt, "Réponds à [EMAIL_1] : ${output}");
+        assert_eq!(input, "merci [EMAIL_1] et [EMAIL_2]");
+    }
+
+    #[test]
+    fn restore_round_trips() {
+        let text = "Contact : a@b.fr, 01 23 45 67 89";
+        let (redacted, session) = redact(text);
+        assert_eq!(session.restore(&redacted), text);
+    }
+
+    #[test]
+    fn restore_handles_ten_or_more_placeholders() {
+        let text = (0..11)
+            .map(|i| format!("u{i}@x.fr"))
+            .collect::<Vec<_>>()
+            .join(" ");
+        let (redacted, session) = redact(&text);
+        assert!(redacted.contains("[EMAIL_11]"));
+        assert_eq!(session.restore(&redacted), text);
+    }
+
+    #[test]
+    fn restore_partial_holds_back_unfinished_placeholder() {
+        let (_, session) = redact("a@b.fr");
+        assert_eq!(session.restore_partial("Écrire à [EMA"), "Écrire à ");
+        assert_eq!(
+            session.restore_partial("Écrire à [EMAIL_1]"),
+            "Écrire à a@b.fr"
+        );
+        assert_eq!(session.restore_partial("Liste [a]"), "Liste [a]");
+    }
+
+    #[test]
+    fn audit_log_never_contains_values() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("pii_audit.jsonl");
+        let (_, session) = redact("a@b.fr et 06 12 34 56 78");
+        append_audit(&path, "openai", &session).unwrap();
+        append_audit(&path, "groq", &session).unwrap();
+
+        let log = std::fs::read_to_string(&path).unwrap();
+        assert_eq!(log.lines().count(), 2);
+        assert!(!log.contains("a@b.fr"));
+        assert!(log.contains("\"email\":1"));
+        assert!(log.contains("[TEL_1]"));
+    }
+}
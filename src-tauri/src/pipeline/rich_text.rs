@@ -0,0 +1,162 @@
+//! Rendu HTML du texte structuré pour le collage riche
+//!
+//! Le pipeline (fallback [`StructureHint`](super::rules::StructureHint) ou
+//! LLM) produit du Markdown minimal : lignes `- item` ou `1. item`, paragraphes
+//! séparés par une ligne vide. Collé tel quel dans Word, Outlook ou Google
+//! Docs, il apparaît avec des tirets littéraux ; ce module en fait des
+//! `<ul>`/`<ol>`/`<p>` à placer en `text/html` à côté du texte brut.
+
+use once_cell::sync::Lazy;
+use regex::Regex;
+
+/// `1. item`, `2) item`
+static ORDERED_ITEM_RE: Lazy<Regex> = Lazy::new(|| Regex::new(r"^\d{1,3}[.)]\s+(.*)$").unwrap());
+
+#[derive(Clone, Copy, PartialEq)]
+enum Block {
+    Paragraph,
+    Unordered,
+    Ordered,
+}
+
+/// HTML équivalent à `text`, ou `None` si le texte n'a aucune structure
+/// (une seule ligne) : le texte brut suffit alors.
+pub fn to_html(text: &str) -> Option<String> {
+    let text = text.trim();
+    if !text.contains('\n') {
+        return None;
+    }
+
+    let mut html = String::with_capacity(text.len() * 2);
+    for chunk in text.split("\n\n") {
+        let mut current: Option<Block> = None;
+        for line in chunk.lines().map(str::trim).filter(|l| !l.is_empty()) {
+            let (block, content) = classify(line);
+            match current {
+                // Ligne suivante du même paragraphe
+                Some(Block::Paragraph) if block == Block::Paragraph => html.push_str("<br>"),
+                Some(open_block) if open_block == block => {}
+                _ => {
+                    close(&mut html, current);
+                    open(&mut html, block);
+                }
+            }
+            match block {
+                Block::Paragraph => html.push_str(&escape(content)),
+                _ => {
+                    html.push_str("<li>");
+                    html.push_str(&escape(content));
+                    html.push_str("</li>");
+                }
+            }
+            current = Some(block);
+        }
+        close(&mut html, current);
+    }
+    Some(html)
+}
+
+fn classify(line: &str) -> (Block, &str) {
+    if let Some(item) = ["- ", "* ", "• "]
+        .iter()
+        .find_map(|marker| line.strip_prefix(marker))
+    {
+        return (Block::Unordered, item.trim());
+    }
+    if let Some(caps) = ORDERED_ITEM_RE.captures(line) {
+        return (
+            Block::Ordered,
+            caps.get(1).map_or("", |m| m.as_str()).trim(),
+        );
+    }
+    (Block::Paragraph, line)
+}
+
+fn open(html: &mut String, block: Block) {
+    html.push_str(match block {
+        Block::Paragraph => "<p>",
+        Block::Unordered => "<ul>",
+        Block::Ordered => "<ol>",
+    });
+}
+
+fn close(html: &mut String, block: Option<Block>) {
+    html.push_str(match block {
+        Some(Block::Paragraph) => "</p>",
+        Some(Block::Unordered) => "</ul>",
+        Some(Block::Ordered) => "</ol>",
+        None => "",
+    });
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pipeline::rules::{apply_structure_fallback, StructureHint};
+
+    #[test]
+    fn single_line_stays_plain() {
+        assert_eq!(to_html("Bonjour tout le monde."), None);
+    }
+
+    #[test]
+    fn list_fallback_becomes_ul() {
+        let text = apply_structure_fallback(
+            "D'abord le lait, ensuite du pain, enfin des œufs.",
+            StructureHint::List,
+        );
+        assert_eq!(
+            to_html(&text).unwrap(),
+            "<ul><li>D'abord le lait</li><li>Ensuite du pain</li><li>Enfin des œufs.</li></ul>"
+        );
+    }
+
+    #[test]
+    fn list_fallback_keeps_intro_paragraph() {
+        let text = apply_structure_fallback(
+            "Pour demain d'abord appeler le client ensuite envoyer le devis",
+            StructureHint::List,
+        );
+        let html = to_html(&text).unwrap();
+        assert!(html.starts_with("<p>Pour demain</p><ul><li>"), "{html}");
+        assert!(html.ends_with("</li></ul>"), "{html}");
+    }
+
+    #[test]
+    fn multi_paragraph_fallback_becomes_p() {
+        let text = apply_structure_fallback(
+            "Le projet avance bien et toutes les fonctionnalités principales sont implémentées. \
+             Les tests couvrent plus de quatre-vingts pourcent du code. Par contre, il reste \
+             quelques bugs à corriger.",
+            StructureHint::MultiParagraph,
+        );
+        let html = to_html(&text).unwrap();
+        assert_eq!(html.matches("<p>").count(), 2, "{html}");
+        assert!(html.contains("</p><p>Par contre"), "{html}");
+    }
+
+    #[test]
+    fn ordered_items_and_line_breaks() {
+        let html = to_html("Étapes :\n1. Ouvrir\n2) Fermer\n\nLigne un\nligne deux").unwrap();
+        assert_eq!(
+            html,
+            "<p>Étapes :</p><ol><li>Ouvrir</li><li>Fermer</li></ol><p>Ligne un<br>ligne deux</p>"
+        );
+    }
+
+    #[test]
+    fn html_is_escaped() {
+        let html = to_html("- a < b & c\n- \"ok\"").unwrap();
+        assert_eq!(
+            html,
+            "<ul><li>a &lt; b &amp; c</li><li>&quot;ok&quot;</li></ul>"
+        );
+    }
+}
@@ -1,11 +1,16 @@
 use log::{debug, warn};
 use serde::de::{self, Visitor};
 use serde::{Deserialize, Deserializer, Serialize};
+use serde_json::{Map, Value};
 use specta::Type;
 use std::collections::HashMap;
 use tauri::AppHandle;
 use tauri_plugin_store::StoreExt;
 
+use crate::pipeline::context::ContextLimits;
+use crate::settings_migrations::{self, SETTINGS_VERSION};
+use crate::whisper_ffi::WhisperPreset;
+
 pub const APPLE_INTELLIGENCE_PROVIDER_ID: &str = "apple_intelligence";
 pub const APPLE_INTELLIGENCE_DEFAULT_MODEL_ID: &str = "Apple Intelligence";
 
@@ -85,6 +90,38 @@ pub struct ShortcutBinding {
     pub current_binding: String,
 }
 
+/// Préfixe des raccourcis de dictée créés par l'utilisateur (`custom_<ms>`)
+pub const CUSTOM_BINDING_PREFIX: &str = "custom_";
+
+pub fn is_custom_binding(id: &str) -> bool {
+    id.len() > CUSTOM_BINDING_PREFIX.len() && id.starts_with(CUSTOM_BINDING_PREFIX)
+}
+
+/// Réglages propres à un raccourci de dictée ; un champ vide reprend le
+/// réglage global
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Type)]
+pub struct BindingProfile {
+    /// "chat" | "pro" | "code"
+    #[serde(default)]
+    pub write_mode: Option<String>,
+    /// Passe la dictée au LLM de post-traitement
+    #[serde(default)]
+    pub post_process: bool,
+    /// Prompt de post-traitement (défaut : prompt sélectionné)
+    #[serde(default)]
+    pub prompt_id: Option<String>,
+    /// Langue de dictée (`auto` ou code ISO)
+    #[serde(default)]
+    pub language: Option<String>,
+    #[serde(default)]
+    pub paste_method: Option<PasteMethod>,
+    #[serde(default)]
+    pub paste_format: Option<PasteFormat>,
+    /// Destinations de la dictée (défaut : celles des réglages globaux)
+    #[serde(default)]
+    pub output_sinks: Option<Vec<OutputSink>>,
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone, Type)]
 pub struct LLMPrompt {
     pub id: String,
@@ -92,17 +129,80 @@ pub struct LLMPrompt {
     pub prompt: String,
 }
 
+/// Étape d'une recette de post-traitement. Les textes (gabarits, prompts)
+/// acceptent `${output}`, `${raw}`, `${language}`, `${app}`, `${date}` et
+/// `${clipboard}`.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Type)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum RecipeStep {
+    /// Règles de nettoyage FR locales (fillers, ponctuation, bégaiements)
+    Rules,
+    /// Mise en forme locale selon la structure détectée (listes, paragraphes)
+    Structure,
+    /// Gabarit sans LLM, ex. `[JIRA] ${output}`
+    Template { template: String },
+    /// Appel LLM — fournisseur et modèle actifs si non précisés
+    Llm {
+        prompt: String,
+        #[serde(default)]
+        provider_id: Option<String>,
+        #[serde(default)]
+        model: Option<String>,
+    },
+}
+
+/// Chaîne d'étapes appliquée à la transcription, ex. nettoyage → traduction
+/// → ticket Jira
+#[derive(Serialize, Deserialize, Debug, Clone, Type)]
+pub struct PostProcessRecipe {
+    pub id: String,
+    pub name: String,
+    pub steps: Vec<RecipeStep>,
+}
+
+/// Protocole d'API d'un fournisseur de post-traitement
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Type)]
+#[serde(rename_all = "kebab-case")]
+pub enum ProviderKind {
+    /// `/chat/completions`, auth Bearer, `response_format` JSON Schema
+    OpenaiCompatible,
+    /// `/messages`, auth `x-api-key`, sortie structurée via un outil forcé
+    AnthropicMessages,
+    /// `/api/chat` d'Ollama, sortie structurée via `format`
+    OllamaNative,
+}
+
+impl Default for ProviderKind {
+    fn default() -> Self {
+        ProviderKind::OpenaiCompatible
+    }
+}
+
+impl ProviderKind {
+    /// Sortie structurée du fournisseur personnalisé : fiable avec les API
+    /// Anthropic et Ollama natives, trop variable derrière un `/chat/completions`
+    /// local (LM Studio, llama.cpp…)
+    pub fn custom_supports_structured_output(self) -> bool {
+        self != ProviderKind::OpenaiCompatible
+    }
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone, Type)]
 pub struct PostProcessProvider {
     pub id: String,
     pub label: String,
     pub base_url: String,
     #[serde(default)]
+    pub kind: ProviderKind,
+    #[serde(default)]
     pub allow_base_url_edit: bool,
     #[serde(default)]
     pub models_endpoint: Option<String>,
     #[serde(default)]
     pub supports_structured_output: bool,
+    /// Timeout par tentative (secondes), sinon selon le fournisseur (local / cloud)
+    #[serde(default)]
+    pub timeout_secs: Option<u64>,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Type)]
@@ -137,6 +237,17 @@ pub enum PasteMethod {
     ExternalScript,
 }
 
+/// Format placé dans le presse-papier par les collages `CtrlV` et
+/// `ShiftInsert` (`CtrlShiftV` et la frappe directe restent en texte brut)
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq, Eq, Type)]
+#[serde(rename_all = "snake_case")]
+pub enum PasteFormat {
+    #[default]
+    Plain,
+    /// `text/plain` + `text/html` : listes et paragraphes arrivent mis en forme
+    Rich,
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Type)]
 #[serde(rename_all = "snake_case")]
 pub enum ClipboardHandling {
@@ -144,6 +255,27 @@ pub enum ClipboardHandling {
     CopyToClipboard,
 }
 
+/// Destination du texte dicté. Plusieurs destinations reçoivent la même dictée.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Type)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum OutputSink {
+    /// Collage dans l'application active (`paste_method`)
+    Paste,
+    /// Ajout au fichier `<directory>/AAAA-MM-JJ.md` ; le gabarit accepte
+    /// `${date}`, `${time}`, `${text}` et `${binding}`
+    MarkdownJournal {
+        directory: String,
+        #[serde(default = "default_journal_template")]
+        template: String,
+    },
+    /// Ajout d'une ligne à un fichier ou à un tube nommé (FIFO)
+    File { path: String },
+    /// Sortie standard du processus (lancement en ligne de commande)
+    Stdout,
+    /// POST JSON vers une URL locale (localhost uniquement)
+    Webhook { url: String },
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Type)]
 #[serde(rename_all = "snake_case")]
 pub enum AutoSubmitKey {
@@ -160,6 +292,57 @@ pub enum RecordingRetentionPeriod {
     Days3,
     Weeks2,
     Months3,
+    /// Supprime les plus anciens enregistrements au-delà de `recording_quota_mb`
+    DiskQuota,
+}
+
+/// Format de stockage des enregistrements de l'historique
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Type)]
+#[serde(rename_all = "snake_case")]
+pub enum RecordingCodec {
+    Wav,
+    /// Sans perte, environ la moitié du WAV
+    Flac,
+    /// Avec perte, au débit `recording_opus_bitrate_kbps`
+    Opus,
+}
+
+impl Default for RecordingCodec {
+    fn default() -> Self {
+        RecordingCodec::Flac
+    }
+}
+
+impl RecordingCodec {
+    pub fn extension(self) -> &'static str {
+        match self {
+            RecordingCodec::Wav => "wav",
+            RecordingCodec::Flac => "flac",
+            RecordingCodec::Opus => "opus",
+        }
+    }
+
+    /// Opus n'est compilé qu'avec la feature `opus`
+    pub fn is_available(self) -> bool {
+        self != RecordingCodec::Opus || cfg!(feature = "opus")
+    }
+
+    /// Une conversion vers ce format ne peut pas être annulée
+    pub fn is_lossy(self) -> bool {
+        self == RecordingCodec::Opus
+    }
+
+    /// Formats utilisables dans cette build
+    pub fn available() -> Vec<RecordingCodec> {
+        [
+            RecordingCodec::Flac,
+            RecordingCodec::Opus,
+            RecordingCodec::Wav,
+        ]
+        .into_iter()
+        .filter(|codec| codec.is_available())
+        .collect()
+    }
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Type)]
@@ -277,6 +460,40 @@ impl Default for TypingTool {
     }
 }
 
+/// Affichage des réponses LLM pendant leur génération
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Type)]
+#[serde(rename_all = "snake_case")]
+pub enum LlmStreamMode {
+    /// Attendre la réponse complète (comportement historique)
+    Off,
+    /// Aperçu du texte dans l'overlay, collage à la fin
+    Preview,
+    /// Frappe au fil de la génération (collage `Direct` uniquement, sinon aperçu)
+    Type,
+}
+
+impl Default for LlmStreamMode {
+    fn default() -> Self {
+        LlmStreamMode::Preview
+    }
+}
+
+/// Masquage des données personnelles avant l'envoi à un fournisseur LLM
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Type)]
+#[serde(rename_all = "snake_case")]
+pub enum RedactionPolicy {
+    /// Fournisseurs cloud uniquement : les serveurs locaux sont exemptés
+    CloudOnly,
+    Always,
+    Never,
+}
+
+impl Default for RedactionPolicy {
+    fn default() -> Self {
+        RedactionPolicy::CloudOnly
+    }
+}
+
 /* still handy for composing the initial JSON in the store ------------- */
 #[derive(Serialize, Deserialize, Debug, Clone, Type)]
 pub struct AppSettings {
@@ -301,6 +518,10 @@ pub struct AppSettings {
     pub selected_microphone: Option<String>,
     #[serde(default)]
     pub clamshell_microphone: Option<String>,
+    /// Micros de secours, par ordre de préférence, quand `selected_microphone`
+    /// est débranché ou absent
+    #[serde(default)]
+    pub microphone_priority: Vec<String>,
     #[serde(default)]
     pub selected_output_device: Option<String>,
     #[serde(default = "default_translate_to_english")]
@@ -323,10 +544,24 @@ pub struct AppSettings {
     pub history_limit: usize,
     #[serde(default = "default_recording_retention_period")]
     pub recording_retention_period: RecordingRetentionPeriod,
+    /// Plafond d'espace disque des enregistrements pour `RecordingRetentionPeriod::DiskQuota`
+    #[serde(default = "default_recording_quota_mb")]
+    pub recording_quota_mb: u32,
+    #[serde(default)]
+    pub recording_codec: RecordingCodec,
+    #[serde(default = "default_recording_opus_bitrate_kbps")]
+    pub recording_opus_bitrate_kbps: u32,
     #[serde(default)]
     pub paste_method: PasteMethod,
     #[serde(default)]
     pub clipboard_handling: ClipboardHandling,
+    #[serde(default)]
+    pub paste_format: PasteFormat,
+    /// Format imposé par application cible (nom de l'application)
+    #[serde(default)]
+    pub app_paste_formats: HashMap<String, PasteFormat>,
+    #[serde(default = "default_output_sinks")]
+    pub output_sinks: Vec<OutputSink>,
     #[serde(default = "default_auto_submit")]
     pub auto_submit: bool,
     #[serde(default)]
@@ -346,6 +581,15 @@ pub struct AppSettings {
     #[serde(default)]
     pub post_process_selected_prompt_id: Option<String>,
     #[serde(default)]
+    pub post_process_recipes: Vec<PostProcessRecipe>,
+    /// Recette associée à un raccourci (id du binding → id de recette) ;
+    /// prioritaire sur le prompt sélectionné
+    #[serde(default)]
+    pub binding_recipes: HashMap<String, String>,
+    /// Profils des raccourcis personnalisés (id du binding → réglages)
+    #[serde(default)]
+    pub binding_profiles: HashMap<String, BindingProfile>,
+    #[serde(default)]
     pub mute_while_recording: bool,
     #[serde(default)]
     pub append_trailing_space: bool,
@@ -359,6 +603,9 @@ pub struct AppSettings {
     pub show_tray_icon: bool,
     #[serde(default = "default_paste_delay_ms")]
     pub paste_delay_ms: u64,
+    /// Délai avant de remettre le presse-papiers remplacé par un collage
+    #[serde(default = "default_clipboard_restore_delay_ms")]
+    pub clipboard_restore_delay_ms: u64,
     #[serde(default = "default_typing_tool")]
     pub typing_tool: TypingTool,
     pub external_script_path: Option<String>,
@@ -368,6 +615,44 @@ pub struct AppSettings {
     /// Touche de déclenchement single-key : "option" | "command" (défaut: "option")
     #[serde(default = "default_trigger_key")]
     pub trigger_key: String,
+    /// Durée max d'une dictée (secondes) — arrêt automatique, avertissement 30 s avant
+    #[serde(default = "default_max_recording_seconds")]
+    pub max_recording_seconds: u32,
+    /// Streaming des réponses LLM : "off" | "preview" | "type" (défaut: "preview")
+    #[serde(default)]
+    pub llm_stream_mode: LlmStreamMode,
+    /// Politique de masquage par fournisseur (défaut : cloud uniquement)
+    #[serde(default)]
+    pub redaction_policies: HashMap<String, RedactionPolicy>,
+    /// Noms propres à masquer avant un appel cloud (clients, collègues…)
+    #[serde(default)]
+    pub redaction_names: Vec<String>,
+    /// Preset de décodage Whisper par modèle (défaut : rapide)
+    #[serde(default)]
+    pub whisper_presets: HashMap<String, WhisperPreset>,
+    /// Dictées récentes passées au nettoyage LLM et au prompt Whisper
+    /// (désactivé par défaut : le texte dicté est réutilisé)
+    #[serde(default)]
+    pub session_context_enabled: bool,
+    /// Nombre max de dictées précédentes dans le contexte
+    #[serde(default = "default_session_context_entries")]
+    pub session_context_entries: usize,
+    /// Pause (secondes) au-delà de laquelle une nouvelle session commence
+    #[serde(default = "default_session_context_timeout_secs")]
+    pub session_context_timeout_secs: u64,
+    /// Budget du contexte, en tokens estimés
+    #[serde(default = "default_session_context_max_tokens")]
+    pub session_context_max_tokens: usize,
+    /// Facteur temps réel maximal (durée de transcription / durée audio) du
+    /// modèle recommandé par le banc d'essai
+    #[serde(default = "default_benchmark_target_rtf")]
+    pub benchmark_target_rtf: f32,
+    /// Sélectionne le modèle recommandé à la fin de chaque banc d'essai
+    #[serde(default)]
+    pub benchmark_auto_select: bool,
+    /// Version du schéma (voir `settings_migrations`) ; 0 avant le versionnage
+    #[serde(default)]
+    pub settings_version: u32,
 }
 
 fn default_model() -> String {
@@ -406,6 +691,10 @@ fn default_trigger_key() -> String {
     "option".to_string()
 }
 
+fn default_max_recording_seconds() -> u32 {
+    600
+}
+
 fn default_overlay_position() -> OverlayPosition {
     #[cfg(target_os = "linux")]
     return OverlayPosition::None;
@@ -429,6 +718,34 @@ fn default_paste_delay_ms() -> u64 {
     60
 }
 
+fn default_clipboard_restore_delay_ms() -> u64 {
+    50
+}
+
+fn default_session_context_entries() -> usize {
+    3
+}
+
+fn default_session_context_timeout_secs() -> u64 {
+    300
+}
+
+fn default_session_context_max_tokens() -> usize {
+    150
+}
+
+fn default_benchmark_target_rtf() -> f32 {
+    0.5
+}
+
+fn default_output_sinks() -> Vec<OutputSink> {
+    vec![OutputSink::Paste]
+}
+
+fn default_journal_template() -> String {
+    "- **${time}** ${text}".to_string()
+}
+
 fn default_auto_submit() -> bool {
     false
 }
@@ -441,6 +758,14 @@ fn default_recording_retention_period() -> RecordingRetentionPeriod {
     RecordingRetentionPeriod::PreserveLimit
 }
 
+fn default_recording_quota_mb() -> u32 {
+    1024
+}
+
+fn default_recording_opus_bitrate_kbps() -> u32 {
+    24
+}
+
 fn default_audio_feedback_volume() -> f32 {
     1.0
 }
@@ -467,55 +792,67 @@ fn default_post_process_provider_id() -> String {
     "openai".to_string()
 }
 
-fn default_post_process_providers() -> Vec<PostProcessProvider> {
+pub(crate) fn default_post_process_providers() -> Vec<PostProcessProvider> {
     let mut providers = vec![
         PostProcessProvider {
             id: "openai".to_string(),
             label: "OpenAI".to_string(),
             base_url: "https://api.openai.com/v1".to_string(),
+            kind: ProviderKind::OpenaiCompatible,
             allow_base_url_edit: false,
             models_endpoint: Some("/models".to_string()),
             supports_structured_output: true,
+            timeout_secs: None,
         },
         PostProcessProvider {
             id: "zai".to_string(),
             label: "Z.AI".to_string(),
             base_url: "https://api.z.ai/api/paas/v4".to_string(),
+            kind: ProviderKind::OpenaiCompatible,
             allow_base_url_edit: false,
             models_endpoint: Some("/models".to_string()),
             supports_structured_output: true,
+            timeout_secs: None,
         },
         PostProcessProvider {
             id: "openrouter".to_string(),
             label: "OpenRouter".to_string(),
             base_url: "https://openrouter.ai/api/v1".to_string(),
+            kind: ProviderKind::OpenaiCompatible,
             allow_base_url_edit: false,
             models_endpoint: Some("/models".to_string()),
             supports_structured_output: true,
+            timeout_secs: None,
         },
         PostProcessProvider {
             id: "anthropic".to_string(),
             label: "Anthropic".to_string(),
             base_url: "https://api.anthropic.com/v1".to_string(),
+            kind: ProviderKind::AnthropicMessages,
             allow_base_url_edit: false,
             models_endpoint: Some("/models".to_string()),
-            supports_structured_output: false,
+            supports_structured_output: true,
+            timeout_secs: None,
         },
         PostProcessProvider {
             id: "groq".to_string(),
             label: "Groq".to_string(),
             base_url: "https://api.groq.com/openai/v1".to_string(),
+            kind: ProviderKind::OpenaiCompatible,
             allow_base_url_edit: false,
             models_endpoint: Some("/models".to_string()),
             supports_structured_output: false,
+            timeout_secs: None,
         },
         PostProcessProvider {
             id: "cerebras".to_string(),
             label: "Cerebras".to_string(),
             base_url: "https://api.cerebras.ai/v1".to_string(),
+            kind: ProviderKind::OpenaiCompatible,
             allow_base_url_edit: false,
             models_endpoint: Some("/models".to_string()),
             supports_structured_output: true,
+            timeout_secs: None,
         },
     ];
 
@@ -529,9 +866,11 @@ fn default_post_process_providers() -> Vec<PostProcessProvider> {
             id: APPLE_INTELLIGENCE_PROVIDER_ID.to_string(),
             label: "Apple Intelligence".to_string(),
             base_url: "apple-intelligence://local".to_string(),
+            kind: ProviderKind::OpenaiCompatible,
             allow_base_url_edit: false,
             models_endpoint: None,
             supports_structured_output: true,
+            timeout_secs: None,
         });
     }
 
@@ -540,9 +879,11 @@ fn default_post_process_providers() -> Vec<PostProcessProvider> {
         id: "custom".to_string(),
         label: "Custom".to_string(),
         base_url: "http://localhost:11434/v1".to_string(),
+        kind: ProviderKind::OpenaiCompatible,
         allow_base_url_edit: true,
         models_endpoint: Some("/models".to_string()),
         supports_structured_output: false,
+        timeout_secs: None,
     });
 
     providers
@@ -556,7 +897,7 @@ fn default_post_process_api_keys() -> HashMap<String, String> {
     map
 }
 
-fn default_model_for_provider(provider_id: &str) -> String {
+pub(crate) fn default_model_for_provider(provider_id: &str) -> String {
     if provider_id == APPLE_INTELLIGENCE_PROVIDER_ID {
         return APPLE_INTELLIGENCE_DEFAULT_MODEL_ID.to_string();
     }
@@ -586,62 +927,6 @@ fn default_typing_tool() -> TypingTool {
     TypingTool::Auto
 }
 
-fn ensure_post_process_defaults(settings: &mut AppSettings) -> bool {
-    let mut changed = false;
-    for provider in default_post_process_providers() {
-        // Use match to do a single lookup - either sync existing or add new
-        match settings
-            .post_process_providers
-            .iter_mut()
-            .find(|p| p.id == provider.id)
-        {
-            Some(existing) => {
-                // Sync supports_structured_output field for existing providers (migration)
-                if existing.supports_structured_output != provider.supports_structured_output {
-                    debug!(
-                        "Updating supports_structured_output for provider '{}' from {} to {}",
-                        provider.id,
-                        existing.supports_structured_output,
-                        provider.supports_structured_output
-                    );
-                    existing.supports_structured_output = provider.supports_structured_output;
-                    changed = true;
-                }
-            }
-            None => {
-                // Provider doesn't exist, add it
-                settings.post_process_providers.push(provider.clone());
-                changed = true;
-            }
-        }
-
-        if !settings.post_process_api_keys.contains_key(&provider.id) {
-            settings
-                .post_process_api_keys
-                .insert(provider.id.clone(), String::new());
-            changed = true;
-        }
-
-        let default_model = default_model_for_provider(&provider.id);
-        match settings.post_process_models.get_mut(&provider.id) {
-            Some(existing) => {
-                if existing.is_empty() && !default_model.is_empty() {
-                    *existing = default_model.clone();
-                    changed = true;
-                }
-            }
-            None => {
-                settings
-                    .post_process_models
-                    .insert(provider.id.clone(), default_model);
-                changed = true;
-            }
-        }
-    }
-
-    changed
-}
-
 pub const SETTINGS_STORE_PATH: &str = "settings_store.json";
 
 pub fn get_default_settings() -> AppSettings {
@@ -694,6 +979,20 @@ pub fn get_default_settings() -> AppSettings {
             current_binding: "escape".to_string(),
         },
     );
+    #[cfg(target_os = "macos")]
+    let default_undo_shortcut = "option+shift+z";
+    #[cfg(not(target_os = "macos"))]
+    let default_undo_shortcut = "alt+shift+z";
+    bindings.insert(
+        "undo_last_dictation".to_string(),
+        ShortcutBinding {
+            id: "undo_last_dictation".to_string(),
+            name: "Annuler la dernière dictée".to_string(),
+            description: "Retire du champ actif le texte de la dernière dictée.".to_string(),
+            default_binding: default_undo_shortcut.to_string(),
+            current_binding: default_undo_shortcut.to_string(),
+        },
+    );
 
     AppSettings {
         bindings,
@@ -708,6 +1007,7 @@ pub fn get_default_settings() -> AppSettings {
         always_on_microphone: false,
         selected_microphone: None,
         clamshell_microphone: None,
+        microphone_priority: Vec::new(),
         selected_output_device: None,
         translate_to_english: false,
         selected_language: "auto".to_string(),
@@ -719,8 +1019,14 @@ pub fn get_default_settings() -> AppSettings {
         word_correction_threshold: default_word_correction_threshold(),
         history_limit: default_history_limit(),
         recording_retention_period: default_recording_retention_period(),
+        recording_quota_mb: default_recording_quota_mb(),
+        recording_codec: RecordingCodec::default(),
+        recording_opus_bitrate_kbps: default_recording_opus_bitrate_kbps(),
         paste_method: PasteMethod::default(),
         clipboard_handling: ClipboardHandling::default(),
+        paste_format: PasteFormat::default(),
+        app_paste_formats: HashMap::new(),
+        output_sinks: default_output_sinks(),
         auto_submit: default_auto_submit(),
         auto_submit_key: AutoSubmitKey::default(),
         post_process_enabled: default_post_process_enabled(),
@@ -730,6 +1036,9 @@ pub fn get_default_settings() -> AppSettings {
         post_process_models: default_post_process_models(),
         post_process_prompts: default_post_process_prompts(),
         post_process_selected_prompt_id: None,
+        post_process_recipes: Vec::new(),
+        binding_recipes: HashMap::new(),
+        binding_profiles: HashMap::new(),
         mute_while_recording: false,
         append_trailing_space: false,
         app_language: default_app_language(),
@@ -737,10 +1046,23 @@ pub fn get_default_settings() -> AppSettings {
         keyboard_implementation: KeyboardImplementation::default(),
         show_tray_icon: default_show_tray_icon(),
         paste_delay_ms: default_paste_delay_ms(),
+        clipboard_restore_delay_ms: default_clipboard_restore_delay_ms(),
         typing_tool: default_typing_tool(),
         external_script_path: None,
         write_mode: default_write_mode(),
         trigger_key: default_trigger_key(),
+        max_recording_seconds: default_max_recording_seconds(),
+        llm_stream_mode: LlmStreamMode::default(),
+        redaction_policies: HashMap::new(),
+        redaction_names: Vec::new(),
+        whisper_presets: HashMap::new(),
+        session_context_enabled: false,
+        session_context_entries: default_session_context_entries(),
+        session_context_timeout_secs: default_session_context_timeout_secs(),
+        session_context_max_tokens: default_session_context_max_tokens(),
+        benchmark_target_rtf: default_benchmark_target_rtf(),
+        benchmark_auto_select: false,
+        settings_version: SETTINGS_VERSION,
     }
 }
 
@@ -757,6 +1079,123 @@ impl AppSettings {
             .find(|provider| provider.id == provider_id)
     }
 
+    /// Recette associée au raccourci, si elle existe encore
+    pub fn recipe_for_binding(&self, binding_id: &str) -> Option<&PostProcessRecipe> {
+        let recipe_id = self.binding_recipes.get(binding_id)?;
+        self.post_process_recipes
+            .iter()
+            .find(|recipe| &recipe.id == recipe_id)
+    }
+
+    /// Clé d'API en clair ; vide si elle est chiffrée et le coffre verrouillé
+    pub fn api_key(&self, provider_id: &str) -> String {
+        self.post_process_api_keys
+            .get(provider_id)
+            .and_then(|key| crate::vault::open_secret(key))
+            .unwrap_or_default()
+    }
+
+    pub fn redaction_policy(&self, provider_id: &str) -> RedactionPolicy {
+        self.redaction_policies
+            .get(provider_id)
+            .copied()
+            .unwrap_or_default()
+    }
+
+    /// Preset de décodage Whisper de `model_id`
+    pub fn whisper_preset(&self, model_id: &str) -> WhisperPreset {
+        self.whisper_presets
+            .get(model_id)
+            .copied()
+            .unwrap_or_default()
+    }
+
+    /// Raccourcis de dictée créés par l'utilisateur
+    pub fn custom_bindings(&self) -> impl Iterator<Item = &ShortcutBinding> {
+        self.bindings
+            .values()
+            .filter(|binding| is_custom_binding(&binding.id))
+    }
+
+    /// Réglages effectifs d'une dictée lancée par `binding_id` : le profil du
+    /// raccourci remplace les réglages globaux qu'il renseigne
+    pub fn for_binding(&self, binding_id: &str) -> AppSettings {
+        let mut settings = self.clone();
+        let Some(profile) = self.binding_profiles.get(binding_id) else {
+            return settings;
+        };
+        if let Some(write_mode) = &profile.write_mode {
+            settings.write_mode = write_mode.clone();
+        }
+        if let Some(prompt_id) = &profile.prompt_id {
+            settings.post_process_selected_prompt_id = Some(prompt_id.clone());
+        }
+        if let Some(language) = &profile.language {
+            settings.selected_language = language.clone();
+        }
+        if let Some(paste_method) = profile.paste_method {
+            settings.paste_method = paste_method;
+        }
+        if let Some(paste_format) = profile.paste_format {
+            settings.paste_format = paste_format;
+        }
+        if let Some(output_sinks) = &profile.output_sinks {
+            settings.output_sinks = output_sinks.clone();
+        }
+        settings
+    }
+
+    /// Format de collage pour l'application `app` : la préférence de
+    /// l'application l'emporte sur celle du raccourci et sur le réglage global
+    pub fn paste_format_for_app(&self, app: Option<&str>) -> PasteFormat {
+        app.and_then(|app| self.app_paste_formats.get(app))
+            .copied()
+            .unwrap_or(self.paste_format)
+    }
+
+    /// Bornes du contexte de session, `None` s'il est désactivé
+    pub fn session_context_limits(&self) -> Option<ContextLimits> {
+        self.session_context_enabled.then(|| ContextLimits {
+            max_entries: self.session_context_entries,
+            inactivity_secs: self.session_context_timeout_secs as i64,
+            max_tokens: self.session_context_max_tokens,
+        })
+    }
+
+    /// Réglages sous forme d'objet JSON, clé par champ
+    pub fn to_fields(&self) -> Map<String, Value> {
+        match serde_json::to_value(self) {
+            Ok(Value::Object(fields)) => fields,
+            _ => unreachable!("AppSettings serializes to an object"),
+        }
+    }
+
+    /// Copie dont les champs `fields` sont remplacés ; une clé inconnue ou une
+    /// valeur du mauvais type est une erreur
+    pub fn with_fields(&self, fields: &Map<String, Value>) -> Result<AppSettings, String> {
+        let mut merged = self.to_fields();
+        for (key, value) in fields {
+            if !merged.contains_key(key) {
+                return Err(format!("Unknown setting '{}'", key));
+            }
+            merged.insert(key.clone(), value.clone());
+        }
+        serde_json::from_value(Value::Object(merged))
+            .map_err(|e| format!("Invalid settings: {}", e))
+    }
+
+    /// true si la dictée est collée dans l'application active
+    pub fn pastes_output(&self) -> bool {
+        self.output_sinks.contains(&OutputSink::Paste)
+    }
+
+    /// true si le profil du raccourci demande le post-traitement LLM
+    pub fn binding_post_process(&self, binding_id: &str) -> bool {
+        self.binding_profiles
+            .get(binding_id)
+            .is_some_and(|profile| profile.post_process)
+    }
+
     pub fn post_process_provider_mut(
         &mut self,
         provider_id: &str,
@@ -767,6 +1206,17 @@ impl AppSettings {
     }
 }
 
+/// Réglages du store, migrés au schéma courant ; `true` s'ils ont été migrés
+/// et doivent être réécrits. Des réglages d'une version plus récente de
+/// l'application sont lus tels quels.
+fn parse_stored(mut value: Value) -> Result<(AppSettings, bool), serde_json::Error> {
+    let migrated = settings_migrations::migrate(&mut value).unwrap_or_else(|e| {
+        warn!("{}", e);
+        false
+    });
+    serde_json::from_value::<AppSettings>(value).map(|settings| (settings, migrated))
+}
+
 pub fn load_or_create_app_settings(app: &AppHandle) -> AppSettings {
     // Initialize store
     let store = app
@@ -775,11 +1225,11 @@ pub fn load_or_create_app_settings(app: &AppHandle) -> AppSettings {
 
     let mut settings = if let Some(settings_value) = store.get("settings") {
         // Parse the entire settings object
-        match serde_json::from_value::<AppSettings>(settings_value) {
-            Ok(mut settings) => {
+        match parse_stored(settings_value) {
+            Ok((mut settings, migrated)) => {
                 debug!("Found existing settings: {:?}", settings);
                 let default_settings = get_default_settings();
-                let mut updated = false;
+                let mut updated = migrated;
 
                 // Merge default bindings into existing settings
                 for (key, value) in default_settings.bindings {
@@ -791,7 +1241,7 @@ pub fn load_or_create_app_settings(app: &AppHandle) -> AppSettings {
                 }
 
                 if updated {
-                    debug!("Settings updated with new bindings");
+                    debug!("Settings updated with migrations or new bindings");
                     store.set("settings", serde_json::to_value(&settings).unwrap());
                 }
 
@@ -811,7 +1261,8 @@ pub fn load_or_create_app_settings(app: &AppHandle) -> AppSettings {
         default_settings
     };
 
-    if ensure_post_process_defaults(&mut settings) {
+    // Réglages verrouillés par l'équipe et fournisseurs interdits
+    if crate::baseline::enforce(&mut settings) {
         store.set("settings", serde_json::to_value(&settings).unwrap());
     }
 
@@ -823,26 +1274,29 @@ pub fn get_settings(app: &AppHandle) -> AppSettings {
         .store(SETTINGS_STORE_PATH)
         .expect("Failed to initialize store");
 
-    let mut settings = if let Some(settings_value) = store.get("settings") {
-        serde_json::from_value::<AppSettings>(settings_value).unwrap_or_else(|_| {
-            let default_settings = get_default_settings();
-            store.set("settings", serde_json::to_value(&default_settings).unwrap());
-            default_settings
-        })
+    if let Some(settings_value) = store.get("settings") {
+        match parse_stored(settings_value) {
+            Ok((settings, migrated)) => {
+                if migrated {
+                    store.set("settings", serde_json::to_value(&settings).unwrap());
+                }
+                settings
+            }
+            Err(_) => {
+                let default_settings = get_default_settings();
+                store.set("settings", serde_json::to_value(&default_settings).unwrap());
+                default_settings
+            }
+        }
     } else {
         let default_settings = get_default_settings();
         store.set("settings", serde_json::to_value(&default_settings).unwrap());
         default_settings
-    };
-
-    if ensure_post_process_defaults(&mut settings) {
-        store.set("settings", serde_json::to_value(&settings).unwrap());
     }
-
-    settings
 }
 
-pub fn write_settings(app: &AppHandle, settings: AppSettings) {
+pub fn write_settings(app: &AppHandle, mut settings: AppSettings) {
+    crate::baseline::enforce(&mut settings);
     let store = app
         .store(SETTINGS_STORE_PATH)
         .expect("Failed to initialize store");
@@ -962,6 +1416,7 @@ mod tests {
         assert!(settings.bindings.contains_key("transcribe"));
         assert!(settings.bindings.contains_key("transcribe_with_post_process"));
         assert!(settings.bindings.contains_key("cancel"));
+        assert!(settings.bindings.contains_key("undo_last_dictation"));
     }
 
     #[test]
@@ -995,6 +1450,12 @@ mod tests {
         );
     }
 
+    #[test]
+    fn default_max_recording_is_ten_minutes() {
+        let settings = get_default_settings();
+        assert_eq!(settings.max_recording_seconds, 600);
+    }
+
     // ── Story 2.1 : Thème sonore Cahier ──
 
     #[test]
@@ -1047,4 +1508,193 @@ mod tests {
         let deserialized: SoundTheme = serde_json::from_str("\"cahier\"").unwrap();
         assert_eq!(deserialized, SoundTheme::Cahier, "\"cahier\" doit se désérialiser en SoundTheme::Cahier");
     }
+
+    #[test]
+    fn recipe_for_binding_ignores_deleted_recipe() {
+        let mut settings = get_default_settings();
+        settings.post_process_recipes.push(PostProcessRecipe {
+            id: "recipe_jira".to_string(),
+            name: "Jira".to_string(),
+            steps: vec![RecipeStep::Rules],
+        });
+        settings
+            .binding_recipes
+            .insert("transcribe".to_string(), "recipe_jira".to_string());
+        settings.binding_recipes.insert(
+            "transcribe_with_post_process".to_string(),
+            "gone".to_string(),
+        );
+
+        assert_eq!(
+            settings
+                .recipe_for_binding("transcribe")
+                .map(|r| r.name.as_str()),
+            Some("Jira")
+        );
+        assert!(settings
+            .recipe_for_binding("transcribe_with_post_process")
+            .is_none());
+        assert!(settings.recipe_for_binding("cancel").is_none());
+    }
+
+    #[test]
+    fn redaction_policy_defaults_to_cloud_only() {
+        let mut settings = get_default_settings();
+        assert_eq!(
+            settings.redaction_policy("openai"),
+            RedactionPolicy::CloudOnly
+        );
+        settings
+            .redaction_policies
+            .insert("openai".to_string(), RedactionPolicy::Never);
+        assert_eq!(settings.redaction_policy("openai"), RedactionPolicy::Never);
+    }
+
+    #[test]
+    fn whisper_preset_defaults_to_fast() {
+        let mut settings = get_default_settings();
+        assert_eq!(settings.whisper_preset("small"), WhisperPreset::Fast);
+        settings
+            .whisper_presets
+            .insert("small".to_string(), WhisperPreset::Accurate);
+        assert_eq!(settings.whisper_preset("small"), WhisperPreset::Accurate);
+        assert_eq!(settings.whisper_preset("base"), WhisperPreset::Fast);
+    }
+
+    #[test]
+    fn custom_binding_ids_need_the_prefix() {
+        assert!(is_custom_binding("custom_1718000000000"));
+        assert!(!is_custom_binding("custom_"));
+        assert!(!is_custom_binding("transcribe"));
+        assert!(!is_custom_binding("my_custom_1"));
+    }
+
+    #[test]
+    fn binding_profile_overrides_only_what_it_sets() {
+        let mut settings = get_default_settings();
+        settings.write_mode = "chat".to_string();
+        settings.paste_method = PasteMethod::CtrlV;
+        settings.binding_profiles.insert(
+            "custom_1".to_string(),
+            BindingProfile {
+                write_mode: Some("code".to_string()),
+                language: Some("en".to_string()),
+                ..Default::default()
+            },
+        );
+
+        let effective = settings.for_binding("custom_1");
+        assert_eq!(effective.write_mode, "code");
+        assert_eq!(effective.selected_language, "en");
+        assert_eq!(effective.paste_method, PasteMethod::CtrlV);
+        assert_eq!(
+            effective.post_process_selected_prompt_id,
+            settings.post_process_selected_prompt_id
+        );
+        assert!(!settings.binding_post_process("custom_1"));
+
+        // Sans profil, les réglages globaux s'appliquent tels quels
+        assert_eq!(settings.for_binding("transcribe").write_mode, "chat");
+    }
+
+    #[test]
+    fn output_sinks_default_to_paste_and_follow_binding_profile() {
+        let mut settings = get_default_settings();
+        assert!(settings.pastes_output());
+
+        let sinks: Vec<OutputSink> = serde_json::from_str(
+            r#"[{"type":"markdown_journal","directory":"/tmp/journal"},{"type":"stdout"}]"#,
+        )
+        .unwrap();
+        assert_eq!(
+            sinks[0],
+            OutputSink::MarkdownJournal {
+                directory: "/tmp/journal".to_string(),
+                template: default_journal_template(),
+            }
+        );
+        settings.binding_profiles.insert(
+            "custom_1".to_string(),
+            BindingProfile {
+                output_sinks: Some(sinks.clone()),
+                ..Default::default()
+            },
+        );
+
+        let effective = settings.for_binding("custom_1");
+        assert_eq!(effective.output_sinks, sinks);
+        assert!(!effective.pastes_output());
+        assert!(settings.for_binding("transcribe").pastes_output());
+    }
+
+    #[test]
+    fn app_paste_format_wins_over_binding_profile() {
+        let mut settings = get_default_settings();
+        assert_eq!(
+            settings.paste_format_for_app(Some("Word")),
+            PasteFormat::Plain
+        );
+
+        settings.binding_profiles.insert(
+            "custom_1".to_string(),
+            BindingProfile {
+                paste_format: Some(PasteFormat::Rich),
+                ..Default::default()
+            },
+        );
+        settings
+            .app_paste_formats
+            .insert("Terminal".to_string(), PasteFormat::Plain);
+
+        let effective = settings.for_binding("custom_1");
+        assert_eq!(effective.paste_format_for_app(None), PasteFormat::Rich);
+        assert_eq!(
+            effective.paste_format_for_app(Some("Word")),
+            PasteFormat::Rich
+        );
+        assert_eq!(
+            effective.paste_format_for_app(Some("Terminal")),
+            PasteFormat::Plain
+        );
+    }
+    #[test]
+    fn with_fields_rejects_unknown_keys_and_bad_values() {
+        let settings = get_default_settings();
+        let fields = serde_json::json!({ "push_to_talk": false, "history_limit": 12 });
+        let updated = settings
+            .with_fields(fields.as_object().unwrap())
+            .expect("valid fields");
+        assert!(!updated.push_to_talk);
+        assert_eq!(updated.history_limit, 12);
+
+        let unknown = serde_json::json!({ "no_such_setting": 1 });
+        assert!(settings.with_fields(unknown.as_object().unwrap()).is_err());
+        let bad_type = serde_json::json!({ "history_limit": "many" });
+        assert!(settings.with_fields(bad_type.as_object().unwrap()).is_err());
+    }
+
+    #[test]
+    fn only_lossless_codecs_are_always_available() {
+        let codecs = RecordingCodec::available();
+        assert!(codecs.contains(&RecordingCodec::Flac));
+        assert!(codecs.contains(&RecordingCodec::Wav));
+        assert_eq!(
+            codecs.contains(&RecordingCodec::Opus),
+            cfg!(feature = "opus")
+        );
+        assert!(RecordingCodec::Opus.is_lossy());
+        assert!(!RecordingCodec::Flac.is_lossy());
+    }
+
+    #[test]
+    fn unversioned_store_is_migrated_on_load() {
+        let mut stored = get_default_settings().to_fields();
+        stored.remove("settings_version");
+        stored.insert("log_level".to_string(), Value::from(4));
+
+        let (settings, migrated) = parse_stored(Value::Object(stored)).expect("parse");
+        assert!(migrated);
+        assert_eq!(settings.log_level, LogLevel::Warn);
+        assert_eq!(settings.settings_version, SETTINGS_VERSION);
+    }
 }
@@ -0,0 +1,344 @@
+//! Export et import des réglages en paquet JSON portable
+//!
+//! Le paquet contient les réglages sans ce qui est propre à la machine
+//! (micros, sortie audio, script externe, démarrage automatique). Les clés
+//! d'API n'y figurent que sur demande, en clair. À l'import, le paquet est
+//! migré au schéma courant, fusionné avec les réglages actuels puis validé ;
+//! l'aperçu liste les réglages modifiés avant l'application.
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use specta::Type;
+
+use crate::baseline::TeamBaseline;
+use crate::settings::AppSettings;
+use crate::settings_migrations::{self, SETTINGS_VERSION};
+use crate::vault;
+
+/// Valeur de `format` des paquets d'export
+pub const BUNDLE_FORMAT: &str = "dictation-ia-settings";
+
+/// Réglages liés au matériel ou aux chemins de cette machine
+const MACHINE_SPECIFIC_KEYS: &[&str] = &[
+    "selected_microphone",
+    "clamshell_microphone",
+    "microphone_priority",
+    "selected_output_device",
+    "external_script_path",
+    "keyboard_implementation",
+    "autostart_enabled",
+];
+
+const API_KEYS_FIELD: &str = "post_process_api_keys";
+/// Affiché à la place d'une clé d'API dans l'aperçu
+const MASKED_SECRET: &str = "••••••";
+
+/// Fichier d'export
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct SettingsBundle {
+    pub format: String,
+    pub settings_version: u32,
+    #[serde(default)]
+    pub app_version: String,
+    /// Timestamp Unix (secondes)
+    #[serde(default)]
+    pub exported_at: i64,
+    pub settings: Map<String, Value>,
+}
+
+/// Réglage modifié par l'import, valeurs en JSON compact
+#[derive(Serialize, Debug, Clone, PartialEq, Type)]
+pub struct SettingChange {
+    pub key: String,
+    pub current: String,
+    pub incoming: String,
+}
+
+/// Aperçu d'un import avant application
+#[derive(Serialize, Debug, Clone, Type)]
+pub struct ImportPreview {
+    pub app_version: String,
+    pub exported_at: i64,
+    pub settings_version: u32,
+    pub changes: Vec<SettingChange>,
+    /// Réglages ignorés (inconnus, propres à la machine, verrouillés)
+    pub warnings: Vec<String>,
+}
+
+/// Paquet d'export de `settings` ; les clés d'API chiffrées sont mises en
+/// clair, ce qui demande un coffre déverrouillé
+pub fn export(
+    settings: &AppSettings,
+    include_api_keys: bool,
+    exported_at: i64,
+) -> Result<SettingsBundle, String> {
+    let mut fields = settings.to_fields();
+    fields.remove("settings_version");
+    for key in MACHINE_SPECIFIC_KEYS {
+        fields.remove(*key);
+    }
+
+    if include_api_keys {
+        let mut keys = Map::new();
+        for (provider, key) in &settings.post_process_api_keys {
+            let plain = vault::open_secret(key)
+                .ok_or_else(|| "Unlock the vault to export API keys".to_string())?;
+            keys.insert(provider.clone(), Value::from(plain));
+        }
+        fields.insert(API_KEYS_FIELD.to_string(), Value::Object(keys));
+    } else {
+        fields.remove(API_KEYS_FIELD);
+    }
+
+    Ok(SettingsBundle {
+        format: BUNDLE_FORMAT.to_string(),
+        settings_version: SETTINGS_VERSION,
+        app_version: env!("CARGO_PKG_VERSION").to_string(),
+        exported_at,
+        settings: fields,
+    })
+}
+
+/// Lit un fichier d'export et le migre au schéma courant
+pub fn parse(json: &str) -> Result<SettingsBundle, String> {
+    let mut bundle: SettingsBundle =
+        serde_json::from_str(json).map_err(|e| format!("Invalid settings file: {}", e))?;
+    if bundle.format != BUNDLE_FORMAT {
+        return Err(format!(
+            "Not a settings export (format '{}')",
+            bundle.format
+        ));
+    }
+
+    let mut settings = Value::Object(std::mem::take(&mut bundle.settings));
+    settings["settings_version"] = Value::from(bundle.settings_version);
+    settings_migrations::migrate(&mut settings)?;
+    if let Value::Object(mut fields) = settings {
+        fields.remove("settings_version");
+        bundle.settings = fields;
+    }
+    Ok(bundle)
+}
+
+/// Réglages obtenus en important `bundle` par-dessus `current`, avec l'aperçu
+/// des changements. Les clés d'API absentes ou vides du paquet gardent leur
+/// valeur actuelle ; les réglages verrouillés par `baseline` ne bougent pas.
+pub fn prepare_import(
+    current: &AppSettings,
+    bundle: &SettingsBundle,
+    baseline: Option<&TeamBaseline>,
+) -> Result<(AppSettings, ImportPreview), String> {
+    let known = current.to_fields();
+    let mut fields = Map::new();
+    let mut warnings = Vec::new();
+
+    for (key, value) in &bundle.settings {
+        if !known.contains_key(key) {
+            warnings.push(format!("Unknown setting '{}' ignored", key));
+        } else if MACHINE_SPECIFIC_KEYS.contains(&key.as_str()) || key == "settings_version" {
+            warnings.push(format!("Machine-specific setting '{}' ignored", key));
+        } else if baseline.is_some_and(|baseline| baseline.is_locked(key)) {
+            if known.get(key) != Some(value) {
+                warnings.push(format!("'{}' is locked by the team baseline", key));
+            }
+        } else if key == API_KEYS_FIELD {
+            fields.insert(key.clone(), merge_api_keys(current, value)?);
+        } else {
+            fields.insert(key.clone(), value.clone());
+        }
+    }
+
+    let mut next = current.with_fields(&fields)?;
+    next.settings_version = SETTINGS_VERSION;
+    if let Some(baseline) = baseline {
+        baseline.enforce(&mut next);
+    }
+
+    let preview = ImportPreview {
+        app_version: bundle.app_version.clone(),
+        exported_at: bundle.exported_at,
+        settings_version: bundle.settings_version,
+        changes: diff(current, &next),
+        warnings,
+    };
+    Ok((next, preview))
+}
+
+/// Clés d'API actuelles complétées par celles, non vides, du paquet ;
+/// chiffrées si le coffre est activé
+fn merge_api_keys(current: &AppSettings, incoming: &Value) -> Result<Value, String> {
+    let incoming = incoming
+        .as_object()
+        .ok_or_else(|| format!("Invalid '{}'", API_KEYS_FIELD))?;
+    let mut keys = current.post_process_api_keys.clone();
+    for (provider, key) in incoming {
+        let Some(key) = key.as_str().filter(|key| !key.is_empty()) else {
+            continue;
+        };
+        let sealed = vault::seal_secret(key).map_err(|e| e.to_string())?;
+        keys.insert(provider.clone(), sealed);
+    }
+    Ok(serde_json::to_value(keys).expect("API keys serialize"))
+}
+
+/// Réglages qui diffèrent entre `current` et `next`, triés par clé ; les clés
+/// d'API sont comparées en clair et masquées
+pub fn diff(current: &AppSettings, next: &AppSettings) -> Vec<SettingChange> {
+    let before = current.to_fields();
+    let after = next.to_fields();
+    let mut changes: Vec<SettingChange> = after
+        .iter()
+        .filter(|(key, _)| key.as_str() != API_KEYS_FIELD)
+        .filter(|(key, value)| before.get(*key) != Some(*value))
+        .map(|(key, value)| SettingChange {
+            key: key.clone(),
+            current: before.get(key).map(Value::to_string).unwrap_or_default(),
+            incoming: value.to_string(),
+        })
+        .collect();
+
+    for (provider, key) in &next.post_process_api_keys {
+        let old = current
+            .post_process_api_keys
+            .get(provider)
+            .and_then(|old| vault::open_secret(old))
+            .unwrap_or_default();
+        let new = vault::open_secret(key).unwrap_or_default();
+        if old != new {
+            changes.push(SettingChange {
+                key: format!("{}.{}", API_KEYS_FIELD, provider),
+                current: mask(&old),
+                incoming: mask(&new),
+            });
+        }
+    }
+
+    changes.sort_by(|a, b| a.key.cmp(&b.key));
+    changes
+}
+
+fn mask(secret: &str) -> String {
+    if secret.is_empty() {
+        String::new()
+    } else {
+        MASKED_SECRET.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::settings::get_default_settings;
+
+    fn roundtrip(bundle: &SettingsBundle) -> SettingsBundle {
+        parse(&serde_json::to_string(bundle).unwrap()).expect("parse bundle")
+    }
+
+    #[test]
+    fn export_drops_machine_settings_and_api_keys_by_default() {
+        let mut settings = get_default_settings();
+        settings.selected_microphone = Some("USB Mic".to_string());
+        settings
+            .post_process_api_keys
+            .insert("openai".to_string(), "sk-secret".to_string());
+
+        let bundle = export(&settings, false, 1_700_000_000).unwrap();
+        assert_eq!(bundle.settings_version, SETTINGS_VERSION);
+        assert!(!bundle.settings.contains_key("selected_microphone"));
+        assert!(!bundle.settings.contains_key(API_KEYS_FIELD));
+        assert!(!serde_json::to_string(&bundle)
+            .unwrap()
+            .contains("sk-secret"));
+
+        let with_keys = export(&settings, true, 1_700_000_000).unwrap();
+        assert_eq!(with_keys.settings[API_KEYS_FIELD]["openai"], "sk-secret");
+    }
+
+    #[test]
+    fn import_previews_changes_and_keeps_local_settings() {
+        let mut source = get_default_settings();
+        source.history_limit = 42;
+        source.push_to_talk = !source.push_to_talk;
+        source
+            .post_process_api_keys
+            .insert("groq".to_string(), "gsk-new".to_string());
+        let bundle = roundtrip(&export(&source, true, 0).unwrap());
+
+        let mut current = get_default_settings();
+        current.selected_microphone = Some("Built-in".to_string());
+        current
+            .post_process_api_keys
+            .insert("openai".to_string(), "sk-mine".to_string());
+
+        let (next, preview) = prepare_import(&current, &bundle, None).unwrap();
+        assert_eq!(next.history_limit, 42);
+        assert_eq!(next.selected_microphone.as_deref(), Some("Built-in"));
+        assert_eq!(next.post_process_api_keys["openai"], "sk-mine");
+        assert_eq!(next.post_process_api_keys["groq"], "gsk-new");
+
+        let keys: Vec<_> = preview.changes.iter().map(|c| c.key.as_str()).collect();
+        assert_eq!(
+            keys,
+            vec![
+                "history_limit",
+                "post_process_api_keys.groq",
+                "push_to_talk"
+            ]
+        );
+        let groq = &preview.changes[1];
+        assert_eq!(
+            (groq.current.as_str(), groq.incoming.as_str()),
+            ("", MASKED_SECRET)
+        );
+    }
+
+    #[test]
+    fn old_bundles_are_migrated_and_unknown_keys_reported() {
+        let json = format!(
+            r#"{{"format":"{}","settings_version":0,"settings":{{"log_level":5,"legacy_flag":true,"post_process_api_keys":{{}}}}}}"#,
+            BUNDLE_FORMAT
+        );
+        let bundle = parse(&json).unwrap();
+        assert_eq!(bundle.settings["post_process_api_keys"]["groq"], "");
+
+        let current = get_default_settings();
+        let (next, preview) = prepare_import(&current, &bundle, None).unwrap();
+        assert_eq!(next.log_level, crate::settings::LogLevel::Error);
+        assert_eq!(
+            preview.warnings,
+            vec!["Unknown setting 'legacy_flag' ignored"]
+        );
+    }
+
+    #[test]
+    fn rejects_foreign_and_newer_files() {
+        assert!(parse(r#"{"format":"other","settings_version":0,"settings":{}}"#).is_err());
+        let newer = format!(
+            r#"{{"format":"{}","settings_version":{},"settings":{{}}}}"#,
+            BUNDLE_FORMAT,
+            SETTINGS_VERSION + 1
+        );
+        assert!(parse(&newer).is_err());
+    }
+
+    #[test]
+    fn locked_settings_are_not_imported() {
+        let baseline = TeamBaseline::parse(
+            r#"{"name":"Team","settings":{"history_limit":10},"locked":["history_limit"]}"#,
+        )
+        .unwrap();
+        let mut source = get_default_settings();
+        source.history_limit = 99;
+        let bundle = export(&source, false, 0).unwrap();
+
+        let mut current = get_default_settings();
+        baseline.apply_defaults(&mut current).unwrap();
+        let (next, preview) = prepare_import(&current, &bundle, Some(&baseline)).unwrap();
+        assert_eq!(next.history_limit, 10);
+        assert!(preview.changes.is_empty(), "{:?}", preview.changes);
+        assert_eq!(
+            preview.warnings,
+            vec!["'history_limit' is locked by the team baseline"]
+        );
+    }
+}
@@ -0,0 +1,195 @@
+//! Migrations du schéma des réglages (`settings_version`)
+//!
+//! Les nouveaux champs arrivent avec `#[serde(default)]` et ne demandent rien.
+//! Les changements de forme (valeur renommée, type modifié) passent par une
+//! migration numérotée, appliquée au JSON brut avant la désérialisation — au
+//! chargement du store comme à l'import d'un export plus ancien.
+//!
+//! Pour ajouter une migration : écrire `migrate_vN` (version N-1 → N), l'ajouter
+//! à la fin de [`MIGRATIONS`] ; [`SETTINGS_VERSION`] suit automatiquement.
+
+use log::{info, warn};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use serde_json::{Map, Value};
+use std::collections::HashMap;
+
+use crate::settings::{
+    default_model_for_provider, default_post_process_providers, PostProcessProvider,
+};
+
+type Migration = fn(&mut Map<String, Value>);
+
+/// `MIGRATIONS[i]` fait passer les réglages de la version `i` à `i + 1`
+const MIGRATIONS: &[Migration] = &[migrate_v1];
+
+/// Version du schéma écrite par cette version de l'application
+pub const SETTINGS_VERSION: u32 = MIGRATIONS.len() as u32;
+
+/// v0 → v1 : fournisseurs de post-traitement par défaut complétés (voir
+/// [`backfill_post_process_providers`])
+fn migrate_v1(settings: &mut Map<String, Value>) {
+    backfill_post_process_providers(settings);
+}
+
+/// Ajoute les fournisseurs par défaut absents (avec clé d'API vide et modèle
+/// par défaut) et réaligne le protocole de ceux qui existent. Le fournisseur
+/// personnalisé garde le protocole choisi par l'utilisateur. À rappeler depuis
+/// une nouvelle migration quand un fournisseur par défaut apparaît ou change.
+fn backfill_post_process_providers(settings: &mut Map<String, Value>) {
+    let defaults = default_post_process_providers();
+
+    if let Some(mut providers) =
+        field::<Vec<PostProcessProvider>>(settings, "post_process_providers")
+    {
+        for provider in &defaults {
+            match providers.iter_mut().find(|p| p.id == provider.id) {
+                Some(existing) if existing.allow_base_url_edit => {
+                    existing.supports_structured_output =
+                        existing.kind.custom_supports_structured_output();
+                }
+                Some(existing) => {
+                    existing.kind = provider.kind;
+                    existing.supports_structured_output = provider.supports_structured_output;
+                }
+                None => providers.push(provider.clone()),
+            }
+        }
+        set_field(settings, "post_process_providers", &providers);
+    }
+
+    if let Some(mut keys) = field::<HashMap<String, String>>(settings, "post_process_api_keys") {
+        for provider in &defaults {
+            keys.entry(provider.id.clone()).or_default();
+        }
+        set_field(settings, "post_process_api_keys", &keys);
+    }
+
+    if let Some(mut models) = field::<HashMap<String, String>>(settings, "post_process_models") {
+        for provider in &defaults {
+            let model = models.entry(provider.id.clone()).or_default();
+            if model.is_empty() {
+                *model = default_model_for_provider(&provider.id);
+            }
+        }
+        set_field(settings, "post_process_models", &models);
+    }
+}
+
+/// Champ `key` lu au type `T` ; absent ou illisible, il est laissé à la
+/// désérialisation des réglages
+fn field<T: DeserializeOwned>(settings: &Map<String, Value>, key: &str) -> Option<T> {
+    let value = settings.get(key)?;
+    serde_json::from_value(value.clone())
+        .map_err(|e| warn!("Skipping migration of '{}': {}", key, e))
+        .ok()
+}
+
+fn set_field<T: Serialize>(settings: &mut Map<String, Value>, key: &str, value: &T) {
+    match serde_json::to_value(value) {
+        Ok(value) => {
+            settings.insert(key.to_string(), value);
+        }
+        Err(e) => warn!("Failed to migrate '{}': {}", key, e),
+    }
+}
+
+/// Version du schéma de `settings` (0 pour les réglages d'avant le versionnage)
+pub fn version_of(settings: &Value) -> u32 {
+    settings
+        .get("settings_version")
+        .and_then(Value::as_u64)
+        .map_or(0, |version| version as u32)
+}
+
+/// Amène `settings` à [`SETTINGS_VERSION`]. Renvoie `Ok(true)` si quelque chose
+/// a changé ; refuse les réglages d'une version plus récente de l'application.
+pub fn migrate(settings: &mut Value) -> Result<bool, String> {
+    let from = version_of(settings);
+    if from > SETTINGS_VERSION {
+        return Err(format!(
+            "Settings schema version {} is newer than supported version {}",
+            from, SETTINGS_VERSION
+        ));
+    }
+    if from == SETTINGS_VERSION {
+        return Ok(false);
+    }
+    let map = settings
+        .as_object_mut()
+        .ok_or_else(|| "Settings must be a JSON object".to_string())?;
+    for (version, migration) in MIGRATIONS.iter().enumerate().skip(from as usize) {
+        migration(map);
+        info!("Settings migrated to schema version {}", version + 1);
+    }
+    map.insert(
+        "settings_version".to_string(),
+        Value::from(SETTINGS_VERSION),
+    );
+    Ok(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn unversioned_settings_are_migrated() {
+        let mut settings = json!({
+            "push_to_talk": true,
+            "post_process_providers": [{
+                "id": "openai",
+                "label": "OpenAI",
+                "base_url": "https://api.openai.com/v1",
+                "kind": "anthropic-messages"
+            }],
+            "post_process_api_keys": { "openai": "sk-test" },
+            "post_process_models": { "openai": "gpt-4o-mini" }
+        });
+        assert_eq!(migrate(&mut settings), Ok(true));
+        assert_eq!(version_of(&settings), SETTINGS_VERSION);
+        assert_eq!(settings["push_to_talk"], true);
+
+        let defaults = default_post_process_providers();
+        let providers = settings["post_process_providers"].as_array().unwrap();
+        assert_eq!(providers.len(), defaults.len());
+        let openai = defaults.iter().find(|p| p.id == "openai").unwrap();
+        assert_eq!(providers[0]["kind"], json!(openai.kind));
+        // Valeurs de l'utilisateur conservées, fournisseurs manquants complétés
+        assert_eq!(settings["post_process_api_keys"]["openai"], "sk-test");
+        assert_eq!(settings["post_process_api_keys"]["groq"], "");
+        assert_eq!(settings["post_process_models"]["openai"], "gpt-4o-mini");
+    }
+
+    #[test]
+    fn custom_provider_keeps_its_protocol() {
+        let mut settings = json!({
+            "post_process_providers": [{
+                "id": "custom",
+                "label": "Custom",
+                "base_url": "http://localhost:11434",
+                "kind": "ollama-native",
+                "allow_base_url_edit": true
+            }]
+        });
+        migrate(&mut settings).unwrap();
+        let custom = &settings["post_process_providers"][0];
+        assert_eq!(custom["kind"], "ollama-native");
+        assert_eq!(custom["supports_structured_output"], true);
+    }
+
+    #[test]
+    fn current_settings_are_untouched() {
+        let mut settings =
+            json!({ "settings_version": SETTINGS_VERSION, "post_process_models": {} });
+        assert_eq!(migrate(&mut settings), Ok(false));
+        assert_eq!(settings["post_process_models"], json!({}));
+    }
+
+    #[test]
+    fn newer_settings_are_rejected() {
+        let mut settings = json!({ "settings_version": SETTINGS_VERSION + 1 });
+        assert!(migrate(&mut settings).is_err());
+    }
+}
@@ -452,6 +452,16 @@ pub fn init_shortcuts(app: &AppHandle) -> Result<(), String> {
         }
     }
 
+    // Raccourcis de dictée créés par l'utilisateur
+    for binding in user_settings.custom_bindings() {
+        if let Err(e) = state.register(binding) {
+            error!(
+                "Failed to register custom handy-keys shortcut {} during init: {}",
+                binding.id, e
+            );
+        }
+    }
+
     app.manage(state);
     info!("handy-keys shortcuts initialized");
     Ok(())
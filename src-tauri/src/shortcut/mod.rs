@@ -21,9 +21,11 @@ use tauri::{AppHandle, Emitter, Manager};
 use tauri_plugin_autostart::ManagerExt;
 
 use crate::settings::{
-    self, get_settings, AutoSubmitKey, ClipboardHandling, KeyboardImplementation, LLMPrompt,
-    OverlayPosition, PasteMethod, ShortcutBinding, SoundTheme, TypingTool,
-    APPLE_INTELLIGENCE_DEFAULT_MODEL_ID, APPLE_INTELLIGENCE_PROVIDER_ID,
+    self, get_settings, is_custom_binding, AutoSubmitKey, BindingProfile, ClipboardHandling,
+    KeyboardImplementation, LLMPrompt, LlmStreamMode, OutputSink, OverlayPosition, PasteFormat,
+    PasteMethod, PostProcessRecipe, ProviderKind, RecipeStep, RedactionPolicy, ShortcutBinding,
+    SoundTheme, TypingTool, APPLE_INTELLIGENCE_DEFAULT_MODEL_ID, APPLE_INTELLIGENCE_PROVIDER_ID,
+    CUSTOM_BINDING_PREFIX,
 };
 use crate::tray;
 
@@ -440,6 +442,41 @@ fn register_all_shortcuts_for_implementation(
         }
     }
 
+    // Raccourcis personnalisés : leur valeur par défaut est celle saisie à la création
+    let custom_bindings: Vec<ShortcutBinding> =
+        current_settings.custom_bindings().cloned().collect();
+    for mut binding in custom_bindings {
+        if validate_shortcut_for_implementation(&binding.current_binding, implementation).is_err() {
+            if let Err(e) =
+                validate_shortcut_for_implementation(&binding.default_binding, implementation)
+            {
+                warn!(
+                    "Custom shortcut '{}' ({}) is invalid for {:?}: {}. Left unregistered.",
+                    binding.id, binding.current_binding, implementation, e
+                );
+                continue;
+            }
+            binding.current_binding = binding.default_binding.clone();
+            current_settings
+                .bindings
+                .insert(binding.id.clone(), binding.clone());
+            reset_bindings.push(binding.id.clone());
+        }
+
+        let id = binding.id.clone();
+        let result = match implementation {
+            KeyboardImplementation::Tauri => tauri_impl::register_shortcut(app, binding),
+            KeyboardImplementation::HandyKeys => handy_keys::register_shortcut(app, binding),
+        };
+
+        if let Err(e) = result {
+            error!(
+                "Failed to register shortcut '{}' for {:?}: {}",
+                id, implementation, e
+            );
+        }
+    }
+
     // Save settings if any bindings were reset
     if !reset_bindings.is_empty() {
         settings::write_settings(app, current_settings);
@@ -678,6 +715,18 @@ pub fn change_word_correction_threshold_setting(
     Ok(())
 }
 
+#[tauri::command]
+#[specta::specta]
+pub fn change_max_recording_seconds_setting(app: AppHandle, seconds: u32) -> Result<(), String> {
+    if seconds < 30 {
+        return Err("Maximum recording length must be at least 30 seconds".to_string());
+    }
+    let mut settings = settings::get_settings(&app);
+    settings.max_recording_seconds = seconds;
+    settings::write_settings(&app, settings);
+    Ok(())
+}
+
 #[tauri::command]
 #[specta::specta]
 pub fn change_paste_method_setting(app: AppHandle, method: String) -> Result<(), String> {
@@ -699,6 +748,24 @@ pub fn change_paste_method_setting(app: AppHandle, method: String) -> Result<(),
     Ok(())
 }
 
+#[tauri::command]
+#[specta::specta]
+pub fn change_llm_stream_mode_setting(app: AppHandle, mode: String) -> Result<(), String> {
+    let mut settings = settings::get_settings(&app);
+    let parsed = match mode.as_str() {
+        "off" => LlmStreamMode::Off,
+        "preview" => LlmStreamMode::Preview,
+        "type" => LlmStreamMode::Type,
+        other => {
+            warn!("Invalid LLM stream mode '{}', defaulting to preview", other);
+            LlmStreamMode::Preview
+        }
+    };
+    settings.llm_stream_mode = parsed;
+    settings::write_settings(&app, settings);
+    Ok(())
+}
+
 #[tauri::command]
 #[specta::specta]
 pub fn get_available_typing_tools() -> Vec<String> {
@@ -765,6 +832,126 @@ pub fn change_clipboard_handling_setting(app: AppHandle, handling: String) -> Re
     Ok(())
 }
 
+/// Destinations de la dictée : collage, journal Markdown, fichier/FIFO, stdout, webhook local
+#[tauri::command]
+#[specta::specta]
+pub fn change_output_sinks_setting(app: AppHandle, sinks: Vec<OutputSink>) -> Result<(), String> {
+    crate::output::validate(&sinks)?;
+    let mut settings = settings::get_settings(&app);
+    settings.output_sinks = sinks;
+    settings::write_settings(&app, settings);
+    Ok(())
+}
+
+/// Délai avant restauration du presse-papiers après un collage (ms)
+#[tauri::command]
+#[specta::specta]
+pub fn change_clipboard_restore_delay_setting(app: AppHandle, delay_ms: u64) -> Result<(), String> {
+    if delay_ms > 5000 {
+        return Err("Clipboard restore delay cannot exceed 5000 ms".to_string());
+    }
+    let mut settings = settings::get_settings(&app);
+    settings.clipboard_restore_delay_ms = delay_ms;
+    settings::write_settings(&app, settings);
+    Ok(())
+}
+
+/// Format des collages via presse-papier : texte brut ou HTML (listes, paragraphes)
+#[tauri::command]
+#[specta::specta]
+pub fn change_paste_format_setting(app: AppHandle, format: PasteFormat) -> Result<(), String> {
+    let mut settings = settings::get_settings(&app);
+    settings.paste_format = format;
+    settings::write_settings(&app, settings);
+    Ok(())
+}
+
+/// Format imposé pour une application cible ; `None` retire l'exception
+#[tauri::command]
+#[specta::specta]
+pub fn set_app_paste_format(
+    app: AppHandle,
+    app_name: String,
+    format: Option<PasteFormat>,
+) -> Result<(), String> {
+    let app_name = app_name.trim();
+    if app_name.is_empty() {
+        return Err("Application name cannot be empty".to_string());
+    }
+    let mut settings = settings::get_settings(&app);
+    match format {
+        Some(format) => {
+            settings
+                .app_paste_formats
+                .insert(app_name.to_string(), format);
+        }
+        None => {
+            settings.app_paste_formats.remove(app_name);
+        }
+    }
+    settings::write_settings(&app, settings);
+    Ok(())
+}
+
+/// Active le contexte de session (dictées récentes dans les prompts)
+#[tauri::command]
+#[specta::specta]
+pub fn change_session_context_enabled_setting(app: AppHandle, enabled: bool) -> Result<(), String> {
+    let mut settings = settings::get_settings(&app);
+    settings.session_context_enabled = enabled;
+    settings::write_settings(&app, settings);
+    Ok(())
+}
+
+/// Bornes du contexte de session : nombre de dictées, pause et budget de tokens
+#[tauri::command]
+#[specta::specta]
+pub fn change_session_context_limits_setting(
+    app: AppHandle,
+    entries: usize,
+    timeout_secs: u64,
+    max_tokens: usize,
+) -> Result<(), String> {
+    if !(1..=10).contains(&entries) {
+        return Err("Session context must keep between 1 and 10 dictations".to_string());
+    }
+    if !(30..=3600).contains(&timeout_secs) {
+        return Err("Session timeout must be between 30 and 3600 seconds".to_string());
+    }
+    if !(20..=1000).contains(&max_tokens) {
+        return Err("Session context budget must be between 20 and 1000 tokens".to_string());
+    }
+    let mut settings = settings::get_settings(&app);
+    settings.session_context_entries = entries;
+    settings.session_context_timeout_secs = timeout_secs;
+    settings.session_context_max_tokens = max_tokens;
+    settings::write_settings(&app, settings);
+    Ok(())
+}
+
+/// Facteur temps réel maximal du modèle recommandé par le banc d'essai
+#[tauri::command]
+#[specta::specta]
+pub fn change_benchmark_target_rtf_setting(app: AppHandle, target_rtf: f32) -> Result<(), String> {
+    if !(0.05..=2.0).contains(&target_rtf) {
+        return Err("Benchmark target must be between 0.05 and 2.0".to_string());
+    }
+    let mut settings = settings::get_settings(&app);
+    settings.benchmark_target_rtf = target_rtf;
+    settings::write_settings(&app, settings);
+    Ok(())
+}
+
+/// Sélectionne le modèle recommandé à la fin de chaque banc d'essai
+#[tauri::command]
+#[specta::specta]
+pub fn change_benchmark_auto_select_setting(app: AppHandle, enabled: bool) -> Result<(), String> {
+    let mut settings = settings::get_settings(&app);
+    settings.benchmark_auto_select = enabled;
+    settings::write_settings(&app, settings);
+    Ok(())
+}
+
 #[tauri::command]
 #[specta::specta]
 pub fn change_auto_submit_setting(app: AppHandle, enabled: bool) -> Result<(), String> {
@@ -850,6 +1037,40 @@ pub fn change_post_process_base_url_setting(
 
     provider.base_url = base_url;
     settings::write_settings(&app, settings);
+    crate::llm::resilience::reset(&provider_id);
+    Ok(())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn change_post_process_kind_setting(
+    app: AppHandle,
+    provider_id: String,
+    kind: String,
+) -> Result<(), String> {
+    let kind = match kind.as_str() {
+        "openai-compatible" => ProviderKind::OpenaiCompatible,
+        "anthropic-messages" => ProviderKind::AnthropicMessages,
+        "ollama-native" => ProviderKind::OllamaNative,
+        other => return Err(format!("Invalid provider kind '{}'", other)),
+    };
+
+    let mut settings = settings::get_settings(&app);
+    let provider = settings
+        .post_process_provider_mut(&provider_id)
+        .ok_or_else(|| format!("Provider '{}' not found", provider_id))?;
+
+    if !provider.allow_base_url_edit {
+        return Err(format!(
+            "Provider '{}' does not allow changing its API kind",
+            provider.label
+        ));
+    }
+
+    provider.kind = kind;
+    provider.supports_structured_output = kind.custom_supports_structured_output();
+    settings::write_settings(&app, settings);
+    crate::llm::resilience::reset(&provider_id);
     Ok(())
 }
 
@@ -877,6 +1098,9 @@ pub fn change_post_process_api_key_setting(
 ) -> Result<(), String> {
     let mut settings = settings::get_settings(&app);
     validate_provider_exists(&settings, &provider_id)?;
+    // A new key deserves a fresh chance, even if the old one tripped the breaker
+    crate::llm::resilience::reset(&provider_id);
+    let api_key = crate::vault::seal_secret(&api_key).map_err(|e| e.to_string())?;
     settings.post_process_api_keys.insert(provider_id, api_key);
     settings::write_settings(&app, settings);
     Ok(())
@@ -891,6 +1115,7 @@ pub fn change_post_process_model_setting(
 ) -> Result<(), String> {
     let mut settings = settings::get_settings(&app);
     validate_provider_exists(&settings, &provider_id)?;
+    crate::llm::resilience::reset(&provider_id);
     settings.post_process_models.insert(provider_id, model);
     settings::write_settings(&app, settings);
     Ok(())
@@ -901,6 +1126,15 @@ pub fn change_post_process_model_setting(
 pub fn set_post_process_provider(app: AppHandle, provider_id: String) -> Result<(), String> {
     let mut settings = settings::get_settings(&app);
     validate_provider_exists(&settings, &provider_id)?;
+    if settings
+        .post_process_provider(&provider_id)
+        .is_some_and(|provider| !crate::baseline::provider_allowed(provider))
+    {
+        return Err(format!(
+            "Provider '{}' is disallowed by the team baseline",
+            provider_id
+        ));
+    }
     settings.post_process_provider_id = provider_id;
     settings::write_settings(&app, settings);
     Ok(())
@@ -978,6 +1212,307 @@ pub fn delete_post_process_prompt(app: AppHandle, id: String) -> Result<(), Stri
             settings.post_process_prompts.first().map(|p| p.id.clone());
     }
 
+    // Custom bindings using it fall back to the selected prompt
+    for profile in settings.binding_profiles.values_mut() {
+        if profile.prompt_id.as_ref() == Some(&id) {
+            profile.prompt_id = None;
+        }
+    }
+
+    settings::write_settings(&app, settings);
+    Ok(())
+}
+
+fn validate_recipe_steps(
+    settings: &settings::AppSettings,
+    steps: &[RecipeStep],
+) -> Result<(), String> {
+    if steps.is_empty() {
+        return Err("A recipe needs at least one step".to_string());
+    }
+    for (index, step) in steps.iter().enumerate() {
+        match step {
+            RecipeStep::Template { template } if template.trim().is_empty() => {
+                return Err(format!("Step {}: template is empty", index + 1));
+            }
+            RecipeStep::Llm {
+                prompt,
+                provider_id,
+                ..
+            } => {
+                if prompt.trim().is_empty() {
+                    return Err(format!("Step {}: prompt is empty", index + 1));
+                }
+                if let Some(provider_id) = provider_id {
+                    validate_provider_exists(settings, provider_id)?;
+                }
+            }
+            _ => {}
+        }
+    }
+    Ok(())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn add_post_process_recipe(
+    app: AppHandle,
+    name: String,
+    steps: Vec<RecipeStep>,
+) -> Result<PostProcessRecipe, String> {
+    let mut settings = settings::get_settings(&app);
+    validate_recipe_steps(&settings, &steps)?;
+
+    let recipe = PostProcessRecipe {
+        id: format!("recipe_{}", chrono::Utc::now().timestamp_millis()),
+        name,
+        steps,
+    };
+
+    settings.post_process_recipes.push(recipe.clone());
+    settings::write_settings(&app, settings);
+
+    Ok(recipe)
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn update_post_process_recipe(
+    app: AppHandle,
+    id: String,
+    name: String,
+    steps: Vec<RecipeStep>,
+) -> Result<(), String> {
+    let mut settings = settings::get_settings(&app);
+    validate_recipe_steps(&settings, &steps)?;
+
+    if let Some(recipe) = settings
+        .post_process_recipes
+        .iter_mut()
+        .find(|r| r.id == id)
+    {
+        recipe.name = name;
+        recipe.steps = steps;
+        settings::write_settings(&app, settings);
+        Ok(())
+    } else {
+        Err(format!("Recipe with id '{}' not found", id))
+    }
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn delete_post_process_recipe(app: AppHandle, id: String) -> Result<(), String> {
+    let mut settings = settings::get_settings(&app);
+
+    let original_len = settings.post_process_recipes.len();
+    settings.post_process_recipes.retain(|r| r.id != id);
+
+    if settings.post_process_recipes.len() == original_len {
+        return Err(format!("Recipe with id '{}' not found", id));
+    }
+
+    // Les raccourcis qui utilisaient la recette reviennent au prompt sélectionné
+    settings
+        .binding_recipes
+        .retain(|_, recipe_id| recipe_id != &id);
+
+    settings::write_settings(&app, settings);
+    Ok(())
+}
+
+/// Associe une recette à un raccourci (`None` = comportement par défaut)
+#[tauri::command]
+#[specta::specta]
+pub fn set_binding_recipe(
+    app: AppHandle,
+    binding_id: String,
+    recipe_id: Option<String>,
+) -> Result<(), String> {
+    let mut settings = settings::get_settings(&app);
+
+    if !settings.bindings.contains_key(&binding_id) {
+        return Err(format!("Binding with id '{}' not found", binding_id));
+    }
+
+    match recipe_id {
+        Some(recipe_id) => {
+            if !settings
+                .post_process_recipes
+                .iter()
+                .any(|r| r.id == recipe_id)
+            {
+                return Err(format!("Recipe with id '{}' not found", recipe_id));
+            }
+            settings.binding_recipes.insert(binding_id, recipe_id);
+        }
+        None => {
+            settings.binding_recipes.remove(&binding_id);
+        }
+    }
+
+    settings::write_settings(&app, settings);
+    Ok(())
+}
+
+// ============================================================================
+// Custom Dictation Bindings
+// ============================================================================
+
+fn validate_binding_profile(
+    settings: &settings::AppSettings,
+    profile: &BindingProfile,
+) -> Result<(), String> {
+    if let Some(mode) = &profile.write_mode {
+        mode.parse::<crate::pipeline::modes::WriteMode>()?;
+    }
+    if let Some(prompt_id) = &profile.prompt_id {
+        if !settings
+            .post_process_prompts
+            .iter()
+            .any(|p| &p.id == prompt_id)
+        {
+            return Err(format!("Prompt with id '{}' not found", prompt_id));
+        }
+    }
+    if profile
+        .language
+        .as_ref()
+        .is_some_and(|language| language.trim().is_empty())
+    {
+        return Err("Language cannot be empty".to_string());
+    }
+    if let Some(sinks) = &profile.output_sinks {
+        crate::output::validate(sinks)?;
+    }
+    Ok(())
+}
+
+/// Create a dictation shortcut with its own write mode, prompt, language and paste method
+#[tauri::command]
+#[specta::specta]
+pub fn add_custom_binding(
+    app: AppHandle,
+    name: String,
+    binding: String,
+    profile: BindingProfile,
+) -> Result<ShortcutBinding, String> {
+    let mut settings = settings::get_settings(&app);
+
+    if name.trim().is_empty() {
+        return Err("Name cannot be empty".to_string());
+    }
+    if binding.trim().is_empty() {
+        return Err("Binding cannot be empty".to_string());
+    }
+    validate_shortcut_for_implementation(&binding, settings.keyboard_implementation)?;
+    validate_binding_profile(&settings, &profile)?;
+
+    let id = format!(
+        "{}{}",
+        CUSTOM_BINDING_PREFIX,
+        chrono::Utc::now().timestamp_millis()
+    );
+    let new_binding = ShortcutBinding {
+        id: id.clone(),
+        name: name.trim().to_string(),
+        description: "Dictée personnalisée".to_string(),
+        default_binding: binding.clone(),
+        current_binding: binding,
+    };
+
+    register_shortcut(&app, new_binding.clone())?;
+
+    settings.bindings.insert(id.clone(), new_binding.clone());
+    settings.binding_profiles.insert(id, profile);
+    settings::write_settings(&app, settings);
+
+    Ok(new_binding)
+}
+
+/// Rename a custom binding and replace its profile (the shortcut itself goes through `change_binding`)
+#[tauri::command]
+#[specta::specta]
+pub fn update_custom_binding(
+    app: AppHandle,
+    id: String,
+    name: String,
+    profile: BindingProfile,
+) -> Result<(), String> {
+    let mut settings = settings::get_settings(&app);
+
+    if name.trim().is_empty() {
+        return Err("Name cannot be empty".to_string());
+    }
+    validate_binding_profile(&settings, &profile)?;
+
+    let binding = settings
+        .bindings
+        .get_mut(&id)
+        .filter(|b| is_custom_binding(&b.id))
+        .ok_or_else(|| format!("Custom binding with id '{}' not found", id))?;
+    binding.name = name.trim().to_string();
+    settings.binding_profiles.insert(id, profile);
+
+    settings::write_settings(&app, settings);
+    Ok(())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn delete_custom_binding(app: AppHandle, id: String) -> Result<(), String> {
+    let mut settings = settings::get_settings(&app);
+
+    if !is_custom_binding(&id) {
+        return Err(format!("Binding '{}' is not a custom binding", id));
+    }
+    let binding = settings
+        .bindings
+        .remove(&id)
+        .ok_or_else(|| format!("Custom binding with id '{}' not found", id))?;
+
+    if let Err(e) = unregister_shortcut(&app, binding) {
+        warn!(
+            "delete_custom_binding: failed to unregister '{}': {}",
+            id, e
+        );
+    }
+
+    settings.binding_profiles.remove(&id);
+    settings.binding_recipes.remove(&id);
+    settings::write_settings(&app, settings);
+    Ok(())
+}
+
+// ============================================================================
+// PII Redaction
+// ============================================================================
+
+#[tauri::command]
+#[specta::specta]
+pub fn set_redaction_policy(
+    app: AppHandle,
+    provider_id: String,
+    policy: RedactionPolicy,
+) -> Result<(), String> {
+    let mut settings = settings::get_settings(&app);
+    validate_provider_exists(&settings, &provider_id)?;
+    settings.redaction_policies.insert(provider_id, policy);
+    settings::write_settings(&app, settings);
+    Ok(())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn update_redaction_names(app: AppHandle, names: Vec<String>) -> Result<(), String> {
+    let mut settings = settings::get_settings(&app);
+    let mut cleaned: Vec<String> = Vec::new();
+    for name in names.iter().map(|n| n.trim()).filter(|n| !n.is_empty()) {
+        if !cleaned.iter().any(|c| c.eq_ignore_ascii_case(name)) {
+            cleaned.push(name.to_string());
+        }
+    }
+    settings.redaction_names = cleaned;
     settings::write_settings(&app, settings);
     Ok(())
 }
@@ -1010,11 +1545,7 @@ pub async fn fetch_post_process_models(
     }
 
     // Get API key
-    let api_key = settings
-        .post_process_api_keys
-        .get(&provider_id)
-        .cloned()
-        .unwrap_or_default();
+    let api_key = settings.api_key(&provider_id);
 
     // Skip fetching if no API key for providers that typically need one
     if api_key.trim().is_empty() && provider.id != "custom" {
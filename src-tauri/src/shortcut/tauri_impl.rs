@@ -35,6 +35,16 @@ pub fn init_shortcuts(app: &AppHandle) {
             error!("Failed to register shortcut {} during init: {}", id, e);
         }
     }
+
+    // Raccourcis de dictée créés par l'utilisateur
+    for binding in user_settings.custom_bindings() {
+        if let Err(e) = register_shortcut(app, binding.clone()) {
+            error!(
+                "Failed to register custom shortcut {} during init: {}",
+                binding.id, e
+            );
+        }
+    }
 }
 
 /// Validate a shortcut string for the Tauri global-shortcut implementation.
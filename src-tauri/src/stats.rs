@@ -0,0 +1,354 @@
+//! Métriques par dictée et statistiques d'usage agrégées
+//!
+//! Chaque dictée micro enregistre ses [`DictationMetrics`] dans l'historique
+//! (colonne JSON `metrics`, jamais chiffrée : elle ne contient aucun texte).
+//! Les agrégats — mots par jour, débit, temps gagné, latences p50/p95 par
+//! modèle et par mode — sont recalculés à la demande, pour l'UI comme pour
+//! `--stats-report`.
+
+use crate::pipeline::hallucination::HallucinationDecision;
+use anyhow::{anyhow, Context, Result};
+use chrono::{DateTime, Local, Utc};
+use rusqlite::{Connection, OpenFlags};
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::collections::BTreeMap;
+use std::fmt::Write;
+
+/// Vitesse de frappe au clavier retenue pour estimer le temps gagné
+const TYPING_WPM: f64 = 40.0;
+/// Identifiant Tauri (`tauri.conf.json`), nom du dossier de données
+const APP_IDENTIFIER: &str = "com.uhama.dictation-ia";
+
+/// Chemin emprunté par le pipeline
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize, Type)]
+#[serde(rename_all = "snake_case")]
+pub enum Routing {
+    /// Fast-path : règles locales seules
+    RulesOnly,
+    /// Règles puis LLM (même si celui-ci a échoué, voir `llm_fallback`)
+    Llm,
+}
+
+/// Mesures d'une dictée, de l'arrêt de l'enregistrement au collage
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, Type)]
+pub struct DictationMetrics {
+    pub audio_ms: u64,
+    pub stt_ms: u64,
+    pub rules_ms: u64,
+    /// Nettoyage LLM du pipeline et post-traitement éventuel
+    pub llm_ms: u64,
+    /// None si le collage a échoué
+    pub paste_ms: Option<u64>,
+    pub routing: Routing,
+    pub llm_fallback: bool,
+    pub model_id: Option<String>,
+    pub write_mode: String,
+    pub confidence: f32,
+    pub word_count: usize,
+    /// Hallucinations Whisper écartées avant le pipeline
+    #[serde(default)]
+    pub hallucinations: Vec<HallucinationDecision>,
+    /// Application cible, quand elle est connue (contexte de session)
+    #[serde(default)]
+    pub target_app: Option<String>,
+}
+
+impl DictationMetrics {
+    /// Latence perçue : de la fin de l'enregistrement au texte collé
+    pub fn latency_ms(&self) -> u64 {
+        self.stt_ms + self.rules_ms + self.llm_ms + self.paste_ms.unwrap_or(0)
+    }
+
+    /// Temps de frappe économisé par rapport à [`TYPING_WPM`], parole comprise
+    fn time_saved_ms(&self) -> u64 {
+        let typing_ms = (self.word_count as f64 / TYPING_WPM * 60_000.0) as u64;
+        typing_ms.saturating_sub(self.audio_ms + self.latency_ms())
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, Type)]
+pub struct DailyUsage {
+    /// Jour local, `AAAA-MM-JJ`
+    pub date: String,
+    pub dictations: usize,
+    pub words: usize,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, Type)]
+pub struct LatencyStats {
+    /// Modèle ou mode d'écriture
+    pub key: String,
+    pub count: usize,
+    pub p50_ms: u64,
+    pub p95_ms: u64,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize, Type)]
+pub struct UsageStats {
+    pub dictations: usize,
+    pub words: usize,
+    pub audio_ms: u64,
+    /// Mots par minute de parole
+    pub words_per_minute: f64,
+    /// Estimation par rapport à une frappe à 40 mots/min
+    pub time_saved_ms: u64,
+    pub rules_only: usize,
+    pub llm_fallbacks: usize,
+    pub daily: Vec<DailyUsage>,
+    pub latency_by_model: Vec<LatencyStats>,
+    pub latency_by_mode: Vec<LatencyStats>,
+}
+
+/// Percentile au rang le plus proche sur des valeurs triées
+fn percentile(sorted: &[u64], p: f64) -> u64 {
+    if sorted.is_empty() {
+        return 0;
+    }
+    let rank = (p / 100.0 * sorted.len() as f64).ceil() as usize;
+    sorted[rank.clamp(1, sorted.len()) - 1]
+}
+
+fn latency_stats(groups: BTreeMap<String, Vec<u64>>) -> Vec<LatencyStats> {
+    groups
+        .into_iter()
+        .map(|(key, mut latencies)| {
+            latencies.sort_unstable();
+            LatencyStats {
+                key,
+                count: latencies.len(),
+                p50_ms: percentile(&latencies, 50.0),
+                p95_ms: percentile(&latencies, 95.0),
+            }
+        })
+        .collect()
+}
+
+fn local_date(timestamp: i64) -> String {
+    DateTime::<Utc>::from_timestamp(timestamp, 0)
+        .map(|dt| dt.with_timezone(&Local).format("%Y-%m-%d").to_string())
+        .unwrap_or_default()
+}
+
+/// Agrège des métriques horodatées (timestamp Unix, ordre quelconque)
+pub fn aggregate(records: &[(i64, DictationMetrics)]) -> UsageStats {
+    let mut stats = UsageStats::default();
+    let mut daily: BTreeMap<String, DailyUsage> = BTreeMap::new();
+    let mut by_model: BTreeMap<String, Vec<u64>> = BTreeMap::new();
+    let mut by_mode: BTreeMap<String, Vec<u64>> = BTreeMap::new();
+
+    for (timestamp, metrics) in records {
+        stats.dictations += 1;
+        stats.words += metrics.word_count;
+        stats.audio_ms += metrics.audio_ms;
+        stats.time_saved_ms += metrics.time_saved_ms();
+        if metrics.routing == Routing::RulesOnly {
+            stats.rules_only += 1;
+        }
+        if metrics.llm_fallback {
+            stats.llm_fallbacks += 1;
+        }
+
+        let date = local_date(*timestamp);
+        let day = daily.entry(date.clone()).or_insert(DailyUsage {
+            date,
+            dictations: 0,
+            words: 0,
+        });
+        day.dictations += 1;
+        day.words += metrics.word_count;
+
+        let model = metrics.model_id.clone().unwrap_or_else(|| "?".to_string());
+        by_model
+            .entry(model)
+            .or_default()
+            .push(metrics.latency_ms());
+        by_mode
+            .entry(metrics.write_mode.clone())
+            .or_default()
+            .push(metrics.latency_ms());
+    }
+
+    if stats.audio_ms > 0 {
+        stats.words_per_minute = stats.words as f64 / (stats.audio_ms as f64 / 60_000.0);
+    }
+    stats.daily = daily.into_values().collect();
+    stats.latency_by_model = latency_stats(by_model);
+    stats.latency_by_mode = latency_stats(by_mode);
+    stats
+}
+
+fn format_duration(ms: u64) -> String {
+    let minutes = ms / 60_000;
+    if minutes >= 60 {
+        format!("{} h {:02} min", minutes / 60, minutes % 60)
+    } else {
+        format!("{} min {:02} s", minutes, ms / 1000 % 60)
+    }
+}
+
+/// Rapport texte des statistiques, tel qu'affiché par `--stats-report`
+pub fn render_report(stats: &UsageStats, days: u32) -> String {
+    let mut out = String::new();
+    let _ = writeln!(out, "Statistiques d'usage — {} derniers jours", days);
+    let _ = writeln!(out);
+    let _ = writeln!(out, "Dictées         : {}", stats.dictations);
+    let _ = writeln!(out, "Mots            : {}", stats.words);
+    let _ = writeln!(out, "Parole          : {}", format_duration(stats.audio_ms));
+    let _ = writeln!(
+        out,
+        "Débit           : {:.0} mots/min",
+        stats.words_per_minute
+    );
+    let _ = writeln!(
+        out,
+        "Temps gagné     : {}",
+        format_duration(stats.time_saved_ms)
+    );
+    let _ = writeln!(
+        out,
+        "Règles seules   : {} | replis LLM : {}",
+        stats.rules_only, stats.llm_fallbacks
+    );
+
+    if !stats.daily.is_empty() {
+        let _ = writeln!(out);
+        let _ = writeln!(out, "Jour        Dictées   Mots");
+        for day in &stats.daily {
+            let _ = writeln!(out, "{}  {:>7}  {:>5}", day.date, day.dictations, day.words);
+        }
+    }
+
+    for (title, groups) in [
+        ("Modèle", &stats.latency_by_model),
+        ("Mode", &stats.latency_by_mode),
+    ] {
+        if groups.is_empty() {
+            continue;
+        }
+        let _ = writeln!(out);
+        let _ = writeln!(out, "{:<24} {:>6} {:>8} {:>8}", title, "n", "p50", "p95");
+        for group in groups {
+            let _ = writeln!(
+                out,
+                "{:<24} {:>6} {:>6}ms {:>6}ms",
+                group.key, group.count, group.p50_ms, group.p95_ms
+            );
+        }
+    }
+    out
+}
+
+/// Rapport des `days` derniers jours lu directement dans la base de
+/// l'application, sans la démarrer
+pub fn cli_report(days: u32) -> Result<String> {
+    let db_path = dirs::data_dir()
+        .ok_or_else(|| anyhow!("Dossier de données introuvable"))?
+        .join(APP_IDENTIFIER)
+        .join("history.db");
+    let conn = Connection::open_with_flags(&db_path, OpenFlags::SQLITE_OPEN_READ_ONLY)
+        .with_context(|| format!("Historique introuvable : {}", db_path.display()))?;
+
+    let since = Utc::now().timestamp() - days as i64 * 24 * 60 * 60;
+    let records = crate::managers::history::read_metrics(&conn, since)
+        .context("Historique antérieur aux métriques : lancez l'application une fois")?;
+    Ok(render_report(&aggregate(&records), days))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn metrics(model: &str, mode: &str, words: usize, stt_ms: u64) -> DictationMetrics {
+        DictationMetrics {
+            audio_ms: 6_000,
+            stt_ms,
+            rules_ms: 2,
+            llm_ms: 0,
+            paste_ms: Some(30),
+            routing: Routing::RulesOnly,
+            llm_fallback: false,
+            model_id: Some(model.to_string()),
+            write_mode: mode.to_string(),
+            confidence: 0.9,
+            word_count: words,
+            hallucinations: Vec::new(),
+            target_app: None,
+        }
+    }
+
+    #[test]
+    fn metrics_without_hallucinations_still_parse() {
+        let mut json = serde_json::to_value(metrics("small", "chat", 5, 100)).unwrap();
+        json.as_object_mut().unwrap().remove("hallucinations");
+        let parsed: DictationMetrics = serde_json::from_value(json).unwrap();
+        assert!(parsed.hallucinations.is_empty());
+    }
+
+    #[test]
+    fn percentile_uses_nearest_rank() {
+        let values: Vec<u64> = (1..=20).collect();
+        assert_eq!(percentile(&values, 50.0), 10);
+        assert_eq!(percentile(&values, 95.0), 19);
+        assert_eq!(percentile(&[7], 95.0), 7);
+        assert_eq!(percentile(&[], 50.0), 0);
+    }
+
+    #[test]
+    fn aggregates_words_days_and_latency_groups() {
+        // Midi heure locale : le jour agrégé ne dépend pas du fuseau du poste
+        let day = Local
+            .with_ymd_and_hms(2025, 6, 15, 12, 0, 0)
+            .unwrap()
+            .timestamp();
+        let mut llm = metrics("small", "pro", 10, 800);
+        llm.routing = Routing::Llm;
+        llm.llm_fallback = true;
+        let records = vec![
+            (day, metrics("small", "chat", 20, 400)),
+            (day, llm),
+            (day + 2 * 86_400, metrics("turbo", "chat", 30, 200)),
+        ];
+
+        let stats = aggregate(&records);
+        assert_eq!(stats.dictations, 3);
+        assert_eq!(stats.words, 60);
+        // 60 mots en 18 s de parole
+        assert!((stats.words_per_minute - 200.0).abs() < 1e-9);
+        assert_eq!(stats.rules_only, 2);
+        assert_eq!(stats.llm_fallbacks, 1);
+
+        assert_eq!(stats.daily.len(), 2);
+        assert_eq!((stats.daily[0].dictations, stats.daily[0].words), (2, 30));
+
+        let small = &stats.latency_by_model[0];
+        assert_eq!((small.key.as_str(), small.count), ("small", 2));
+        assert_eq!((small.p50_ms, small.p95_ms), (432, 832));
+        let modes: Vec<&str> = stats
+            .latency_by_mode
+            .iter()
+            .map(|l| l.key.as_str())
+            .collect();
+        assert_eq!(modes, ["chat", "pro"]);
+    }
+
+    #[test]
+    fn time_saved_never_goes_negative() {
+        // 20 mots = 30 s de frappe, contre 6 s de parole et ~0,4 s de traitement
+        let fast = metrics("small", "chat", 20, 400);
+        assert_eq!(fast.time_saved_ms(), 30_000 - 6_000 - 432);
+        // 1 mot : dicter est plus long que taper
+        assert_eq!(metrics("small", "chat", 1, 400).time_saved_ms(), 0);
+    }
+
+    #[test]
+    fn report_lists_groups() {
+        let stats = aggregate(&[(1_750_000_000, metrics("small", "chat", 20, 400))]);
+        let report = render_report(&stats, 7);
+        assert!(report.contains("7 derniers jours"));
+        assert!(report.contains("Dictées         : 1"));
+        assert!(report.contains("small"));
+        assert!(report.contains("432ms"));
+    }
+}
@@ -1,6 +1,8 @@
-use crate::actions::ACTION_MAP;
+use crate::actions::action_for_binding;
 use crate::managers::audio::AudioRecordingManager;
+use crate::settings::is_custom_binding;
 use log::{debug, error, warn};
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::mpsc::{self, Sender};
 use std::sync::Arc;
 use std::thread;
@@ -17,6 +19,12 @@ enum Command {
         is_pressed: bool,
         push_to_talk: bool,
     },
+    /// Arrêt explicite : n'a d'effet que si cette liaison enregistre,
+    /// ne démarre jamais d'enregistrement contrairement à un appui.
+    Stop {
+        binding_id: String,
+        hotkey_string: String,
+    },
     Cancel {
         recording_was_active: bool,
     },
@@ -35,15 +43,19 @@ enum Stage {
 /// the async transcribe-paste pipeline.
 pub struct TranscriptionCoordinator {
     tx: Sender<Command>,
+    /// Mirror of `stage != Idle` for readers outside the coordinator thread.
+    busy: Arc<AtomicBool>,
 }
 
 pub fn is_transcribe_binding(id: &str) -> bool {
-    id == "transcribe" || id == "transcribe_with_post_process"
+    id == "transcribe" || id == "transcribe_with_post_process" || is_custom_binding(id)
 }
 
 impl TranscriptionCoordinator {
     pub fn new(app: AppHandle) -> Self {
         let (tx, rx) = mpsc::channel();
+        let busy = Arc::new(AtomicBool::new(false));
+        let busy_flag = busy.clone();
 
         thread::spawn(move || {
             let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
@@ -91,6 +103,16 @@ impl TranscriptionCoordinator {
                                 }
                             }
                         }
+                        Command::Stop {
+                            binding_id,
+                            hotkey_string,
+                        } => {
+                            if matches!(&stage, Stage::Recording(id) if id == &binding_id) {
+                                stop(&app, &mut stage, &binding_id, &hotkey_string);
+                            } else {
+                                debug!("Ignoring stop for '{binding_id}': not recording");
+                            }
+                        }
                         Command::Cancel {
                             recording_was_active,
                         } => {
@@ -105,6 +127,7 @@ impl TranscriptionCoordinator {
                             stage = Stage::Idle;
                         }
                     }
+                    busy_flag.store(!matches!(stage, Stage::Idle), Ordering::Release);
                 }
                 debug!("Transcription coordinator exited");
             }));
@@ -113,7 +136,12 @@ impl TranscriptionCoordinator {
             }
         });
 
-        Self { tx }
+        Self { tx, busy }
+    }
+
+    /// Vrai du début d'un enregistrement à la fin de son traitement.
+    pub fn is_busy(&self) -> bool {
+        self.busy.load(Ordering::Acquire)
     }
 
     /// Send a keyboard/signal input event for a transcribe binding.
@@ -139,6 +167,20 @@ impl TranscriptionCoordinator {
         }
     }
 
+    /// Arrête l'enregistrement en cours de `binding_id`, sans effet sinon.
+    pub fn request_stop(&self, binding_id: &str, hotkey_string: &str) {
+        if self
+            .tx
+            .send(Command::Stop {
+                binding_id: binding_id.to_string(),
+                hotkey_string: hotkey_string.to_string(),
+            })
+            .is_err()
+        {
+            warn!("Transcription coordinator channel closed");
+        }
+    }
+
     pub fn notify_cancel(&self, recording_was_active: bool) {
         if self
             .tx
@@ -159,7 +201,7 @@ impl TranscriptionCoordinator {
 }
 
 fn start(app: &AppHandle, stage: &mut Stage, binding_id: &str, hotkey_string: &str) {
-    let Some(action) = ACTION_MAP.get(binding_id) else {
+    let Some(action) = action_for_binding(binding_id) else {
         warn!("No action in ACTION_MAP for '{binding_id}'");
         return;
     };
@@ -175,7 +217,7 @@ fn start(app: &AppHandle, stage: &mut Stage, binding_id: &str, hotkey_string: &s
 }
 
 fn stop(app: &AppHandle, stage: &mut Stage, binding_id: &str, hotkey_string: &str) {
-    let Some(action) = ACTION_MAP.get(binding_id) else {
+    let Some(action) = action_for_binding(binding_id) else {
         warn!("No action in ACTION_MAP for '{binding_id}'");
         return;
     };
@@ -193,6 +235,12 @@ mod tests {
         assert!(is_transcribe_binding("transcribe_with_post_process"));
     }
 
+    #[test]
+    fn is_transcribe_binding_recognizes_custom_bindings() {
+        assert!(is_transcribe_binding("custom_1718000000000"));
+        assert!(!is_transcribe_binding("custom_"));
+    }
+
     #[test]
     fn is_transcribe_binding_rejects_other_bindings() {
         assert!(!is_transcribe_binding("cancel"));
@@ -124,6 +124,14 @@ pub fn update_tray_menu(app: &AppHandle, state: &TrayIconState, locale: Option<&
         None::<&str>,
     )
     .expect("failed to create copy last transcript item");
+    let undo_last_dictation_i = MenuItem::with_id(
+        app,
+        "undo_last_dictation",
+        &strings.undo_last_dictation,
+        true,
+        None::<&str>,
+    )
+    .expect("failed to create undo last dictation item");
     let model_loaded = app.state::<Arc<TranscriptionManager>>().is_model_loaded();
     let unload_model_i = MenuItem::with_id(
         app,
@@ -164,6 +172,7 @@ pub fn update_tray_menu(app: &AppHandle, state: &TrayIconState, locale: Option<&
                 &version_i,
                 &separator(),
                 &copy_last_transcript_i,
+                &undo_last_dictation_i,
                 &unload_model_i,
                 &separator(),
                 &settings_i,
@@ -234,6 +243,9 @@ mod tests {
             post_processed_text: post_processed.map(|text| text.to_string()),
             post_process_prompt: None,
             write_mode: None,
+            source_file: None,
+            post_process_steps: Vec::new(),
+            metrics: None,
         }
     }
 
@@ -2,7 +2,7 @@ use crate::managers::audio::AudioRecordingManager;
 use crate::managers::transcription::TranscriptionManager;
 use crate::shortcut;
 use crate::TranscriptionCoordinator;
-use log::info;
+use log::{info, warn};
 use std::sync::Arc;
 use tauri::{AppHandle, Manager};
 
@@ -12,6 +12,16 @@ pub use crate::clipboard::*;
 pub use crate::overlay::*;
 pub use crate::tray::*;
 
+/// Annule la dernière dictée (raccourci ou menu de la barre système).
+/// S'exécute sur le thread principal, comme le collage.
+pub fn undo_last_dictation(app: &AppHandle, trigger: UndoTrigger) {
+    let app_clone = app.clone();
+    let _ = app.run_on_main_thread(move || match undo_last_paste(&app_clone, trigger) {
+        Ok(()) => info!("Last dictation undone"),
+        Err(e) => warn!("Cannot undo last dictation: {}", e),
+    });
+}
+
 /// Centralized cancellation function that can be called from anywhere in the app.
 /// Handles cancelling both recording and transcription operations and updates UI state.
 pub fn cancel_current_operation(app: &AppHandle) {
@@ -20,6 +30,9 @@ pub fn cancel_current_operation(app: &AppHandle) {
     // Unregister the cancel shortcut asynchronously
     shortcut::unregister_cancel_shortcut(app);
 
+    // Interrupt any LLM generation still streaming
+    crate::llm::stream::cancel_active();
+
     // Cancel any ongoing recording
     let audio_manager = app.state::<Arc<AudioRecordingManager>>();
     let recording_was_active = audio_manager.is_recording();
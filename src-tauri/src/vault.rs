@@ -0,0 +1,506 @@
+//! Coffre de chiffrement au repos — clés d'API, historique, enregistrements
+//!
+//! Une clé maître de 256 bits chiffre (XChaCha20-Poly1305) :
+//! - les clés d'API de `post_process_api_keys` et les colonnes texte de
+//!   `history.db`, sous forme de chaînes `enc:v1:<base64(nonce ‖ chiffré)>`
+//! - les enregistrements `recordings/handy-*`, préfixés par [`FILE_MAGIC`]
+//!
+//! La clé est soit dérivée d'une phrase secrète (Argon2id, sel stocké dans
+//! `vault.json`), soit générée aléatoirement et confiée au trousseau du système.
+//! Les valeurs en clair restent lisibles : [`open_secret`] et [`decode_file`]
+//! laissent passer ce qui n'est pas chiffré, ce qui permet de migrer les
+//! données existantes sans interruption.
+
+use argon2::Argon2;
+use base64::{engine::general_purpose::STANDARD, Engine};
+use chacha20poly1305::aead::rand_core::RngCore;
+use chacha20poly1305::aead::{Aead, AeadCore, KeyInit, OsRng};
+use chacha20poly1305::{Key, XChaCha20Poly1305, XNonce};
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::RwLock;
+
+/// Préfixe d'une chaîne chiffrée
+pub const SECRET_PREFIX: &str = "enc:v1:";
+/// En-tête d'un fichier chiffré
+pub const FILE_MAGIC: &[u8; 8] = b"DIAVLT01";
+/// Texte affiché à la place d'une valeur chiffrée tant que le coffre est verrouillé
+pub const LOCKED_PLACEHOLDER: &str = "[verrouillé]";
+
+const VAULT_FILE: &str = "vault.json";
+/// Copies en clair déposées pour le lecteur audio par les versions
+/// précédentes ; les enregistrements sont désormais servis depuis la mémoire
+const LEGACY_PLAYBACK_DIR: &str = "playback";
+const KEYRING_SERVICE: &str = "dictation-ia";
+const KEYRING_USER: &str = "master-key";
+/// Chiffré avec la clé pour vérifier une phrase secrète au déverrouillage
+const CHECK_PLAINTEXT: &str = "dictation-ia-vault";
+const NONCE_LEN: usize = 24;
+const SALT_LEN: usize = 16;
+
+/// Origine de la clé maître
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Type)]
+#[serde(rename_all = "snake_case")]
+pub enum VaultMode {
+    /// Dérivée d'une phrase secrète saisie à chaque démarrage
+    Passphrase,
+    /// Aléatoire, conservée dans le trousseau du système
+    Keyring,
+}
+
+/// Contenu de `vault.json` — jamais la clé elle-même
+#[derive(Serialize, Deserialize, Debug, Clone)]
+struct VaultConfig {
+    mode: VaultMode,
+    /// Sel Argon2 en base64 (mode phrase secrète)
+    #[serde(default)]
+    salt: Option<String>,
+    /// [`CHECK_PLAINTEXT`] chiffré
+    check: String,
+}
+
+#[derive(Serialize, Debug, Clone, PartialEq, Eq, Type)]
+pub struct VaultStatus {
+    pub enabled: bool,
+    pub mode: Option<VaultMode>,
+    pub unlocked: bool,
+}
+
+#[derive(Debug)]
+pub enum VaultError {
+    /// Le coffre est activé mais la clé n'est pas chargée
+    Locked,
+    WrongPassphrase,
+    AlreadyEnabled,
+    NotEnabled,
+    /// Données chiffrées illisibles (tronquées ou autre clé)
+    Corrupt,
+    Keyring(String),
+    Io(std::io::Error),
+}
+
+impl std::fmt::Display for VaultError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            VaultError::Locked => f.write_str("Le coffre est verrouillé"),
+            VaultError::WrongPassphrase => f.write_str("Phrase secrète incorrecte"),
+            VaultError::AlreadyEnabled => f.write_str("Le chiffrement est déjà activé"),
+            VaultError::NotEnabled => f.write_str("Le chiffrement n'est pas activé"),
+            VaultError::Corrupt => f.write_str("Données chiffrées illisibles"),
+            VaultError::Keyring(reason) => write!(f, "Trousseau du système : {}", reason),
+            VaultError::Io(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl std::error::Error for VaultError {}
+
+impl From<std::io::Error> for VaultError {
+    fn from(e: std::io::Error) -> Self {
+        VaultError::Io(e)
+    }
+}
+
+impl From<keyring::Error> for VaultError {
+    fn from(e: keyring::Error) -> Self {
+        VaultError::Keyring(e.to_string())
+    }
+}
+
+#[derive(Default)]
+struct VaultState {
+    dir: Option<PathBuf>,
+    config: Option<VaultConfig>,
+    key: Option<Key>,
+}
+
+static STATE: Lazy<RwLock<VaultState>> = Lazy::new(|| RwLock::new(VaultState::default()));
+
+// ── Primitives ──────────────────────────────────────────────────────────────
+
+/// Dérive la clé maître d'une phrase secrète (Argon2id, paramètres par défaut)
+pub fn derive_key(passphrase: &str, salt: &[u8]) -> Key {
+    let mut key = Key::default();
+    Argon2::default()
+        .hash_password_into(passphrase.as_bytes(), salt, &mut key)
+        .expect("Argon2 output length is valid");
+    key
+}
+
+/// `nonce ‖ chiffré` ; un nonce aléatoire de 192 bits par message
+pub fn seal_bytes(key: &Key, plain: &[u8]) -> Vec<u8> {
+    let cipher = XChaCha20Poly1305::new(key);
+    let nonce = XChaCha20Poly1305::generate_nonce(&mut OsRng);
+    let sealed = cipher
+        .encrypt(&nonce, plain)
+        .expect("XChaCha20-Poly1305 encryption does not fail");
+    let mut out = Vec::with_capacity(NONCE_LEN + sealed.len());
+    out.extend_from_slice(&nonce);
+    out.extend_from_slice(&sealed);
+    out
+}
+
+pub fn open_bytes(key: &Key, data: &[u8]) -> Result<Vec<u8>, VaultError> {
+    if data.len() < NONCE_LEN {
+        return Err(VaultError::Corrupt);
+    }
+    let (nonce, sealed) = data.split_at(NONCE_LEN);
+    XChaCha20Poly1305::new(key)
+        .decrypt(XNonce::from_slice(nonce), sealed)
+        .map_err(|_| VaultError::Corrupt)
+}
+
+pub fn is_sealed_secret(value: &str) -> bool {
+    value.starts_with(SECRET_PREFIX)
+}
+
+pub fn is_sealed_file(data: &[u8]) -> bool {
+    data.starts_with(FILE_MAGIC)
+}
+
+pub fn encode_secret(key: &Key, plain: &str) -> String {
+    format!(
+        "{}{}",
+        SECRET_PREFIX,
+        STANDARD.encode(seal_bytes(key, plain.as_bytes()))
+    )
+}
+
+/// Déchiffre une chaîne ; une valeur sans préfixe est rendue telle quelle
+pub fn decode_secret(key: &Key, value: &str) -> Result<String, VaultError> {
+    let Some(encoded) = value.strip_prefix(SECRET_PREFIX) else {
+        return Ok(value.to_string());
+    };
+    let data = STANDARD.decode(encoded).map_err(|_| VaultError::Corrupt)?;
+    String::from_utf8(open_bytes(key, &data)?).map_err(|_| VaultError::Corrupt)
+}
+
+pub fn encode_file(key: &Key, plain: &[u8]) -> Vec<u8> {
+    let mut out = FILE_MAGIC.to_vec();
+    out.extend(seal_bytes(key, plain));
+    out
+}
+
+/// Déchiffre le contenu d'un fichier ; un contenu sans en-tête est rendu tel quel
+pub fn decode_file(key: &Key, data: Vec<u8>) -> Result<Vec<u8>, VaultError> {
+    match data.strip_prefix(FILE_MAGIC.as_slice()) {
+        Some(sealed) => open_bytes(key, sealed),
+        None => Ok(data),
+    }
+}
+
+// ── État global ─────────────────────────────────────────────────────────────
+
+fn config_path(dir: &Path) -> PathBuf {
+    dir.join(VAULT_FILE)
+}
+
+fn keyring_entry() -> Result<keyring::Entry, VaultError> {
+    Ok(keyring::Entry::new(KEYRING_SERVICE, KEYRING_USER)?)
+}
+
+fn keyring_key() -> Result<Key, VaultError> {
+    let encoded = keyring_entry()?.get_password()?;
+    let bytes = STANDARD.decode(encoded).map_err(|_| VaultError::Corrupt)?;
+    if bytes.len() != 32 {
+        return Err(VaultError::Corrupt);
+    }
+    Ok(*Key::from_slice(&bytes))
+}
+
+fn check_key(config: &VaultConfig, key: &Key) -> Result<(), VaultError> {
+    match decode_secret(key, &config.check) {
+        Ok(check) if check == CHECK_PLAINTEXT => Ok(()),
+        _ => Err(VaultError::WrongPassphrase),
+    }
+}
+
+fn write_config(dir: &Path, config: &VaultConfig) -> Result<(), VaultError> {
+    let json = serde_json::to_string_pretty(config).expect("vault config serializes");
+    fs::write(config_path(dir), json)?;
+    Ok(())
+}
+
+/// Charge `vault.json` ; en mode trousseau, la clé est récupérée aussitôt.
+/// Supprime au passage les copies en clair laissées par d'anciennes versions.
+pub fn init(app_data_dir: &Path) {
+    let legacy_playback = app_data_dir.join(LEGACY_PLAYBACK_DIR);
+    if legacy_playback.exists() {
+        match fs::remove_dir_all(&legacy_playback) {
+            Ok(()) => log::info!("Removed plaintext playback copies"),
+            Err(e) => log::error!("Failed to remove {}: {}", legacy_playback.display(), e),
+        }
+    }
+
+    let mut state = STATE.write().unwrap();
+    state.dir = Some(app_data_dir.to_path_buf());
+    state.config = fs::read_to_string(config_path(app_data_dir))
+        .ok()
+        .and_then(|json| match serde_json::from_str::<VaultConfig>(&json) {
+            Ok(config) => Some(config),
+            Err(e) => {
+                log::error!("Invalid {}: {}", VAULT_FILE, e);
+                None
+            }
+        });
+
+    if let Some(config) = state.config.clone() {
+        if config.mode == VaultMode::Keyring {
+            match keyring_key().and_then(|key| check_key(&config, &key).map(|_| key)) {
+                Ok(key) => state.key = Some(key),
+                Err(e) => log::error!("Failed to unlock vault from keyring: {}", e),
+            }
+        }
+        log::info!(
+            "Encryption at rest enabled ({:?}, {})",
+            config.mode,
+            if state.key.is_some() {
+                "unlocked"
+            } else {
+                "locked"
+            }
+        );
+    }
+}
+
+pub fn status() -> VaultStatus {
+    let state = STATE.read().unwrap();
+    VaultStatus {
+        enabled: state.config.is_some(),
+        mode: state.config.as_ref().map(|c| c.mode),
+        unlocked: state.key.is_some(),
+    }
+}
+
+pub fn is_enabled() -> bool {
+    STATE.read().unwrap().config.is_some()
+}
+
+/// Activé mais clé non chargée : rien ne peut être chiffré
+pub fn is_locked() -> bool {
+    let state = STATE.read().unwrap();
+    state.config.is_some() && state.key.is_none()
+}
+
+/// Crée la clé maître et `vault.json` ; les données existantes sont ensuite
+/// chiffrées par l'appelant (voir `commands::vault`)
+pub fn enable(mode: VaultMode, passphrase: Option<&str>) -> Result<(), VaultError> {
+    let mut state = STATE.write().unwrap();
+    if state.config.is_some() {
+        return Err(VaultError::AlreadyEnabled);
+    }
+    let dir = state.dir.clone().ok_or(VaultError::NotEnabled)?;
+
+    let (key, salt) = match mode {
+        VaultMode::Passphrase => {
+            let passphrase = passphrase
+                .filter(|p| !p.is_empty())
+                .ok_or(VaultError::WrongPassphrase)?;
+            let mut salt = [0u8; SALT_LEN];
+            OsRng.fill_bytes(&mut salt);
+            (derive_key(passphrase, &salt), Some(STANDARD.encode(salt)))
+        }
+        VaultMode::Keyring => {
+            let key = XChaCha20Poly1305::generate_key(&mut OsRng);
+            keyring_entry()?.set_password(&STANDARD.encode(key))?;
+            (key, None)
+        }
+    };
+
+    let config = VaultConfig {
+        mode,
+        salt,
+        check: encode_secret(&key, CHECK_PLAINTEXT),
+    };
+    write_config(&dir, &config)?;
+    state.config = Some(config);
+    state.key = Some(key);
+    Ok(())
+}
+
+/// Charge la clé : phrase secrète en mode `Passphrase`, trousseau sinon
+pub fn unlock(passphrase: Option<&str>) -> Result<(), VaultError> {
+    let mut state = STATE.write().unwrap();
+    let config = state.config.clone().ok_or(VaultError::NotEnabled)?;
+    let key = match config.mode {
+        VaultMode::Passphrase => {
+            let salt = config
+                .salt
+                .as_deref()
+                .and_then(|s| STANDARD.decode(s).ok())
+                .ok_or(VaultError::Corrupt)?;
+            derive_key(passphrase.unwrap_or_default(), &salt)
+        }
+        VaultMode::Keyring => keyring_key()?,
+    };
+    check_key(&config, &key)?;
+    state.key = Some(key);
+    Ok(())
+}
+
+/// Oublie la clé
+pub fn lock() -> Result<(), VaultError> {
+    let mut state = STATE.write().unwrap();
+    if state.config.is_none() {
+        return Err(VaultError::NotEnabled);
+    }
+    state.key = None;
+    Ok(())
+}
+
+/// Supprime `vault.json` et la clé ; les données doivent déjà être en clair
+pub fn disable() -> Result<(), VaultError> {
+    let mut state = STATE.write().unwrap();
+    let config = state.config.take().ok_or(VaultError::NotEnabled)?;
+    if let Some(dir) = &state.dir {
+        fs::remove_file(config_path(dir))?;
+    }
+    if config.mode == VaultMode::Keyring {
+        if let Err(e) = keyring_entry().and_then(|e| Ok(e.delete_credential()?)) {
+            log::warn!("Failed to delete vault key from keyring: {}", e);
+        }
+    }
+    state.key = None;
+    Ok(())
+}
+
+/// Chiffre une valeur si le coffre est activé ; une chaîne vide reste vide
+pub fn seal_secret(plain: &str) -> Result<String, VaultError> {
+    let state = STATE.read().unwrap();
+    if state.config.is_none() || plain.is_empty() {
+        return Ok(plain.to_string());
+    }
+    let key = state.key.as_ref().ok_or(VaultError::Locked)?;
+    Ok(encode_secret(key, plain))
+}
+
+/// Valeur en clair, ou `None` si elle est chiffrée et le coffre verrouillé
+pub fn open_secret(value: &str) -> Option<String> {
+    if !is_sealed_secret(value) {
+        return Some(value.to_string());
+    }
+    let state = STATE.read().unwrap();
+    let key = state.key.as_ref()?;
+    match decode_secret(key, value) {
+        Ok(plain) => Some(plain),
+        Err(e) => {
+            log::error!("Failed to decrypt value: {}", e);
+            None
+        }
+    }
+}
+
+/// Réécrit un fichier chiffré (`seal`) ou en clair, via un fichier temporaire
+pub fn reseal_file(path: &Path, seal: bool) -> Result<(), VaultError> {
+    let data = fs::read(path)?;
+    if is_sealed_file(&data) == seal {
+        return Ok(());
+    }
+    let state = STATE.read().unwrap();
+    let key = state.key.as_ref().ok_or(VaultError::Locked)?;
+    let out = if seal {
+        encode_file(key, &data)
+    } else {
+        decode_file(key, data)?
+    };
+    let tmp = path.with_extension("tmp");
+    fs::write(&tmp, out)?;
+    fs::rename(&tmp, path)?;
+    Ok(())
+}
+
+/// Écrit un fichier, chiffré si le coffre est activé
+pub fn write_file(path: &Path, data: &[u8]) -> Result<(), VaultError> {
+    let state = STATE.read().unwrap();
+    if state.config.is_none() {
+        fs::write(path, data)?;
+        return Ok(());
+    }
+    let key = state.key.as_ref().ok_or(VaultError::Locked)?;
+    fs::write(path, encode_file(key, data))?;
+    Ok(())
+}
+
+/// Contenu en clair d'un fichier, chiffré ou non
+pub fn read_file(path: &Path) -> Result<Vec<u8>, VaultError> {
+    let data = fs::read(path)?;
+    if !is_sealed_file(&data) {
+        return Ok(data);
+    }
+    let state = STATE.read().unwrap();
+    let key = state.key.as_ref().ok_or(VaultError::Locked)?;
+    decode_file(key, data)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_key() -> Key {
+        XChaCha20Poly1305::generate_key(&mut OsRng)
+    }
+
+    #[test]
+    fn secret_round_trip() {
+        let key = test_key();
+        let sealed = encode_secret(&key, "sk-live-123");
+        assert!(is_sealed_secret(&sealed));
+        assert!(!sealed.contains("sk-live"));
+        assert_eq!(decode_secret(&key, &sealed).unwrap(), "sk-live-123");
+    }
+
+    #[test]
+    fn plaintext_passes_through() {
+        let key = test_key();
+        assert_eq!(
+            decode_secret(&key, "déjà en clair").unwrap(),
+            "déjà en clair"
+        );
+        assert_eq!(decode_file(&key, b"RIFF".to_vec()).unwrap(), b"RIFF");
+    }
+
+    #[test]
+    fn same_plaintext_gives_different_ciphertexts() {
+        let key = test_key();
+        assert_ne!(encode_secret(&key, "abc"), encode_secret(&key, "abc"));
+    }
+
+    #[test]
+    fn wrong_key_or_tampering_is_rejected() {
+        let key = test_key();
+        let sealed = encode_secret(&key, "secret");
+        assert!(matches!(
+            decode_secret(&test_key(), &sealed),
+            Err(VaultError::Corrupt)
+        ));
+
+        let mut file = encode_file(&key, b"RIFF....WAVE");
+        let last = file.len() - 1;
+        file[last] ^= 1;
+        assert!(matches!(decode_file(&key, file), Err(VaultError::Corrupt)));
+        assert!(matches!(
+            decode_file(&key, FILE_MAGIC.to_vec()),
+            Err(VaultError::Corrupt)
+        ));
+    }
+
+    #[test]
+    fn file_round_trip_keeps_magic() {
+        let key = test_key();
+        let sealed = encode_file(&key, b"RIFF....WAVE");
+        assert!(is_sealed_file(&sealed));
+        assert_eq!(decode_file(&key, sealed).unwrap(), b"RIFF....WAVE");
+    }
+
+    #[test]
+    fn passphrase_derivation_is_deterministic_per_salt() {
+        let salt = [7u8; SALT_LEN];
+        let key = derive_key("correct horse", &salt);
+        assert_eq!(key, derive_key("correct horse", &salt));
+        assert_ne!(key, derive_key("correct horse", &[8u8; SALT_LEN]));
+        assert_ne!(key, derive_key("wrong horse", &salt));
+    }
+}
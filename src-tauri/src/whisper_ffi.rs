@@ -13,8 +13,11 @@
 /// - whisper_wrapper.c encapsule la config de whisper_full_params (struct trop large pour Rust)
 /// - Metal détecté à la compilation (arm64 macOS = Metal disponible)
 /// - CoreML nécessite Xcode complet + recompilation avec WHISPER_COREML=ON
+/// - Linux x86_64 / aarch64 : backend CPU (AVX2 / NEON), OpenBLAS si présent au build
 
 use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+use specta::Type;
 
 /// Params de transcription Whisper optimisés pour le français (ADR-002)
 #[derive(Debug, Clone)]
@@ -25,6 +28,30 @@ pub struct WhisperParams {
     pub translate: bool,
     /// Seuil de non-parole — évite les hallucinations
     pub no_speech_threshold: f32,
+    /// Prompt initial (fin du segment précédent pour les dictées longues)
+    pub initial_prompt: Option<String>,
+    /// Largeur du beam search — 1 = décodage greedy
+    pub beam_size: u32,
+    /// Candidats échantillonnés en greedy lors du repli en température
+    pub best_of: u32,
+    /// Température initiale (0.0 = déterministe)
+    pub temperature: f32,
+    /// Pas du repli en température quand un décodage échoue (0.0 = pas de repli)
+    pub temperature_inc: f32,
+    /// Entropie au-delà de laquelle un décodage est rejeté (répétitions)
+    pub entropy_threshold: f32,
+    /// Log-probabilité moyenne en dessous de laquelle un décodage est rejeté
+    pub logprob_threshold: f32,
+    /// Interdit un blanc en début de segment
+    pub suppress_blank: bool,
+    /// Ignore le texte des segments précédents (dictée = phrases isolées)
+    pub no_context: bool,
+    /// Force un seul segment par appel
+    pub single_segment: bool,
+    /// Threads de calcul (0 = parallélisme disponible)
+    pub n_threads: u32,
+    /// Longueur max d'un segment en caractères (0 = illimitée)
+    pub max_segment_len: u32,
 }
 
 impl Default for WhisperParams {
@@ -33,10 +60,57 @@ impl Default for WhisperParams {
             language: "fr".to_string(),
             translate: false,
             no_speech_threshold: 0.6, // ADR-002
+            initial_prompt: None,
+            beam_size: 1,
+            best_of: 1,
+            temperature: 0.0,
+            temperature_inc: 0.2,
+            entropy_threshold: 2.4,
+            logprob_threshold: -1.0,
+            suppress_blank: true,
+            no_context: true,
+            single_segment: true,
+            n_threads: 4, // M1 = 4 perf cores
+            max_segment_len: 0,
         }
     }
 }
 
+/// Réglage de décodage par modèle
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, Type)]
+#[serde(rename_all = "snake_case")]
+pub enum WhisperPreset {
+    /// Greedy, un seul candidat — latence minimale
+    #[default]
+    Fast,
+    /// Beam search 5 — plus lent, moins d'erreurs sur les phrases longues
+    Accurate,
+}
+
+impl WhisperParams {
+    /// Params par défaut ajustés pour `preset`
+    pub fn for_preset(preset: WhisperPreset) -> Self {
+        match preset {
+            WhisperPreset::Fast => Self::default(),
+            WhisperPreset::Accurate => Self {
+                beam_size: 5,
+                best_of: 5,
+                ..Self::default()
+            },
+        }
+    }
+
+    /// Nombre de threads effectif (`n_threads` = 0 → parallélisme disponible)
+    pub fn effective_threads(&self) -> u32 {
+        if self.n_threads > 0 {
+            return self.n_threads;
+        }
+        std::thread::available_parallelism()
+            .map(|n| n.get() as u32)
+            .unwrap_or(4)
+    }
+}
+
 /// Résultat de transcription avec métadonnées
 #[derive(Debug, Clone)]
 pub struct WhisperResult {
@@ -67,16 +141,57 @@ mod ffi {
             i_segment: c_int,
         ) -> c_float;
 
+        // Capacités CPU compilées dans ggml-cpu
+        pub fn ggml_cpu_has_avx2() -> c_int;
+        pub fn ggml_cpu_has_neon() -> c_int;
+
         // Wrapper C (whisper_wrapper.c) — encapsule la config whisper_full_params
         // language: code ISO (ex: "fr"), NULL = auto-détection
+        // initial_prompt: NULL = pas de contexte
         pub fn whisper_run(
             ctx: *mut std::ffi::c_void,
             language: *const c_char,
             translate: bool,
+            initial_prompt: *const c_char,
+            options: *const RunOptions,
             samples: *const c_float,
             n_samples: c_int,
         ) -> c_int;
     }
+
+    /// Miroir de `struct whisper_run_options` (whisper_wrapper.c)
+    #[repr(C)]
+    pub struct RunOptions {
+        pub beam_size: c_int,
+        pub best_of: c_int,
+        pub temperature: c_float,
+        pub temperature_inc: c_float,
+        pub entropy_thold: c_float,
+        pub logprob_thold: c_float,
+        pub suppress_blank: bool,
+        pub no_context: bool,
+        pub single_segment: bool,
+        pub n_threads: c_int,
+        pub max_len: c_int,
+    }
+
+    impl From<&super::WhisperParams> for RunOptions {
+        fn from(p: &super::WhisperParams) -> Self {
+            Self {
+                beam_size: p.beam_size as c_int,
+                best_of: p.best_of as c_int,
+                temperature: p.temperature,
+                temperature_inc: p.temperature_inc,
+                entropy_thold: p.entropy_threshold,
+                logprob_thold: p.logprob_threshold,
+                suppress_blank: p.suppress_blank,
+                no_context: p.no_context,
+                single_segment: p.single_segment,
+                n_threads: p.effective_threads() as c_int,
+                max_len: p.max_segment_len as c_int,
+            }
+        }
+    }
 }
 
 // ─── WhisperContext ───────────────────────────────────────────────────────────
@@ -113,11 +228,7 @@ impl WhisperContext {
                     model_path.display()
                 ));
             }
-            let coreml = cfg!(whisper_coreml);
-            log::info!(
-                "whisper.cpp chargé — Metal: true (Apple Silicon), CoreML: {}",
-                if coreml { "true (Xcode complet)" } else { "false (CLT seulement)" }
-            );
+            log::info!("whisper.cpp chargé — backend: {}", backend_description());
             Ok(Self { ptr })
         }
 
@@ -138,6 +249,14 @@ impl WhisperContext {
             use std::ffi::{CStr, CString};
 
             let language_c = CString::new(params.language.as_str())?;
+            let prompt_c = params
+                .initial_prompt
+                .as_deref()
+                .filter(|p| !p.is_empty())
+                .map(CString::new)
+                .transpose()?;
+
+            let options = ffi::RunOptions::from(params);
 
             // Transcription via le wrapper C (gère whisper_full_params)
             let ret = unsafe {
@@ -145,6 +264,8 @@ impl WhisperContext {
                     self.ptr,
                     language_c.as_ptr(),
                     params.translate,
+                    prompt_c.as_ref().map_or(std::ptr::null(), |p| p.as_ptr()),
+                    &options,
                     audio.as_ptr(),
                     audio.len() as std::os::raw::c_int,
                 )
@@ -216,6 +337,62 @@ pub fn is_coreml_available() -> bool {
     cfg!(all(target_os = "macos", whisper_coreml, whisper_native))
 }
 
+/// OpenBLAS lié (Linux, détecté par build-whisper.sh)
+pub fn is_blas_available() -> bool {
+    cfg!(all(whisper_blas, whisper_native))
+}
+
+/// Kernels AVX2 compilés dans ggml-cpu (x86_64)
+pub fn is_avx2_available() -> bool {
+    #[cfg(whisper_native)]
+    {
+        unsafe { ffi::ggml_cpu_has_avx2() != 0 }
+    }
+    #[cfg(not(whisper_native))]
+    {
+        false
+    }
+}
+
+/// Kernels NEON compilés dans ggml-cpu (aarch64)
+pub fn is_neon_available() -> bool {
+    #[cfg(whisper_native)]
+    {
+        unsafe { ffi::ggml_cpu_has_neon() != 0 }
+    }
+    #[cfg(not(whisper_native))]
+    {
+        false
+    }
+}
+
+/// Résumé des accélérations actives, pour les logs (ex: "CPU AVX2 + OpenBLAS")
+pub fn backend_description() -> String {
+    if !cfg!(whisper_native) {
+        return "stub".to_string();
+    }
+    let mut parts = Vec::new();
+    if is_metal_available() {
+        parts.push("Metal");
+    }
+    if is_coreml_available() {
+        parts.push("CoreML");
+    }
+    if parts.is_empty() {
+        parts.push(if is_avx2_available() {
+            "CPU AVX2"
+        } else if is_neon_available() {
+            "CPU NEON"
+        } else {
+            "CPU"
+        });
+    }
+    if is_blas_available() {
+        parts.push("OpenBLAS");
+    }
+    parts.join(" + ")
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -226,6 +403,30 @@ mod tests {
         assert_eq!(p.language, "fr");
         assert!(!p.translate);
         assert_eq!(p.no_speech_threshold, 0.6);
+        assert!(p.initial_prompt.is_none());
+        // Comportement historique du wrapper : greedy, un segment, 4 threads
+        assert_eq!(p.beam_size, 1);
+        assert!(p.no_context && p.single_segment && p.suppress_blank);
+        assert_eq!(p.n_threads, 4);
+    }
+
+    #[test]
+    fn accurate_preset_uses_beam_search() {
+        let fast = WhisperParams::for_preset(WhisperPreset::Fast);
+        let accurate = WhisperParams::for_preset(WhisperPreset::Accurate);
+        assert_eq!(fast.beam_size, 1);
+        assert_eq!(accurate.beam_size, 5);
+        assert_eq!(accurate.language, "fr");
+        assert_eq!(WhisperPreset::default(), WhisperPreset::Fast);
+    }
+
+    #[test]
+    fn zero_threads_means_available_parallelism() {
+        let p = WhisperParams {
+            n_threads: 0,
+            ..Default::default()
+        };
+        assert!(p.effective_threads() >= 1);
     }
 
     #[test]
@@ -245,12 +446,71 @@ mod tests {
         {
             assert!(!is_coreml_available());
             assert!(!is_metal_available());
+            assert!(!is_blas_available());
+            assert!(!is_avx2_available());
+            assert!(!is_neon_available());
+            assert_eq!(backend_description(), "stub");
         }
         // En mode natif sur Apple Silicon, Metal est disponible
-        #[cfg(all(whisper_native, target_arch = "aarch64"))]
+        #[cfg(all(whisper_native, target_os = "macos", target_arch = "aarch64"))]
         {
             assert!(is_metal_available());
             assert!(!is_coreml_available()); // pas de Xcode complet
         }
     }
+
+    /// Modèle ggml-tiny pour les tests natifs (fourni par la CI Linux)
+    #[cfg(whisper_native)]
+    fn test_model() -> Option<std::path::PathBuf> {
+        std::env::var_os("WHISPER_TEST_MODEL").map(std::path::PathBuf::from)
+    }
+
+    #[cfg(all(whisper_native, target_os = "linux"))]
+    #[test]
+    fn linux_backend_uses_cpu_kernels() {
+        assert!(!is_metal_available());
+        #[cfg(target_arch = "aarch64")]
+        assert!(is_neon_available());
+        assert!(backend_description().starts_with("CPU"));
+    }
+
+    #[cfg(whisper_native)]
+    #[test]
+    fn native_load_missing_model_errs() {
+        let result = WhisperContext::load(std::path::Path::new("/nonexistent/model.bin"));
+        assert!(result.is_err());
+    }
+
+    #[cfg(whisper_native)]
+    #[test]
+    fn native_transcribes_silence() {
+        let Some(model) = test_model() else {
+            eprintln!("WHISPER_TEST_MODEL absent — test ignoré");
+            return;
+        };
+        let ctx = WhisperContext::load(&model).expect("chargement du modèle tiny");
+        let silence = vec![0.0f32; 16_000 * 2];
+        let result = ctx
+            .transcribe(&silence, &WhisperParams::default())
+            .expect("transcription");
+        assert!((0.0..=1.0).contains(&result.no_speech_prob));
+    }
+
+    #[cfg(whisper_native)]
+    #[test]
+    fn native_accurate_preset_runs_beam_search() {
+        let Some(model) = test_model() else {
+            eprintln!("WHISPER_TEST_MODEL absent — test ignoré");
+            return;
+        };
+        let ctx = WhisperContext::load(&model).expect("chargement du modèle tiny");
+        let tone: Vec<f32> = (0..16_000)
+            .map(|i| (i as f32 * 440.0 * std::f32::consts::TAU / 16_000.0).sin() * 0.1)
+            .collect();
+        let params = WhisperParams {
+            language: "en".to_string(),
+            ..WhisperParams::for_preset(WhisperPreset::Accurate)
+        };
+        assert!(ctx.transcribe(&tone, &params).is_ok());
+    }
 }
@@ -11,42 +11,80 @@
 #include "whisper.h"
 
 /**
- * Lance une transcription avec des params optimisés pour la latence.
+ * Réglages de décodage exposés à Rust (miroir : whisper_ffi.rs `RunOptions`).
+ */
+struct whisper_run_options {
+    int   beam_size;        // <= 1 : greedy
+    int   best_of;          // Candidats greedy lors du repli en température
+    float temperature;
+    float temperature_inc;  // 0 = pas de repli en température
+    float entropy_thold;
+    float logprob_thold;
+    bool  suppress_blank;
+    bool  no_context;
+    bool  single_segment;
+    int   n_threads;
+    int   max_len;          // Longueur max d'un segment (caractères), 0 = illimitée
+};
+
+/**
+ * Lance une transcription avec les réglages de décodage fournis.
  *
  * @param ctx      Contexte whisper (de whisper_init_from_file)
  * @param language Code langue ISO (ex: "fr", "en") — NULL = auto-détection
  * @param translate true = traduire vers l'anglais
+ * @param initial_prompt Contexte textuel (segment précédent) — NULL = aucun
+ * @param options  Réglages de décodage (preset rapide / précis)
  * @param samples  Samples audio PCM f32 mono 16kHz
  * @param n_samples Nombre de samples
  * @return Code retour whisper_full() — 0 = succès
  */
 int whisper_run(
-    struct whisper_context* ctx,
-    const char*             language,
-    bool                    translate,
-    const float*            samples,
-    int                     n_samples
+    struct whisper_context*            ctx,
+    const char*                        language,
+    bool                               translate,
+    const char*                        initial_prompt,
+    const struct whisper_run_options*  options,
+    const float*                       samples,
+    int                                n_samples
 ) {
-    struct whisper_full_params* p =
-        whisper_full_default_params_by_ref(WHISPER_SAMPLING_GREEDY);
+    bool beam = options->beam_size > 1;
+    struct whisper_full_params* p = whisper_full_default_params_by_ref(
+        beam ? WHISPER_SAMPLING_BEAM_SEARCH : WHISPER_SAMPLING_GREEDY);
 
     // --- Langue et mode ---
     p->language         = language;
     p->translate        = translate;
+    p->initial_prompt   = initial_prompt;
 
-    // --- Optimisations latence ---
-    p->no_context       = true;   // Pas d'historique entre segments (dictée = phrases isolées)
-    p->single_segment   = true;   // Un seul segment — évite le découpage multi-segment
-    p->n_threads        = 4;      // M1 = 4 perf cores, saturer les cores efficaces
+    // --- Segmentation et contexte ---
+    p->no_context       = options->no_context;
+    p->single_segment   = options->single_segment;
+    p->n_threads        = options->n_threads;
     // Note: flash_attn belongs to whisper_context_params, not whisper_full_params
 
-    // --- Greedy optimisé ---
-    p->greedy.best_of   = 1;      // Pas de beam search, un seul candidat (plus rapide)
+    // --- Stratégie de décodage ---
+    if (beam) {
+        p->beam_search.beam_size = options->beam_size;
+    }
+    p->greedy.best_of   = options->best_of;
+    p->temperature      = options->temperature;
+    p->temperature_inc  = options->temperature_inc;
+    p->entropy_thold    = options->entropy_thold;
+    p->logprob_thold    = options->logprob_thold;
 
     // --- Réduction du travail inutile ---
-    p->suppress_blank   = true;   // Supprime les tokens vides
+    p->suppress_blank   = options->suppress_blank;
     p->suppress_nst     = true;   // Supprime les tokens non-speech
-    p->no_timestamps    = true;   // Pas de timestamps (on n'en a pas besoin)
+
+    // max_len découpe sur les timestamps de tokens, inutiles sinon
+    if (options->max_len > 0) {
+        p->max_len          = options->max_len;
+        p->token_timestamps = true;
+        p->no_timestamps    = false;
+    } else {
+        p->no_timestamps    = true;
+    }
 
     // --- Désactiver toute sortie console ---
     p->print_special    = false;
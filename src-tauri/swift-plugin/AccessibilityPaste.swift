@@ -31,6 +31,38 @@ public func accessibilitySaveTargetApp() {
     savedTargetApp = NSWorkspace.shared.frontmostApplication
 }
 
+/// Nom de l'app cible mémorisée (variable `${app}` des recettes).
+/// Chaîne allouée par strdup — libérée côté Rust avec free().
+@_cdecl("accessibility_target_app_name")
+public func accessibilityTargetAppName() -> UnsafeMutablePointer<CChar>? {
+    guard let name = savedTargetApp?.localizedName else { return nil }
+    return strdup(name)
+}
+
+/// Empreinte de l'élément qui a le focus clavier (« pid:hash AX »), pour
+/// refuser « annuler la dernière dictée » si la cible a changé depuis le collage.
+/// Chaîne allouée par strdup — libérée côté Rust avec free().
+@_cdecl("accessibility_focused_element_id")
+public func accessibilityFocusedElementId() -> UnsafeMutablePointer<CChar>? {
+    guard AXIsProcessTrusted() else { return nil }
+
+    let systemWide = AXUIElementCreateSystemWide()
+    var focusedRef: CFTypeRef?
+    guard AXUIElementCopyAttributeValue(
+        systemWide,
+        kAXFocusedUIElementAttribute as CFString,
+        &focusedRef
+    ) == .success, let focusedRef = focusedRef else {
+        return nil
+    }
+
+    // swiftlint:disable:next force_cast
+    let focused = focusedRef as! AXUIElement
+    var pid: pid_t = 0
+    AXUIElementGetPid(focused, &pid)
+    return strdup("\(pid):\(CFHash(focused))")
+}
+
 // MARK: - Public FFI API
 
 /// Colle le texte à la position du curseur dans l'application active.
@@ -145,8 +177,9 @@ private func tryAXPaste(text: String) -> Bool {
 
 /// Presse-papier + simulation Cmd+V — fallback universel.
 ///
-/// Sauvegarde le contenu du presse-papier avant le collage et le restaure
-/// 150ms après pour minimiser l'impact sur le workflow de l'utilisateur.
+/// Sauvegarde le contenu du presse-papier (tous les éléments et tous leurs
+/// types : images, fichiers, RTF…) avant le collage et le restaure ensuite
+/// pour minimiser l'impact sur le workflow de l'utilisateur.
 ///
 /// Réactive explicitement l'app cible avant le Cmd+V pour les apps comme
 /// Chrome/IDX où le Monaco editor peut perdre son focus interne quand
@@ -155,8 +188,16 @@ private func pasteViaClipboard(text: String) {
     let pasteboard = NSPasteboard.general
     let savedChangeCount = pasteboard.changeCount
 
-    // Sauvegarder le contenu existant (string uniquement pour l'instant)
-    let savedString = pasteboard.string(forType: .string)
+    // Sauvegarder le contenu existant : chaque élément avec tous ses types
+    let savedItems: [NSPasteboardItem] = (pasteboard.pasteboardItems ?? []).map { item in
+        let copy = NSPasteboardItem()
+        for type in item.types {
+            if let data = item.data(forType: type) {
+                copy.setData(data, forType: type)
+            }
+        }
+        return copy
+    }
 
     // Écrire le texte à coller
     pasteboard.clearContents()
@@ -197,11 +238,9 @@ private func pasteViaClipboard(text: String) {
         // autre application entre-temps (changeCount +1 = notre propre modification)
         guard pasteboard.changeCount == savedChangeCount + 1 else { return }
 
-        if let saved = savedString {
-            pasteboard.clearContents()
-            pasteboard.setString(saved, forType: .string)
-        } else {
-            pasteboard.clearContents()
+        pasteboard.clearContents()
+        if !savedItems.isEmpty {
+            pasteboard.writeObjects(savedItems)
         }
     }
 }
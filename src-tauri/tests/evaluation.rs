@@ -0,0 +1,246 @@
+/// Évaluation qualité — WER / CER / ponctuation / structure
+///
+/// 1. Corpus synthétique (toujours exécuté) : STT simulé, vérifie le harnais.
+/// 2. Corpus réel (optionnel) : Whisper natif sur les paires du manifeste,
+///    pipeline dans chaque mode avec et sans LLM, rapports JSON + Markdown.
+///
+/// Exécution :
+///   cargo test --test evaluation -- --nocapture
+///   EVAL_MANIFEST=corpus/manifest.jsonl EVAL_MODEL=models/ggml-small.bin \
+///     cargo test --test evaluation real_corpus -- --nocapture
+///   EVAL_MANIFEST=... EVAL_MODEL=... \
+///     cargo test --test evaluation whisper_presets -- --nocapture
+///
+/// Variables d'environnement :
+///   EVAL_MANIFEST=path.jsonl   # Manifeste {audio, reference, structure?} (requis)
+///   EVAL_MODEL=path.bin        # Modèle GGML whisper.cpp (requis)
+///   EVAL_MODES=chat,pro        # Modes évalués (défaut : chat,pro,code)
+///   EVAL_LLM=0                 # Désactive les runs LLM (défaut : si Ollama dispo)
+///   EVAL_OUTPUT=target/eval    # Dossier des rapports (défaut : target/eval)
+
+use dictation_ia_lib::eval::{self, ManifestEntry, Transcript};
+use dictation_ia_lib::pipeline::modes::WriteMode;
+use dictation_ia_lib::pipeline::rules::StructureHint;
+use dictation_ia_lib::whisper_ffi::{WhisperContext, WhisperParams, WhisperPreset};
+use hound::{SampleFormat, WavSpec, WavWriter};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+const ALL_MODES: [WriteMode; 3] = [WriteMode::Chat, WriteMode::Pro, WriteMode::Code];
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Helpers
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// WAV 16 kHz mono de `secs` secondes (le contenu n'importe pas : STT simulé)
+fn write_wav(path: &Path, secs: f32) {
+    let spec = WavSpec {
+        channels: 1,
+        sample_rate: 16_000,
+        bits_per_sample: 16,
+        sample_format: SampleFormat::Int,
+    };
+    let mut writer = WavWriter::create(path, spec).unwrap();
+    for i in 0..(16_000.0 * secs) as usize {
+        let s = (i as f32 * 0.05).sin() * 8_000.0;
+        writer.write_sample(s as i16).unwrap();
+    }
+    writer.finalize().unwrap();
+}
+
+fn env_modes() -> Vec<WriteMode> {
+    match std::env::var("EVAL_MODES") {
+        Ok(v) => v
+            .split(',')
+            .filter_map(|m| m.trim().parse::<WriteMode>().ok())
+            .collect(),
+        Err(_) => ALL_MODES.to_vec(),
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Test 1 : corpus synthétique
+// ─────────────────────────────────────────────────────────────────────────────
+
+#[test]
+fn synthetic_corpus_report() {
+    let dir = tempfile::tempdir().unwrap();
+    let corpus = [
+        (
+            "salutation",
+            "euh bonjour comment tu vas",
+            "Bonjour, comment tu vas.",
+            Some(StructureHint::SingleMessage),
+        ),
+        (
+            "courses",
+            "premièrement du pain deuxièmement du lait troisièmement des œufs",
+            "- Du pain\n- Du lait\n- Des œufs.",
+            Some(StructureHint::List),
+        ),
+    ];
+
+    let mut entries: Vec<ManifestEntry> = corpus
+        .iter()
+        .map(|(id, _, reference, structure)| {
+            let audio = dir.path().join(format!("{}.wav", id));
+            write_wav(&audio, 1.0);
+            ManifestEntry {
+                id: id.to_string(),
+                audio,
+                reference: reference.to_string(),
+                structure: *structure,
+            }
+        })
+        .collect();
+    entries.push(ManifestEntry {
+        id: "absent".to_string(),
+        audio: dir.path().join("absent.wav"),
+        reference: "rien".to_string(),
+        structure: None,
+    });
+
+    // STT simulé : renvoie les transcriptions brutes dans l'ordre du corpus
+    let mut raw = corpus.iter().map(|(_, raw, _, _)| raw.to_string());
+    let report = eval::evaluate(
+        "simulé",
+        &entries,
+        &ALL_MODES,
+        None,
+        |_samples: &[f32]| {
+            Ok(Transcript {
+                text: raw.next().unwrap_or_default(),
+                confidence: 0.9,
+            })
+        },
+    );
+
+    assert_eq!(report.failures.len(), 1);
+    assert_eq!(report.failures[0].id, "absent");
+    // stt + 3 modes sans LLM
+    assert_eq!(report.summaries.len(), 4);
+    assert_eq!(report.utterances.len(), 2 * 4);
+
+    let stt = report.summary("stt").unwrap();
+    let chat = report.summary("chat").unwrap();
+    println!("\n{}", report.to_markdown());
+
+    // Le pipeline (filler « euh » retiré, ponctuation) doit battre le STT brut
+    assert!(chat.wer < stt.wer, "chat {:.3} >= stt {:.3}", chat.wer, stt.wer);
+    assert!(chat.punctuation_f1 > stt.punctuation_f1);
+    assert_eq!(stt.structure_accuracy, None);
+    assert_eq!(chat.structure_annotated, 2);
+
+    let json: serde_json::Value = serde_json::from_str(&report.to_json().unwrap()).unwrap();
+    assert_eq!(json["utterances"].as_array().unwrap().len(), 8);
+
+    let (json_path, md_path) = report.write_to_dir(&dir.path().join("out")).unwrap();
+    assert!(json_path.exists() && md_path.exists());
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Test 2 : corpus réel (optionnel)
+// ─────────────────────────────────────────────────────────────────────────────
+
+#[test]
+fn real_corpus_report() {
+    let (Ok(manifest), Ok(model)) = (std::env::var("EVAL_MANIFEST"), std::env::var("EVAL_MODEL"))
+    else {
+        println!("\n  ⚠️  EVAL_MANIFEST / EVAL_MODEL non définis — évaluation ignorée");
+        return;
+    };
+
+    let entries = eval::load_manifest(Path::new(&manifest)).expect("manifeste invalide");
+    let ctx = WhisperContext::load(Path::new(&model)).expect("chargement du modèle Whisper");
+    let params = WhisperParams::default();
+
+    let use_llm = std::env::var("EVAL_LLM").map(|v| v != "0").unwrap_or(true)
+        && dictation_ia_lib::llm::cleanup::is_model_available();
+    if !use_llm {
+        println!("\n  ⚠️  Runs LLM désactivés (EVAL_LLM=0 ou Ollama indisponible)");
+    }
+    let llm: Option<&eval::LlmCleanupFn> = if use_llm {
+        Some(&dictation_ia_lib::llm::cleanup::run as &eval::LlmCleanupFn)
+    } else {
+        None
+    };
+
+    let report = eval::evaluate(&model, &entries, &env_modes(), llm, |samples: &[f32]| {
+        let result = ctx.transcribe(samples, &params)?;
+        Ok(Transcript {
+            confidence: if result.text.trim().is_empty() {
+                0.0
+            } else {
+                (1.0 - result.no_speech_prob).clamp(0.0, 1.0)
+            },
+            text: result.text,
+        })
+    });
+
+    let out_dir = std::env::var("EVAL_OUTPUT")
+        .map(PathBuf::from)
+        .unwrap_or_else(|_| PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("target/eval"));
+    let (json_path, md_path) = report.write_to_dir(&out_dir).expect("écriture des rapports");
+
+    for s in &report.summaries {
+        println!(
+            "  📊 {:<10} n={:<4} WER={:.1}%  CER={:.1}%  ponct.F1={:.1}%",
+            s.config,
+            s.utterances,
+            s.wer * 100.0,
+            s.cer * 100.0,
+            s.punctuation_f1 * 100.0
+        );
+    }
+    println!("\n  📝 {}\n  📝 {}", json_path.display(), md_path.display());
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Test 3 : presets de décodage Whisper (optionnel)
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Compare les presets rapide (greedy) et précis (beam 5) sur le corpus réel :
+/// WER / CER du STT brut et latence de décodage par énoncé.
+#[test]
+fn whisper_presets_benchmark() {
+    let (Ok(manifest), Ok(model)) = (std::env::var("EVAL_MANIFEST"), std::env::var("EVAL_MODEL"))
+    else {
+        println!("\n  ⚠️  EVAL_MANIFEST / EVAL_MODEL non définis — benchmark ignoré");
+        return;
+    };
+
+    let entries = eval::load_manifest(Path::new(&manifest)).expect("manifeste invalide");
+    let ctx = WhisperContext::load(Path::new(&model)).expect("chargement du modèle Whisper");
+
+    for preset in [WhisperPreset::Fast, WhisperPreset::Accurate] {
+        let params = WhisperParams::for_preset(preset);
+        let mut latencies: Vec<Duration> = Vec::new();
+        let report = eval::evaluate(&model, &entries, &[], None, |samples: &[f32]| {
+            let start = Instant::now();
+            let result = ctx.transcribe(samples, &params)?;
+            latencies.push(start.elapsed());
+            Ok(Transcript {
+                confidence: (1.0 - result.no_speech_prob).clamp(0.0, 1.0),
+                text: result.text,
+            })
+        });
+
+        let stt = report.summary("stt").expect("résumé STT");
+        latencies.sort_unstable();
+        let at = |p: f64| {
+            latencies
+                .get(((latencies.len() as f64 * p).ceil() as usize).saturating_sub(1))
+                .copied()
+                .unwrap_or_default()
+        };
+        println!(
+            "  📊 {:<9} n={:<4} WER={:.1}%  CER={:.1}%  p50={:?}  p95={:?}",
+            format!("{:?}", preset),
+            stt.utterances,
+            stt.wer * 100.0,
+            stt.cer * 100.0,
+            at(0.50),
+            at(0.95)
+        );
+    }
+}
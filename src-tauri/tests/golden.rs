@@ -0,0 +1,273 @@
+/// Tests de non-régression par fichiers de référence (golden files)
+///
+/// Chaque ligne des fichiers `tests/fixtures/rules/*.jsonl` décrit une dictée
+/// brute et les sorties attendues de chaque étape du pipeline sans LLM :
+///
+///   {"name": "…", "input": "texte Whisper brut", "confidence": 0.95,
+///    "rules": "rules::apply(input)",
+///    "structure": "detect_structure(rules)",
+///    "outputs": {"chat": "orchestrator::process(…, Chat, None)", "pro": …}}
+///
+/// Seul `input` est obligatoire ; les attentes absentes ne sont pas vérifiées.
+/// `outputs` ne vérifie que les modes listés. Les lignes vides et celles
+/// commençant par `#` sont ignorées.
+///
+/// Tous les écarts sont collectés puis affichés avec un diff avant l'échec.
+///
+/// Exécution :
+///   cargo test --test golden
+///
+/// Mode bless (réécrit les attentes avec les sorties actuelles — relire le
+/// diff git avant de commiter) :
+///   GOLDEN_BLESS=1 cargo test --test golden
+///
+/// Pour ajouter une dictée problématique : ajouter une ligne `{"input": "…"}`
+/// puis lancer en mode bless pour générer les attentes.
+
+use dictation_ia_lib::pipeline::{modes::WriteMode, orchestrator, rules, rules::StructureHint};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+/// Confiance Whisper par défaut : au-dessus du seuil de routage
+const DEFAULT_CONFIDENCE: f32 = 0.95;
+
+/// Modes rejoués ; les sorties sont indexées par `WriteMode::to_string()`
+const ALL_MODES: [WriteMode; 3] = [WriteMode::Chat, WriteMode::Pro, WriteMode::Code];
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct Fixture {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    name: Option<String>,
+    input: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    confidence: Option<f32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    rules: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    structure: Option<StructureHint>,
+    /// Sortie finale sans LLM, par mode (`chat`, `pro`, `code`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    outputs: Option<BTreeMap<String, String>>,
+}
+
+/// Sorties réelles du pipeline pour une fixture
+struct Actual {
+    rules: String,
+    structure: StructureHint,
+    outputs: BTreeMap<String, String>,
+}
+
+impl Fixture {
+    fn run(&self) -> Actual {
+        let confidence = self.confidence.unwrap_or(DEFAULT_CONFIDENCE);
+        let rules = rules::apply(&self.input);
+        let structure = rules::detect_structure(&rules);
+        let outputs = ALL_MODES
+            .iter()
+            .map(|mode| {
+                let result = orchestrator::process(&self.input, confidence, *mode, None);
+                (mode.to_string(), result.text)
+            })
+            .collect();
+        Actual {
+            rules,
+            structure,
+            outputs,
+        }
+    }
+
+    /// Fixture avec les attentes remplacées par les sorties réelles.
+    /// Les modes absents de `outputs` le restent, sauf si `outputs` l'est
+    /// entièrement (nouvelle fixture) : tous les modes sont alors générés.
+    fn blessed(&self, actual: &Actual) -> Fixture {
+        let outputs = match &self.outputs {
+            Some(expected) => expected
+                .keys()
+                .filter_map(|mode| actual.outputs.get_key_value(mode))
+                .map(|(k, v)| (k.clone(), v.clone()))
+                .collect(),
+            None => actual.outputs.clone(),
+        };
+        Fixture {
+            rules: Some(actual.rules.clone()),
+            structure: Some(actual.structure),
+            outputs: Some(outputs),
+            ..self.clone()
+        }
+    }
+
+    fn label(&self, file: &Path, line: usize) -> String {
+        let file = file.file_name().unwrap_or_default().to_string_lossy();
+        match &self.name {
+            Some(name) => format!("{}:{} ({})", file, line, name),
+            None => format!("{}:{}", file, line),
+        }
+    }
+}
+
+struct Mismatch {
+    location: String,
+    field: String,
+    expected: String,
+    actual: String,
+}
+
+impl Mismatch {
+    fn print(&self) {
+        println!("\n  ✗ {} — `{}`", self.location, self.field);
+        print!("{}", diff(&self.expected, &self.actual));
+    }
+}
+
+/// Diff ligne à ligne (LCS) : `-` attendu, `+` obtenu. Les lignes sont
+/// affichées en `{:?}` pour rendre visibles espaces et ponctuation.
+fn diff(expected: &str, actual: &str) -> String {
+    let a: Vec<&str> = expected.lines().collect();
+    let b: Vec<&str> = actual.lines().collect();
+    let mut lcs = vec![vec![0usize; b.len() + 1]; a.len() + 1];
+    for i in (0..a.len()).rev() {
+        for j in (0..b.len()).rev() {
+            lcs[i][j] = if a[i] == b[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+
+    let mut out = String::new();
+    let (mut i, mut j) = (0, 0);
+    while i < a.len() || j < b.len() {
+        if i < a.len() && j < b.len() && a[i] == b[j] {
+            out.push_str(&format!("      {:?}\n", a[i]));
+            i += 1;
+            j += 1;
+        } else if i < a.len() && (j == b.len() || lcs[i + 1][j] >= lcs[i][j + 1]) {
+            out.push_str(&format!("    - {:?}\n", a[i]));
+            i += 1;
+        } else {
+            out.push_str(&format!("    + {:?}\n", b[j]));
+            j += 1;
+        }
+    }
+    out
+}
+
+fn fixtures_dir() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests")
+        .join("fixtures")
+        .join("rules")
+}
+
+fn fixture_files() -> Vec<PathBuf> {
+    let mut files: Vec<PathBuf> = std::fs::read_dir(fixtures_dir())
+        .expect("dossier tests/fixtures/rules introuvable")
+        .filter_map(|e| e.ok().map(|e| e.path()))
+        .filter(|p| p.extension().is_some_and(|e| e == "jsonl"))
+        .collect();
+    files.sort();
+    files
+}
+
+fn is_blessing() -> bool {
+    std::env::var("GOLDEN_BLESS").is_ok_and(|v| v != "0")
+}
+
+fn check(fixture: &Fixture, actual: &Actual, location: &str, mismatches: &mut Vec<Mismatch>) {
+    let mut push = |field: String, expected: String, actual: String| {
+        if expected != actual {
+            mismatches.push(Mismatch {
+                location: location.to_string(),
+                field,
+                expected,
+                actual,
+            });
+        }
+    };
+
+    if let Some(expected) = &fixture.rules {
+        push("rules".into(), expected.clone(), actual.rules.clone());
+    }
+    if let Some(expected) = fixture.structure {
+        push(
+            "structure".into(),
+            format!("{:?}", expected),
+            format!("{:?}", actual.structure),
+        );
+    }
+    for (mode, expected) in fixture.outputs.iter().flatten() {
+        match actual.outputs.get(mode) {
+            Some(output) => push(format!("outputs.{}", mode), expected.clone(), output.clone()),
+            None => push(
+                format!("outputs.{}", mode),
+                expected.clone(),
+                "<mode inconnu, attendu : chat | pro | code>".to_string(),
+            ),
+        }
+    }
+}
+
+#[test]
+fn golden_rules_fixtures() {
+    let bless = is_blessing();
+    let mut mismatches = Vec::new();
+    let mut count = 0;
+
+    for file in fixture_files() {
+        let content = std::fs::read_to_string(&file).unwrap();
+        let mut rewritten = Vec::new();
+
+        for (i, line) in content.lines().enumerate() {
+            let trimmed = line.trim();
+            if trimmed.is_empty() || trimmed.starts_with('#') {
+                rewritten.push(line.to_string());
+                continue;
+            }
+            let fixture: Fixture = serde_json::from_str(trimmed).unwrap_or_else(|e| {
+                panic!("{}:{} : fixture invalide : {}", file.display(), i + 1, e)
+            });
+            let actual = fixture.run();
+            count += 1;
+
+            if bless {
+                rewritten.push(serde_json::to_string(&fixture.blessed(&actual)).unwrap());
+            } else {
+                check(&fixture, &actual, &fixture.label(&file, i + 1), &mut mismatches);
+            }
+        }
+
+        if bless {
+            let mut output = rewritten.join("\n");
+            output.push('\n');
+            if output != content {
+                std::fs::write(&file, output).unwrap();
+                println!("  ✎ {} réécrit", file.display());
+            }
+        }
+    }
+
+    assert!(count > 0, "aucune fixture trouvée dans {}", fixtures_dir().display());
+    if bless {
+        println!("\n  ✓ {} fixtures réécrites (GOLDEN_BLESS)", count);
+        return;
+    }
+
+    for m in &mismatches {
+        m.print();
+    }
+    assert!(
+        mismatches.is_empty(),
+        "{} écart(s) sur {} fixtures — voir le diff ci-dessus \
+         (GOLDEN_BLESS=1 pour accepter les nouvelles sorties)",
+        mismatches.len(),
+        count
+    );
+}
+
+#[test]
+fn diff_marks_changed_lines() {
+    let out = diff("- Un\n- Deux", "- Un\n- Trois");
+    assert_eq!(out, "      \"- Un\"\n    - \"- Deux\"\n    + \"- Trois\"\n");
+}
@@ -21,6 +21,15 @@ import { getLanguageDirection, initializeRTL } from "@/lib/utils/rtl";
 
 type OnboardingStep = "accessibility" | "model" | "quicktest" | "done";
 
+// Payload of the "llm-fallback" event (see llm/resilience.rs)
+type LlmFallbackEvent = {
+  backend: string;
+  label: string;
+  reason: "error" | "circuit_open" | "breaker_opened" | "breaker_closed";
+  detail: string | null;
+  retry_in_secs: number | null;
+};
+
 const renderSettingsContent = (section: SidebarSection) => {
   const ActiveComponent =
     SECTIONS_CONFIG[section]?.component || SECTIONS_CONFIG.accueil.component;
@@ -104,14 +113,94 @@ function App() {
   useEffect(() => {
     if (!postProcessEnabled) return;
     let unlisten: (() => void) | undefined;
-    listen("llm-fallback", () => {
-      toast.info(t("ollama.fallbackToast"), { duration: 3000 });
+    listen<LlmFallbackEvent>("llm-fallback", (event) => {
+      const { reason, label, retry_in_secs } = event.payload;
+      switch (reason) {
+        case "breaker_opened":
+          toast.warning(
+            t("ollama.breakerOpened", {
+              backend: label,
+              minutes: Math.ceil((retry_in_secs ?? 60) / 60),
+            }),
+            { duration: 6000 },
+          );
+          break;
+        case "breaker_closed":
+          toast.success(t("ollama.breakerClosed", { backend: label }), {
+            duration: 3000,
+          });
+          break;
+        case "circuit_open":
+          // Already announced when the breaker opened
+          break;
+        default:
+          toast.info(t("ollama.fallbackToast"), { duration: 3000 });
+      }
     }).then((fn) => {
       unlisten = fn;
     });
     return () => unlisten?.();
   }, [t, postProcessEnabled]);
 
+  // Banc d'essai périmé (machine ou modèles changés) : proposer de le
+  // relancer plutôt que de charger chaque modèle sans prévenir
+  useEffect(() => {
+    const offerBenchmark = () =>
+      toast.info(t("settings.models.benchmark.staleToast"), {
+        duration: 10000,
+        action: {
+          label: t("settings.models.benchmark.run.button"),
+          onClick: async () => {
+            const result = await commands.runModelBenchmark();
+            if (result.status === "error") toast.error(result.error);
+          },
+        },
+      });
+    commands.getModelBenchmark().then((result) => {
+      if (result.status === "ok" && result.data?.stale) offerBenchmark();
+    });
+    let unlisten: (() => void) | undefined;
+    listen("model-benchmark-stale", offerBenchmark).then((fn) => {
+      unlisten = fn;
+    });
+    return () => unlisten?.();
+  }, [t]);
+
+  // Hot-plug micro : rafraîchit la liste et explique les bascules automatiques
+  useEffect(() => {
+    const unlisteners: Array<() => void> = [];
+    listen("microphone-devices-changed", () => {
+      refreshAudioDevices();
+    }).then((fn) => unlisteners.push(fn));
+    listen<{
+      from: string | null;
+      to: string | null;
+      reason: string;
+      recording: boolean;
+    }>("microphone-switched", ({ payload }) => {
+      const device = payload.to ?? t("microphoneSwitch.defaultDevice");
+      const from = payload.from ?? t("microphoneSwitch.defaultDevice");
+      if (payload.reason === "no_device") {
+        toast.warning(t("microphoneSwitch.noDevice"), { duration: 5000 });
+      } else if (payload.reason === "disconnected") {
+        toast.warning(
+          t(
+            payload.recording
+              ? "microphoneSwitch.switchedWhileRecording"
+              : "microphoneSwitch.disconnected",
+            { from, device },
+          ),
+          { duration: 5000 },
+        );
+      } else {
+        toast.info(t("microphoneSwitch.switched", { device }), {
+          duration: 3000,
+        });
+      }
+    }).then((fn) => unlisteners.push(fn));
+    return () => unlisteners.forEach((fn) => fn());
+  }, [t, refreshAudioDevices]);
+
   const checkOnboardingStatus = async () => {
     try {
       // Check if they have any models available
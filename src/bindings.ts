@@ -117,6 +117,14 @@ async changeWordCorrectionThresholdSetting(threshold: number) : Promise<Result<n
     else return { status: "error", error: e  as any };
 }
 },
+async changeMaxRecordingSecondsSetting(seconds: number) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("change_max_recording_seconds_setting", { seconds }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
 async changePasteMethodSetting(method: string) : Promise<Result<null, string>> {
     try {
     return { status: "ok", data: await TAURI_INVOKE("change_paste_method_setting", { method }) };
@@ -125,6 +133,14 @@ async changePasteMethodSetting(method: string) : Promise<Result<null, string>> {
     else return { status: "error", error: e  as any };
 }
 },
+async changeLlmStreamModeSetting(mode: string) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("change_llm_stream_mode_setting", { mode }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
 async getAvailableTypingTools() : Promise<string[]> {
     return await TAURI_INVOKE("get_available_typing_tools");
 },
@@ -152,6 +168,94 @@ async changeClipboardHandlingSetting(handling: string) : Promise<Result<null, st
     else return { status: "error", error: e  as any };
 }
 },
+/**
+ * Destinations de la dictée : collage, journal Markdown, fichier/FIFO, stdout, webhook local
+ */
+async changeOutputSinksSetting(sinks: OutputSink[]) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("change_output_sinks_setting", { sinks }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Délai avant restauration du presse-papiers après un collage (ms)
+ */
+async changeClipboardRestoreDelaySetting(delayMs: number) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("change_clipboard_restore_delay_setting", { delayMs }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Format des collages via presse-papier : texte brut ou HTML (listes, paragraphes)
+ */
+async changePasteFormatSetting(format: PasteFormat) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("change_paste_format_setting", { format }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Format imposé pour une application cible ; `None` retire l'exception
+ */
+async setAppPasteFormat(appName: string, format: PasteFormat | null) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("set_app_paste_format", { appName, format }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Active le contexte de session (dictées récentes dans les prompts)
+ */
+async changeSessionContextEnabledSetting(enabled: boolean) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("change_session_context_enabled_setting", { enabled }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Bornes du contexte de session : nombre de dictées, pause et budget de tokens
+ */
+async changeSessionContextLimitsSetting(entries: number, timeoutSecs: number, maxTokens: number) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("change_session_context_limits_setting", { entries, timeoutSecs, maxTokens }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Facteur temps réel maximal du modèle recommandé par le banc d'essai
+ */
+async changeBenchmarkTargetRtfSetting(targetRtf: number) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("change_benchmark_target_rtf_setting", { targetRtf }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Sélectionne le modèle recommandé à la fin de chaque banc d'essai
+ */
+async changeBenchmarkAutoSelectSetting(enabled: boolean) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("change_benchmark_auto_select_setting", { enabled }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
 async changeAutoSubmitSetting(enabled: boolean) : Promise<Result<null, string>> {
     try {
     return { status: "ok", data: await TAURI_INVOKE("change_auto_submit_setting", { enabled }) };
@@ -192,6 +296,14 @@ async changePostProcessBaseUrlSetting(providerId: string, baseUrl: string) : Pro
     else return { status: "error", error: e  as any };
 }
 },
+async changePostProcessKindSetting(providerId: string, kind: string) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("change_post_process_kind_setting", { providerId, kind }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
 async changePostProcessApiKeySetting(providerId: string, apiKey: string) : Promise<Result<null, string>> {
     try {
     return { status: "ok", data: await TAURI_INVOKE("change_post_process_api_key_setting", { providerId, apiKey }) };
@@ -248,6 +360,87 @@ async deletePostProcessPrompt(id: string) : Promise<Result<null, string>> {
     else return { status: "error", error: e  as any };
 }
 },
+async addPostProcessRecipe(name: string, steps: RecipeStep[]) : Promise<Result<PostProcessRecipe, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("add_post_process_recipe", { name, steps }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async updatePostProcessRecipe(id: string, name: string, steps: RecipeStep[]) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("update_post_process_recipe", { id, name, steps }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async deletePostProcessRecipe(id: string) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("delete_post_process_recipe", { id }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Associe une recette à un raccourci (`None` = comportement par défaut)
+ */
+async setBindingRecipe(bindingId: string, recipeId: string | null) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("set_binding_recipe", { bindingId, recipeId }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Create a dictation shortcut with its own write mode, prompt, language and paste method
+ */
+async addCustomBinding(name: string, binding: string, profile: BindingProfile) : Promise<Result<ShortcutBinding, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("add_custom_binding", { name, binding, profile }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Rename a custom binding and replace its profile (the shortcut itself goes through `change_binding`)
+ */
+async updateCustomBinding(id: string, name: string, profile: BindingProfile) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("update_custom_binding", { id, name, profile }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async deleteCustomBinding(id: string) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("delete_custom_binding", { id }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async setRedactionPolicy(providerId: string, policy: RedactionPolicy) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("set_redaction_policy", { providerId, policy }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async updateRedactionNames(names: string[]) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("update_redaction_names", { names }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
 async setPostProcessSelectedPrompt(id: string) : Promise<Result<null, string>> {
     try {
     return { status: "ok", data: await TAURI_INVOKE("set_post_process_selected_prompt", { id }) };
@@ -591,6 +784,37 @@ async hasAnyModelsOrDownloads() : Promise<Result<boolean, string>> {
     else return { status: "error", error: e  as any };
 }
 },
+async setWhisperPreset(modelId: string, preset: WhisperPreset) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("set_whisper_preset", { modelId, preset }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Mesure chaque modèle téléchargé sur l'extrait de référence et recommande
+ * le plus précis qui tient l'objectif de latence
+ */
+async runModelBenchmark() : Promise<Result<BenchmarkSummary, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("run_model_benchmark") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Dernières mesures, avec la recommandation pour l'objectif actuel
+ */
+async getModelBenchmark() : Promise<Result<BenchmarkSummary | null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_model_benchmark") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
 async updateMicrophoneMode(alwaysOn: boolean) : Promise<Result<null, string>> {
     try {
     return { status: "ok", data: await TAURI_INVOKE("update_microphone_mode", { alwaysOn }) };
@@ -677,6 +901,29 @@ async getClamshellMicrophone() : Promise<Result<string, string>> {
     else return { status: "error", error: e  as any };
 }
 },
+async setMicrophonePriority(devices: string[]) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("set_microphone_priority", { devices }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async getMicrophonePriority() : Promise<Result<string[], string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_microphone_priority") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Micro réellement ouvert (peut différer du micro sélectionné après un
+ * débranchement). None si le flux est fermé.
+ */
+async getActiveMicrophone() : Promise<string | null> {
+    return await TAURI_INVOKE("get_active_microphone");
+},
 async isRecording() : Promise<boolean> {
     return await TAURI_INVOKE("is_recording");
 },
@@ -699,6 +946,17 @@ async unloadModelManually() : Promise<Result<null, string>> {
     else return { status: "error", error: e  as any };
 }
 },
+/**
+ * Transcrit un fichier audio/vidéo (m4a, mp3, ogg, wav, mp4…) et l'ajoute à l'historique
+ */
+async transcribeAudioFile(path: string) : Promise<Result<string, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("transcribe_audio_file", { path }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
 async getHistoryEntries() : Promise<Result<HistoryEntry[], string>> {
     try {
     return { status: "ok", data: await TAURI_INVOKE("get_history_entries") };
@@ -715,9 +973,12 @@ async toggleHistoryEntrySaved(id: number) : Promise<Result<null, string>> {
     else return { status: "error", error: e  as any };
 }
 },
-async getAudioFilePath(fileName: string) : Promise<Result<string, string>> {
+/**
+ * Statistiques d'usage des `days` derniers jours
+ */
+async getUsageStats(days: number) : Promise<Result<UsageStats, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("get_audio_file_path", { fileName }) };
+    return { status: "ok", data: await TAURI_INVOKE("get_usage_stats", { days }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
@@ -747,6 +1008,143 @@ async updateRecordingRetentionPeriod(period: string) : Promise<Result<null, stri
     else return { status: "error", error: e  as any };
 }
 },
+async updateRecordingQuotaMb(quotaMb: number) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("update_recording_quota_mb", { quotaMb }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Formats de stockage compilés dans cette build
+ */
+async getAvailableRecordingCodecs() : Promise<RecordingCodec[]> {
+    return await TAURI_INVOKE("get_available_recording_codecs");
+},
+/**
+ * Change le format de stockage ; les enregistrements WAV existants sont
+ * convertis en arrière-plan, vers un format avec perte seulement si
+ * `convert_lossy` (conversion irréversible confirmée par l'utilisateur)
+ */
+async changeRecordingCodecSetting(codec: RecordingCodec, convertLossy: boolean) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("change_recording_codec_setting", { codec, convertLossy }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async changeRecordingOpusBitrateSetting(kbps: number) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("change_recording_opus_bitrate_setting", { kbps }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async getVaultStatus() : Promise<VaultStatus> {
+    return await TAURI_INVOKE("get_vault_status");
+},
+/**
+ * Crée la clé maître puis chiffre les données existantes
+ */
+async enableVault(mode: VaultMode, passphrase: string | null) : Promise<Result<VaultStatus, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("enable_vault", { mode, passphrase }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Charge la clé ; reprend aussi une migration interrompue
+ */
+async unlockVault(passphrase: string | null) : Promise<Result<VaultStatus, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("unlock_vault", { passphrase }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async lockVault() : Promise<Result<VaultStatus, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("lock_vault") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Remet toutes les données en clair puis supprime la clé maître
+ */
+async disableVault() : Promise<Result<VaultStatus, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("disable_vault") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Écrit les réglages dans `path` ; les clés d'API seulement si demandé
+ */
+async exportSettings(path: string, includeApiKeys: boolean) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("export_settings", { path, includeApiKeys }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Réglages que l'import de `path` modifierait, sans rien appliquer
+ */
+async previewSettingsImport(path: string) : Promise<Result<ImportPreview, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("preview_settings_import", { path }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Applique l'import de `path` ; renvoie les changements et avertissements
+ */
+async importSettings(path: string) : Promise<Result<ImportPreview, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("import_settings", { path }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async getTeamBaseline() : Promise<BaselineInfo | null> {
+    return await TAURI_INVOKE("get_team_baseline");
+},
+/**
+ * Installe le fichier de baseline `path` et applique ses valeurs
+ */
+async installTeamBaseline(path: string) : Promise<Result<BaselineInfo, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("install_team_baseline", { path }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Retire la baseline si elle l'autorise ; les réglages restent tels quels
+ */
+async removeTeamBaseline() : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("remove_team_baseline") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
 /**
  * Checks if the Mac is a laptop by detecting battery presence
  * 
@@ -773,7 +1171,33 @@ async isLaptop() : Promise<Result<boolean, string>> {
 
 /** user-defined types **/
 
-export type AppSettings = { bindings: Partial<{ [key in string]: ShortcutBinding }>; push_to_talk: boolean; audio_feedback: boolean; audio_feedback_volume?: number; sound_theme?: SoundTheme; start_hidden?: boolean; autostart_enabled?: boolean; update_checks_enabled?: boolean; selected_model?: string; always_on_microphone?: boolean; selected_microphone?: string | null; clamshell_microphone?: string | null; selected_output_device?: string | null; translate_to_english?: boolean; selected_language?: string; overlay_position?: OverlayPosition; debug_mode?: boolean; log_level?: LogLevel; custom_words?: string[]; model_unload_timeout?: ModelUnloadTimeout; word_correction_threshold?: number; history_limit?: number; recording_retention_period?: RecordingRetentionPeriod; paste_method?: PasteMethod; clipboard_handling?: ClipboardHandling; auto_submit?: boolean; auto_submit_key?: AutoSubmitKey; post_process_enabled?: boolean; post_process_provider_id?: string; post_process_providers?: PostProcessProvider[]; post_process_api_keys?: Partial<{ [key in string]: string }>; post_process_models?: Partial<{ [key in string]: string }>; post_process_prompts?: LLMPrompt[]; post_process_selected_prompt_id?: string | null; mute_while_recording?: boolean; append_trailing_space?: boolean; app_language?: string; experimental_enabled?: boolean; keyboard_implementation?: KeyboardImplementation; show_tray_icon?: boolean; paste_delay_ms?: number; typing_tool?: TypingTool; external_script_path: string | null; 
+export type AppSettings = { bindings: Partial<{ [key in string]: ShortcutBinding }>; push_to_talk: boolean; audio_feedback: boolean; audio_feedback_volume?: number; sound_theme?: SoundTheme; start_hidden?: boolean; autostart_enabled?: boolean; update_checks_enabled?: boolean; selected_model?: string; always_on_microphone?: boolean; selected_microphone?: string | null; clamshell_microphone?: string | null; 
+/**
+ * Micros de secours, par ordre de préférence, quand `selected_microphone`
+ * est débranché ou absent
+ */
+microphone_priority?: string[]; selected_output_device?: string | null; translate_to_english?: boolean; selected_language?: string; overlay_position?: OverlayPosition; debug_mode?: boolean; log_level?: LogLevel; custom_words?: string[]; model_unload_timeout?: ModelUnloadTimeout; word_correction_threshold?: number; history_limit?: number; recording_retention_period?: RecordingRetentionPeriod; 
+/**
+ * Plafond d'espace disque des enregistrements pour `RecordingRetentionPeriod::DiskQuota`
+ */
+recording_quota_mb?: number; recording_codec?: RecordingCodec; recording_opus_bitrate_kbps?: number; paste_method?: PasteMethod; clipboard_handling?: ClipboardHandling; paste_format?: PasteFormat; 
+/**
+ * Format imposé par application cible (nom de l'application)
+ */
+app_paste_formats?: Partial<{ [key in string]: PasteFormat }>; output_sinks?: OutputSink[]; auto_submit?: boolean; auto_submit_key?: AutoSubmitKey; post_process_enabled?: boolean; post_process_provider_id?: string; post_process_providers?: PostProcessProvider[]; post_process_api_keys?: Partial<{ [key in string]: string }>; post_process_models?: Partial<{ [key in string]: string }>; post_process_prompts?: LLMPrompt[]; post_process_selected_prompt_id?: string | null; post_process_recipes?: PostProcessRecipe[]; 
+/**
+ * Recette associée à un raccourci (id du binding → id de recette) ;
+ * prioritaire sur le prompt sélectionné
+ */
+binding_recipes?: Partial<{ [key in string]: string }>; 
+/**
+ * Profils des raccourcis personnalisés (id du binding → réglages)
+ */
+binding_profiles?: Partial<{ [key in string]: BindingProfile }>; mute_while_recording?: boolean; append_trailing_space?: boolean; app_language?: string; experimental_enabled?: boolean; keyboard_implementation?: KeyboardImplementation; show_tray_icon?: boolean; paste_delay_ms?: number; 
+/**
+ * Délai avant de remettre le presse-papiers remplacé par un collage
+ */
+clipboard_restore_delay_ms?: number; typing_tool?: TypingTool; external_script_path: string | null; 
 /**
  * Mode d'écriture pipeline FR : "chat" | "pro" | "code" (défaut: "chat")
  */
@@ -781,18 +1205,174 @@ write_mode?: string;
 /**
  * Touche de déclenchement single-key : "option" | "command" (défaut: "option")
  */
-trigger_key?: string }
+trigger_key?: string; 
+/**
+ * Durée max d'une dictée (secondes) — arrêt automatique, avertissement 30 s avant
+ */
+max_recording_seconds?: number; 
+/**
+ * Streaming des réponses LLM : "off" | "preview" | "type" (défaut: "preview")
+ */
+llm_stream_mode?: LlmStreamMode; 
+/**
+ * Politique de masquage par fournisseur (défaut : cloud uniquement)
+ */
+redaction_policies?: Partial<{ [key in string]: RedactionPolicy }>; 
+/**
+ * Noms propres à masquer avant un appel cloud (clients, collègues…)
+ */
+redaction_names?: string[]; 
+/**
+ * Preset de décodage Whisper par modèle (défaut : rapide)
+ */
+whisper_presets?: Partial<{ [key in string]: WhisperPreset }>; 
+/**
+ * Dictées récentes passées au nettoyage LLM et au prompt Whisper
+ * (désactivé par défaut : le texte dicté est réutilisé)
+ */
+session_context_enabled?: boolean; 
+/**
+ * Nombre max de dictées précédentes dans le contexte
+ */
+session_context_entries?: number; 
+/**
+ * Pause (secondes) au-delà de laquelle une nouvelle session commence
+ */
+session_context_timeout_secs?: number; 
+/**
+ * Budget du contexte, en tokens estimés
+ */
+session_context_max_tokens?: number; 
+/**
+ * Facteur temps réel maximal (durée de transcription / durée audio) du
+ * modèle recommandé par le banc d'essai
+ */
+benchmark_target_rtf?: number; 
+/**
+ * Sélectionne le modèle recommandé à la fin de chaque banc d'essai
+ */
+benchmark_auto_select?: boolean; 
+/**
+ * Version du schéma (voir `settings_migrations`) ; 0 avant le versionnage
+ */
+settings_version?: number }
 export type AudioDevice = { index: string; name: string; is_default: boolean }
 export type AutoSubmitKey = "enter" | "ctrl_enter" | "cmd_enter"
+/**
+ * Résumé affiché dans les réglages
+ */
+export type BaselineInfo = { name: string; locked: string[]; disallowed_providers: string[]; disallow_cloud_providers: boolean; allow_user_removal: boolean }
+/**
+ * Rapport et recommandation affichés dans les réglages
+ */
+export type BenchmarkSummary = { hardware: HardwareProfile; ran_at: number; sample_ms: number; results: ModelBenchmark[]; recommended_model: string | null; 
+/**
+ * Machine ou modèles téléchargés différents depuis les mesures
+ */
+stale: boolean }
+export type BindingProfile = { 
+/**
+ * "chat" | "pro" | "code"
+ */
+write_mode?: string | null; 
+/**
+ * Passe la dictée au LLM de post-traitement
+ */
+post_process?: boolean; 
+/**
+ * Prompt de post-traitement (défaut : prompt sélectionné)
+ */
+prompt_id?: string | null; 
+/**
+ * Langue de dictée (`auto` ou code ISO)
+ */
+language?: string | null; paste_method?: PasteMethod | null; paste_format?: PasteFormat | null; 
+/**
+ * Destinations de la dictée (défaut : celles des réglages globaux)
+ */
+output_sinks?: OutputSink[] | null }
 export type BindingResponse = { success: boolean; binding: ShortcutBinding | null; error: string | null }
 export type ClipboardHandling = "dont_modify" | "copy_to_clipboard"
 export type CustomSounds = { start: boolean; stop: boolean }
+export type DailyUsage = { 
+/**
+ * Jour local, `AAAA-MM-JJ`
+ */
+date: string; dictations: number; words: number }
+/**
+ * Mesures d'une dictée, de l'arrêt de l'enregistrement au collage
+ */
+export type DictationMetrics = { audio_ms: number; stt_ms: number; rules_ms: number; 
+/**
+ * Nettoyage LLM du pipeline et post-traitement éventuel
+ */
+llm_ms: number; 
+/**
+ * None si le collage a échoué
+ */
+paste_ms: number | null; routing: Routing; llm_fallback: boolean; model_id: string | null; write_mode: string; confidence: number; word_count: number; 
+/**
+ * Hallucinations Whisper écartées avant le pipeline
+ */
+hallucinations?: HallucinationDecision[]; 
+/**
+ * Application cible, quand elle est connue (contexte de session)
+ */
+target_app?: string | null }
 export type EngineType = 
 /**
  * Whisper uniquement — Parakeet/Moonshine/SenseVoice retirés (MVP macOS FR)
  */
 "Whisper"
-export type HistoryEntry = { id: number; file_name: string; timestamp: number; saved: boolean; title: string; transcription_text: string; post_processed_text: string | null; post_process_prompt: string | null; write_mode: string | null }
+/**
+ * Décision prise sur la transcription brute
+ */
+export type HallucinationDecision = { kind: HallucinationKind; words_removed: number }
+/**
+ * Type d'hallucination détecté
+ */
+export type HallucinationKind = 
+/**
+ * Crédit de sous-titrage ou formule de fin de vidéo
+ */
+"known_phrase" | 
+/**
+ * N-gramme répété en boucle
+ */
+"repetition_loop" | 
+/**
+ * Texte produit sur un audio quasi silencieux
+ */
+"silent_audio"
+/**
+ * Machine sur laquelle les mesures ont été prises
+ */
+export type HardwareProfile = { os: string; arch: string; cpu_threads: number; 
+/**
+ * Mémoire physique (0 si inconnue)
+ */
+memory_mb: number; 
+/**
+ * Accélérations de whisper.cpp (ex : "Metal + CoreML", "CPU AVX2")
+ */
+backend: string }
+export type HistoryEntry = { id: number; 
+/**
+ * Enregistrement dans `recordings/` — vide pour un import, qui n'en garde pas
+ */
+file_name: string; timestamp: number; saved: boolean; title: string; transcription_text: string; post_processed_text: string | null; post_process_prompt: string | null; write_mode: string | null; 
+/**
+ * Chemin du fichier importé (mémo vocal, vidéo) — None pour une dictée micro
+ */
+source_file: string | null; 
+/**
+ * Sortie de chaque étape quand une recette a été appliquée (JSON en base)
+ */
+post_process_steps: RecipeStepOutput[]; 
+/**
+ * Mesures de la dictée (JSON en base, jamais chiffré) — None pour un import
+ */
+metrics: DictationMetrics | null }
 /**
  * Result of changing keyboard implementation
  */
@@ -801,19 +1381,164 @@ export type ImplementationChangeResult = { success: boolean;
  * List of binding IDs that were reset to defaults due to incompatibility
  */
 reset_bindings: string[] }
+/**
+ * Aperçu d'un import avant application
+ */
+export type ImportPreview = { app_version: string; exported_at: number; settings_version: number; changes: SettingChange[]; 
+/**
+ * Réglages ignorés (inconnus, propres à la machine, verrouillés)
+ */
+warnings: string[] }
 export type KeyboardImplementation = "tauri" | "handy_keys"
+export type LatencyStats = { 
+/**
+ * Modèle ou mode d'écriture
+ */
+key: string; count: number; p50_ms: number; p95_ms: number }
 export type LLMPrompt = { id: string; name: string; prompt: string }
+export type LlmStreamMode = "off" | "preview" | "type"
 export type LogLevel = "trace" | "debug" | "info" | "warn" | "error"
-export type ModelInfo = { id: string; name: string; description: string; filename: string; url: string | null; size_mb: number; is_downloaded: boolean; is_downloading: boolean; partial_size: number; is_directory: boolean; engine_type: EngineType; accuracy_score: number; speed_score: number; supports_translation: boolean; is_recommended: boolean; supported_languages: string[]; is_custom: boolean }
+/**
+ * Mesures d'un modèle
+ */
+export type ModelBenchmark = { model_id: string; load_ms: number; transcribe_ms: number; 
+/**
+ * Durée de transcription / durée de l'extrait (< 1 : plus rapide que le temps réel)
+ */
+real_time_factor: number; 
+/**
+ * Mémoire résidente de pointe ajoutée par le modèle (None si non mesurable)
+ */
+peak_rss_mb: number | null; 
+/**
+ * Échec du chargement ou de la transcription
+ */
+error: string | null }
+export type ModelInfo = { id: string; name: string; description: string; filename: string; url: string | null; 
+/**
+ * Empreinte SHA-256 attendue (catalogue), vérifiée après téléchargement
+ */
+sha256: string | null; size_mb: number; is_downloaded: boolean; is_downloading: boolean; partial_size: number; is_directory: boolean; engine_type: EngineType; accuracy_score: number; speed_score: number; supports_translation: boolean; is_recommended: boolean; supported_languages: string[]; is_custom: boolean }
 export type ModelLoadStatus = { is_loaded: boolean; current_model: string | null }
 export type ModelUnloadTimeout = "never" | "immediately" | "min_2" | "min_5" | "min_10" | "min_15" | "hour_1" | "sec_5"
+/**
+ * Destination du texte dicté. Plusieurs destinations reçoivent la même dictée.
+ */
+export type OutputSink = 
+/**
+ * Collage dans l'application active (`paste_method`)
+ */
+{ type: "paste" } | 
+/**
+ * Ajout au fichier `<directory>/AAAA-MM-JJ.md` ; le gabarit accepte
+ * `${date}`, `${time}`, `${text}` et `${binding}`
+ */
+{ type: "markdown_journal"; directory: string; template?: string } | 
+/**
+ * Ajout d'une ligne à un fichier ou à un tube nommé (FIFO)
+ */
+{ type: "file"; path: string } | 
+/**
+ * Sortie standard du processus (lancement en ligne de commande)
+ */
+{ type: "stdout" } | 
+/**
+ * POST JSON vers une URL locale (localhost uniquement)
+ */
+{ type: "webhook"; url: string }
 export type OverlayPosition = "none" | "top" | "bottom"
+/**
+ * Format placé dans le presse-papier par les collages `CtrlV`, `CtrlShiftV`
+ * et `ShiftInsert` (la frappe directe reste en texte brut)
+ */
+export type PasteFormat = "plain" | 
+/**
+ * `text/plain` + `text/html` : listes et paragraphes arrivent mis en forme
+ */
+"rich"
 export type PasteMethod = "ctrl_v" | "direct" | "none" | "shift_insert" | "ctrl_shift_v" | "external_script"
-export type PostProcessProvider = { id: string; label: string; base_url: string; allow_base_url_edit?: boolean; models_endpoint?: string | null; supports_structured_output?: boolean }
-export type RecordingRetentionPeriod = "never" | "preserve_limit" | "days_3" | "weeks_2" | "months_3"
+export type PostProcessProvider = { id: string; label: string; base_url: string; kind?: ProviderKind; allow_base_url_edit?: boolean; models_endpoint?: string | null; supports_structured_output?: boolean; 
+/**
+ * Timeout par tentative (secondes), sinon selon le fournisseur (local / cloud)
+ */
+timeout_secs?: number | null }
+export type PostProcessRecipe = { id: string; name: string; steps: RecipeStep[] }
+export type ProviderKind = "openai-compatible" | "anthropic-messages" | "ollama-native"
+export type RecipeStep = { type: "rules" } | { type: "structure" } | { type: "template"; template: string } | { type: "llm"; prompt: string; provider_id?: string | null; model?: string | null }
+export type RecipeStepOutput = { 
+/**
+ * "rules" | "structure" | "template" | "llm"
+ */
+kind: string; 
+/**
+ * Fournisseur et modèle d'une étape LLM
+ */
+detail: string | null; output: string }
+export type RecordingCodec = "wav" | 
+/**
+ * Sans perte, environ la moitié du WAV
+ */
+"flac" | 
+/**
+ * Avec perte, au débit `recording_opus_bitrate_kbps`
+ */
+"opus"
+export type RecordingRetentionPeriod = "never" | "preserve_limit" | "days_3" | "weeks_2" | "months_3" | 
+/**
+ * Supprime les plus anciens enregistrements au-delà de `recording_quota_mb`
+ */
+"disk_quota"
+export type RedactionPolicy = "cloud_only" | "always" | "never"
+/**
+ * Chemin emprunté par le pipeline
+ */
+export type Routing = 
+/**
+ * Fast-path : règles locales seules
+ */
+"rules_only" | 
+/**
+ * Règles puis LLM (même si celui-ci a échoué, voir `llm_fallback`)
+ */
+"llm"
+/**
+ * Réglage modifié par l'import, valeurs en JSON compact
+ */
+export type SettingChange = { key: string; current: string; incoming: string }
 export type ShortcutBinding = { id: string; name: string; description: string; default_binding: string; current_binding: string }
 export type SoundTheme = "marimba" | "pop" | "cahier" | "custom"
 export type TypingTool = "auto" | "wtype" | "kwtype" | "dotool" | "ydotool" | "xdotool"
+export type UsageStats = { dictations: number; words: number; audio_ms: number; 
+/**
+ * Mots par minute de parole
+ */
+words_per_minute: number; 
+/**
+ * Estimation par rapport à une frappe à 40 mots/min
+ */
+time_saved_ms: number; rules_only: number; llm_fallbacks: number; daily: DailyUsage[]; latency_by_model: LatencyStats[]; latency_by_mode: LatencyStats[] }
+export type VaultMode = 
+/**
+ * Dérivée d'une phrase secrète saisie à chaque démarrage
+ */
+"passphrase" | 
+/**
+ * Aléatoire, conservée dans le trousseau du système
+ */
+"keyring"
+export type VaultStatus = { enabled: boolean; mode: VaultMode | null; unlocked: boolean }
+/**
+ * Réglage de décodage par modèle
+ */
+export type WhisperPreset = 
+/**
+ * Greedy, un seul candidat — latence minimale
+ */
+"fast" | 
+/**
+ * Beam search 5 — plus lent, moins d'erreurs sur les phrases longues
+ */
+"accurate"
 
 /** tauri-specta globals **/
 
@@ -0,0 +1,357 @@
+import React, { useEffect, useState } from "react";
+import { useTranslation } from "react-i18next";
+import { toast } from "sonner";
+import {
+  commands,
+  type BindingProfile,
+  type PasteFormat,
+  type PasteMethod,
+  type ShortcutBinding,
+} from "@/bindings";
+
+import { Dropdown, SettingContainer } from "@/components/ui";
+import { Button } from "../ui/Button";
+import { Input } from "../ui/Input";
+import { ShortcutInput } from "./ShortcutInput";
+import { OutputSinksEditor } from "./OutputSinks";
+import { useSettings } from "../../hooks/useSettings";
+import { useOsType } from "../../hooks/useOsType";
+import { WRITE_MODES } from "@/config/writeModes";
+import { LANGUAGES } from "../../lib/constants/languages";
+
+/** Valeur des menus pour « réglage global » */
+const INHERIT = "__inherit__";
+/** Menu prompt : pas de post-traitement / prompt sélectionné */
+const NO_POST_PROCESS = "__off__";
+const SELECTED_PROMPT = "__selected__";
+
+const EMPTY_PROFILE: BindingProfile = {};
+
+const isCustomBinding = (id: string) =>
+  id.startsWith("custom_") && id.length > "custom_".length;
+
+const promptValue = (profile: BindingProfile) => {
+  if (!profile.post_process) return NO_POST_PROCESS;
+  return profile.prompt_id ?? SELECTED_PROMPT;
+};
+
+const withPrompt = (profile: BindingProfile, value: string): BindingProfile => {
+  switch (value) {
+    case NO_POST_PROCESS:
+      return { ...profile, post_process: false, prompt_id: null };
+    case SELECTED_PROMPT:
+      return { ...profile, post_process: true, prompt_id: null };
+    default:
+      return { ...profile, post_process: true, prompt_id: value };
+  }
+};
+
+interface CustomBindingEditorProps {
+  binding: ShortcutBinding;
+  profile: BindingProfile;
+}
+
+const CustomBindingEditor: React.FC<CustomBindingEditorProps> = ({
+  binding,
+  profile,
+}) => {
+  const { t } = useTranslation();
+  const { getSetting, refreshSettings } = useSettings();
+  const osType = useOsType();
+  const [name, setName] = useState(binding.name);
+  const [draft, setDraft] = useState<BindingProfile>(profile);
+
+  useEffect(() => {
+    setName(binding.name);
+    setDraft(profile);
+  }, [binding.name, profile]);
+
+  const prompts = getSetting("post_process_prompts") || [];
+  const inherit = t("settings.general.customBindings.inherit");
+  const modifier = osType === "macos" ? "Cmd" : "Ctrl";
+
+  const pasteMethods: { value: PasteMethod; label: string }[] = [
+    {
+      value: "ctrl_v",
+      label: t("settings.advanced.pasteMethod.options.clipboard", {
+        modifier,
+      }),
+    },
+    {
+      value: "direct",
+      label: t("settings.advanced.pasteMethod.options.direct"),
+    },
+    { value: "none", label: t("settings.advanced.pasteMethod.options.none") },
+  ];
+
+  const handleSave = async () => {
+    const result = await commands.updateCustomBinding(
+      binding.id,
+      name.trim(),
+      draft,
+    );
+    if (result.status === "error") {
+      toast.error(result.error);
+      return;
+    }
+    await refreshSettings();
+  };
+
+  const handleDelete = async () => {
+    const result = await commands.deleteCustomBinding(binding.id);
+    if (result.status === "error") {
+      toast.error(result.error);
+      return;
+    }
+    await refreshSettings();
+  };
+
+  return (
+    <div className="space-y-2 p-3 rounded-md border border-mid-gray/20">
+      <Input
+        type="text"
+        value={name}
+        onChange={(e) => setName(e.target.value)}
+        placeholder={t("settings.general.customBindings.namePlaceholder")}
+        variant="compact"
+      />
+      <ShortcutInput shortcutId={binding.id} grouped={true} />
+
+      <SettingContainer
+        title={t("settings.general.writeMode.title")}
+        description={t("settings.general.customBindings.inheritDescription")}
+        descriptionMode="tooltip"
+        layout="horizontal"
+        grouped={true}
+      >
+        <Dropdown
+          options={[
+            { value: INHERIT, label: inherit },
+            ...WRITE_MODES.map((mode) => ({
+              value: mode,
+              label: t(`settings.general.writeMode.modes.${mode}.label`),
+            })),
+          ]}
+          selectedValue={draft.write_mode ?? INHERIT}
+          onSelect={(value) =>
+            setDraft({ ...draft, write_mode: value === INHERIT ? null : value })
+          }
+        />
+      </SettingContainer>
+
+      <SettingContainer
+        title={t("settings.general.customBindings.prompt")}
+        description={t("settings.general.customBindings.promptDescription")}
+        descriptionMode="tooltip"
+        layout="horizontal"
+        grouped={true}
+      >
+        <Dropdown
+          options={[
+            {
+              value: NO_POST_PROCESS,
+              label: t("settings.general.customBindings.noPostProcess"),
+            },
+            {
+              value: SELECTED_PROMPT,
+              label: t("settings.general.customBindings.selectedPrompt"),
+            },
+            ...prompts.map((p) => ({ value: p.id, label: p.name })),
+          ]}
+          selectedValue={promptValue(draft)}
+          onSelect={(value) => setDraft(withPrompt(draft, value))}
+        />
+      </SettingContainer>
+
+      <SettingContainer
+        title={t("settings.general.language.title")}
+        description={t("settings.general.customBindings.inheritDescription")}
+        descriptionMode="tooltip"
+        layout="horizontal"
+        grouped={true}
+      >
+        <Dropdown
+          options={[{ value: INHERIT, label: inherit }, ...LANGUAGES]}
+          selectedValue={draft.language ?? INHERIT}
+          onSelect={(value) =>
+            setDraft({ ...draft, language: value === INHERIT ? null : value })
+          }
+        />
+      </SettingContainer>
+
+      <SettingContainer
+        title={t("settings.advanced.pasteMethod.title")}
+        description={t("settings.general.customBindings.inheritDescription")}
+        descriptionMode="tooltip"
+        layout="horizontal"
+        grouped={true}
+      >
+        <Dropdown
+          options={[{ value: INHERIT, label: inherit }, ...pasteMethods]}
+          selectedValue={draft.paste_method ?? INHERIT}
+          onSelect={(value) =>
+            setDraft({
+              ...draft,
+              paste_method: value === INHERIT ? null : (value as PasteMethod),
+            })
+          }
+        />
+      </SettingContainer>
+
+      <SettingContainer
+        title={t("settings.advanced.pasteFormat.title")}
+        description={t("settings.general.customBindings.inheritDescription")}
+        descriptionMode="tooltip"
+        layout="horizontal"
+        grouped={true}
+      >
+        <Dropdown
+          options={[
+            { value: INHERIT, label: inherit },
+            {
+              value: "plain",
+              label: t("settings.advanced.pasteFormat.options.plain"),
+            },
+            {
+              value: "rich",
+              label: t("settings.advanced.pasteFormat.options.rich"),
+            },
+          ]}
+          selectedValue={draft.paste_format ?? INHERIT}
+          onSelect={(value) =>
+            setDraft({
+              ...draft,
+              paste_format: value === INHERIT ? null : (value as PasteFormat),
+            })
+          }
+        />
+      </SettingContainer>
+
+      <SettingContainer
+        title={t("settings.advanced.outputSinks.title")}
+        description={t("settings.general.customBindings.inheritDescription")}
+        descriptionMode="tooltip"
+        layout="stacked"
+        grouped={true}
+      >
+        <div className="space-y-2">
+          <label className="flex items-center gap-2 text-sm">
+            <input
+              type="checkbox"
+              checked={!draft.output_sinks}
+              onChange={(e) =>
+                setDraft({
+                  ...draft,
+                  output_sinks: e.target.checked
+                    ? null
+                    : (getSetting("output_sinks") ?? [{ type: "paste" }]),
+                })
+              }
+            />
+            {inherit}
+          </label>
+          {draft.output_sinks && (
+            <OutputSinksEditor
+              sinks={draft.output_sinks}
+              onChange={(output_sinks) => setDraft({ ...draft, output_sinks })}
+            />
+          )}
+        </div>
+      </SettingContainer>
+
+      <div className="flex gap-2 pt-2">
+        <Button
+          onClick={handleSave}
+          variant="primary"
+          size="md"
+          disabled={name.trim() === ""}
+        >
+          {t("settings.general.customBindings.save")}
+        </Button>
+        <Button onClick={handleDelete} variant="secondary" size="md">
+          {t("settings.general.customBindings.delete")}
+        </Button>
+      </div>
+    </div>
+  );
+};
+
+const CustomBindingsComponent: React.FC = () => {
+  const { t } = useTranslation();
+  const { getSetting, refreshSettings } = useSettings();
+  const [name, setName] = useState("");
+  const [shortcut, setShortcut] = useState("");
+
+  const bindings = getSetting("bindings") || {};
+  const profiles = getSetting("binding_profiles") || {};
+  const customBindings = Object.values(bindings)
+    .filter((b): b is ShortcutBinding => !!b && isCustomBinding(b.id))
+    .sort((a, b) => a.id.localeCompare(b.id));
+
+  const handleCreate = async () => {
+    const result = await commands.addCustomBinding(
+      name.trim(),
+      shortcut.trim(),
+      {},
+    );
+    if (result.status === "error") {
+      toast.error(result.error);
+      return;
+    }
+    setName("");
+    setShortcut("");
+    await refreshSettings();
+  };
+
+  return (
+    <SettingContainer
+      title={t("settings.general.customBindings.title")}
+      description={t("settings.general.customBindings.description")}
+      descriptionMode="tooltip"
+      layout="stacked"
+      grouped={true}
+    >
+      <div className="space-y-3">
+        {customBindings.map((binding) => (
+          <CustomBindingEditor
+            key={binding.id}
+            binding={binding}
+            profile={profiles[binding.id] ?? EMPTY_PROFILE}
+          />
+        ))}
+
+        <div className="flex gap-2">
+          <Input
+            type="text"
+            value={name}
+            onChange={(e) => setName(e.target.value)}
+            placeholder={t("settings.general.customBindings.namePlaceholder")}
+            variant="compact"
+            className="flex-1"
+          />
+          <Input
+            type="text"
+            value={shortcut}
+            onChange={(e) => setShortcut(e.target.value)}
+            placeholder={t(
+              "settings.general.customBindings.shortcutPlaceholder",
+            )}
+            variant="compact"
+            className="flex-1"
+          />
+          <Button
+            onClick={handleCreate}
+            variant="primary"
+            size="md"
+            disabled={name.trim() === "" || shortcut.trim() === ""}
+          >
+            {t("settings.general.customBindings.add")}
+          </Button>
+        </div>
+      </div>
+    </SettingContainer>
+  );
+};
+
+export const CustomBindings = React.memo(CustomBindingsComponent);
+CustomBindings.displayName = "CustomBindings";
@@ -0,0 +1,157 @@
+import React, { useEffect, useState } from "react";
+import { useTranslation } from "react-i18next";
+import { toast } from "sonner";
+import {
+  commands,
+  type Result,
+  type VaultMode,
+  type VaultStatus,
+} from "@/bindings";
+
+import { Dropdown, SettingContainer } from "@/components/ui";
+import { Button } from "../ui/Button";
+import { Input } from "../ui/Input";
+import { useSettings } from "../../hooks/useSettings";
+
+interface EncryptionAtRestProps {
+  descriptionMode?: "tooltip" | "inline";
+  grouped?: boolean;
+}
+
+export const EncryptionAtRest: React.FC<EncryptionAtRestProps> = ({
+  descriptionMode = "inline",
+  grouped = false,
+}) => {
+  const { t } = useTranslation();
+  const { refreshSettings } = useSettings();
+  const [status, setStatus] = useState<VaultStatus | null>(null);
+  const [mode, setMode] = useState<VaultMode>("keyring");
+  const [passphrase, setPassphrase] = useState("");
+  const [busy, setBusy] = useState(false);
+
+  useEffect(() => {
+    commands.getVaultStatus().then(setStatus);
+  }, []);
+
+  const run = async (action: () => Promise<Result<VaultStatus, string>>) => {
+    setBusy(true);
+    try {
+      const result = await action();
+      if (result.status === "error") {
+        toast.error(result.error);
+        return;
+      }
+      setStatus(result.data);
+      setPassphrase("");
+      // Les clés d'API affichées dépendent de l'état du coffre
+      await refreshSettings();
+    } finally {
+      setBusy(false);
+    }
+  };
+
+  if (!status) return null;
+
+  const needsPassphrase = status.enabled
+    ? status.mode === "passphrase" && !status.unlocked
+    : mode === "passphrase";
+
+  const stateLabel = !status.enabled
+    ? t("settings.advanced.encryption.state.disabled")
+    : status.unlocked
+      ? t("settings.advanced.encryption.state.unlocked", {
+          mode: t(`settings.advanced.encryption.modes.${status.mode}`),
+        })
+      : t("settings.advanced.encryption.state.locked");
+
+  return (
+    <SettingContainer
+      title={t("settings.advanced.encryption.title")}
+      description={t("settings.advanced.encryption.description")}
+      descriptionMode={descriptionMode}
+      grouped={grouped}
+      layout="stacked"
+    >
+      <div className="space-y-2">
+        <p className="text-sm text-mid-gray">{stateLabel}</p>
+
+        {!status.enabled && (
+          <Dropdown
+            options={(["keyring", "passphrase"] as VaultMode[]).map(
+              (value) => ({
+                value,
+                label: t(`settings.advanced.encryption.modes.${value}`),
+              }),
+            )}
+            selectedValue={mode}
+            onSelect={(value) => setMode(value as VaultMode)}
+          />
+        )}
+
+        {needsPassphrase && (
+          <Input
+            type="password"
+            value={passphrase}
+            onChange={(e) => setPassphrase(e.target.value)}
+            placeholder={t("settings.advanced.encryption.passphrase")}
+            variant="compact"
+          />
+        )}
+
+        <div className="flex gap-2">
+          {!status.enabled && (
+            <Button
+              onClick={() =>
+                run(() =>
+                  commands.enableVault(
+                    mode,
+                    mode === "passphrase" ? passphrase : null,
+                  ),
+                )
+              }
+              variant="primary"
+              size="md"
+              disabled={busy || (needsPassphrase && passphrase === "")}
+            >
+              {t("settings.advanced.encryption.enable")}
+            </Button>
+          )}
+          {status.enabled && !status.unlocked && (
+            <Button
+              onClick={() =>
+                run(() =>
+                  commands.unlockVault(needsPassphrase ? passphrase : null),
+                )
+              }
+              variant="primary"
+              size="md"
+              disabled={busy}
+            >
+              {t("settings.advanced.encryption.unlock")}
+            </Button>
+          )}
+          {status.enabled && status.unlocked && (
+            <>
+              <Button
+                onClick={() => run(commands.lockVault)}
+                variant="secondary"
+                size="md"
+                disabled={busy}
+              >
+                {t("settings.advanced.encryption.lock")}
+              </Button>
+              <Button
+                onClick={() => run(commands.disableVault)}
+                variant="secondary"
+                size="md"
+                disabled={busy}
+              >
+                {t("settings.advanced.encryption.disable")}
+              </Button>
+            </>
+          )}
+        </div>
+      </div>
+    </SettingContainer>
+  );
+};
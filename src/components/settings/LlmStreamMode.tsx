@@ -0,0 +1,58 @@
+import React from "react";
+import { useTranslation } from "react-i18next";
+import { Dropdown } from "../ui/Dropdown";
+import { SettingContainer } from "../ui/SettingContainer";
+import { useSettings } from "../../hooks/useSettings";
+import type { LlmStreamMode } from "@/bindings";
+
+interface LlmStreamModeProps {
+  descriptionMode?: "inline" | "tooltip";
+  grouped?: boolean;
+}
+
+export const LlmStreamModeSetting: React.FC<LlmStreamModeProps> = React.memo(
+  ({ descriptionMode = "tooltip", grouped = false }) => {
+    const { t } = useTranslation();
+    const { getSetting, updateSetting, isUpdating } = useSettings();
+
+    // La frappe progressive passe par le collage direct
+    const isDirectPaste = getSetting("paste_method") === "direct";
+
+    const streamModeOptions = [
+      {
+        value: "off",
+        label: t("settings.advanced.llmStreamMode.options.off"),
+      },
+      {
+        value: "preview",
+        label: t("settings.advanced.llmStreamMode.options.preview"),
+      },
+      {
+        value: "type",
+        label: t("settings.advanced.llmStreamMode.options.type"),
+        disabled: !isDirectPaste,
+      },
+    ];
+
+    const selectedMode = (getSetting("llm_stream_mode") ||
+      "preview") as LlmStreamMode;
+
+    return (
+      <SettingContainer
+        title={t("settings.advanced.llmStreamMode.title")}
+        description={t("settings.advanced.llmStreamMode.description")}
+        descriptionMode={descriptionMode}
+        grouped={grouped}
+      >
+        <Dropdown
+          options={streamModeOptions}
+          selectedValue={selectedMode}
+          onSelect={(value) =>
+            updateSetting("llm_stream_mode", value as LlmStreamMode)
+          }
+          disabled={isUpdating("llm_stream_mode")}
+        />
+      </SettingContainer>
+    );
+  },
+);
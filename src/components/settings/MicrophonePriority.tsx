@@ -0,0 +1,129 @@
+import React from "react";
+import { useTranslation } from "react-i18next";
+import { useSettings } from "../../hooks/useSettings";
+import { Button } from "../ui/Button";
+import { Dropdown } from "../ui/Dropdown";
+import { SettingContainer } from "../ui/SettingContainer";
+
+interface MicrophonePriorityProps {
+  descriptionMode?: "inline" | "tooltip";
+  grouped?: boolean;
+}
+
+/**
+ * Micros de secours, utilisés dans l'ordre quand le micro sélectionné est
+ * débranché. Les périphériques absents restent dans la liste (grisés).
+ */
+export const MicrophonePriority: React.FC<MicrophonePriorityProps> =
+  React.memo(({ descriptionMode = "tooltip", grouped = false }) => {
+    const { t } = useTranslation();
+    const {
+      getSetting,
+      updateSetting,
+      isUpdating,
+      audioDevices,
+      refreshAudioDevices,
+    } = useSettings();
+    const priority = getSetting("microphone_priority") || [];
+    const updating = isUpdating("microphone_priority");
+    const connected = new Set(audioDevices.map((device) => device.name));
+
+    const addOptions = audioDevices
+      .filter(
+        (device) =>
+          device.name !== "Default" && !priority.includes(device.name),
+      )
+      .map((device) => ({ value: device.name, label: device.name }));
+
+    const move = (index: number, offset: number) => {
+      const next = [...priority];
+      const [item] = next.splice(index, 1);
+      next.splice(index + offset, 0, item);
+      updateSetting("microphone_priority", next);
+    };
+
+    const remove = (name: string) => {
+      updateSetting(
+        "microphone_priority",
+        priority.filter((device) => device !== name),
+      );
+    };
+
+    return (
+      <>
+        <SettingContainer
+          title={t("settings.sound.microphonePriority.title")}
+          description={t("settings.sound.microphonePriority.description")}
+          descriptionMode={descriptionMode}
+          grouped={grouped}
+        >
+          <Dropdown
+            options={addOptions}
+            selectedValue={null}
+            onSelect={(name) =>
+              updateSetting("microphone_priority", [...priority, name])
+            }
+            placeholder={t("settings.sound.microphonePriority.add")}
+            disabled={updating || addOptions.length === 0}
+            onRefresh={refreshAudioDevices}
+          />
+        </SettingContainer>
+        {priority.length > 0 && (
+          <ol
+            className={`px-4 p-2 ${grouped ? "" : "rounded-lg border border-mid-gray/20"} space-y-1`}
+          >
+            {priority.map((name, index) => (
+              <li key={name} className="flex items-center gap-2 text-sm">
+                <span className="w-4 text-mid-gray">{index + 1}.</span>
+                <span
+                  className={`flex-1 truncate ${connected.has(name) ? "" : "text-mid-gray line-through"}`}
+                  title={
+                    connected.has(name)
+                      ? name
+                      : t("settings.sound.microphonePriority.disconnected")
+                  }
+                >
+                  {name}
+                </span>
+                <Button
+                  onClick={() => move(index, -1)}
+                  disabled={updating || index === 0}
+                  variant="secondary"
+                  size="sm"
+                  aria-label={t("settings.sound.microphonePriority.moveUp", {
+                    name,
+                  })}
+                >
+                  ↑
+                </Button>
+                <Button
+                  onClick={() => move(index, 1)}
+                  disabled={updating || index === priority.length - 1}
+                  variant="secondary"
+                  size="sm"
+                  aria-label={t("settings.sound.microphonePriority.moveDown", {
+                    name,
+                  })}
+                >
+                  ↓
+                </Button>
+                <Button
+                  onClick={() => remove(name)}
+                  disabled={updating}
+                  variant="secondary"
+                  size="sm"
+                  aria-label={t("settings.sound.microphonePriority.remove", {
+                    name,
+                  })}
+                >
+                  ×
+                </Button>
+              </li>
+            ))}
+          </ol>
+        )}
+      </>
+    );
+  });
+
+MicrophonePriority.displayName = "MicrophonePriority";
@@ -0,0 +1,177 @@
+import React, { useEffect, useState } from "react";
+import { useTranslation } from "react-i18next";
+import { toast } from "sonner";
+import { listen } from "@tauri-apps/api/event";
+import { commands, type BenchmarkSummary } from "@/bindings";
+
+import { SettingContainer } from "@/components/ui";
+import { Button } from "../ui/Button";
+import { Slider } from "../ui/Slider";
+import { SettingsGroup } from "../ui/SettingsGroup";
+import { ToggleSwitch } from "../ui/ToggleSwitch";
+import { useSettings } from "../../hooks/useSettings";
+import { useModelStore } from "../../stores/modelStore";
+
+type Progress = { model_id: string; index: number; total: number };
+
+/** Banc d'essai des modèles téléchargés et modèle recommandé pour la machine */
+export const ModelBenchmark: React.FC = () => {
+  const { t } = useTranslation();
+  const { getSetting, updateSetting, isUpdating } = useSettings();
+  const { models, currentModel, selectModel, loadCurrentModel } =
+    useModelStore();
+  const [summary, setSummary] = useState<BenchmarkSummary | null>(null);
+  const [progress, setProgress] = useState<Progress | null>(null);
+  const [running, setRunning] = useState(false);
+
+  const targetRtf = getSetting("benchmark_target_rtf") ?? 0.5;
+  const autoSelect = getSetting("benchmark_auto_select") ?? false;
+
+  const loadSummary = async () => {
+    const result = await commands.getModelBenchmark();
+    if (result.status === "ok") setSummary(result.data);
+  };
+
+  useEffect(() => {
+    loadSummary();
+    const unlisteners: (() => void)[] = [];
+
+    listen<Progress>("model-benchmark-progress", (event) => {
+      setProgress(event.payload);
+    }).then((fn) => unlisteners.push(fn));
+
+    // Aussi émis par un banc d'essai lancé depuis la notification
+    listen<BenchmarkSummary>("model-benchmark-completed", (event) => {
+      setProgress(null);
+      setSummary(event.payload);
+      loadCurrentModel();
+    }).then((fn) => unlisteners.push(fn));
+
+    return () => {
+      unlisteners.forEach((fn) => fn());
+    };
+  }, []);
+
+  const handleRun = async () => {
+    setRunning(true);
+    try {
+      const result = await commands.runModelBenchmark();
+      if (result.status === "error") {
+        toast.error(result.error);
+        return;
+      }
+      setSummary(result.data);
+    } finally {
+      setRunning(false);
+      setProgress(null);
+    }
+  };
+
+  const handleTargetChange = async (value: number) => {
+    await updateSetting("benchmark_target_rtf", value);
+    await loadSummary();
+  };
+
+  const modelName = (id: string) =>
+    models.find((model) => model.id === id)?.name ?? id;
+  const recommended = summary?.recommended_model ?? null;
+
+  return (
+    <SettingsGroup title={t("settings.models.benchmark.title")}>
+      <SettingContainer
+        title={t("settings.models.benchmark.run.title")}
+        description={t("settings.models.benchmark.run.description")}
+        descriptionMode="inline"
+        grouped={true}
+        layout="stacked"
+      >
+        <div className="space-y-2">
+          <Button
+            onClick={handleRun}
+            variant="secondary"
+            size="md"
+            disabled={running}
+          >
+            {progress
+              ? t("settings.models.benchmark.running", {
+                  model: modelName(progress.model_id),
+                  index: progress.index + 1,
+                  total: progress.total,
+                })
+              : t("settings.models.benchmark.run.button")}
+          </Button>
+
+          {summary && (
+            <div className="space-y-1 text-sm">
+              {summary.stale && (
+                <p className="text-xs text-mid-gray">
+                  {t("settings.models.benchmark.stale")}
+                </p>
+              )}
+              <ul className="space-y-0.5 font-mono text-xs">
+                {summary.results.map((result) => (
+                  <li
+                    key={result.model_id}
+                    className={
+                      result.model_id === recommended
+                        ? "text-logo-primary"
+                        : undefined
+                    }
+                  >
+                    {modelName(result.model_id)} —{" "}
+                    {result.error
+                      ? t("settings.models.benchmark.failed", {
+                          error: result.error,
+                        })
+                      : t("settings.models.benchmark.result", {
+                          rtf: result.real_time_factor.toFixed(2),
+                          load: (result.load_ms / 1000).toFixed(1),
+                          memory: result.peak_rss_mb ?? "?",
+                        })}
+                  </li>
+                ))}
+              </ul>
+              {recommended && (
+                <div className="flex items-center gap-2">
+                  <p>
+                    {t("settings.models.benchmark.recommended", {
+                      model: modelName(recommended),
+                    })}
+                  </p>
+                  {recommended !== currentModel && (
+                    <Button
+                      onClick={() => selectModel(recommended)}
+                      variant="primary"
+                      size="sm"
+                    >
+                      {t("settings.models.benchmark.use")}
+                    </Button>
+                  )}
+                </div>
+              )}
+            </div>
+          )}
+        </div>
+      </SettingContainer>
+      <Slider
+        value={targetRtf}
+        onChange={handleTargetChange}
+        min={0.1}
+        max={1.5}
+        step={0.05}
+        label={t("settings.models.benchmark.target.title")}
+        description={t("settings.models.benchmark.target.description")}
+        grouped={true}
+        formatValue={(v) => `×${v.toFixed(2)}`}
+      />
+      <ToggleSwitch
+        checked={autoSelect}
+        onChange={(enabled) => updateSetting("benchmark_auto_select", enabled)}
+        isUpdating={isUpdating("benchmark_auto_select")}
+        label={t("settings.models.benchmark.autoSelect.label")}
+        description={t("settings.models.benchmark.autoSelect.description")}
+        grouped={true}
+      />
+    </SettingsGroup>
+  );
+};
@@ -0,0 +1,186 @@
+import React, { useEffect, useState } from "react";
+import { useTranslation } from "react-i18next";
+import { toast } from "sonner";
+import { commands, type OutputSink } from "@/bindings";
+import { Button } from "../ui/Button";
+import { Input } from "../ui/Input";
+import { SettingContainer } from "../ui/SettingContainer";
+import { useSettings } from "../../hooks/useSettings";
+
+type SinkKind = OutputSink["type"];
+
+const SINK_KINDS: SinkKind[] = [
+  "paste",
+  "markdown_journal",
+  "file",
+  "stdout",
+  "webhook",
+];
+
+/** Même gabarit que `default_journal_template` côté Rust */
+const DEFAULT_JOURNAL_TEMPLATE = "- **${time}** ${text}";
+
+const emptySink = (kind: SinkKind): OutputSink => {
+  switch (kind) {
+    case "markdown_journal":
+      return {
+        type: kind,
+        directory: "",
+        template: DEFAULT_JOURNAL_TEMPLATE,
+      };
+    case "file":
+      return { type: kind, path: "" };
+    case "webhook":
+      return { type: kind, url: "http://localhost:" };
+    default:
+      return { type: kind };
+  }
+};
+
+interface OutputSinksEditorProps {
+  sinks: OutputSink[];
+  onChange: (sinks: OutputSink[]) => void;
+}
+
+/** Cases à cocher par destination, avec leurs champs ; une dictée part vers toutes */
+export const OutputSinksEditor: React.FC<OutputSinksEditorProps> = ({
+  sinks,
+  onChange,
+}) => {
+  const { t } = useTranslation();
+
+  const toggle = (kind: SinkKind, enabled: boolean) =>
+    onChange(
+      enabled
+        ? [...sinks, emptySink(kind)]
+        : sinks.filter((sink) => sink.type !== kind),
+    );
+
+  const update = (next: OutputSink) =>
+    onChange(sinks.map((sink) => (sink.type === next.type ? next : sink)));
+
+  return (
+    <div className="space-y-2">
+      {SINK_KINDS.map((kind) => {
+        const sink = sinks.find((s) => s.type === kind);
+        return (
+          <div key={kind} className="space-y-1">
+            <label className="flex items-center gap-2 text-sm">
+              <input
+                type="checkbox"
+                checked={!!sink}
+                onChange={(e) => toggle(kind, e.target.checked)}
+              />
+              {t(`settings.advanced.outputSinks.kinds.${kind}`)}
+            </label>
+            {sink?.type === "markdown_journal" && (
+              <div className="flex gap-2 pl-6">
+                <Input
+                  type="text"
+                  value={sink.directory}
+                  onChange={(e) =>
+                    update({ ...sink, directory: e.target.value })
+                  }
+                  placeholder={t(
+                    "settings.advanced.outputSinks.directoryPlaceholder",
+                  )}
+                  variant="compact"
+                  className="flex-1"
+                />
+                <Input
+                  type="text"
+                  value={sink.template ?? DEFAULT_JOURNAL_TEMPLATE}
+                  onChange={(e) =>
+                    update({ ...sink, template: e.target.value })
+                  }
+                  title={t("settings.advanced.outputSinks.templateHint")}
+                  variant="compact"
+                  className="flex-1"
+                />
+              </div>
+            )}
+            {sink?.type === "file" && (
+              <div className="pl-6">
+                <Input
+                  type="text"
+                  value={sink.path}
+                  onChange={(e) => update({ ...sink, path: e.target.value })}
+                  placeholder={t(
+                    "settings.advanced.outputSinks.pathPlaceholder",
+                  )}
+                  variant="compact"
+                  className="w-full"
+                />
+              </div>
+            )}
+            {sink?.type === "webhook" && (
+              <div className="pl-6">
+                <Input
+                  type="text"
+                  value={sink.url}
+                  onChange={(e) => update({ ...sink, url: e.target.value })}
+                  placeholder="http://localhost:8080/dictee"
+                  variant="compact"
+                  className="w-full"
+                />
+              </div>
+            )}
+          </div>
+        );
+      })}
+    </div>
+  );
+};
+
+interface OutputSinksProps {
+  descriptionMode?: "inline" | "tooltip";
+  grouped?: boolean;
+}
+
+export const OutputSinksSetting: React.FC<OutputSinksProps> = React.memo(
+  ({ descriptionMode = "tooltip", grouped = false }) => {
+    const { t } = useTranslation();
+    const { getSetting, refreshSettings } = useSettings();
+    const saved = getSetting("output_sinks");
+    const [draft, setDraft] = useState<OutputSink[]>(
+      saved ?? [{ type: "paste" }],
+    );
+
+    useEffect(() => {
+      if (saved) setDraft(saved);
+    }, [saved]);
+
+    const handleSave = async () => {
+      const result = await commands.changeOutputSinksSetting(draft);
+      if (result.status === "error") {
+        toast.error(result.error);
+        return;
+      }
+      await refreshSettings();
+    };
+
+    return (
+      <SettingContainer
+        title={t("settings.advanced.outputSinks.title")}
+        description={t("settings.advanced.outputSinks.description")}
+        descriptionMode={descriptionMode}
+        layout="stacked"
+        grouped={grouped}
+      >
+        <div className="space-y-2">
+          <OutputSinksEditor sinks={draft} onChange={setDraft} />
+          <Button
+            onClick={handleSave}
+            variant="primary"
+            size="md"
+            disabled={JSON.stringify(draft) === JSON.stringify(saved)}
+          >
+            {t("settings.advanced.outputSinks.save")}
+          </Button>
+        </div>
+      </SettingContainer>
+    );
+  },
+);
+
+OutputSinksSetting.displayName = "OutputSinksSetting";
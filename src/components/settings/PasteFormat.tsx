@@ -0,0 +1,112 @@
+import React, { useState } from "react";
+import { useTranslation } from "react-i18next";
+import { toast } from "sonner";
+import { commands, type PasteFormat } from "@/bindings";
+import { Button } from "../ui/Button";
+import { Dropdown } from "../ui/Dropdown";
+import { Input } from "../ui/Input";
+import { SettingContainer } from "../ui/SettingContainer";
+import { useSettings } from "../../hooks/useSettings";
+
+interface PasteFormatProps {
+  descriptionMode?: "inline" | "tooltip";
+  grouped?: boolean;
+}
+
+/** Texte brut ou HTML pour les collages, avec exceptions par application */
+export const PasteFormatSetting: React.FC<PasteFormatProps> = React.memo(
+  ({ descriptionMode = "tooltip", grouped = false }) => {
+    const { t } = useTranslation();
+    const { getSetting, updateSetting, isUpdating, refreshSettings } =
+      useSettings();
+    const [newApp, setNewApp] = useState("");
+
+    const formatOptions = [
+      {
+        value: "plain",
+        label: t("settings.advanced.pasteFormat.options.plain"),
+      },
+      {
+        value: "rich",
+        label: t("settings.advanced.pasteFormat.options.rich"),
+      },
+    ];
+
+    const selectedFormat = (getSetting("paste_format") ||
+      "plain") as PasteFormat;
+    const appFormats = getSetting("app_paste_formats") ?? {};
+
+    const setAppFormat = async (app: string, format: PasteFormat | null) => {
+      const result = await commands.setAppPasteFormat(app, format);
+      if (result.status === "error") {
+        toast.error(result.error);
+        return;
+      }
+      await refreshSettings();
+    };
+
+    const handleAdd = async () => {
+      // Par défaut, l'exception inverse le format global
+      await setAppFormat(newApp, selectedFormat === "rich" ? "plain" : "rich");
+      setNewApp("");
+    };
+
+    return (
+      <SettingContainer
+        title={t("settings.advanced.pasteFormat.title")}
+        description={t("settings.advanced.pasteFormat.description")}
+        descriptionMode={descriptionMode}
+        layout="stacked"
+        grouped={grouped}
+      >
+        <div className="space-y-2">
+          <Dropdown
+            options={formatOptions}
+            selectedValue={selectedFormat}
+            onSelect={(value) =>
+              updateSetting("paste_format", value as PasteFormat)
+            }
+            disabled={isUpdating("paste_format")}
+          />
+          {Object.entries(appFormats).map(([app, format]) => (
+            <div key={app} className="flex items-center gap-2 text-sm">
+              <span className="flex-1 truncate">{app}</span>
+              <Dropdown
+                options={formatOptions}
+                selectedValue={format ?? "plain"}
+                onSelect={(value) => setAppFormat(app, value as PasteFormat)}
+              />
+              <Button
+                onClick={() => setAppFormat(app, null)}
+                variant="secondary"
+                size="sm"
+              >
+                {t("settings.advanced.pasteFormat.remove")}
+              </Button>
+            </div>
+          ))}
+          <div className="flex gap-2">
+            <Input
+              type="text"
+              value={newApp}
+              onChange={(e) => setNewApp(e.target.value)}
+              placeholder={t("settings.advanced.pasteFormat.appPlaceholder")}
+              variant="compact"
+              className="flex-1"
+            />
+            <Button
+              onClick={handleAdd}
+              variant="primary"
+              size="md"
+              disabled={newApp.trim() === ""}
+            >
+              {t("settings.advanced.pasteFormat.addApp")}
+            </Button>
+          </div>
+        </div>
+      </SettingContainer>
+    );
+  },
+);
+
+PasteFormatSetting.displayName = "PasteFormatSetting";
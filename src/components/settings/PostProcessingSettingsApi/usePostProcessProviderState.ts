@@ -1,6 +1,10 @@
 import { useCallback, useMemo, useState } from "react";
 import { useSettings } from "../../../hooks/useSettings";
-import { commands, type PostProcessProvider } from "@/bindings";
+import {
+  commands,
+  type PostProcessProvider,
+  type ProviderKind,
+} from "@/bindings";
 import type { ModelOption } from "./types";
 import type { DropdownOption } from "../../ui/Dropdown";
 
@@ -14,6 +18,9 @@ type PostProcessProviderState = {
   baseUrl: string;
   handleBaseUrlChange: (value: string) => void;
   isBaseUrlUpdating: boolean;
+  kind: ProviderKind;
+  handleKindChange: (value: string) => void;
+  isKindUpdating: boolean;
   apiKey: string;
   handleApiKeyChange: (value: string) => void;
   isApiKeyUpdating: boolean;
@@ -36,6 +43,7 @@ export const usePostProcessProviderState = (): PostProcessProviderState => {
     isUpdating,
     setPostProcessProvider,
     updatePostProcessBaseUrl,
+    updatePostProcessKind,
     updatePostProcessApiKey,
     updatePostProcessModel,
     fetchPostProcessModels,
@@ -62,6 +70,7 @@ export const usePostProcessProviderState = (): PostProcessProviderState => {
 
   // Use settings directly as single source of truth
   const baseUrl = selectedProvider?.base_url ?? "";
+  const kind = selectedProvider?.kind ?? "openai-compatible";
   const apiKey = settings?.post_process_api_keys?.[selectedProviderId] ?? "";
   const model = settings?.post_process_models?.[selectedProviderId] ?? "";
 
@@ -107,6 +116,18 @@ export const usePostProcessProviderState = (): PostProcessProviderState => {
     [selectedProvider, baseUrl, updatePostProcessBaseUrl],
   );
 
+  const handleKindChange = useCallback(
+    (value: string) => {
+      if (!selectedProvider || selectedProvider.id !== "custom") {
+        return;
+      }
+      if (value !== kind) {
+        void updatePostProcessKind(selectedProvider.id, value);
+      }
+    },
+    [selectedProvider, kind, updatePostProcessKind],
+  );
+
   const handleApiKeyChange = useCallback(
     (value: string) => {
       const trimmed = value.trim();
@@ -173,6 +194,7 @@ export const usePostProcessProviderState = (): PostProcessProviderState => {
   const isBaseUrlUpdating = isUpdating(
     `post_process_base_url:${selectedProviderId}`,
   );
+  const isKindUpdating = isUpdating(`post_process_kind:${selectedProviderId}`);
   const isApiKeyUpdating = isUpdating(
     `post_process_api_key:${selectedProviderId}`,
   );
@@ -197,6 +219,9 @@ export const usePostProcessProviderState = (): PostProcessProviderState => {
     baseUrl,
     handleBaseUrlChange,
     isBaseUrlUpdating,
+    kind,
+    handleKindChange,
+    isKindUpdating,
     apiKey,
     handleApiKeyChange,
     isApiKeyUpdating,
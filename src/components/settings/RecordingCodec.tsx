@@ -0,0 +1,102 @@
+import React, { useEffect, useState } from "react";
+import { useTranslation } from "react-i18next";
+import { ask } from "@tauri-apps/plugin-dialog";
+import { toast } from "sonner";
+import { Dropdown } from "../ui/Dropdown";
+import { Input } from "../ui/Input";
+import { SettingContainer } from "../ui/SettingContainer";
+import { useSettings } from "../../hooks/useSettings";
+import { commands, type RecordingCodec } from "@/bindings";
+
+interface RecordingCodecProps {
+  descriptionMode?: "inline" | "tooltip";
+  grouped?: boolean;
+}
+
+/** Formats avec perte : convertir les WAV existants est irréversible */
+const LOSSY_CODECS: RecordingCodec[] = ["opus"];
+
+export const RecordingCodecSelector: React.FC<RecordingCodecProps> =
+  React.memo(({ descriptionMode = "tooltip", grouped = false }) => {
+    const { t } = useTranslation();
+    const { getSetting, updateSetting, isUpdating, refreshSettings } =
+      useSettings();
+    const [codecs, setCodecs] = useState<RecordingCodec[]>(["flac", "wav"]);
+
+    useEffect(() => {
+      commands.getAvailableRecordingCodecs().then(setCodecs);
+    }, []);
+
+    const codec = getSetting("recording_codec") ?? "flac";
+    const bitrate = getSetting("recording_opus_bitrate_kbps") ?? 24;
+
+    const handleBitrateChange = async (
+      event: React.ChangeEvent<HTMLInputElement>,
+    ) => {
+      const value = parseInt(event.target.value, 10);
+      if (!isNaN(value) && value >= 6 && value <= 128) {
+        updateSetting("recording_opus_bitrate_kbps", value);
+      }
+    };
+
+    const handleCodecSelect = async (value: RecordingCodec) => {
+      if (!LOSSY_CODECS.includes(value)) {
+        updateSetting("recording_codec", value);
+        return;
+      }
+      const convert = await ask(
+        t("settings.debug.recordingCodec.convertLossyConfirm", {
+          codec: t(`settings.debug.recordingCodec.codecs.${value}`),
+        }),
+        {
+          title: t("settings.debug.recordingCodec.convertLossyTitle"),
+          kind: "warning",
+        },
+      );
+      const result = await commands.changeRecordingCodecSetting(
+        value,
+        convert,
+      );
+      if (result.status === "error") toast.error(result.error);
+      await refreshSettings();
+    };
+
+    return (
+      <SettingContainer
+        title={t("settings.debug.recordingCodec.title")}
+        description={t("settings.debug.recordingCodec.description")}
+        descriptionMode={descriptionMode}
+        grouped={grouped}
+      >
+        <div className="flex items-center space-x-2">
+          <Dropdown
+            options={codecs.map((value) => ({
+              value,
+              label: t(`settings.debug.recordingCodec.codecs.${value}`),
+            }))}
+            selectedValue={codec}
+            onSelect={(value) => handleCodecSelect(value as RecordingCodec)}
+            disabled={isUpdating("recording_codec")}
+          />
+          {codec === "opus" && (
+            <>
+              <Input
+                type="number"
+                min="6"
+                max="128"
+                value={bitrate}
+                onChange={handleBitrateChange}
+                disabled={isUpdating("recording_opus_bitrate_kbps")}
+                className="w-20"
+              />
+              <span className="text-sm text-text">
+                {t("settings.debug.recordingCodec.kbps")}
+              </span>
+            </>
+          )}
+        </div>
+      </SettingContainer>
+    );
+  });
+
+RecordingCodecSelector.displayName = "RecordingCodecSelector";
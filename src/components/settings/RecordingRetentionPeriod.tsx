@@ -1,6 +1,7 @@
 import React from "react";
 import { useTranslation } from "react-i18next";
 import { Dropdown } from "../ui/Dropdown";
+import { Input } from "../ui/Input";
 import { SettingContainer } from "../ui/SettingContainer";
 import { useSettings } from "../../hooks/useSettings";
 import { RecordingRetentionPeriod } from "@/bindings";
@@ -18,6 +19,7 @@ export const RecordingRetentionPeriodSelector: React.FC<RecordingRetentionPeriod
     const selectedRetentionPeriod =
       getSetting("recording_retention_period") || "never";
     const historyLimit = getSetting("history_limit") || 5;
+    const quotaMb = getSetting("recording_quota_mb") ?? 1024;
 
     const handleRetentionPeriodSelect = async (period: string) => {
       await updateSetting(
@@ -26,6 +28,15 @@ export const RecordingRetentionPeriodSelector: React.FC<RecordingRetentionPeriod
       );
     };
 
+    const handleQuotaChange = async (
+      event: React.ChangeEvent<HTMLInputElement>,
+    ) => {
+      const value = parseInt(event.target.value, 10);
+      if (!isNaN(value) && value > 0) {
+        updateSetting("recording_quota_mb", value);
+      }
+    };
+
     const retentionOptions = [
       { value: "never", label: t("settings.debug.recordingRetention.never") },
       {
@@ -40,6 +51,10 @@ export const RecordingRetentionPeriodSelector: React.FC<RecordingRetentionPeriod
         value: "months3",
         label: t("settings.debug.recordingRetention.months3"),
       },
+      {
+        value: "disk_quota",
+        label: t("settings.debug.recordingRetention.diskQuota"),
+      },
     ];
 
     return (
@@ -49,13 +64,30 @@ export const RecordingRetentionPeriodSelector: React.FC<RecordingRetentionPeriod
         descriptionMode={descriptionMode}
         grouped={grouped}
       >
-        <Dropdown
-          options={retentionOptions}
-          selectedValue={selectedRetentionPeriod}
-          onSelect={handleRetentionPeriodSelect}
-          placeholder={t("settings.debug.recordingRetention.placeholder")}
-          disabled={isUpdating("recording_retention_period")}
-        />
+        <div className="flex items-center space-x-2">
+          <Dropdown
+            options={retentionOptions}
+            selectedValue={selectedRetentionPeriod}
+            onSelect={handleRetentionPeriodSelect}
+            placeholder={t("settings.debug.recordingRetention.placeholder")}
+            disabled={isUpdating("recording_retention_period")}
+          />
+          {selectedRetentionPeriod === "disk_quota" && (
+            <>
+              <Input
+                type="number"
+                min="1"
+                value={quotaMb}
+                onChange={handleQuotaChange}
+                disabled={isUpdating("recording_quota_mb")}
+                className="w-24"
+              />
+              <span className="text-sm text-text">
+                {t("settings.debug.recordingRetention.megabytes")}
+              </span>
+            </>
+          )}
+        </div>
       </SettingContainer>
     );
   });
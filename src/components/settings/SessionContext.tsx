@@ -0,0 +1,108 @@
+import React from "react";
+import { useTranslation } from "react-i18next";
+import { toast } from "sonner";
+import { commands } from "@/bindings";
+import { Slider } from "../ui/Slider";
+import { ToggleSwitch } from "../ui/ToggleSwitch";
+import { useSettings } from "../../hooks/useSettings";
+
+interface SessionContextProps {
+  descriptionMode?: "inline" | "tooltip";
+  grouped?: boolean;
+}
+
+type Limits = { entries: number; timeoutSecs: number; maxTokens: number };
+
+/** Dictées récentes de la session passées au nettoyage LLM et à Whisper */
+export const SessionContextSetting: React.FC<SessionContextProps> = React.memo(
+  ({ descriptionMode = "tooltip", grouped = false }) => {
+    const { t } = useTranslation();
+    const { getSetting, updateSetting, isUpdating, refreshSettings } =
+      useSettings();
+
+    const enabled = getSetting("session_context_enabled") ?? false;
+    const limits: Limits = {
+      entries: getSetting("session_context_entries") ?? 3,
+      timeoutSecs: getSetting("session_context_timeout_secs") ?? 300,
+      maxTokens: getSetting("session_context_max_tokens") ?? 150,
+    };
+
+    const updateLimits = async (patch: Partial<Limits>) => {
+      const next = { ...limits, ...patch };
+      const result = await commands.changeSessionContextLimitsSetting(
+        next.entries,
+        next.timeoutSecs,
+        next.maxTokens,
+      );
+      if (result.status === "error") {
+        toast.error(result.error);
+        return;
+      }
+      await refreshSettings();
+    };
+
+    return (
+      <>
+        <ToggleSwitch
+          checked={enabled}
+          onChange={(enabled) =>
+            updateSetting("session_context_enabled", enabled)
+          }
+          isUpdating={isUpdating("session_context_enabled")}
+          label={t("settings.advanced.sessionContext.label")}
+          description={t("settings.advanced.sessionContext.description")}
+          descriptionMode={descriptionMode}
+          grouped={grouped}
+        />
+        {enabled && (
+          <>
+            <Slider
+              value={limits.entries}
+              onChange={(entries) => updateLimits({ entries })}
+              min={1}
+              max={10}
+              step={1}
+              label={t("settings.advanced.sessionContext.entries.title")}
+              description={t(
+                "settings.advanced.sessionContext.entries.description",
+              )}
+              descriptionMode={descriptionMode}
+              grouped={grouped}
+              formatValue={(v) => `${v}`}
+            />
+            <Slider
+              value={limits.timeoutSecs}
+              onChange={(timeoutSecs) => updateLimits({ timeoutSecs })}
+              min={30}
+              max={1800}
+              step={30}
+              label={t("settings.advanced.sessionContext.timeout.title")}
+              description={t(
+                "settings.advanced.sessionContext.timeout.description",
+              )}
+              descriptionMode={descriptionMode}
+              grouped={grouped}
+              formatValue={(v) => `${v}s`}
+            />
+            <Slider
+              value={limits.maxTokens}
+              onChange={(maxTokens) => updateLimits({ maxTokens })}
+              min={20}
+              max={500}
+              step={10}
+              label={t("settings.advanced.sessionContext.maxTokens.title")}
+              description={t(
+                "settings.advanced.sessionContext.maxTokens.description",
+              )}
+              descriptionMode={descriptionMode}
+              grouped={grouped}
+              formatValue={(v) => `${v}`}
+            />
+          </>
+        )}
+      </>
+    );
+  },
+);
+
+SessionContextSetting.displayName = "SessionContextSetting";
@@ -0,0 +1,235 @@
+import React, { useEffect, useState } from "react";
+import { useTranslation } from "react-i18next";
+import { toast } from "sonner";
+import { open, save } from "@tauri-apps/plugin-dialog";
+import { commands, type BaselineInfo, type ImportPreview } from "@/bindings";
+
+import { SettingContainer } from "@/components/ui";
+import { Button } from "../ui/Button";
+import { useSettings } from "../../hooks/useSettings";
+
+interface SettingsTransferProps {
+  descriptionMode?: "tooltip" | "inline";
+  grouped?: boolean;
+}
+
+const JSON_FILTERS = [{ name: "JSON", extensions: ["json"] }];
+
+/** Export/import des réglages et baseline d'équipe */
+export const SettingsTransfer: React.FC<SettingsTransferProps> = ({
+  descriptionMode = "inline",
+  grouped = false,
+}) => {
+  const { t } = useTranslation();
+  const { refreshSettings } = useSettings();
+  const [includeApiKeys, setIncludeApiKeys] = useState(false);
+  const [importPath, setImportPath] = useState<string | null>(null);
+  const [preview, setPreview] = useState<ImportPreview | null>(null);
+  const [baseline, setBaseline] = useState<BaselineInfo | null>(null);
+  const [busy, setBusy] = useState(false);
+
+  useEffect(() => {
+    commands.getTeamBaseline().then(setBaseline);
+  }, []);
+
+  const pickJson = async () => {
+    const path = await open({ multiple: false, filters: JSON_FILTERS });
+    return typeof path === "string" ? path : null;
+  };
+
+  const handleExport = async () => {
+    const path = await save({
+      defaultPath: "dictation-ia-settings.json",
+      filters: JSON_FILTERS,
+    });
+    if (!path) return;
+    const result = await commands.exportSettings(path, includeApiKeys);
+    if (result.status === "error") {
+      toast.error(result.error);
+      return;
+    }
+    toast.success(t("settings.advanced.settingsTransfer.exported"));
+  };
+
+  const handlePickImport = async () => {
+    const path = await pickJson();
+    if (!path) return;
+    const result = await commands.previewSettingsImport(path);
+    if (result.status === "error") {
+      toast.error(result.error);
+      return;
+    }
+    setImportPath(path);
+    setPreview(result.data);
+  };
+
+  const handleApplyImport = async () => {
+    if (!importPath) return;
+    setBusy(true);
+    try {
+      const result = await commands.importSettings(importPath);
+      if (result.status === "error") {
+        toast.error(result.error);
+        return;
+      }
+      result.data.warnings.forEach((warning) => toast.warning(warning));
+      toast.success(
+        t("settings.advanced.settingsTransfer.imported", {
+          count: result.data.changes.length,
+        }),
+      );
+      setImportPath(null);
+      setPreview(null);
+      await refreshSettings();
+    } finally {
+      setBusy(false);
+    }
+  };
+
+  const handleInstallBaseline = async () => {
+    const path = await pickJson();
+    if (!path) return;
+    const result = await commands.installTeamBaseline(path);
+    if (result.status === "error") {
+      toast.error(result.error);
+      return;
+    }
+    setBaseline(result.data);
+    await refreshSettings();
+  };
+
+  const handleRemoveBaseline = async () => {
+    const result = await commands.removeTeamBaseline();
+    if (result.status === "error") {
+      toast.error(result.error);
+      return;
+    }
+    setBaseline(null);
+  };
+
+  // Fournisseurs interdits par la baseline, s'il y en a
+  const providerRule = !baseline
+    ? null
+    : baseline.disallow_cloud_providers
+      ? t("settings.advanced.settingsTransfer.baseline.noCloud")
+      : baseline.disallowed_providers.length > 0
+        ? t("settings.advanced.settingsTransfer.baseline.disallowed", {
+            providers: baseline.disallowed_providers.join(", "),
+          })
+        : null;
+
+  return (
+    <SettingContainer
+      title={t("settings.advanced.settingsTransfer.title")}
+      description={t("settings.advanced.settingsTransfer.description")}
+      descriptionMode={descriptionMode}
+      grouped={grouped}
+      layout="stacked"
+    >
+      <div className="space-y-2">
+        <div className="flex items-center gap-2">
+          <Button onClick={handleExport} variant="secondary" size="md">
+            {t("settings.advanced.settingsTransfer.export")}
+          </Button>
+          <label className="flex items-center gap-2 text-sm">
+            <input
+              type="checkbox"
+              checked={includeApiKeys}
+              onChange={(e) => setIncludeApiKeys(e.target.checked)}
+            />
+            {t("settings.advanced.settingsTransfer.includeApiKeys")}
+          </label>
+        </div>
+
+        <Button onClick={handlePickImport} variant="secondary" size="md">
+          {t("settings.advanced.settingsTransfer.import")}
+        </Button>
+
+        {preview && (
+          <div className="space-y-1 text-sm">
+            <p className="text-mid-gray">
+              {t("settings.advanced.settingsTransfer.previewTitle", {
+                count: preview.changes.length,
+                version: preview.app_version,
+              })}
+            </p>
+            <ul className="max-h-48 overflow-y-auto font-mono text-xs">
+              {preview.changes.map((change) => (
+                <li key={change.key} className="truncate">
+                  {change.key}: {change.current || "∅"} →{" "}
+                  {change.incoming || "∅"}
+                </li>
+              ))}
+            </ul>
+            {preview.warnings.map((warning) => (
+              <p key={warning} className="text-xs text-mid-gray">
+                {warning}
+              </p>
+            ))}
+            <div className="flex gap-2">
+              <Button
+                onClick={handleApplyImport}
+                variant="primary"
+                size="md"
+                disabled={busy || preview.changes.length === 0}
+              >
+                {t("settings.advanced.settingsTransfer.apply")}
+              </Button>
+              <Button
+                onClick={() => setPreview(null)}
+                variant="secondary"
+                size="md"
+                disabled={busy}
+              >
+                {t("settings.advanced.settingsTransfer.cancel")}
+              </Button>
+            </div>
+          </div>
+        )}
+
+        <div className="space-y-1 text-sm">
+          {baseline ? (
+            <>
+              <p>
+                {t("settings.advanced.settingsTransfer.baseline.active", {
+                  name: baseline.name,
+                })}
+              </p>
+              {baseline.locked.length > 0 && (
+                <p className="text-xs text-mid-gray">
+                  {t("settings.advanced.settingsTransfer.baseline.locked", {
+                    keys: baseline.locked.join(", "),
+                  })}
+                </p>
+              )}
+              {providerRule && (
+                <p className="text-xs text-mid-gray">{providerRule}</p>
+              )}
+              {baseline.allow_user_removal ? (
+                <Button
+                  onClick={handleRemoveBaseline}
+                  variant="secondary"
+                  size="md"
+                >
+                  {t("settings.advanced.settingsTransfer.baseline.remove")}
+                </Button>
+              ) : (
+                <p className="text-xs text-mid-gray">
+                  {t("settings.advanced.settingsTransfer.baseline.managed")}
+                </p>
+              )}
+            </>
+          ) : (
+            <Button
+              onClick={handleInstallBaseline}
+              variant="secondary"
+              size="md"
+            >
+              {t("settings.advanced.settingsTransfer.baseline.install")}
+            </Button>
+          )}
+        </div>
+      </div>
+    </SettingContainer>
+  );
+};
@@ -0,0 +1,65 @@
+import React from "react";
+import { useTranslation } from "react-i18next";
+import { toast } from "sonner";
+import { Dropdown } from "../ui/Dropdown";
+import { SettingContainer } from "../ui/SettingContainer";
+import { useSettings } from "../../hooks/useSettings";
+import { commands, type WhisperPreset } from "@/bindings";
+
+interface WhisperPresetProps {
+  modelId: string;
+  descriptionMode?: "inline" | "tooltip";
+  grouped?: boolean;
+}
+
+const PRESETS: WhisperPreset[] = ["fast", "accurate"];
+
+export const WhisperPresetSelector: React.FC<WhisperPresetProps> = React.memo(
+  ({ modelId, descriptionMode = "tooltip", grouped = false }) => {
+    const { t } = useTranslation();
+    const { getSetting, isUpdating, refreshSettings } = useSettings();
+
+    const preset = getSetting("whisper_presets")?.[modelId] ?? "fast";
+
+    const handleSelect = async (value: string) => {
+      if (value === preset) return;
+
+      try {
+        const result = await commands.setWhisperPreset(
+          modelId,
+          value as WhisperPreset,
+        );
+        if (result.status === "error") {
+          console.error("Failed to update Whisper preset:", result.error);
+          toast.error(String(result.error));
+          return;
+        }
+        await refreshSettings();
+      } catch (error) {
+        console.error("Failed to update Whisper preset:", error);
+        toast.error(String(error));
+      }
+    };
+
+    return (
+      <SettingContainer
+        title={t("settings.modelSettings.whisperPreset.title")}
+        description={t("settings.modelSettings.whisperPreset.description")}
+        descriptionMode={descriptionMode}
+        grouped={grouped}
+      >
+        <Dropdown
+          options={PRESETS.map((value) => ({
+            value,
+            label: t(`settings.modelSettings.whisperPreset.presets.${value}`),
+          }))}
+          selectedValue={preset}
+          onSelect={handleSelect}
+          disabled={isUpdating("whisper_presets")}
+        />
+      </SettingContainer>
+    );
+  },
+);
+
+WhisperPresetSelector.displayName = "WhisperPresetSelector";
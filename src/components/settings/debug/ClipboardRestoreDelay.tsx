@@ -0,0 +1,36 @@
+import React from "react";
+import { useTranslation } from "react-i18next";
+import { Slider } from "../../ui/Slider";
+import { useSettings } from "../../../hooks/useSettings";
+
+interface ClipboardRestoreDelayProps {
+  descriptionMode?: "tooltip" | "inline";
+  grouped?: boolean;
+}
+
+export const ClipboardRestoreDelay: React.FC<ClipboardRestoreDelayProps> = ({
+  descriptionMode = "tooltip",
+  grouped = false,
+}) => {
+  const { t } = useTranslation();
+  const { settings, updateSetting } = useSettings();
+
+  const handleDelayChange = (value: number) => {
+    updateSetting("clipboard_restore_delay_ms", value);
+  };
+
+  return (
+    <Slider
+      value={settings?.clipboard_restore_delay_ms ?? 50}
+      onChange={handleDelayChange}
+      min={50}
+      max={2000}
+      step={50}
+      label={t("settings.debug.clipboardRestoreDelay.title")}
+      description={t("settings.debug.clipboardRestoreDelay.description")}
+      descriptionMode={descriptionMode}
+      grouped={grouped}
+      formatValue={(v) => `${v}ms`}
+    />
+  );
+};
@@ -2,6 +2,7 @@ import React from "react";
 import { useTranslation } from "react-i18next";
 import { SettingsGroup } from "../../ui/SettingsGroup";
 import { LanguageSelector } from "../LanguageSelector";
+import { WhisperPresetSelector } from "../WhisperPreset";
 import { useModelStore } from "../../../stores/modelStore";
 import type { ModelInfo } from "@/bindings";
 
@@ -34,6 +35,13 @@ export const ModelSettingsCard: React.FC = () => {
           supportedLanguages={currentModelInfo.supported_languages}
         />
       )}
+      {currentModelInfo.engine_type === "Whisper" && (
+        <WhisperPresetSelector
+          modelId={currentModelInfo.id}
+          descriptionMode="tooltip"
+          grouped={true}
+        />
+      )}
     </SettingsGroup>
   );
 };
@@ -16,6 +16,9 @@ const makeEntry = (overrides: Partial<HistoryEntry> = {}): HistoryEntry => ({
   post_processed_text: null,
   post_process_prompt: null,
   write_mode: "chat",
+  source_file: null,
+  post_process_steps: [],
+  metrics: null,
   ...overrides,
 });
 
@@ -138,6 +141,65 @@ describe("HistorySettings", () => {
     expect(screen.getByText("Texte corrigé et amélioré.")).toBeInTheDocument();
   });
 
+  it("lists recipe step outputs on demand", async () => {
+    vi.mocked(commands.getHistoryEntries).mockResolvedValue({
+      status: "ok",
+      data: [
+        makeEntry({
+          post_processed_text: "[JIRA] Fix the login",
+          post_process_steps: [
+            { kind: "rules", detail: null, output: "Corriger le login." },
+            { kind: "llm", detail: "OpenAI · gpt-4o-mini", output: "Fix the login" },
+            { kind: "template", detail: null, output: "[JIRA] Fix the login" },
+          ],
+        }),
+      ],
+    });
+
+    renderWithI18n(<HistorySettings />);
+
+    await waitFor(() => {
+      expect(
+        screen.getByText("Voir les étapes de la recette (3)"),
+      ).toBeInTheDocument();
+    });
+    expect(screen.queryByText("Corriger le login.")).not.toBeInTheDocument();
+
+    await userEvent.click(screen.getByText("Voir les étapes de la recette (3)"));
+
+    expect(screen.getByText("Corriger le login.")).toBeInTheDocument();
+    expect(screen.getByText(/OpenAI · gpt-4o-mini/)).toBeInTheDocument();
+  });
+
+  it("shows dictation metrics when recorded", async () => {
+    vi.mocked(commands.getHistoryEntries).mockResolvedValue({
+      status: "ok",
+      data: [
+        makeEntry({
+          metrics: {
+            audio_ms: 4000,
+            stt_ms: 300,
+            rules_ms: 2,
+            llm_ms: 0,
+            paste_ms: 40,
+            routing: "rules_only",
+            llm_fallback: false,
+            model_id: "small",
+            write_mode: "chat",
+            confidence: 0.9,
+            word_count: 12,
+          },
+        }),
+      ],
+    });
+
+    renderWithI18n(<HistorySettings />);
+
+    await waitFor(() => {
+      expect(screen.getByText("12 mots · 342 ms · small")).toBeInTheDocument();
+    });
+  });
+
   it("copies transcription text to clipboard when copy button is clicked", async () => {
     vi.mocked(commands.getHistoryEntries).mockResolvedValue({
       status: "ok",
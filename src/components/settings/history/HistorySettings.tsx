@@ -5,11 +5,9 @@ import { Button } from "../../ui/Button";
 import { Copy, Star, Check, Trash2, FolderOpen } from "lucide-react";
 import { convertFileSrc } from "@tauri-apps/api/core";
 import { listen } from "@tauri-apps/api/event";
-import { readFile } from "@tauri-apps/plugin-fs";
 import { commands, type HistoryEntry } from "@/bindings";
 import { formatDateTime, formatRelativeTime } from "@/utils/dateFormat";
 import { WRITE_MODE_CONFIG, type WriteMode } from "@/config/writeModes";
-import { useOsType } from "@/hooks/useOsType";
 
 interface OpenRecordingsButtonProps {
   onClick: () => void;
@@ -34,7 +32,6 @@ const OpenRecordingsButton: React.FC<OpenRecordingsButtonProps> = ({
 
 export const HistorySettings: React.FC = () => {
   const { t } = useTranslation();
-  const osType = useOsType();
   const [historyEntries, setHistoryEntries] = useState<HistoryEntry[]>([]);
   const [loading, setLoading] = useState(true);
 
@@ -93,28 +90,21 @@ export const HistorySettings: React.FC = () => {
     }
   };
 
-  const getAudioUrl = useCallback(
-    async (fileName: string) => {
-      try {
-        const result = await commands.getAudioFilePath(fileName);
-        if (result.status === "ok") {
-          if (osType === "linux") {
-            const fileData = await readFile(result.data);
-            const blob = new Blob([fileData], { type: "audio/wav" });
-
-            return URL.createObjectURL(blob);
-          }
-
-          return convertFileSrc(result.data, "asset");
-        }
-        return null;
-      } catch (error) {
-        console.error("Failed to get audio file path:", error);
+  // Servi depuis la mémoire par le protocole `recording://` (déchiffré au
+  // besoin) : aucune copie en clair sur le disque
+  const getAudioUrl = useCallback(async (fileName: string) => {
+    try {
+      const response = await fetch(convertFileSrc(fileName, "recording"));
+      if (!response.ok) {
+        console.error("Failed to load recording:", await response.text());
         return null;
       }
-    },
-    [osType],
-  );
+      return URL.createObjectURL(await response.blob());
+    } catch (error) {
+      console.error("Failed to load recording:", error);
+      return null;
+    }
+  }, []);
 
   const deleteAudioEntry = async (id: number) => {
     try {
@@ -234,6 +224,7 @@ const HistoryEntryComponent: React.FC<HistoryEntryProps> = ({
   const { t, i18n } = useTranslation();
   const [showCopied, setShowCopied] = useState(false);
   const [showRawText, setShowRawText] = useState(false);
+  const [showSteps, setShowSteps] = useState(false);
 
   const handleLoadAudio = useCallback(
     () => getAudioUrl(entry.file_name),
@@ -322,6 +313,19 @@ const HistoryEntryComponent: React.FC<HistoryEntryProps> = ({
       <p className="italic text-text/90 text-sm pb-2 select-text cursor-text">
         {displayText}
       </p>
+      {entry.metrics && (
+        <p className="text-[11px] text-text/40">
+          {t("settings.history.metrics", {
+            words: entry.metrics.word_count,
+            latency:
+              entry.metrics.stt_ms +
+              entry.metrics.rules_ms +
+              entry.metrics.llm_ms +
+              (entry.metrics.paste_ms ?? 0),
+            model: entry.metrics.model_id ?? "?",
+          })}
+        </p>
+      )}
       {hasPostProcessed && (
         <button
           onClick={() => setShowRawText(!showRawText)}
@@ -330,7 +334,48 @@ const HistoryEntryComponent: React.FC<HistoryEntryProps> = ({
           {showRawText ? t("settings.history.showProcessed") : t("settings.history.showRaw")}
         </button>
       )}
-      <AudioPlayer onLoadRequest={handleLoadAudio} className="w-full" />
+      {entry.post_process_steps.length > 0 && (
+        <>
+          <button
+            onClick={() => setShowSteps(!showSteps)}
+            className="self-start text-[11px] text-text/40 hover:text-text/70 transition-colors cursor-pointer"
+          >
+            {showSteps
+              ? t("settings.history.hideSteps")
+              : t("settings.history.showSteps", { count: entry.post_process_steps.length })}
+          </button>
+          {showSteps && (
+            <ol className="flex flex-col gap-2 text-xs">
+              {entry.post_process_steps.map((step, index) => (
+                <li key={index} className="border-l-2 border-mid-gray/20 pl-2">
+                  <p className="text-text/50">
+                    {`${index + 1}. ${t(`settings.postProcessing.recipes.stepTypes.${step.kind}`)}`}
+                    {step.detail && ` · ${step.detail}`}
+                  </p>
+                  <p className="text-text/80 whitespace-pre-wrap select-text cursor-text">
+                    {step.output}
+                  </p>
+                </li>
+              ))}
+            </ol>
+          )}
+        </>
+      )}
+      {entry.file_name ? (
+        <AudioPlayer onLoadRequest={handleLoadAudio} className="w-full" />
+      ) : (
+        entry.source_file && (
+          // Import : seul le lien vers le fichier d'origine est conservé
+          <p
+            className="text-[11px] text-text/40 truncate"
+            title={entry.source_file}
+          >
+            {t("settings.history.importedFrom", {
+              file: entry.source_file.split(/[\\/]/).pop(),
+            })}
+          </p>
+        )
+      )}
     </div>
   );
 };
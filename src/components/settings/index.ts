@@ -13,6 +13,7 @@ export { ModelsSettings } from "./models/ModelsSettings";
 // Individual setting components
 export { MicrophoneSelector } from "./MicrophoneSelector";
 export { ClamshellMicrophoneSelector } from "./ClamshellMicrophoneSelector";
+export { MicrophonePriority } from "./MicrophonePriority";
 export { OutputDeviceSelector } from "./OutputDeviceSelector";
 export { AlwaysOnMicrophone } from "./AlwaysOnMicrophone";
 export { PushToTalk } from "./PushToTalk";
@@ -30,5 +31,12 @@ export { ModelUnloadTimeoutSetting } from "./ModelUnloadTimeout";
 export { StartHidden } from "./StartHidden";
 export { HistoryLimit } from "./HistoryLimit";
 export { RecordingRetentionPeriodSelector } from "./RecordingRetentionPeriod";
+export { RecordingCodecSelector } from "./RecordingCodec";
+export { OutputSinksSetting } from "./OutputSinks";
+export { PasteFormatSetting } from "./PasteFormat";
+export { SessionContextSetting } from "./SessionContext";
+export { SettingsTransfer } from "./SettingsTransfer";
+export { ModelBenchmark } from "./ModelBenchmark";
+export { WhisperPresetSelector } from "./WhisperPreset";
 export { AutostartToggle } from "./AutostartToggle";
 export { UpdateChecksToggle } from "./UpdateChecksToggle";
@@ -4,6 +4,7 @@ import { ask } from "@tauri-apps/plugin-dialog";
 import { ChevronDown, Globe } from "lucide-react";
 import type { ModelCardStatus } from "@/components/onboarding";
 import { ModelCard } from "@/components/onboarding";
+import { ModelBenchmark } from "@/components/settings/ModelBenchmark";
 import { useModelStore } from "@/stores/modelStore";
 import { LANGUAGES } from "@/lib/constants/languages.ts";
 import type { ModelInfo } from "@/bindings";
@@ -328,6 +329,8 @@ export const ModelsSettings: React.FC = () => {
             ))}
           </div>
 
+          {downloadedModels.length > 0 && <ModelBenchmark />}
+
           {/* Available Models Section */}
           {availableModels.length > 0 && (
             <div className="space-y-3">
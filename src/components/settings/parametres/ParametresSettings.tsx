@@ -2,12 +2,15 @@ import React, { useState } from "react";
 import { useTranslation } from "react-i18next";
 import { ChevronDown } from "lucide-react";
 import { MicrophoneSelector } from "../MicrophoneSelector";
+import { MicrophonePriority } from "../MicrophonePriority";
 import { OutputDeviceSelector } from "../OutputDeviceSelector";
 import { AudioFeedback } from "../AudioFeedback";
 import { VolumeSlider } from "../VolumeSlider";
 import { MuteWhileRecording } from "../MuteWhileRecording";
 import { PushToTalk } from "../PushToTalk";
 import { TriggerKeySelector } from "../TriggerKeySelector";
+import { CustomBindings } from "../CustomBindings";
+import { ShortcutInput } from "../ShortcutInput";
 import { ModelSettingsCard } from "../general/ModelSettingsCard";
 import { ModelsSettings } from "../models/ModelsSettings";
 import { ShowOverlay } from "../ShowOverlay";
@@ -17,13 +20,20 @@ import { StartHidden } from "../StartHidden";
 import { AutostartToggle } from "../AutostartToggle";
 import { ShowTrayIcon } from "../ShowTrayIcon";
 import { PasteMethodSetting } from "../PasteMethod";
+import { OutputSinksSetting } from "../OutputSinks";
+import { PasteFormatSetting } from "../PasteFormat";
 import { TypingToolSetting } from "../TypingTool";
 import { ClipboardHandlingSetting } from "../ClipboardHandling";
+import { LlmStreamModeSetting } from "../LlmStreamMode";
+import { SessionContextSetting } from "../SessionContext";
 import { AutoSubmit } from "../AutoSubmit";
 import { PostProcessingToggle } from "../PostProcessingToggle";
 import { AppendTrailingSpace } from "../AppendTrailingSpace";
 import { HistoryLimit } from "../HistoryLimit";
 import { RecordingRetentionPeriodSelector } from "../RecordingRetentionPeriod";
+import { RecordingCodecSelector } from "../RecordingCodec";
+import { EncryptionAtRest } from "../EncryptionAtRest";
+import { SettingsTransfer } from "../SettingsTransfer";
 import { ExperimentalToggle } from "../ExperimentalToggle";
 import { KeyboardImplementationSelector } from "../debug/KeyboardImplementationSelector";
 import { HistorySettings } from "../history/HistorySettings";
@@ -106,6 +116,7 @@ export const ParametresSettings: React.FC = () => {
       >
         <div className="space-y-1">
           <MicrophoneSelector descriptionMode="tooltip" grouped={true} />
+          <MicrophonePriority descriptionMode="tooltip" grouped={true} />
           <MuteWhileRecording descriptionMode="tooltip" grouped={true} />
           <AudioFeedback descriptionMode="tooltip" grouped={true} />
           <OutputDeviceSelector
@@ -126,6 +137,8 @@ export const ParametresSettings: React.FC = () => {
       >
         <div className="space-y-1">
           <TriggerKeySelector descriptionMode="tooltip" grouped={true} />
+          <ShortcutInput shortcutId="undo_last_dictation" grouped={true} />
+          <CustomBindings />
         </div>
       </AccordionSection>
 
@@ -170,16 +183,20 @@ export const ParametresSettings: React.FC = () => {
           </div>
           <div className="space-y-1">
             <PasteMethodSetting descriptionMode="tooltip" grouped={true} />
+            <PasteFormatSetting descriptionMode="tooltip" grouped={true} />
+            <OutputSinksSetting descriptionMode="tooltip" grouped={true} />
             <TypingToolSetting descriptionMode="tooltip" grouped={true} />
             <ClipboardHandlingSetting
               descriptionMode="tooltip"
               grouped={true}
             />
             <AutoSubmit descriptionMode="tooltip" grouped={true} />
+            <LlmStreamModeSetting descriptionMode="tooltip" grouped={true} />
           </div>
           <div className="space-y-1">
             <CustomWords descriptionMode="tooltip" grouped />
             <AppendTrailingSpace descriptionMode="tooltip" grouped={true} />
+            <SessionContextSetting descriptionMode="tooltip" grouped={true} />
           </div>
           <div className="space-y-1">
             <HistoryLimit descriptionMode="tooltip" grouped={true} />
@@ -187,6 +204,9 @@ export const ParametresSettings: React.FC = () => {
               descriptionMode="tooltip"
               grouped={true}
             />
+            <RecordingCodecSelector descriptionMode="tooltip" grouped={true} />
+            <EncryptionAtRest descriptionMode="tooltip" grouped={true} />
+            <SettingsTransfer descriptionMode="tooltip" grouped={true} />
           </div>
           {experimentalEnabled && (
             <div className="space-y-1">
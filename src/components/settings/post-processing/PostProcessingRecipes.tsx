@@ -0,0 +1,387 @@
+import React, { useEffect, useState } from "react";
+import { useTranslation } from "react-i18next";
+import { ArrowDown, ArrowUp, Trash2 } from "lucide-react";
+import { toast } from "sonner";
+import { commands, type RecipeStep } from "@/bindings";
+
+import { Dropdown, SettingContainer, Textarea } from "@/components/ui";
+import { Button } from "../../ui/Button";
+import { Input } from "../../ui/Input";
+import { useSettings } from "../../../hooks/useSettings";
+
+type StepType = RecipeStep["type"];
+
+const STEP_TYPES: StepType[] = ["rules", "structure", "template", "llm"];
+
+/** Valeur du menu fournisseur pour « fournisseur actif » */
+const ACTIVE_PROVIDER = "__active__";
+const NO_RECIPE = "__none__";
+
+const emptyStep = (type: StepType): RecipeStep => {
+  switch (type) {
+    case "template":
+      return { type, template: "${output}" };
+    case "llm":
+      return { type, prompt: "", provider_id: null, model: null };
+    default:
+      return { type };
+  }
+};
+
+const isStepValid = (step: RecipeStep) => {
+  if (step.type === "template") return step.template.trim() !== "";
+  if (step.type === "llm") return step.prompt.trim() !== "";
+  return true;
+};
+
+interface StepEditorProps {
+  step: RecipeStep;
+  index: number;
+  count: number;
+  providerOptions: { value: string; label: string }[];
+  onChange: (step: RecipeStep) => void;
+  onMove: (offset: number) => void;
+  onRemove: () => void;
+}
+
+const StepEditor: React.FC<StepEditorProps> = ({
+  step,
+  index,
+  count,
+  providerOptions,
+  onChange,
+  onMove,
+  onRemove,
+}) => {
+  const { t } = useTranslation();
+
+  return (
+    <div className="space-y-2 p-3 rounded-md border border-mid-gray/20">
+      <div className="flex items-center gap-2">
+        <span className="text-xs font-semibold text-mid-gray w-6">
+          {`${index + 1}.`}
+        </span>
+        <Dropdown
+          options={STEP_TYPES.map((type) => ({
+            value: type,
+            label: t(`settings.postProcessing.recipes.stepTypes.${type}`),
+          }))}
+          selectedValue={step.type}
+          onSelect={(value) => onChange(emptyStep(value as StepType))}
+          className="flex-1"
+        />
+        <Button
+          variant="ghost"
+          size="sm"
+          onClick={() => onMove(-1)}
+          disabled={index === 0}
+          aria-label={t("settings.postProcessing.recipes.moveUp")}
+        >
+          <ArrowUp className="h-4 w-4" />
+        </Button>
+        <Button
+          variant="ghost"
+          size="sm"
+          onClick={() => onMove(1)}
+          disabled={index === count - 1}
+          aria-label={t("settings.postProcessing.recipes.moveDown")}
+        >
+          <ArrowDown className="h-4 w-4" />
+        </Button>
+        <Button
+          variant="danger-ghost"
+          size="sm"
+          onClick={onRemove}
+          aria-label={t("settings.postProcessing.recipes.removeStep")}
+        >
+          <Trash2 className="h-4 w-4" />
+        </Button>
+      </div>
+
+      {step.type === "template" && (
+        <Textarea
+          value={step.template}
+          onChange={(e) => onChange({ ...step, template: e.target.value })}
+          placeholder={t(
+            "settings.postProcessing.recipes.templatePlaceholder",
+          )}
+        />
+      )}
+
+      {step.type === "llm" && (
+        <>
+          <div className="flex gap-2">
+            <Dropdown
+              options={providerOptions}
+              selectedValue={step.provider_id ?? ACTIVE_PROVIDER}
+              onSelect={(value) =>
+                onChange({
+                  ...step,
+                  provider_id: value === ACTIVE_PROVIDER ? null : value,
+                })
+              }
+              className="flex-1"
+            />
+            <Input
+              type="text"
+              value={step.model ?? ""}
+              onChange={(e) =>
+                onChange({ ...step, model: e.target.value || null })
+              }
+              placeholder={t("settings.postProcessing.recipes.modelPlaceholder")}
+              variant="compact"
+              className="flex-1"
+            />
+          </div>
+          <Textarea
+            value={step.prompt}
+            onChange={(e) => onChange({ ...step, prompt: e.target.value })}
+            placeholder={t("settings.postProcessing.recipes.promptPlaceholder")}
+          />
+        </>
+      )}
+    </div>
+  );
+};
+
+const PostProcessingRecipesComponent: React.FC = () => {
+  const { t } = useTranslation();
+  const { getSetting, refreshSettings } = useSettings();
+  const [selectedId, setSelectedId] = useState<string | null>(null);
+  const [isCreating, setIsCreating] = useState(false);
+  const [draftName, setDraftName] = useState("");
+  const [draftSteps, setDraftSteps] = useState<RecipeStep[]>([]);
+
+  const recipes = getSetting("post_process_recipes") || [];
+  const providers = getSetting("post_process_providers") || [];
+  const bindings = getSetting("bindings") || {};
+  const bindingRecipes = getSetting("binding_recipes") || {};
+  const selectedRecipe = recipes.find((r) => r.id === selectedId) || null;
+
+  useEffect(() => {
+    if (isCreating) return;
+    setDraftName(selectedRecipe?.name ?? "");
+    setDraftSteps(selectedRecipe?.steps ?? []);
+  }, [isCreating, selectedId, selectedRecipe]);
+
+  const providerOptions = [
+    {
+      value: ACTIVE_PROVIDER,
+      label: t("settings.postProcessing.recipes.activeProvider"),
+    },
+    ...providers.map((p) => ({ value: p.id, label: p.label })),
+  ];
+
+  const updateStep = (index: number, step: RecipeStep) =>
+    setDraftSteps((steps) => steps.map((s, i) => (i === index ? step : s)));
+
+  const moveStep = (index: number, offset: number) =>
+    setDraftSteps((steps) => {
+      const next = [...steps];
+      const [step] = next.splice(index, 1);
+      next.splice(index + offset, 0, step);
+      return next;
+    });
+
+  const removeStep = (index: number) =>
+    setDraftSteps((steps) => steps.filter((_, i) => i !== index));
+
+  const canSave =
+    draftName.trim() !== "" &&
+    draftSteps.length > 0 &&
+    draftSteps.every(isStepValid);
+
+  const handleSave = async () => {
+    if (!canSave) return;
+    const result = isCreating
+      ? await commands.addPostProcessRecipe(draftName.trim(), draftSteps)
+      : await commands.updatePostProcessRecipe(
+          selectedId!,
+          draftName.trim(),
+          draftSteps,
+        );
+    if (result.status === "error") {
+      toast.error(result.error);
+      return;
+    }
+    await refreshSettings();
+    if (isCreating && result.data) {
+      setSelectedId(result.data.id);
+    }
+    setIsCreating(false);
+  };
+
+  const handleDelete = async () => {
+    if (!selectedId) return;
+    const result = await commands.deletePostProcessRecipe(selectedId);
+    if (result.status === "error") {
+      toast.error(result.error);
+      return;
+    }
+    setSelectedId(null);
+    await refreshSettings();
+  };
+
+  const handleStartCreate = () => {
+    setIsCreating(true);
+    setDraftName("");
+    setDraftSteps([emptyStep("rules"), emptyStep("llm")]);
+  };
+
+  const handleBindingRecipe = async (bindingId: string, value: string) => {
+    const result = await commands.setBindingRecipe(
+      bindingId,
+      value === NO_RECIPE ? null : value,
+    );
+    if (result.status === "error") {
+      toast.error(result.error);
+      return;
+    }
+    await refreshSettings();
+  };
+
+  const recipeOptions = [
+    { value: NO_RECIPE, label: t("settings.postProcessing.recipes.noRecipe") },
+    ...recipes.map((r) => ({ value: r.id, label: r.name })),
+  ];
+
+  const showEditor = isCreating || selectedRecipe !== null;
+
+  return (
+    <>
+      <SettingContainer
+        title={t("settings.postProcessing.recipes.editor.title")}
+        description={t("settings.postProcessing.recipes.editor.description")}
+        descriptionMode="tooltip"
+        layout="stacked"
+        grouped={true}
+      >
+        <div className="space-y-3">
+          <div className="flex gap-2">
+            <Dropdown
+              selectedValue={selectedId}
+              options={recipes.map((r) => ({ value: r.id, label: r.name }))}
+              onSelect={(value) => {
+                setSelectedId(value);
+                setIsCreating(false);
+              }}
+              placeholder={
+                recipes.length === 0
+                  ? t("settings.postProcessing.recipes.noRecipes")
+                  : t("settings.postProcessing.recipes.selectRecipe")
+              }
+              disabled={isCreating}
+              className="flex-1"
+            />
+            <Button
+              onClick={handleStartCreate}
+              variant="primary"
+              size="md"
+              disabled={isCreating}
+            >
+              {t("settings.postProcessing.recipes.createNew")}
+            </Button>
+          </div>
+
+          {showEditor && (
+            <div className="space-y-3">
+              <Input
+                type="text"
+                value={draftName}
+                onChange={(e) => setDraftName(e.target.value)}
+                placeholder={t(
+                  "settings.postProcessing.recipes.namePlaceholder",
+                )}
+                variant="compact"
+              />
+
+              {draftSteps.map((step, index) => (
+                <StepEditor
+                  key={index}
+                  step={step}
+                  index={index}
+                  count={draftSteps.length}
+                  providerOptions={providerOptions}
+                  onChange={(next) => updateStep(index, next)}
+                  onMove={(offset) => moveStep(index, offset)}
+                  onRemove={() => removeStep(index)}
+                />
+              ))}
+
+              <p
+                className="text-xs text-mid-gray/70"
+                dangerouslySetInnerHTML={{
+                  __html: t("settings.postProcessing.recipes.variablesTip"),
+                }}
+              />
+
+              <div className="flex gap-2 pt-2">
+                <Button
+                  onClick={() =>
+                    setDraftSteps((steps) => [...steps, emptyStep("llm")])
+                  }
+                  variant="secondary"
+                  size="md"
+                >
+                  {t("settings.postProcessing.recipes.addStep")}
+                </Button>
+                <Button
+                  onClick={handleSave}
+                  variant="primary"
+                  size="md"
+                  disabled={!canSave}
+                >
+                  {isCreating
+                    ? t("settings.postProcessing.recipes.create")
+                    : t("settings.postProcessing.recipes.update")}
+                </Button>
+                {isCreating ? (
+                  <Button
+                    onClick={() => setIsCreating(false)}
+                    variant="secondary"
+                    size="md"
+                  >
+                    {t("settings.postProcessing.recipes.cancel")}
+                  </Button>
+                ) : (
+                  <Button onClick={handleDelete} variant="secondary" size="md">
+                    {t("settings.postProcessing.recipes.delete")}
+                  </Button>
+                )}
+              </div>
+            </div>
+          )}
+        </div>
+      </SettingContainer>
+
+      {Object.values(bindings)
+        .filter(
+          (binding) =>
+            binding &&
+            binding.id !== "cancel" &&
+            binding.id !== "undo_last_dictation",
+        )
+        .map((binding) => (
+          <SettingContainer
+            key={binding!.id}
+            title={binding!.name}
+            description={t(
+              "settings.postProcessing.recipes.binding.description",
+            )}
+            descriptionMode="tooltip"
+            layout="horizontal"
+            grouped={true}
+          >
+            <Dropdown
+              options={recipeOptions}
+              selectedValue={bindingRecipes[binding!.id] ?? NO_RECIPE}
+              onSelect={(value) => handleBindingRecipe(binding!.id, value)}
+            />
+          </SettingContainer>
+        ))}
+    </>
+  );
+};
+
+export const PostProcessingRecipes = React.memo(PostProcessingRecipesComponent);
+PostProcessingRecipes.displayName = "PostProcessingRecipes";
@@ -0,0 +1,101 @@
+import React, { useEffect, useState } from "react";
+import { useTranslation } from "react-i18next";
+import { toast } from "sonner";
+import { commands, type RedactionPolicy } from "@/bindings";
+
+import { Dropdown, SettingContainer, Textarea } from "@/components/ui";
+import { Button } from "../../ui/Button";
+import { useSettings } from "../../../hooks/useSettings";
+
+const POLICIES: RedactionPolicy[] = ["cloud_only", "always", "never"];
+
+const PostProcessingRedactionComponent: React.FC = () => {
+  const { t } = useTranslation();
+  const { getSetting, refreshSettings, updateSetting, isUpdating } =
+    useSettings();
+
+  const providers = getSetting("post_process_providers") || [];
+  const policies = getSetting("redaction_policies") || {};
+  const savedNames = (getSetting("redaction_names") || []).join("\n");
+  const [draftNames, setDraftNames] = useState(savedNames);
+
+  useEffect(() => {
+    setDraftNames(savedNames);
+  }, [savedNames]);
+
+  const policyOptions = POLICIES.map((policy) => ({
+    value: policy,
+    label: t(`settings.postProcessing.redaction.policies.${policy}`),
+  }));
+
+  const handlePolicy = async (providerId: string, policy: string) => {
+    const result = await commands.setRedactionPolicy(
+      providerId,
+      policy as RedactionPolicy,
+    );
+    if (result.status === "error") {
+      toast.error(result.error);
+      return;
+    }
+    await refreshSettings();
+  };
+
+  const parsedNames = draftNames
+    .split("\n")
+    .map((name) => name.trim())
+    .filter((name) => name !== "");
+
+  return (
+    <>
+      {providers.map((provider) => (
+        <SettingContainer
+          key={provider.id}
+          title={provider.label}
+          description={t(
+            "settings.postProcessing.redaction.policy.description",
+          )}
+          descriptionMode="tooltip"
+          layout="horizontal"
+          grouped={true}
+        >
+          <Dropdown
+            options={policyOptions}
+            selectedValue={policies[provider.id] ?? "cloud_only"}
+            onSelect={(value) => handlePolicy(provider.id, value)}
+          />
+        </SettingContainer>
+      ))}
+
+      <SettingContainer
+        title={t("settings.postProcessing.redaction.names.title")}
+        description={t("settings.postProcessing.redaction.names.description")}
+        descriptionMode="tooltip"
+        layout="stacked"
+        grouped={true}
+      >
+        <div className="space-y-2">
+          <Textarea
+            value={draftNames}
+            onChange={(e) => setDraftNames(e.target.value)}
+            placeholder={t(
+              "settings.postProcessing.redaction.names.placeholder",
+            )}
+          />
+          <Button
+            onClick={() => updateSetting("redaction_names", parsedNames)}
+            variant="primary"
+            size="md"
+            disabled={isUpdating("redaction_names")}
+          >
+            {t("settings.postProcessing.redaction.names.save")}
+          </Button>
+        </div>
+      </SettingContainer>
+    </>
+  );
+};
+
+export const PostProcessingRedaction = React.memo(
+  PostProcessingRedactionComponent,
+);
+PostProcessingRedaction.displayName = "PostProcessingRedaction";
@@ -20,6 +20,8 @@ import { ApiKeyField } from "../PostProcessingSettingsApi/ApiKeyField";
 import { ModelSelect } from "../PostProcessingSettingsApi/ModelSelect";
 import { usePostProcessProviderState } from "../PostProcessingSettingsApi/usePostProcessProviderState";
 import { ShortcutInput } from "../ShortcutInput";
+import { PostProcessingRecipes } from "./PostProcessingRecipes";
+import { PostProcessingRedaction } from "./PostProcessingRedaction";
 import { useSettings } from "../../../hooks/useSettings";
 
 const PostProcessingSettingsApiComponent: React.FC = () => {
@@ -52,6 +54,42 @@ const PostProcessingSettingsApiComponent: React.FC = () => {
         ) : null
       ) : (
         <>
+          {state.selectedProvider?.id === "custom" && (
+            <SettingContainer
+              title={t("settings.postProcessing.api.kind.title")}
+              description={t("settings.postProcessing.api.kind.description")}
+              descriptionMode="tooltip"
+              layout="horizontal"
+              grouped={true}
+            >
+              <Dropdown
+                options={[
+                  {
+                    value: "openai-compatible",
+                    label: t(
+                      "settings.postProcessing.api.kind.options.openaiCompatible",
+                    ),
+                  },
+                  {
+                    value: "anthropic-messages",
+                    label: t(
+                      "settings.postProcessing.api.kind.options.anthropicMessages",
+                    ),
+                  },
+                  {
+                    value: "ollama-native",
+                    label: t(
+                      "settings.postProcessing.api.kind.options.ollamaNative",
+                    ),
+                  },
+                ]}
+                selectedValue={state.kind}
+                onSelect={state.handleKindChange}
+                disabled={state.isKindUpdating}
+              />
+            </SettingContainer>
+          )}
+
           {state.selectedProvider?.id === "custom" && (
             <SettingContainer
               title={t("settings.postProcessing.api.baseUrl.title")}
@@ -443,6 +481,14 @@ export const PostProcessingSettings: React.FC = () => {
       <SettingsGroup title={t("settings.postProcessing.prompts.title")}>
         <PostProcessingSettingsPrompts />
       </SettingsGroup>
+
+      <SettingsGroup title={t("settings.postProcessing.recipes.title")}>
+        <PostProcessingRecipes />
+      </SettingsGroup>
+
+      <SettingsGroup title={t("settings.postProcessing.redaction.title")}>
+        <PostProcessingRedaction />
+      </SettingsGroup>
     </div>
   );
 };
@@ -35,6 +35,7 @@ interface UseSettingsReturn {
     providerId: string,
     baseUrl: string,
   ) => Promise<void>;
+  updatePostProcessKind: (providerId: string, kind: string) => Promise<void>;
   updatePostProcessApiKey: (
     providerId: string,
     apiKey: string,
@@ -71,6 +72,7 @@ export const useSettings = (): UseSettingsReturn => {
     getSetting: store.getSetting,
     setPostProcessProvider: store.setPostProcessProvider,
     updatePostProcessBaseUrl: store.updatePostProcessBaseUrl,
+    updatePostProcessKind: store.updatePostProcessKind,
     updatePostProcessApiKey: store.updatePostProcessApiKey,
     updatePostProcessModel: store.updatePostProcessModel,
     fetchPostProcessModels: store.fetchPostProcessModels,
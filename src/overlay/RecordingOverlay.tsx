@@ -16,6 +16,7 @@ const MIN_AMP = 6; // Plus petit à l'origine
 const MAX_AMP = 36; // Maximum headroom
 const SCROLL_SPEED = 0.5; // Légèrement plus lent
 const WAVE_SPEED = 0.1; // Vitesse de propagation de l'onde interne
+const PREVIEW_CHARS = 30; // Fin de la réponse LLM affichée pendant le streaming
 
 /** Renders animated cursive "e" loops that scroll and undulate, responding to voice */
 const CursiveLoops: React.FC<{ level: number }> = React.memo(({ level }) => {
@@ -126,6 +127,8 @@ const RecordingOverlay: React.FC = () => {
   const [isVisible, setIsVisible] = useState(false);
   const [state, setState] = useState<OverlayState>("recording");
   const [voiceLevel, setVoiceLevel] = useState(0);
+  const [limitRemaining, setLimitRemaining] = useState<number | null>(null);
+  const [preview, setPreview] = useState<string | null>(null);
   const smoothedLevelRef = useRef(0);
   const direction = getLanguageDirection(i18n.language);
 
@@ -135,6 +138,11 @@ const RecordingOverlay: React.FC = () => {
         await syncLanguageFromSettings();
         const overlayState = event.payload as OverlayState;
         setState(overlayState);
+        setPreview(null);
+
+        if (overlayState !== "recording") {
+          setLimitRemaining(null);
+        }
 
         // Only show for recording state; other states → just hide
         if (overlayState === "recording") {
@@ -151,6 +159,22 @@ const RecordingOverlay: React.FC = () => {
 
       const unlistenHide = await listen("hide-overlay", () => {
         setIsVisible(false);
+        setLimitRemaining(null);
+        setPreview(null);
+      });
+
+      // Réponse LLM en cours de génération : seule la fin du texte est affichée
+      const unlistenStream = await listen<string>("llm-stream", (event) => {
+        const text = event.payload.replace(/\s+/g, " ").trim();
+        setPreview(
+          text.length > PREVIEW_CHARS ? `…${text.slice(-PREVIEW_CHARS)}` : text,
+        );
+        setIsVisible(true);
+      });
+
+      // Durée max d'enregistrement bientôt atteinte
+      const unlistenLimit = await listen<number>("recording-limit-warning", (event) => {
+        setLimitRemaining(event.payload);
       });
 
       // Voice level for loop amplitude
@@ -169,7 +193,9 @@ const RecordingOverlay: React.FC = () => {
         unlistenShow();
         unlistenShowError();
         unlistenHide();
+        unlistenLimit();
         unlistenLevel();
+        unlistenStream();
       };
     };
 
@@ -182,8 +208,15 @@ const RecordingOverlay: React.FC = () => {
       className={`recording-overlay ${isVisible ? "fade-in" : ""}`}
     >
       <div className="overlay-middle">
-        {state === "recording" && <CursiveLoops level={voiceLevel} />}
+        {preview !== null ? (
+          <div className="overlay-preview">{preview}</div>
+        ) : (
+          state === "recording" && <CursiveLoops level={voiceLevel} />
+        )}
       </div>
+      {state === "recording" && limitRemaining !== null && (
+        <div className="overlay-limit">{limitRemaining}s</div>
+      )}
     </div>
   );
 };
@@ -1,6 +1,11 @@
 import { create } from "zustand";
 import { subscribeWithSelector } from "zustand/middleware";
-import type { AppSettings as Settings, AudioDevice } from "@/bindings";
+import type {
+  AppSettings as Settings,
+  AudioDevice,
+  PasteFormat,
+  RecordingCodec,
+} from "@/bindings";
 import { commands } from "@/bindings";
 
 interface SettingsStore {
@@ -32,7 +37,7 @@ interface SettingsStore {
   checkCustomSounds: () => Promise<void>;
   setPostProcessProvider: (providerId: string) => Promise<void>;
   updatePostProcessSetting: (
-    settingType: "base_url" | "api_key" | "model",
+    settingType: "base_url" | "kind" | "api_key" | "model",
     providerId: string,
     value: string,
   ) => Promise<void>;
@@ -40,6 +45,7 @@ interface SettingsStore {
     providerId: string,
     baseUrl: string,
   ) => Promise<void>;
+  updatePostProcessKind: (providerId: string, kind: string) => Promise<void>;
   updatePostProcessApiKey: (
     providerId: string,
     apiKey: string,
@@ -93,6 +99,8 @@ const settingUpdaters: {
     commands.setClamshellMicrophone(
       (value as string) === "Default" ? "default" : (value as string),
     ),
+  microphone_priority: (value) =>
+    commands.setMicrophonePriority(value as string[]),
   selected_output_device: (value) =>
     commands.setSelectedOutputDevice(
       (value as string) === "Default" || value === null
@@ -101,6 +109,12 @@ const settingUpdaters: {
     ),
   recording_retention_period: (value) =>
     commands.updateRecordingRetentionPeriod(value as string),
+  recording_quota_mb: (value) =>
+    commands.updateRecordingQuotaMb(value as number),
+  recording_codec: (value) =>
+    commands.changeRecordingCodecSetting(value as RecordingCodec, false),
+  recording_opus_bitrate_kbps: (value) =>
+    commands.changeRecordingOpusBitrateSetting(value as number),
   translate_to_english: (value) =>
     commands.changeTranslateToEnglishSetting(value as boolean),
   selected_language: (value) =>
@@ -109,14 +123,28 @@ const settingUpdaters: {
     commands.changeOverlayPositionSetting(value as string),
   debug_mode: (value) => commands.changeDebugModeSetting(value as boolean),
   custom_words: (value) => commands.updateCustomWords(value as string[]),
+  redaction_names: (value) =>
+    commands.updateRedactionNames(value as string[]),
   word_correction_threshold: (value) =>
     commands.changeWordCorrectionThresholdSetting(value as number),
   paste_method: (value) => commands.changePasteMethodSetting(value as string),
+  llm_stream_mode: (value) =>
+    commands.changeLlmStreamModeSetting(value as string),
   typing_tool: (value) => commands.changeTypingToolSetting(value as string),
   external_script_path: (value) =>
     commands.changeExternalScriptPathSetting(value as string | null),
   clipboard_handling: (value) =>
     commands.changeClipboardHandlingSetting(value as string),
+  clipboard_restore_delay_ms: (value) =>
+    commands.changeClipboardRestoreDelaySetting(value as number),
+  paste_format: (value) =>
+    commands.changePasteFormatSetting(value as PasteFormat),
+  session_context_enabled: (value) =>
+    commands.changeSessionContextEnabledSetting(value as boolean),
+  benchmark_target_rtf: (value) =>
+    commands.changeBenchmarkTargetRtfSetting(value as number),
+  benchmark_auto_select: (value) =>
+    commands.changeBenchmarkAutoSelectSetting(value as boolean),
   auto_submit: (value) => commands.changeAutoSubmitSetting(value as boolean),
   auto_submit_key: (value) =>
     commands.changeAutoSubmitKeySetting(value as string),
@@ -408,7 +436,7 @@ export const useSettingsStore = create<SettingsStore>()(
 
     // Generic updater for post-processing provider settings
     updatePostProcessSetting: async (
-      settingType: "base_url" | "api_key" | "model",
+      settingType: "base_url" | "kind" | "api_key" | "model",
       providerId: string,
       value: string,
     ) => {
@@ -420,6 +448,8 @@ export const useSettingsStore = create<SettingsStore>()(
       try {
         if (settingType === "base_url") {
           await commands.changePostProcessBaseUrlSetting(providerId, value);
+        } else if (settingType === "kind") {
+          await commands.changePostProcessKindSetting(providerId, value);
         } else if (settingType === "api_key") {
           await commands.changePostProcessApiKeySetting(providerId, value);
         } else if (settingType === "model") {
@@ -440,6 +470,17 @@ export const useSettingsStore = create<SettingsStore>()(
       return get().updatePostProcessSetting("base_url", providerId, baseUrl);
     },
 
+    updatePostProcessKind: async (providerId, kind) => {
+      // Model list depends on the API kind - user should click refresh after
+      set((state) => ({
+        postProcessModelOptions: {
+          ...state.postProcessModelOptions,
+          [providerId]: [],
+        },
+      }));
+      return get().updatePostProcessSetting("kind", providerId, kind);
+    },
+
     updatePostProcessApiKey: async (providerId, apiKey) => {
       // Clear cached models when API key changes - user should click refresh after
       set((state) => ({
@@ -49,6 +49,8 @@ vi.mock("@tauri-apps/plugin-dialog", () => ({
   ask: vi.fn(() => Promise.resolve(true)),
   confirm: vi.fn(() => Promise.resolve(true)),
   message: vi.fn(() => Promise.resolve()),
+  open: vi.fn(() => Promise.resolve(null)),
+  save: vi.fn(() => Promise.resolve(null)),
 }));
 
 // =============================================================================
@@ -173,6 +175,15 @@ vi.mock("@/bindings", () => ({
     hasAnyModelsOrDownloads: vi.fn(() =>
       Promise.resolve({ status: "ok", data: true }),
     ),
+    setWhisperPreset: vi.fn(() =>
+      Promise.resolve({ status: "ok", data: null }),
+    ),
+    runModelBenchmark: vi.fn(() =>
+      Promise.resolve({ status: "error", error: "not mocked" }),
+    ),
+    getModelBenchmark: vi.fn(() =>
+      Promise.resolve({ status: "ok", data: null }),
+    ),
     getAvailableModels: vi.fn(() =>
       Promise.resolve({ status: "ok", data: [] }),
     ),
@@ -245,15 +256,24 @@ vi.mock("@/bindings", () => ({
     deleteHistoryEntry: vi.fn(() =>
       Promise.resolve({ status: "ok", data: null }),
     ),
-    getAudioFilePath: vi.fn(() =>
-      Promise.resolve({ status: "ok", data: "" }),
-    ),
     updateHistoryLimit: vi.fn(() =>
       Promise.resolve({ status: "ok", data: null }),
     ),
     updateRecordingRetentionPeriod: vi.fn(() =>
       Promise.resolve({ status: "ok", data: null }),
     ),
+    updateRecordingQuotaMb: vi.fn(() =>
+      Promise.resolve({ status: "ok", data: null }),
+    ),
+    getAvailableRecordingCodecs: vi.fn(() =>
+      Promise.resolve(["flac", "opus", "wav"]),
+    ),
+    changeRecordingCodecSetting: vi.fn(() =>
+      Promise.resolve({ status: "ok", data: null }),
+    ),
+    changeRecordingOpusBitrateSetting: vi.fn(() =>
+      Promise.resolve({ status: "ok", data: null }),
+    ),
 
     // App settings
     getAppSettings: vi.fn(() =>
@@ -270,6 +290,64 @@ vi.mock("@/bindings", () => ({
     getDefaultSettings: vi.fn(() =>
       Promise.resolve({ status: "ok", data: {} }),
     ),
+    changeOutputSinksSetting: vi.fn(() =>
+      Promise.resolve({ status: "ok", data: null }),
+    ),
+    changeClipboardRestoreDelaySetting: vi.fn(() =>
+      Promise.resolve({ status: "ok", data: null }),
+    ),
+    changePasteFormatSetting: vi.fn(() =>
+      Promise.resolve({ status: "ok", data: null }),
+    ),
+    setAppPasteFormat: vi.fn(() =>
+      Promise.resolve({ status: "ok", data: null }),
+    ),
+    changeSessionContextEnabledSetting: vi.fn(() =>
+      Promise.resolve({ status: "ok", data: null }),
+    ),
+    changeSessionContextLimitsSetting: vi.fn(() =>
+      Promise.resolve({ status: "ok", data: null }),
+    ),
+    changeBenchmarkTargetRtfSetting: vi.fn(() =>
+      Promise.resolve({ status: "ok", data: null }),
+    ),
+    changeBenchmarkAutoSelectSetting: vi.fn(() =>
+      Promise.resolve({ status: "ok", data: null }),
+    ),
+    exportSettings: vi.fn(() =>
+      Promise.resolve({ status: "ok", data: null }),
+    ),
+    previewSettingsImport: vi.fn(() =>
+      Promise.resolve({
+        status: "ok",
+        data: {
+          app_version: "",
+          exported_at: 0,
+          settings_version: 1,
+          changes: [],
+          warnings: [],
+        },
+      }),
+    ),
+    importSettings: vi.fn(() =>
+      Promise.resolve({
+        status: "ok",
+        data: {
+          app_version: "",
+          exported_at: 0,
+          settings_version: 1,
+          changes: [],
+          warnings: [],
+        },
+      }),
+    ),
+    getTeamBaseline: vi.fn(() => Promise.resolve(null)),
+    installTeamBaseline: vi.fn(() =>
+      Promise.resolve({ status: "error", error: "not mocked" }),
+    ),
+    removeTeamBaseline: vi.fn(() =>
+      Promise.resolve({ status: "ok", data: null }),
+    ),
 
     // Misc
     checkAccessibilityPermission: vi.fn(() => Promise.resolve(true)),